@@ -1,13 +1,16 @@
 use crate::sql::ast::Expr;
 use crate::storage::btree::BTree;
-use crate::storage::page::PAGE_SIZE;
 use crate::storage::pager::Pager;
-use crate::storage::row::{ColumnType, ColumnValue, Row, RowData};
+use crate::storage::row::{ColumnType, ColumnValue, Row, RowData, RowKey};
 use crate::storage::vacuum::VacuumReport;
 use crate::transaction::{Snapshot, TransactionId};
 use std::collections::HashMap;
 use std::io;
 
+/// Catalog row keys from here on hold views; tables take keys counting up
+/// from 1.
+const VIEW_KEY_BASE: RowKey = 1 << 32;
+
 /// In‐memory representation of a table’s metadata.
 #[derive(Debug, Clone)]
 pub struct TableInfo {
@@ -19,11 +22,25 @@ pub struct TableInfo {
     pub auto_increment: Vec<bool>,
     pub fks: Vec<crate::sql::ast::ForeignKey>,
     pub primary_key: Option<Vec<String>>,
+    /// Per-column `GENERATED ALWAYS AS (expr) STORED` expressions.
+    pub generated: Vec<Option<Expr>>,
+    /// Per-column `COLLATE NOCASE` flags.
+    pub nocase: Vec<bool>,
+}
+
+impl TableInfo {
+    /// Whether `column` was declared `COLLATE NOCASE`.
+    pub fn is_nocase(&self, column: &str) -> bool {
+        self.columns
+            .iter()
+            .position(|(c, _)| c == column)
+            .is_some_and(|idx| self.nocase.get(idx).copied().unwrap_or(false))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SequenceInfo {
-    pub key: i32,
+    pub key: RowKey,
     pub current_value: i64,
     pub start_value: i64,
     pub increment: i64,
@@ -37,16 +54,51 @@ pub struct IndexInfo {
     pub root_page: u32,
 }
 
+/// One indexed value, as text, and the row keys posted under it.
+pub type IndexBucket = (String, Vec<RowKey>);
+
+/// Rows of one table keyed by column name, returned by
+/// [`Catalog::scan_table`]. Yields `(row key, values)` in row-key order.
+pub struct TableScan {
+    columns: Vec<String>,
+    rows: std::vec::IntoIter<Row>,
+}
+
+impl TableScan {
+    /// Column names of the scanned table, in declaration order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+}
+
+impl Iterator for TableScan {
+    type Item = (RowKey, HashMap<String, ColumnValue>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        let values = self.columns.iter().cloned().zip(row.data.0).collect();
+        Some((row.key, values))
+    }
+}
+
 /// The Catalog holds all user tables. Internally, it also persists itself in B-Tree page 1.
 pub struct Catalog {
     tables: HashMap<String, TableInfo>,
     indexes: HashMap<String, IndexInfo>,
     sequences: HashMap<String, SequenceInfo>,
+    /// View name to the SQL text of its query.
+    views: HashMap<String, String>,
     pub(crate) pager: Pager,
-    /// In-memory index map captured at BEGIN so an aborted transaction's DDL
-    /// (CREATE INDEX / DROP TABLE side effects) can be undone. Index metadata is
-    /// not persisted on disk, so page-level rollback alone cannot restore it.
-    pre_tx_indexes: Option<HashMap<String, IndexInfo>>,
+    /// Use an index to skip rows failing `indexed_col != value` during scans.
+    index_exclusion: bool,
+    /// Check the foreign keys of an INSERT's rows after all of them are in.
+    defer_foreign_keys: bool,
+    /// Decimal places DOUBLE and DECIMAL results are shown with.
+    float_precision: Option<usize>,
+    /// Show SQL NULL in query results as `NULL_MARKER` instead of `NULL`.
+    mark_nulls: bool,
+    /// Rows handed to the WHERE clause by SELECT scans since open.
+    rows_visited: u64,
 }
 
 impl Catalog {
@@ -57,7 +109,7 @@ impl Catalog {
         if pager.file_length_pages() <= 1 {
             // Allocate page 1
             pager.allocate_page()?;
-            let page = pager.get_page(1)?;
+            let page = pager.get_page_mut(1)?;
             crate::storage::page::set_node_type(&mut page.data, crate::storage::page::NODE_LEAF);
             crate::storage::page::set_is_root(&mut page.data, true);
             crate::storage::page::set_parent(&mut page.data, 0);
@@ -68,7 +120,7 @@ impl Catalog {
         // Ensure page 2 exists for sequence catalog
         if pager.file_length_pages() <= 2 {
             pager.allocate_page()?;
-            let page = pager.get_page(2)?;
+            let page = pager.get_page_mut(2)?;
             crate::storage::page::set_node_type(&mut page.data, crate::storage::page::NODE_LEAF);
             crate::storage::page::set_is_root(&mut page.data, true);
             crate::storage::page::set_parent(&mut page.data, 0);
@@ -78,12 +130,35 @@ impl Catalog {
 
         // Now read all catalog entries (if any) from page 1
         let mut tables = HashMap::new();
+        let mut indexes = HashMap::new();
+        let mut views = HashMap::new();
         {
+            // Read through `all_versions` rather than a cursor so a page that
+            // fails to load surfaces as an error instead of ending the scan.
             let mut catalog_btree = BTree::open_root(&mut pager, 1)?;
-            let mut cursor = catalog_btree.scan_all_rows();
-            while let Some(blob_row) = cursor.next() {
-                let (table_name, root_page, columns, not_null, defaults, auto_inc, fks, pk) =
-                    Self::deserialize_catalog_row(&blob_row)?;
+            let live = catalog_btree
+                .all_versions()?
+                .into_iter()
+                .filter(|row| row.deleted_tx.is_none());
+            for blob_row in live {
+                if let Some((name, sql)) = Self::deserialize_view_row(&blob_row) {
+                    views.insert(name, sql);
+                    continue;
+                }
+                let (
+                    table_name,
+                    root_page,
+                    columns,
+                    not_null,
+                    defaults,
+                    auto_inc,
+                    fks,
+                    pk,
+                    generated,
+                    nocase,
+                    table_indexes,
+                ) = Self::deserialize_catalog_row(&blob_row)?;
+                indexes.extend(table_indexes.into_iter().map(|i| (i.name.clone(), i)));
                 tables.insert(
                     table_name.clone(),
                     TableInfo {
@@ -95,6 +170,8 @@ impl Catalog {
                         fks,
                         auto_increment: auto_inc,
                         primary_key: if pk.is_empty() { None } else { Some(pk) },
+                        generated,
+                        nocase,
                     },
                 );
             }
@@ -104,8 +181,11 @@ impl Catalog {
         let mut sequences = HashMap::new();
         {
             let mut seq_btree = BTree::open_root(&mut pager, 2)?;
-            let mut cursor = seq_btree.scan_all_rows();
-            while let Some(row) = cursor.next() {
+            let live = seq_btree
+                .all_versions()?
+                .into_iter()
+                .filter(|row| row.deleted_tx.is_none());
+            for row in live {
                 let (name, current, start, inc) = Self::deserialize_sequence_row(&row)?;
                 sequences.insert(
                     name.clone(),
@@ -121,20 +201,46 @@ impl Catalog {
 
         Ok(Catalog {
             tables,
-            indexes: HashMap::new(),
+            indexes,
             sequences,
+            views,
             pager,
-            pre_tx_indexes: None,
+            index_exclusion: false,
+            defer_foreign_keys: false,
+            float_precision: None,
+            mark_nulls: false,
+            rows_visited: 0,
         })
     }
 
+    /// Rebuild the in-memory table, index, view and sequence maps from the
+    /// catalog pages, as ROLLBACK does after reverting them.
     pub(crate) fn reload_tables(&mut self) -> io::Result<()> {
         self.tables.clear();
+        self.indexes.clear();
+        self.views.clear();
         let mut catalog_btree = BTree::open_root(&mut self.pager, 1)?;
         let mut cursor = catalog_btree.scan_all_rows();
         while let Some(blob_row) = cursor.next() {
-            let (table_name, root_page, columns, not_null, defaults, auto_inc, fks, pk) =
-                Self::deserialize_catalog_row(&blob_row)?;
+            if let Some((name, sql)) = Self::deserialize_view_row(&blob_row) {
+                self.views.insert(name, sql);
+                continue;
+            }
+            let (
+                table_name,
+                root_page,
+                columns,
+                not_null,
+                defaults,
+                auto_inc,
+                fks,
+                pk,
+                generated,
+                nocase,
+                indexes,
+            ) = Self::deserialize_catalog_row(&blob_row)?;
+            self.indexes
+                .extend(indexes.into_iter().map(|i| (i.name.clone(), i)));
             self.tables.insert(
                 table_name.clone(),
                 TableInfo {
@@ -146,6 +252,8 @@ impl Catalog {
                     fks,
                     auto_increment: auto_inc,
                     primary_key: if pk.is_empty() { None } else { Some(pk) },
+                    generated,
+                    nocase,
                 },
             );
         }
@@ -167,11 +275,63 @@ impl Catalog {
                 );
             }
         }
+        self.resolve_live_roots()
+    }
+
+    /// Point every table and index at the live root of its tree (see
+    /// [`BTree::live_root`]), in case the recorded root was split by a
+    /// transaction that then aborted.
+    fn resolve_live_roots(&mut self) -> io::Result<()> {
+        for table in self.tables.values_mut() {
+            table.root_page = BTree::open_root(&mut self.pager, table.root_page)?.live_root()?;
+        }
+        for index in self.indexes.values_mut() {
+            index.root_page = BTree::open_root(&mut self.pager, index.root_page)?.live_root()?;
+        }
         Ok(())
     }
 
+    /// The indexes on `table`, in name order.
+    fn table_indexes(&self, table: &str) -> Vec<IndexInfo> {
+        let mut indexes: Vec<IndexInfo> = self
+            .indexes
+            .values()
+            .filter(|index| index.table_name == table)
+            .cloned()
+            .collect();
+        indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        indexes
+    }
+
+    /// Rewrite `table`'s catalog row so it records the table's current indexes.
+    fn persist_indexes(&mut self, table: &str) -> io::Result<()> {
+        let info = self.get_table(table)?.clone();
+        self.rewrite_catalog_row(table, &info)
+    }
+
+    /// Run `f` on the B-tree of table `name`. If `f` moved the root, as a split
+    /// of the root page does, the new root is recorded in memory and in the
+    /// catalog, whether or not `f` succeeded.
+    pub fn with_table_tree<T, E: From<io::Error>>(
+        &mut self,
+        name: &str,
+        f: impl FnOnce(&mut BTree<'_>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let root_page = self.get_table(name)?.root_page;
+        let (result, new_root) = {
+            let mut tree = BTree::open_root(&mut self.pager, root_page)?;
+            let result = f(&mut tree);
+            (result, tree.root_page())
+        };
+        if new_root != root_page {
+            self.get_table_mut(name)?.root_page = new_root;
+            self.update_catalog_root(name, new_root)?;
+        }
+        result
+    }
+
     pub(crate) fn update_catalog_root(&mut self, name: &str, new_root: u32) -> io::Result<()> {
-        let (target_key, columns, not_null, defaults, ai_vec, fks, pk_cols) = {
+        let (target_key, columns, not_null, defaults, ai_vec, fks, pk_cols, generated, nocase) = {
             let mut tree = BTree::open_root(&mut self.pager, 1)?;
             let mut cursor = tree.scan_all_rows();
             let mut found = None;
@@ -181,8 +341,14 @@ impl Catalog {
             let mut def_vec = Vec::new();
             let mut ai_vec = Vec::new();
             let mut pk_vec = Vec::new();
+            let mut gen_vec = Vec::new();
+            let mut nocase_vec = Vec::new();
             while let Some(row) = cursor.next() {
-                let (tbl, _rp, c, nn, def, ai, f, pk) = Self::deserialize_catalog_row(&row)?;
+                if Self::deserialize_view_row(&row).is_some() {
+                    continue;
+                }
+                let (tbl, _rp, c, nn, def, ai, f, pk, generated, nocase, _) =
+                    Self::deserialize_catalog_row(&row)?;
                 if tbl == name {
                     found = Some(row.key);
                     cols = c;
@@ -191,15 +357,17 @@ impl Catalog {
                     ai_vec = ai;
                     fk_vec = f;
                     pk_vec = pk;
+                    gen_vec = generated;
+                    nocase_vec = nocase;
                     break;
                 }
             }
-            (found, cols, nn_vec, def_vec, ai_vec, fk_vec, pk_vec)
+            (
+                found, cols, nn_vec, def_vec, ai_vec, fk_vec, pk_vec, gen_vec, nocase_vec,
+            )
         };
 
         if let Some(key) = target_key {
-            let mut tree = BTree::open_root(&mut self.pager, 1)?;
-            tree.delete(key)?;
             let cols: Vec<_> = columns
                 .iter()
                 .cloned()
@@ -208,20 +376,20 @@ impl Catalog {
                 .zip(ai_vec.iter().cloned())
                 .map(|((((n, t), nn), d), a)| (n, t, nn, d, a))
                 .collect();
+            let indexes = self.table_indexes(name);
+            let mut tree = BTree::open_root(&mut self.pager, 1)?;
+            tree.delete(key)?;
             tree.insert(
                 key,
-                Self::serialize_catalog_row(name, new_root, &cols, &fks, &pk_cols),
+                Self::serialize_catalog_row(
+                    name, new_root, &cols, &fks, &pk_cols, &generated, &nocase, &indexes,
+                ),
             )?;
             let new_root_page = tree.root_page();
             if new_root_page != 1 {
-                let src_buf = {
-                    let src = self.pager.get_page(new_root_page)?;
-                    let mut buf = [0u8; PAGE_SIZE];
-                    buf.copy_from_slice(&src.data);
-                    buf
-                };
+                let src_buf = self.pager.get_page(new_root_page)?.data.clone();
                 {
-                    let dst = self.pager.get_page(1)?;
+                    let dst = self.pager.get_page_mut(1)?;
                     dst.data.copy_from_slice(&src_buf);
                 }
                 self.pager.flush_page(1)?;
@@ -230,26 +398,6 @@ impl Catalog {
         Ok(())
     }
 
-    /// Capture the in-memory index map at BEGIN so ROLLBACK can undo DDL that
-    /// mutated it (index metadata is not persisted, so page rollback alone
-    /// cannot restore it). Driven by [`crate::transaction::TransactionManager`].
-    pub(crate) fn capture_pre_tx_indexes(&mut self) {
-        self.pre_tx_indexes = Some(self.indexes.clone());
-    }
-
-    /// Drop the BEGIN-time index snapshot after a successful COMMIT.
-    pub(crate) fn clear_pre_tx_indexes(&mut self) {
-        self.pre_tx_indexes = None;
-    }
-
-    /// Restore the BEGIN-time index map during ROLLBACK so aborted DDL leaves no
-    /// trace in the (non-persisted) index metadata.
-    pub(crate) fn restore_pre_tx_indexes(&mut self) {
-        if let Some(saved_indexes) = self.pre_tx_indexes.take() {
-            self.indexes = saved_indexes;
-        }
-    }
-
     pub(crate) fn recheck_constraints_for_commit(
         &mut self,
         transaction_id: TransactionId,
@@ -276,7 +424,9 @@ impl Catalog {
                                 .unwrap_or(false)
                         })
                     }) {
-                        return Err(crate::error::DbError::DuplicateKey(row.key));
+                        return Err(crate::constraints::primary_key::duplicate_key_error(
+                            table, pk_cols, &row.data,
+                        ));
                     }
                 }
             }
@@ -318,7 +468,10 @@ impl Catalog {
                 .iter()
                 .position(|(name, _)| name == &fk.columns[0])
                 .ok_or_else(|| crate::error::DbError::ColumnNotFound(fk.columns[0].clone()))?;
-            let ColumnValue::Integer(child_val) = row.0[child_idx] else {
+            if matches!(row.0[child_idx], ColumnValue::Null) {
+                continue;
+            }
+            let Some(child_val) = row.0[child_idx].as_row_key() else {
                 return Err(crate::error::DbError::InvalidValue(
                     "FK column must be INTEGER".into(),
                 ));
@@ -387,6 +540,32 @@ impl Catalog {
         self.pager.transaction_id()
     }
 
+    /// fsyncs issued by the pager since open; see [`Pager::sync_count`].
+    pub fn sync_count(&self) -> u64 {
+        self.pager.sync_count()
+    }
+
+    /// Rows examined by SELECT scans since open. A scan stops counting once
+    /// it has produced every row its caller asked for.
+    pub fn rows_visited(&self) -> u64 {
+        self.rows_visited
+    }
+
+    pub(crate) fn note_row_visited(&mut self) {
+        self.rows_visited += 1;
+    }
+
+    /// Row payload bytes decoded into column values since open; see
+    /// [`Pager::bytes_decoded`].
+    pub fn bytes_decoded(&self) -> u64 {
+        self.pager.bytes_decoded()
+    }
+
+    /// Write pages held back by group commit; see [`Pager::checkpoint`].
+    pub fn checkpoint(&mut self) -> io::Result<()> {
+        self.pager.checkpoint()
+    }
+
     pub fn transaction_snapshot(&self) -> Option<&Snapshot> {
         self.pager.transaction_snapshot()
     }
@@ -395,6 +574,51 @@ impl Catalog {
         self.transaction_snapshot().cloned()
     }
 
+    /// Insert rows of typed values into `table_name` without going through SQL
+    /// text; see [`crate::execution::runtime::execute_insert_typed`].
+    pub fn insert_typed(
+        &mut self,
+        table_name: &str,
+        rows: Vec<Vec<ColumnValue>>,
+    ) -> crate::error::DbResult<usize> {
+        crate::execution::runtime::execute_insert_typed(self, table_name, rows)
+    }
+
+    /// Run two queries and report whether they return the same rows, ignoring
+    /// row order. Useful for checking that a rewrite (such as an index lookup
+    /// versus a full scan) does not change a query's result.
+    pub fn query_eq(&mut self, sql_a: &str, sql_b: &str) -> crate::error::DbResult<bool> {
+        self.compare_queries(sql_a, sql_b, false)
+    }
+
+    /// Like [`Catalog::query_eq`], but the rows must also come back in the
+    /// same order.
+    pub fn query_eq_ordered(&mut self, sql_a: &str, sql_b: &str) -> crate::error::DbResult<bool> {
+        self.compare_queries(sql_a, sql_b, true)
+    }
+
+    fn compare_queries(
+        &mut self,
+        sql_a: &str,
+        sql_b: &str,
+        ordered: bool,
+    ) -> crate::error::DbResult<bool> {
+        let mut run = |sql: &str| -> crate::error::DbResult<Vec<Vec<String>>> {
+            let stmt = crate::sql::parser::parse_statement(sql)
+                .map_err(crate::error::DbError::ParseError)?;
+            let mut rows = Vec::new();
+            crate::execution::runtime::execute_select_statement(self, &stmt, &mut rows, None)?;
+            Ok(rows)
+        };
+        let mut a = run(sql_a)?;
+        let mut b = run(sql_b)?;
+        if !ordered {
+            a.sort();
+            b.sort();
+        }
+        Ok(a == b)
+    }
+
     /// Create a new table with `name` and `columns`. Allocates a fresh page for the table’s root,
     /// then inserts one catalog row into page 1 (the catalog B-Tree), and updates `tables`.
     pub fn create_table(
@@ -412,7 +636,7 @@ impl Catalog {
     /// Transactional DDL rule: when a transaction is active, catalog pages are
     /// written through the pager as dirty pages, so CREATE/DROP TABLE and CREATE
     /// INDEX commit or roll back atomically with the surrounding transaction
-    /// (page rollback + `reload_tables` + index-snapshot restore on ROLLBACK).
+    /// (page rollback + `reload_tables` on ROLLBACK).
     /// The catalog itself is intentionally NOT snapshot-versioned: a committed
     /// schema change is visible to any handle opened afterward — there is no
     /// per-snapshot schema isolation between concurrent handles.
@@ -422,6 +646,33 @@ impl Catalog {
         columns: Vec<(String, ColumnType, bool, Option<Expr>, bool)>,
         fks: Vec<crate::sql::ast::ForeignKey>,
         primary_key: Option<Vec<String>>,
+    ) -> io::Result<()> {
+        self.create_table_with_generated(name, columns, fks, primary_key, Vec::new())
+    }
+
+    /// Like [`Catalog::create_table_with_fks`], additionally recording the
+    /// expression of each generated column (`None` for ordinary columns).
+    pub fn create_table_with_generated(
+        &mut self,
+        name: &str,
+        columns: Vec<(String, ColumnType, bool, Option<Expr>, bool)>,
+        fks: Vec<crate::sql::ast::ForeignKey>,
+        primary_key: Option<Vec<String>>,
+        generated: Vec<Option<Expr>>,
+    ) -> io::Result<()> {
+        self.create_table_with_collations(name, columns, fks, primary_key, generated, Vec::new())
+    }
+
+    /// Like [`Catalog::create_table_with_generated`], additionally marking
+    /// the columns declared `COLLATE NOCASE`.
+    pub fn create_table_with_collations(
+        &mut self,
+        name: &str,
+        columns: Vec<(String, ColumnType, bool, Option<Expr>, bool)>,
+        fks: Vec<crate::sql::ast::ForeignKey>,
+        primary_key: Option<Vec<String>>,
+        mut generated: Vec<Option<Expr>>,
+        mut nocase: Vec<bool>,
     ) -> io::Result<()> {
         if self.tables.contains_key(name) {
             return Err(io::Error::new(
@@ -429,24 +680,47 @@ impl Catalog {
                 format!("Table {} already exists", name),
             ));
         }
-
-        // Allocate a new leaf page for this table’s data
-        let new_root = self.pager.allocate_page()?;
+        if self.views.contains_key(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("View {} already exists", name),
+            ));
+        }
+        // Rows are keyed on the first column, so it must hold integers.
+        if let Some((first, ty, ..)) = columns.first()
+            && !matches!(
+                ty,
+                ColumnType::Integer
+                    | ColumnType::BigInt
+                    | ColumnType::SmallInt { .. }
+                    | ColumnType::MediumInt { .. }
+            )
         {
-            let page = self.pager.get_page(new_root)?;
-            crate::storage::page::set_node_type(&mut page.data, crate::storage::page::NODE_LEAF);
-            crate::storage::page::set_is_root(&mut page.data, true);
-            crate::storage::page::set_parent(&mut page.data, 0);
-            crate::storage::page::set_cell_count(&mut page.data, 0);
-            self.pager.flush_page(new_root)?;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "First column {} of table {} is {}, but the first column is the row key \
+                     and must be an integer type; add an INTEGER id column before it",
+                    first,
+                    name,
+                    ty.as_str()
+                ),
+            ));
         }
 
+        // Allocate a new leaf page for this table’s data
+        let new_root = self.empty_tree()?;
+
         // Build the catalog row payload: [name_len][name][root_page][num_columns][col1_len][col1]...
         let pk_cols = primary_key.clone().unwrap_or_default();
-        let blob_data = Self::serialize_catalog_row(name, new_root, &columns, &fks, &pk_cols);
+        generated.resize(columns.len(), None);
+        nocase.resize(columns.len(), false);
+        let blob_data = Self::serialize_catalog_row(
+            name, new_root, &columns, &fks, &pk_cols, &generated, &nocase, &[],
+        );
 
         // Use a synthetic key = (current number of tables + 1)
-        let key = (self.tables.len() as i32) + 1;
+        let key = (self.tables.len() as RowKey) + 1;
         {
             let mut catalog_btree = BTree::open_root(&mut self.pager, 1)?;
             catalog_btree.insert(key, blob_data)?;
@@ -474,14 +748,16 @@ impl Catalog {
                 fks,
                 auto_increment: auto_inc,
                 primary_key,
+                generated,
+                nocase,
             },
         );
         Ok(())
     }
 
     /// See [`Catalog::create_table_with_fks`] for the transactional-DDL rule.
-    /// Because the index map is not persisted, its rollback relies on the
-    /// snapshot captured at BEGIN rather than on page rollback alone.
+    /// The index is recorded in its table's catalog row, so ROLLBACK undoes it
+    /// with the catalog page.
     pub fn create_index(
         &mut self,
         index_name: &str,
@@ -491,44 +767,19 @@ impl Catalog {
         if self.indexes.contains_key(index_name) {
             return Err(io::Error::new(io::ErrorKind::Other, "Index already exists"));
         }
-        let (table_root, col_idx) = {
+        let (table_root, col_idx, nocase) = {
             let table = self.get_table(table_name)?;
             let idx = table
                 .columns
                 .iter()
                 .position(|(c, _)| c == column_name)
                 .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Column not found"))?;
-            (table.root_page, idx)
+            (table.root_page, idx, table.is_nocase(column_name))
         };
 
-        let mut root_page = self.pager.allocate_page()?;
-        {
-            let page = self.pager.get_page(root_page)?;
-            crate::storage::page::set_node_type(&mut page.data, crate::storage::page::NODE_LEAF);
-            crate::storage::page::set_is_root(&mut page.data, true);
-            crate::storage::page::set_parent(&mut page.data, 0);
-            crate::storage::page::set_cell_count(&mut page.data, 0);
-            self.pager.flush_page(root_page)?;
-        }
+        let mut root_page = self.empty_tree()?;
 
-        // Build index from existing rows
-        {
-            let mut table_btree = BTree::open_root(&mut self.pager, table_root)?;
-            let mut cursor = table_btree.scan_all_rows();
-            let mut rows = Vec::new();
-            while let Some(row) = cursor.next() {
-                rows.push(row);
-            }
-            drop(cursor);
-            let mut index_tree = BTree::open_root(&mut self.pager, root_page)?;
-            for row in rows {
-                if let Some(val) = row.data.0.get(col_idx).cloned() {
-                    root_page = Catalog::insert_index_value(&mut index_tree, val, row.key)?;
-                }
-            }
-            // update root_page in case tree changed
-            self.pager.flush_page(root_page)?;
-        }
+        root_page = self.build_index(table_root, col_idx, nocase, root_page)?;
 
         self.indexes.insert(
             index_name.to_string(),
@@ -539,73 +790,269 @@ impl Catalog {
                 root_page,
             },
         );
-        Ok(())
+        self.persist_indexes(table_name)
+    }
+
+    /// Build an index from existing rows: group row keys by value hash and
+    /// bulk load the postings in hash order into the empty leaf `root_page`.
+    /// A `nocase` column is indexed by its lowercased values. Returns the
+    /// root of the loaded tree.
+    fn build_index(
+        &mut self,
+        table_root: u32,
+        col_idx: usize,
+        nocase: bool,
+        root_page: u32,
+    ) -> io::Result<u32> {
+        let mut postings: std::collections::BTreeMap<RowKey, Vec<IndexBucket>> =
+            std::collections::BTreeMap::new();
+        let mut table_btree = BTree::open_root(&mut self.pager, table_root)?;
+        for row in table_btree.scan_all_rows() {
+            let Some(val) = row.data.0.get(col_idx) else {
+                continue;
+            };
+            let val = &Self::collate_value(val.clone(), nocase);
+            let hash = Catalog::hash_value(val);
+            Self::add_to_buckets(
+                postings.entry(hash).or_default(),
+                Self::value_to_string(val),
+                row.key,
+            );
+        }
+        let mut index_tree = BTree::open_root(&mut self.pager, root_page)?;
+        index_tree.bulk_load(
+            postings
+                .into_iter()
+                .map(|(hash, buckets)| (hash, Self::encode_index_buckets(&buckets)))
+                .collect(),
+        )
+    }
+
+    /// Rebuild index B-Trees from scratch by re-scanning their tables: the
+    /// index `index`, every index on `table`, or every index when both are
+    /// `None`. Returns the number of indexes rebuilt.
+    ///
+    /// Each rebuild resets the old root page to an empty leaf and loads the
+    /// postings into it. The old tree's other pages are left unreferenced, as
+    /// with [`Catalog::drop_index`]; only [`Catalog::compact_table`] returns
+    /// pages to the pager's free list.
+    pub fn reindex(&mut self, index: Option<&str>, table: Option<&str>) -> io::Result<usize> {
+        let mut names: Vec<String> = match (index, table) {
+            (Some(name), _) => {
+                if !self.indexes.contains_key(name) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("index '{}' not found", name),
+                    ));
+                }
+                vec![name.to_string()]
+            }
+            (None, Some(table)) => {
+                self.get_table(table)?;
+                self.indexes
+                    .values()
+                    .filter(|info| info.table_name == table)
+                    .map(|info| info.name.clone())
+                    .collect()
+            }
+            (None, None) => self.indexes.keys().cloned().collect(),
+        };
+        names.sort();
+
+        for name in &names {
+            let info = self.indexes[name].clone();
+            let (table_root, col_idx, nocase) = {
+                let table = self.get_table(&info.table_name)?;
+                let idx = table
+                    .columns
+                    .iter()
+                    .position(|(c, _)| c == &info.column_name)
+                    .ok_or_else(|| io::Error::other("index column not found"))?;
+                (table.root_page, idx, table.is_nocase(&info.column_name))
+            };
+            {
+                let page = self.pager.get_page_mut(info.root_page)?;
+                crate::storage::page::set_node_type(
+                    &mut page.data,
+                    crate::storage::page::NODE_LEAF,
+                );
+                crate::storage::page::set_is_root(&mut page.data, true);
+                crate::storage::page::set_parent(&mut page.data, 0);
+                crate::storage::page::set_cell_count(&mut page.data, 0);
+                crate::storage::page::set_next_leaf(&mut page.data, 0);
+                self.pager.flush_page(info.root_page)?;
+            }
+            let root_page = self.build_index(table_root, col_idx, nocase, info.root_page)?;
+            if let Some(index_info) = self.indexes.get_mut(name) {
+                index_info.root_page = root_page;
+            }
+            self.persist_indexes(&info.table_name)?;
+        }
+        Ok(names.len())
+    }
+
+    /// Every entry of index `name` in stored key order, which is the order of
+    /// the values' [`Catalog::hash_value`], not of the values themselves;
+    /// values sharing a hash follow each other in the order they were first
+    /// posted. Each entry is the indexed value as text (lowercased for a
+    /// `nocase` column) and the row keys posted under it, oldest first. Stale
+    /// candidates left by UPDATE and DELETE are included until vacuum or
+    /// `REINDEX` drops them.
+    pub fn dump_index(&mut self, name: &str) -> io::Result<Vec<IndexBucket>> {
+        let root_page = self
+            .indexes
+            .get(name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("index '{}' not found", name),
+                )
+            })?
+            .root_page;
+        let mut tree = BTree::open_root(&mut self.pager, root_page)?;
+        Ok(tree
+            .scan_all_rows()
+            .flat_map(|row| Self::decode_index_buckets(&row.data))
+            .collect())
     }
 
     pub fn drop_index(&mut self, name: &str) -> io::Result<bool> {
-        if self.indexes.remove(name).is_some() {
-            // In the future pages belonging to the index would be freed here
+        if let Some(index) = self.indexes.remove(name) {
+            self.persist_indexes(&index.table_name)?;
+            self.free_tree(index.root_page)?;
             Ok(true)
         } else {
             Ok(false)
         }
     }
 
+    /// Rename index `old` to `new`, keeping its tree. Index names are unique
+    /// across all tables, so this fails if any index is already named `new`.
+    /// Follows the transactional-DDL rule of [`Catalog::create_table_with_fks`].
+    pub fn rename_index(&mut self, old: &str, new: &str) -> io::Result<()> {
+        if self.indexes.contains_key(new) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Index '{}' already exists", new),
+            ));
+        }
+        let mut index = self.indexes.remove(old).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Index '{}' not found", old),
+            )
+        })?;
+        index.name = new.to_string();
+        let table = index.table_name.clone();
+        self.indexes.insert(new.to_string(), index);
+        self.persist_indexes(&table)
+    }
+
     /// Append an index candidate for a logical base-row key.
     ///
-    /// Index payloads are `[indexed_value_as_text, logical_row_key, ...]`; they do
-    /// not own MVCC visibility. UPDATE and DELETE may leave stale candidates until
-    /// vacuum, and indexed reads must call `find_visible` on the base table before
-    /// returning a row.
+    /// Index payloads hold one bucket per distinct value with that hash, each
+    /// `[indexed_value_as_text, logical_row_key, ...]`, concatenated; a text
+    /// cell starts the next bucket, so colliding values coexist in one cell.
+    /// They do not own MVCC visibility. UPDATE and DELETE may leave stale
+    /// candidates until vacuum, and indexed reads must call `find_visible` on
+    /// the base table before returning a row. Values of a `nocase` column are
+    /// stored lowercased.
     pub(crate) fn insert_index_value(
         index_tree: &mut BTree,
         value: ColumnValue,
-        row_key: i32,
+        row_key: RowKey,
+        nocase: bool,
     ) -> io::Result<u32> {
+        let value = Self::collate_value(value, nocase);
         let hash = Catalog::hash_value(&value);
-        if let Some(mut existing) = index_tree.find(hash)? {
-            if let ColumnValue::Text(ref s) = existing.data.0[0] {
-                if *s == Self::value_to_string(&value) {
-                    existing.data.0.push(ColumnValue::Integer(row_key));
-                    index_tree.delete(hash)?;
-                    index_tree.insert(hash, existing.data)?;
-                    return Ok(index_tree.root_page());
+        let text = Self::value_to_string(&value);
+        let mut buckets = match index_tree.find(hash)? {
+            Some(existing) => {
+                index_tree.delete(hash)?;
+                Self::decode_index_buckets(&existing.data)
+            }
+            None => Vec::new(),
+        };
+        Self::add_to_buckets(&mut buckets, text, row_key);
+        index_tree.insert(hash, Self::encode_index_buckets(&buckets))?;
+        Ok(index_tree.root_page())
+    }
+
+    /// Post `row_key` under `value` in the buckets of one hash, opening a new
+    /// bucket when `value` collides with the others.
+    fn add_to_buckets(buckets: &mut Vec<IndexBucket>, value: String, row_key: RowKey) {
+        match buckets.iter_mut().find(|(stored, _)| *stored == value) {
+            Some((_, keys)) => keys.push(row_key),
+            None => buckets.push((value, vec![row_key])),
+        }
+    }
+
+    /// Split an index payload into its `(value, row_keys)` buckets.
+    pub(crate) fn decode_index_buckets(data: &RowData) -> Vec<IndexBucket> {
+        let mut buckets: Vec<IndexBucket> = Vec::new();
+        for value in &data.0 {
+            match value {
+                ColumnValue::Text(text) => buckets.push((text.clone(), Vec::new())),
+                other => {
+                    if let (Some((_, keys)), Some(key)) = (buckets.last_mut(), other.as_row_key()) {
+                        keys.push(key);
+                    }
                 }
             }
         }
-        let data = RowData(vec![
-            ColumnValue::Text(Self::value_to_string(&value)),
-            ColumnValue::Integer(row_key),
-        ]);
-        index_tree.insert(hash, data)?;
-        Ok(index_tree.root_page())
+        buckets
+    }
+
+    fn encode_index_buckets(buckets: &[IndexBucket]) -> RowData {
+        let mut values = Vec::new();
+        for (value, keys) in buckets {
+            values.push(ColumnValue::Text(value.clone()));
+            values.extend(keys.iter().map(|&key| ColumnValue::BigInt(key)));
+        }
+        RowData(values)
+    }
+
+    /// `value` as a `COLLATE NOCASE` column compares it: text lowercased,
+    /// anything else unchanged. With `nocase` false the value is returned as is.
+    pub(crate) fn collate_value(value: ColumnValue, nocase: bool) -> ColumnValue {
+        match value {
+            ColumnValue::Text(s) if nocase => ColumnValue::Text(s.to_lowercase()),
+            ColumnValue::Char(s) if nocase => ColumnValue::Char(s.to_lowercase()),
+            other => other,
+        }
     }
 
     pub(crate) fn value_to_string(val: &ColumnValue) -> String {
         match val {
             ColumnValue::Null => "NULL".into(),
             ColumnValue::Integer(i) => i.to_string(),
+            ColumnValue::BigInt(i) => i.to_string(),
             ColumnValue::Text(s) => s.clone(),
             ColumnValue::Boolean(b) => b.to_string(),
             ColumnValue::Char(s) => s.clone(),
             ColumnValue::Double(f) => f.to_string(),
+            ColumnValue::Decimal { value, scale } => {
+                crate::storage::row::format_decimal(*value as i128, *scale)
+            }
             ColumnValue::Date(d) => ColumnValue::Date(*d).to_string_value(),
             ColumnValue::DateTime(ts) => ColumnValue::DateTime(*ts).to_string_value(),
             ColumnValue::Timestamp(ts) => ColumnValue::Timestamp(*ts).to_string_value(),
             ColumnValue::Time(t) => ColumnValue::Time(*t).to_string_value(),
             ColumnValue::Year(y) => ColumnValue::Year(*y).to_string_value(),
+            ColumnValue::Blob(b) => crate::storage::row::format_blob_hex(b),
         }
     }
 
-    pub fn hash_value(val: &ColumnValue) -> i32 {
+    pub fn hash_value(val: &ColumnValue) -> RowKey {
         match val {
             ColumnValue::Null => 0,
-            ColumnValue::Integer(i) => *i,
+            ColumnValue::Integer(i) => *i as RowKey,
+            ColumnValue::BigInt(i) => *i,
             ColumnValue::Text(s) => {
                 use std::hash::{Hash, Hasher};
                 let mut h = std::collections::hash_map::DefaultHasher::new();
                 s.hash(&mut h);
-                (h.finish() as i64 & 0x7FFF_FFFF) as i32
+                h.finish() as i64 & 0x7FFF_FFFF
             }
             ColumnValue::Boolean(b) => {
                 if *b {
@@ -618,14 +1065,21 @@ impl Catalog {
                 use std::hash::{Hash, Hasher};
                 let mut h = std::collections::hash_map::DefaultHasher::new();
                 s.hash(&mut h);
-                (h.finish() as i64 & 0x7FFF_FFFF) as i32
+                h.finish() as i64 & 0x7FFF_FFFF
+            }
+            ColumnValue::Double(f) => *f as RowKey,
+            ColumnValue::Decimal { value, .. } => *value,
+            ColumnValue::Date(d) => *d as RowKey,
+            ColumnValue::DateTime(ts) => *ts,
+            ColumnValue::Timestamp(ts) => *ts,
+            ColumnValue::Time(t) => *t as RowKey,
+            ColumnValue::Year(y) => *y as RowKey,
+            ColumnValue::Blob(b) => {
+                use std::hash::{Hash, Hasher};
+                let mut h = std::collections::hash_map::DefaultHasher::new();
+                b.hash(&mut h);
+                h.finish() as i64 & 0x7FFF_FFFF
             }
-            ColumnValue::Double(f) => *f as i32,
-            ColumnValue::Date(d) => *d,
-            ColumnValue::DateTime(ts) => (*ts % i32::MAX as i64) as i32,
-            ColumnValue::Timestamp(ts) => (*ts % i32::MAX as i64) as i32,
-            ColumnValue::Time(t) => *t,
-            ColumnValue::Year(y) => *y as i32,
         }
     }
 
@@ -636,7 +1090,7 @@ impl Catalog {
                 format!("Sequence '{}' already exists", name),
             ));
         }
-        let key = (self.sequences.len() as i32) + 1;
+        let key = (self.sequences.len() as RowKey) + 1;
         let current = start - increment;
         {
             let mut tree = BTree::open_root(&mut self.pager, 2)?;
@@ -657,6 +1111,11 @@ impl Catalog {
         Ok(())
     }
 
+    /// The current state of sequence `name`, if it exists.
+    pub fn sequence(&self, name: &str) -> Option<&SequenceInfo> {
+        self.sequences.get(name)
+    }
+
     pub fn next_sequence_value(&mut self, name: &str) -> io::Result<i64> {
         let info = self.sequences.get_mut(name).ok_or_else(|| {
             io::Error::new(
@@ -703,24 +1162,82 @@ impl Catalog {
         Ok(())
     }
 
+    /// Reset sequence `name` so its next value is the start value again.
+    pub fn restart_sequence(&mut self, name: &str) -> io::Result<()> {
+        let info = self.sequences.get_mut(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Sequence '{}' not found", name),
+            )
+        })?;
+        info.current_value = info.start_value - info.increment;
+        let mut tree = BTree::open_root(&mut self.pager, 2)?;
+        tree.delete(info.key)?;
+        tree.insert(
+            info.key,
+            Self::serialize_sequence_row(
+                name,
+                info.current_value,
+                info.start_value,
+                info.increment,
+            ),
+        )?;
+        Ok(())
+    }
+
+    /// Rename a sequence, keeping its current value, start and increment.
+    pub fn rename_sequence(&mut self, old: &str, new: &str) -> io::Result<()> {
+        if self.sequences.contains_key(new) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Sequence '{}' already exists", new),
+            ));
+        }
+        let info = self.sequences.remove(old).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Sequence '{}' not found", old),
+            )
+        })?;
+        {
+            let mut tree = BTree::open_root(&mut self.pager, 2)?;
+            tree.delete(info.key)?;
+            tree.insert(
+                info.key,
+                Self::serialize_sequence_row(
+                    new,
+                    info.current_value,
+                    info.start_value,
+                    info.increment,
+                ),
+            )?;
+        }
+        self.sequences.insert(new.to_string(), info);
+        Ok(())
+    }
+
     pub fn insert_into_indexes(&mut self, table_name: &str, row_data: &RowData) -> io::Result<()> {
         let indices: Vec<IndexInfo> = self.indexes.values().cloned().collect();
         for idx in indices {
             if idx.table_name == table_name {
-                let col_pos = self
-                    .tables
-                    .get(table_name)
-                    .and_then(|t| t.columns.iter().position(|(c, _)| c == &idx.column_name))
+                let table = self.tables.get(table_name).unwrap();
+                let col_pos = table
+                    .columns
+                    .iter()
+                    .position(|(c, _)| c == &idx.column_name)
                     .unwrap();
+                let nocase = table.is_nocase(&idx.column_name);
                 if let Some(val) = row_data.0.get(col_pos).cloned() {
                     let mut tree = BTree::open_root(&mut self.pager, idx.root_page)?;
-                    let key = match row_data.0[0] {
-                        ColumnValue::Integer(i) => i,
-                        _ => continue,
+                    let Some(key) = row_data.0[0].as_row_key() else {
+                        continue;
                     };
-                    let new_root = Catalog::insert_index_value(&mut tree, val, key)?;
-                    if let Some(idx_info) = self.indexes.get_mut(&idx.name) {
-                        idx_info.root_page = new_root;
+                    let new_root = Catalog::insert_index_value(&mut tree, val, key, nocase)?;
+                    if new_root != idx.root_page {
+                        if let Some(idx_info) = self.indexes.get_mut(&idx.name) {
+                            idx_info.root_page = new_root;
+                        }
+                        self.persist_indexes(table_name)?;
                     }
                 }
             }
@@ -728,6 +1245,58 @@ impl Catalog {
         Ok(())
     }
 
+    /// Whether `!=` scans consult an index to find the rows they exclude.
+    pub fn index_exclusion(&self) -> bool {
+        self.index_exclusion
+    }
+
+    /// Enable index-driven exclusion for `indexed_col != value` predicates.
+    /// It pays off for low-cardinality columns, where the excluded value
+    /// covers many rows; it is off by default.
+    pub fn set_index_exclusion(&mut self, enabled: bool) {
+        self.index_exclusion = enabled;
+    }
+
+    /// Whether INSERT checks foreign keys once the whole statement's rows are
+    /// in, rather than row by row.
+    pub fn defer_foreign_keys(&self) -> bool {
+        self.defer_foreign_keys
+    }
+
+    /// Defer the foreign-key checks of each INSERT to the end of the
+    /// statement, so rows may reference rows later in the same VALUES list.
+    /// Set by `SET CONSTRAINTS DEFERRED`; off by default.
+    pub fn set_defer_foreign_keys(&mut self, deferred: bool) {
+        self.defer_foreign_keys = deferred;
+    }
+
+    /// Decimal places DOUBLE and DECIMAL query results are shown with; `None`
+    /// for their exact form.
+    pub fn float_precision(&self) -> Option<usize> {
+        self.float_precision
+    }
+
+    /// Show DOUBLE and DECIMAL query results with `places` decimal places, or
+    /// exactly with `None`. Set by `PRAGMA float_precision`; comparisons and
+    /// stored values are unaffected.
+    pub fn set_float_precision(&mut self, places: Option<usize>) {
+        self.float_precision = places;
+    }
+
+    /// Whether query results show SQL NULL as
+    /// [`crate::storage::row::NULL_MARKER`] rather than `NULL`.
+    pub fn marks_nulls(&self) -> bool {
+        self.mark_nulls
+    }
+
+    /// Show SQL NULL in query results as
+    /// [`crate::storage::row::NULL_MARKER`], so it is not confused with the
+    /// text `'NULL'`. Exports turn this on for the query they format; it is
+    /// off by default.
+    pub fn set_mark_nulls(&mut self, mark: bool) {
+        self.mark_nulls = mark;
+    }
+
     pub fn find_index(&self, table: &str, column: &str) -> Option<&IndexInfo> {
         self.indexes
             .values()
@@ -738,7 +1307,7 @@ impl Catalog {
         &mut self,
         table_name: &str,
         row_data: &RowData,
-        row_key: i32,
+        row_key: RowKey,
     ) -> io::Result<()> {
         // Logical index entries are deliberately not removed immediately. DELETE
         // and UPDATE make old base-row versions invisible in the table B-tree;
@@ -749,6 +1318,14 @@ impl Catalog {
     }
 
     /// Look up a table’s metadata, or return an error if it doesn’t exist.
+    pub fn table_exists(&self, name: &str) -> bool {
+        self.tables.contains_key(name)
+    }
+
+    pub fn index_exists(&self, name: &str) -> bool {
+        self.indexes.contains_key(name)
+    }
+
     pub fn get_table(&self, name: &str) -> io::Result<&TableInfo> {
         self.tables
             .get(name)
@@ -762,6 +1339,22 @@ impl Catalog {
             .ok_or_else(|| io::Error::new(io::ErrorKind::Other, format!("No such table: {}", name)))
     }
 
+    /// Scan `name`, yielding each row visible to the live transaction (or
+    /// every committed row outside one) with its values keyed by column name.
+    pub fn scan_table(&mut self, name: &str) -> io::Result<TableScan> {
+        let table = self.get_table(name)?;
+        let columns = table.columns.iter().map(|(col, _)| col.clone()).collect();
+        let root_page = table.root_page;
+        let snapshot = self
+            .current_snapshot()
+            .unwrap_or_else(|| Snapshot::new(u64::MAX, Vec::new()));
+        let rows = BTree::open_root(&mut self.pager, root_page)?.scan_visible(&snapshot)?;
+        Ok(TableScan {
+            columns,
+            rows: rows.into_iter(),
+        })
+    }
+
     pub fn all_tables(&self) -> Vec<TableInfo> {
         self.tables.values().cloned().collect()
     }
@@ -770,6 +1363,36 @@ impl Catalog {
         self.indexes.values().cloned().collect()
     }
 
+    /// Run [`BTree::verify`] over every table and then every index, in name
+    /// order. Returns the first violation, or `None` when all trees are sound.
+    pub fn integrity_check(&mut self) -> io::Result<Option<String>> {
+        let mut tables: Vec<(String, u32)> = self
+            .tables
+            .values()
+            .map(|t| (format!("table '{}'", t.name), t.root_page))
+            .collect();
+        let mut indexes: Vec<(String, u32)> = self
+            .indexes
+            .values()
+            .map(|i| (format!("index '{}'", i.name), i.root_page))
+            .collect();
+        tables.sort();
+        indexes.sort();
+        let trees = tables.into_iter().chain(indexes);
+
+        for (label, root_page) in trees {
+            let mut tree = BTree::open_root(&mut self.pager, root_page)?;
+            match tree.verify() {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                    return Ok(Some(format!("{}: {}", label, e)));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
     /// Explicit internal maintenance API for physically pruning obsolete MVCC
     /// versions from one table and lazily rebuilding that table's indexes to
     /// remove stale index candidates left behind by UPDATE/DELETE. `global_xmin`
@@ -796,9 +1419,95 @@ impl Catalog {
         Ok(VacuumReport {
             versions_removed,
             indexes_cleaned,
+            pages_freed: 0,
         })
     }
 
+    /// Rewrite `table_name`'s B-tree, and those of its indexes, into freshly
+    /// bulk-loaded trees without the row versions [`Catalog::vacuum_table`]
+    /// would prune, then free every page of the old trees. Surviving versions
+    /// keep their MVCC fields. No transaction may be live: one could still
+    /// hold the old pages; see [`crate::engine::Engine::execute`] for `VACUUM`.
+    pub fn compact_table(
+        &mut self,
+        table_name: &str,
+        global_xmin: TransactionId,
+    ) -> io::Result<VacuumReport> {
+        let table = self.get_table(table_name)?.clone();
+        let tx_table = self.pager.transaction_table().clone();
+
+        let (versions, mut old_pages) = {
+            let mut tree = BTree::open_root(&mut self.pager, table.root_page)?;
+            (tree.all_versions()?, tree.pages()?)
+        };
+        let before = versions.len();
+        let kept: Vec<Row> = versions
+            .into_iter()
+            .filter(|row| {
+                !crate::storage::vacuum::deleted_version_is_removable(
+                    row.deleted_tx,
+                    global_xmin,
+                    &tx_table,
+                ) && !crate::storage::vacuum::aborted_creator_is_removable(
+                    row.created_tx,
+                    &tx_table,
+                )
+            })
+            .collect();
+        let versions_removed = before - kept.len();
+        let empty = self.empty_tree()?;
+        let root_page = BTree::open_root(&mut self.pager, empty)?.bulk_load_versions(kept)?;
+        self.get_table_mut(table_name)?.root_page = root_page;
+
+        let mut indexes_cleaned = 0;
+        for index in self.table_indexes(table_name) {
+            old_pages.extend(BTree::open_root(&mut self.pager, index.root_page)?.pages()?);
+            let col_idx = table
+                .columns
+                .iter()
+                .position(|(column, _)| column == &index.column_name)
+                .ok_or_else(|| io::Error::other("index column not found"))?;
+            let nocase = table.is_nocase(&index.column_name);
+            let empty = self.empty_tree()?;
+            let index_root = self.build_index(root_page, col_idx, nocase, empty)?;
+            if let Some(info) = self.indexes.get_mut(&index.name) {
+                info.root_page = index_root;
+            }
+            indexes_cleaned += 1;
+        }
+        self.update_catalog_root(table_name, root_page)?;
+
+        for &page_num in &old_pages {
+            self.pager.free_page(page_num);
+        }
+        Ok(VacuumReport {
+            versions_removed,
+            indexes_cleaned,
+            pages_freed: old_pages.len(),
+        })
+    }
+
+    /// Free every page of the tree rooted at `root_page`, overflow pages
+    /// included, once nothing refers to it any more.
+    fn free_tree(&mut self, root_page: u32) -> io::Result<()> {
+        let pages = BTree::open_root(&mut self.pager, root_page)?.pages()?;
+        for page_num in pages {
+            self.pager.free_page(page_num);
+        }
+        Ok(())
+    }
+
+    /// Pages in `table_name`'s B-tree, overflow pages included.
+    pub fn table_page_count(&mut self, table_name: &str) -> io::Result<usize> {
+        let root_page = self.get_table(table_name)?.root_page;
+        Ok(BTree::open_root(&mut self.pager, root_page)?.pages()?.len())
+    }
+
+    /// Allocate an empty leaf to serve as the root of a new tree.
+    fn empty_tree(&mut self) -> io::Result<u32> {
+        BTree::create_empty(&mut self.pager)
+    }
+
     fn rebuild_indexes_for_table(&mut self, table_name: &str) -> io::Result<usize> {
         let table = self.get_table(table_name)?.clone();
         let index_names: Vec<String> = self
@@ -818,6 +1527,7 @@ impl Catalog {
                 .iter()
                 .position(|(column, _)| column == &index.column_name)
                 .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "index column not found"))?;
+            let nocase = table.is_nocase(&index.column_name);
 
             let mut rows = Vec::new();
             {
@@ -828,23 +1538,13 @@ impl Catalog {
                 }
             }
 
-            let mut new_root = self.pager.allocate_page()?;
-            {
-                let page = self.pager.get_page(new_root)?;
-                crate::storage::page::set_node_type(
-                    &mut page.data,
-                    crate::storage::page::NODE_LEAF,
-                );
-                crate::storage::page::set_is_root(&mut page.data, true);
-                crate::storage::page::set_parent(&mut page.data, 0);
-                crate::storage::page::set_cell_count(&mut page.data, 0);
-                self.pager.flush_page(new_root)?;
-            }
+            let mut new_root = self.empty_tree()?;
             {
                 let mut index_tree = BTree::open_root(&mut self.pager, new_root)?;
                 for row in rows {
                     if let Some(value) = row.data.0.get(col_idx).cloned() {
-                        new_root = Catalog::insert_index_value(&mut index_tree, value, row.key)?;
+                        new_root =
+                            Catalog::insert_index_value(&mut index_tree, value, row.key, nocase)?;
                     }
                 }
             }
@@ -853,6 +1553,9 @@ impl Catalog {
             }
             rebuilt += 1;
         }
+        if rebuilt > 0 {
+            self.persist_indexes(table_name)?;
+        }
 
         Ok(rebuilt)
     }
@@ -881,7 +1584,10 @@ impl Catalog {
             let mut cursor = catalog_btree.scan_all_rows();
             let mut found = None;
             while let Some(row) = cursor.next() {
-                let (table_name, _rp, _cols, _nn, _defaults, _ai, _fks, _) =
+                if Self::deserialize_view_row(&row).is_some() {
+                    continue;
+                }
+                let (table_name, _rp, _cols, _nn, _defaults, _ai, _fks, _, _, _, _) =
                     Self::deserialize_catalog_row(&row)?;
                 if table_name == name {
                     found = Some(row.key);
@@ -896,36 +1602,293 @@ impl Catalog {
             catalog_btree.delete(key)?;
             let new_root = catalog_btree.root_page();
             if new_root != 1 {
-                let src_buf = {
-                    let src = self.pager.get_page(new_root)?;
-                    let mut buf = [0u8; PAGE_SIZE];
-                    buf.copy_from_slice(&src.data);
-                    buf
-                };
+                let src_buf = self.pager.get_page(new_root)?.data.clone();
                 {
-                    let dst = self.pager.get_page(1)?;
+                    let dst = self.pager.get_page_mut(1)?;
                     dst.data.copy_from_slice(&src_buf);
                 }
                 self.pager.flush_page(1)?;
             }
-            self.tables.remove(name);
+            if let Some(table) = self.tables.remove(name) {
+                self.free_tree(table.root_page)?;
+            }
             Ok(true)
         } else {
             Ok(false)
         }
     }
 
+    /// Record view `name` over the query `sql` in the catalog page. Follows
+    /// the transactional-DDL rule of [`Catalog::create_table_with_fks`].
+    pub fn create_view(&mut self, name: &str, sql: &str) -> io::Result<()> {
+        if self.views.contains_key(name) || self.tables.contains_key(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Table or view {} already exists", name),
+            ));
+        }
+        // View rows are keyed above any table row key.
+        let key = {
+            let mut tree = BTree::open_root(&mut self.pager, 1)?;
+            let mut key = VIEW_KEY_BASE;
+            for row in tree.scan_all_rows() {
+                key = key.max(row.key + 1);
+            }
+            key
+        };
+        let mut tree = BTree::open_root(&mut self.pager, 1)?;
+        tree.insert(key, Self::serialize_view_row(name, sql))?;
+        let new_root = tree.root_page();
+        self.keep_catalog_root(new_root)?;
+        self.views.insert(name.to_string(), sql.to_string());
+        Ok(())
+    }
+
+    /// Drop view `name` if it exists. Returns true if the view was removed.
+    pub fn drop_view(&mut self, name: &str) -> io::Result<bool> {
+        if !self.views.contains_key(name) {
+            return Ok(false);
+        }
+        let key = {
+            let mut tree = BTree::open_root(&mut self.pager, 1)?;
+            tree.scan_all_rows()
+                .find(|row| Self::deserialize_view_row(row).is_some_and(|(n, _)| n == name))
+                .map(|row| row.key)
+        };
+        if let Some(key) = key {
+            let mut tree = BTree::open_root(&mut self.pager, 1)?;
+            tree.delete(key)?;
+            let new_root = tree.root_page();
+            self.keep_catalog_root(new_root)?;
+        }
+        self.views.remove(name);
+        Ok(true)
+    }
+
+    /// The SQL text of view `name`'s query, if there is such a view.
+    pub fn view_sql(&self, name: &str) -> Option<&str> {
+        self.views.get(name).map(String::as_str)
+    }
+
+    /// Copy the catalog tree's root back to page 1 if a split moved it.
+    fn keep_catalog_root(&mut self, root: u32) -> io::Result<()> {
+        if root != 1 {
+            let src_buf = self.pager.get_page(root)?.data.clone();
+            self.pager.get_page_mut(1)?.data.copy_from_slice(&src_buf);
+            self.pager.flush_page(1)?;
+        }
+        Ok(())
+    }
+
+    /// Rename a table, carrying along its indexes, foreign keys that reference
+    /// it, and the `table_column` sequences of its AUTO_INCREMENT columns.
+    /// Follows the transactional-DDL rule of [`Catalog::create_table_with_fks`].
+    pub fn rename_table(&mut self, old: &str, new: &str) -> io::Result<()> {
+        if self.tables.contains_key(new) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Table {} already exists", new),
+            ));
+        }
+        let mut info = self.tables.remove(old).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("Table {} not found", old))
+        })?;
+        info.name = new.to_string();
+        for fk in info.fks.iter_mut() {
+            if fk.parent_table == old {
+                fk.parent_table = new.to_string();
+            }
+        }
+        for idx in self.indexes.values_mut() {
+            if idx.table_name == old {
+                idx.table_name = new.to_string();
+            }
+        }
+        self.rewrite_catalog_row(old, &info)?;
+        for (col, _) in info
+            .columns
+            .iter()
+            .zip(info.auto_increment.iter())
+            .filter(|(_, ai)| **ai)
+        {
+            let seq = format!("{}_{}", old, col.0);
+            if self.sequences.contains_key(&seq) {
+                self.rename_sequence(&seq, &format!("{}_{}", new, col.0))?;
+            }
+        }
+        self.tables.insert(new.to_string(), info);
+
+        let children: Vec<String> = self
+            .tables
+            .values()
+            .filter(|t| t.name != new && t.fks.iter().any(|fk| fk.parent_table == old))
+            .map(|t| t.name.clone())
+            .collect();
+        for child in children {
+            let mut info = self.tables[&child].clone();
+            for fk in info.fks.iter_mut() {
+                if fk.parent_table == old {
+                    fk.parent_table = new.to_string();
+                }
+            }
+            self.rewrite_catalog_row(&child, &info)?;
+            self.tables.insert(child, info);
+        }
+        Ok(())
+    }
+
+    /// Rename a column of `table`, updating its primary key, indexes, foreign
+    /// keys on either side, and its AUTO_INCREMENT sequence. Columns used by a
+    /// generated column's expression cannot be renamed.
+    pub fn rename_column(&mut self, table: &str, old: &str, new: &str) -> io::Result<()> {
+        let mut info = self.get_table(table)?.clone();
+        let pos = info
+            .columns
+            .iter()
+            .position(|(c, _)| c == old)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("Column {} not found", old))
+            })?;
+        if info.columns.iter().any(|(c, _)| c == new) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Column {} already exists in table {}", new, table),
+            ));
+        }
+        let referenced = info.generated.iter().flatten().any(|expr| {
+            crate::sql::ast::expr_to_string(expr)
+                .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .any(|tok| tok.rsplit('.').next() == Some(old))
+        });
+        if referenced {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Column {} is used by a generated column", old),
+            ));
+        }
+
+        let rename = |cols: &mut Vec<String>| {
+            for c in cols.iter_mut().filter(|c| *c == old) {
+                *c = new.to_string();
+            }
+        };
+        info.columns[pos].0 = new.to_string();
+        if let Some(pk) = info.primary_key.as_mut() {
+            rename(pk);
+        }
+        for fk in info.fks.iter_mut() {
+            rename(&mut fk.columns);
+        }
+        self.tables.insert(table.to_string(), info);
+
+        // Foreign keys in any table (including `table` itself) that point at the column.
+        let parents: Vec<String> = self
+            .tables
+            .values()
+            .filter(|t| t.fks.iter().any(|fk| fk.parent_table == table))
+            .map(|t| t.name.clone())
+            .collect();
+        for name in parents {
+            let t = self.tables.get_mut(&name).unwrap();
+            for fk in t.fks.iter_mut().filter(|fk| fk.parent_table == table) {
+                rename(&mut fk.parent_columns);
+            }
+            if name != table {
+                let t = t.clone();
+                self.rewrite_catalog_row(&name, &t)?;
+            }
+        }
+        for idx in self.indexes.values_mut() {
+            if idx.table_name == table && idx.column_name == old {
+                idx.column_name = new.to_string();
+            }
+        }
+        let info = self.tables[table].clone();
+        self.rewrite_catalog_row(table, &info)?;
+
+        let seq = format!("{}_{}", table, old);
+        if info.auto_increment[pos] && self.sequences.contains_key(&seq) {
+            self.rename_sequence(&seq, &format!("{}_{}", table, new))?;
+        }
+        Ok(())
+    }
+
+    /// Replace the persisted catalog row stored under `stored_name` with one
+    /// describing `info`, keeping its key.
+    fn rewrite_catalog_row(&mut self, stored_name: &str, info: &TableInfo) -> io::Result<()> {
+        let key = {
+            let mut tree = BTree::open_root(&mut self.pager, 1)?;
+            let mut found = None;
+            for row in tree.scan_all_rows() {
+                if Self::deserialize_view_row(&row).is_none()
+                    && Self::deserialize_catalog_row(&row)?.0 == stored_name
+                {
+                    found = Some(row.key);
+                    break;
+                }
+            }
+            found
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Table {} not found", stored_name),
+            )
+        })?;
+
+        let cols: Vec<_> = info
+            .columns
+            .iter()
+            .cloned()
+            .zip(info.not_null.iter().cloned())
+            .zip(info.default_values.iter().cloned())
+            .zip(info.auto_increment.iter().cloned())
+            .map(|((((n, t), nn), d), a)| (n, t, nn, d, a))
+            .collect();
+        let pk_cols = info.primary_key.clone().unwrap_or_default();
+        let indexes = self.table_indexes(&info.name);
+        let mut tree = BTree::open_root(&mut self.pager, 1)?;
+        tree.delete(key)?;
+        tree.insert(
+            key,
+            Self::serialize_catalog_row(
+                &info.name,
+                info.root_page,
+                &cols,
+                &info.fks,
+                &pk_cols,
+                &info.generated,
+                &info.nocase,
+                &indexes,
+            ),
+        )?;
+        let new_root_page = tree.root_page();
+        if new_root_page != 1 {
+            let src_buf = self.pager.get_page(new_root_page)?.data.clone();
+            {
+                let dst = self.pager.get_page_mut(1)?;
+                dst.data.copy_from_slice(&src_buf);
+            }
+            self.pager.flush_page(1)?;
+        }
+        Ok(())
+    }
+
     /// Serialize a catalog row into a UTF-8 string:
     ///
     /// [u32 name_len][name_bytes][u32 root_page][u16 num_columns]
     /// for each column: [u32 col_len][col_bytes]
     /// then [u16 num_fks] followed by each foreign key description
+    #[allow(clippy::too_many_arguments)]
     fn serialize_catalog_row(
         name: &str,
         root_page: u32,
         columns: &[(String, ColumnType, bool, Option<Expr>, bool)],
         fks: &[crate::sql::ast::ForeignKey],
         pk: &[String],
+        generated: &[Option<Expr>],
+        nocase: &[bool],
+        indexes: &[IndexInfo],
     ) -> RowData {
         let mut vals = Vec::new();
         vals.push(ColumnValue::Text(name.to_string()));
@@ -935,7 +1898,9 @@ impl Catalog {
             vals.push(ColumnValue::Text(name.clone()));
             vals.push(ColumnValue::Integer(ty.to_code()));
             match ty {
-                ColumnType::Char(size) => vals.push(ColumnValue::Integer(*size as i32)),
+                ColumnType::Char(size) | ColumnType::Varchar(size) => {
+                    vals.push(ColumnValue::Integer(*size as i32))
+                }
                 ColumnType::SmallInt { width, unsigned } => {
                     vals.push(ColumnValue::Integer(*width as i32));
                     vals.push(ColumnValue::Integer(if *unsigned { 1 } else { 0 }));
@@ -953,6 +1918,10 @@ impl Catalog {
                     vals.push(ColumnValue::Integer(*scale as i32));
                     vals.push(ColumnValue::Integer(if *unsigned { 1 } else { 0 }));
                 }
+                ColumnType::Decimal { precision, scale } => {
+                    vals.push(ColumnValue::Integer(*precision as i32));
+                    vals.push(ColumnValue::Integer(*scale as i32));
+                }
                 _ => {}
             }
             vals.push(ColumnValue::Integer(if *nn { 1 } else { 0 }));
@@ -960,7 +1929,7 @@ impl Catalog {
                 Some(expr) => {
                     vals.push(ColumnValue::Integer(1));
                     vals.push(ColumnValue::Text(
-                        crate::sql::ast::expr_to_string(expr).into(),
+                        crate::sql::ast::default_expr_to_string(expr),
                     ));
                 }
                 None => {
@@ -991,10 +1960,43 @@ impl Catalog {
         for c in pk {
             vals.push(ColumnValue::Text(c.clone()));
         }
+        // Trailing section (absent in older catalogs): [count] then
+        // [column_index][expression_text] for each generated column.
+        let gen_cols: Vec<_> = generated
+            .iter()
+            .enumerate()
+            .filter_map(|(i, g)| g.as_ref().map(|e| (i, e)))
+            .collect();
+        vals.push(ColumnValue::Integer(gen_cols.len() as i32));
+        for (i, expr) in gen_cols {
+            vals.push(ColumnValue::Integer(i as i32));
+            vals.push(ColumnValue::Text(crate::sql::ast::expr_to_string(expr)));
+        }
+        // Trailing section (absent in older catalogs): [count] then the
+        // [column_index] of each `COLLATE NOCASE` column.
+        let nocase_cols: Vec<_> = nocase
+            .iter()
+            .enumerate()
+            .filter(|(_, nc)| **nc)
+            .map(|(i, _)| i)
+            .collect();
+        vals.push(ColumnValue::Integer(nocase_cols.len() as i32));
+        for i in nocase_cols {
+            vals.push(ColumnValue::Integer(i as i32));
+        }
+        // Trailing section (absent in older catalogs): [count] then
+        // [index_name][column_name][root_page] for each index on the table.
+        vals.push(ColumnValue::Integer(indexes.len() as i32));
+        for index in indexes {
+            vals.push(ColumnValue::Text(index.name.clone()));
+            vals.push(ColumnValue::Text(index.column_name.clone()));
+            vals.push(ColumnValue::Integer(index.root_page as i32));
+        }
         RowData(vals)
     }
 
-    /// Deserialize a catalog row back into (table_name, root_page, Vec<columns>, Vec<ForeignKey>).
+    /// Deserialize a catalog row back into (table_name, root_page, Vec<columns>, Vec<ForeignKey>)
+    /// and the rest of the table's metadata, ending with its indexes.
     fn deserialize_catalog_row(
         row: &Row,
     ) -> io::Result<(
@@ -1006,6 +2008,9 @@ impl Catalog {
         Vec<bool>,
         Vec<crate::sql::ast::ForeignKey>,
         Vec<String>,
+        Vec<Option<Expr>>,
+        Vec<bool>,
+        Vec<IndexInfo>,
     )> {
         let values = &row.data.0;
         if values.len() < 3 {
@@ -1056,6 +2061,14 @@ impl Catalog {
                     idx += 1;
                     ColumnType::Char(size)
                 }
+                Some(ColumnType::Varchar(_)) => {
+                    let size = match values.get(idx) {
+                        Some(ColumnValue::Integer(sz)) => *sz as usize,
+                        _ => return Err(io::Error::other("varchar size")),
+                    };
+                    idx += 1;
+                    ColumnType::Varchar(size)
+                }
                 Some(ColumnType::SmallInt { .. }) => {
                     let width = match values.get(idx) {
                         Some(ColumnValue::Integer(w)) => *w as usize,
@@ -1104,6 +2117,19 @@ impl Catalog {
                         unsigned,
                     }
                 }
+                Some(ColumnType::Decimal { .. }) => {
+                    let precision = match values.get(idx) {
+                        Some(ColumnValue::Integer(p)) => *p as usize,
+                        _ => 10,
+                    };
+                    idx += 1;
+                    let scale = match values.get(idx) {
+                        Some(ColumnValue::Integer(s)) => *s as usize,
+                        _ => 0,
+                    };
+                    idx += 1;
+                    ColumnType::Decimal { precision, scale }
+                }
                 Some(ColumnType::Date) => ColumnType::Date,
                 Some(other) => other,
                 None => return Err(io::Error::new(io::ErrorKind::Other, "bad type")),
@@ -1215,17 +2241,91 @@ impl Catalog {
                 idx += 1;
             }
         }
+        let mut generated = vec![None; columns.len()];
+        let gen_len = match values.get(idx) {
+            Some(ColumnValue::Integer(i)) => *i as usize,
+            _ => 0,
+        };
+        idx += 1;
+        for _ in 0..gen_len {
+            let (Some(ColumnValue::Integer(col)), Some(ColumnValue::Text(text))) =
+                (values.get(idx), values.get(idx + 1))
+            else {
+                return Err(io::Error::other("generated column"));
+            };
+            let expr = crate::sql::parser::parse_expression_str(text).map_err(io::Error::other)?;
+            if let Some(slot) = generated.get_mut(*col as usize) {
+                *slot = Some(expr);
+            }
+            idx += 2;
+        }
+        let mut nocase = vec![false; columns.len()];
+        let nocase_len = match values.get(idx) {
+            Some(ColumnValue::Integer(i)) => *i as usize,
+            _ => 0,
+        };
+        idx += 1;
+        for _ in 0..nocase_len {
+            let Some(ColumnValue::Integer(col)) = values.get(idx) else {
+                return Err(io::Error::other("nocase column"));
+            };
+            if let Some(slot) = nocase.get_mut(*col as usize) {
+                *slot = true;
+            }
+            idx += 1;
+        }
+        let index_len = match values.get(idx) {
+            Some(ColumnValue::Integer(i)) => *i as usize,
+            _ => 0,
+        };
+        idx += 1;
+        let mut indexes = Vec::new();
+        for _ in 0..index_len {
+            let (
+                Some(ColumnValue::Text(index_name)),
+                Some(ColumnValue::Text(column_name)),
+                Some(ColumnValue::Integer(index_root)),
+            ) = (values.get(idx), values.get(idx + 1), values.get(idx + 2))
+            else {
+                return Err(io::Error::other("index entry"));
+            };
+            indexes.push(IndexInfo {
+                name: index_name.clone(),
+                table_name: name.clone(),
+                column_name: column_name.clone(),
+                root_page: *index_root as u32,
+            });
+            idx += 3;
+        }
         Ok((
-            name, root_page, columns, not_null, defaults, auto_inc, fks, pk_cols,
+            name, root_page, columns, not_null, defaults, auto_inc, fks, pk_cols, generated,
+            nocase, indexes,
         ))
     }
 
+    /// A view's catalog row: `[name, sql]`. Table rows always carry their
+    /// root page as an integer second value, so the two never look alike.
+    fn serialize_view_row(name: &str, sql: &str) -> RowData {
+        RowData(vec![
+            ColumnValue::Text(name.to_string()),
+            ColumnValue::Text(sql.to_string()),
+        ])
+    }
+
+    /// The `(name, sql)` of a view's catalog row, or `None` for a table's.
+    fn deserialize_view_row(row: &Row) -> Option<(String, String)> {
+        match row.data.0.as_slice() {
+            [ColumnValue::Text(name), ColumnValue::Text(sql)] => Some((name.clone(), sql.clone())),
+            _ => None,
+        }
+    }
+
     fn serialize_sequence_row(name: &str, current: i64, start: i64, increment: i64) -> RowData {
         RowData(vec![
             ColumnValue::Text(name.to_string()),
-            ColumnValue::Integer(current as i32),
-            ColumnValue::Integer(start as i32),
-            ColumnValue::Integer(increment as i32),
+            ColumnValue::BigInt(current),
+            ColumnValue::BigInt(start),
+            ColumnValue::BigInt(increment),
         ])
     }
 
@@ -1238,18 +2338,71 @@ impl Catalog {
             ColumnValue::Text(s) => s.clone(),
             _ => return Err(io::Error::new(io::ErrorKind::Other, "seq name")),
         };
-        let cur = match vals[1] {
-            ColumnValue::Integer(i) => i as i64,
-            _ => 0,
-        };
-        let start = match vals[2] {
-            ColumnValue::Integer(i) => i as i64,
-            _ => 1,
-        };
-        let inc = match vals[3] {
-            ColumnValue::Integer(i) => i as i64,
-            _ => 1,
-        };
+        // Older catalogs stored these as INTEGER; both widths read back.
+        let cur = vals[1].as_row_key().unwrap_or(0);
+        let start = vals[2].as_row_key().unwrap_or(1);
+        let inc = vals[3].as_row_key().unwrap_or(1);
         Ok((name, cur, start, inc))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::runtime::{execute_select_statement, handle_statement};
+    use crate::sql::parser::parse_statement;
+    use tempfile::NamedTempFile;
+
+    fn run(catalog: &mut Catalog, sql: &str) {
+        handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+    }
+
+    fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+        let mut rows = Vec::new();
+        execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut rows, None).unwrap();
+        rows.into_iter().map(|r| r.join(",")).collect()
+    }
+
+    #[test]
+    fn reindex_repairs_a_corrupted_index_tree() {
+        let file = NamedTempFile::new().unwrap();
+        let mut catalog =
+            Catalog::open(Pager::new(file.path().to_str().unwrap()).unwrap()).unwrap();
+        run(&mut catalog, "CREATE TABLE t (id INTEGER, name TEXT)");
+        run(
+            &mut catalog,
+            "INSERT INTO t VALUES (1, 'alice'), (2, 'bob'), (3, 'carol'), (4, 'bob')",
+        );
+        run(&mut catalog, "CREATE INDEX idx_name ON t (name)");
+        assert_eq!(
+            ids(&mut catalog, "SELECT id FROM t WHERE name = 'bob'"),
+            vec!["2", "4"]
+        );
+
+        // Drop every posting, then add one that points at the wrong row.
+        let root = catalog.find_index("t", "name").unwrap().root_page;
+        let page = catalog.pager.get_page(root).unwrap();
+        crate::storage::page::set_cell_count(&mut page.data, 0);
+        catalog.pager.flush_page(root).unwrap();
+        let mut tree = BTree::open_root(&mut catalog.pager, root).unwrap();
+        Catalog::insert_index_value(&mut tree, ColumnValue::Text("carol".into()), 1, false)
+            .unwrap();
+        assert!(ids(&mut catalog, "SELECT id FROM t WHERE name = 'bob'").is_empty());
+        assert!(ids(&mut catalog, "SELECT id FROM t WHERE name = 'carol'").is_empty());
+
+        run(&mut catalog, "REINDEX INDEX idx_name");
+        assert_eq!(
+            ids(&mut catalog, "SELECT id FROM t WHERE name = 'bob'"),
+            vec!["2", "4"]
+        );
+        assert_eq!(
+            ids(&mut catalog, "SELECT id FROM t WHERE name = 'carol'"),
+            vec!["3"]
+        );
+        assert_eq!(
+            ids(&mut catalog, "SELECT id FROM t WHERE name = 'alice'"),
+            vec!["1"]
+        );
+        assert_eq!(catalog.integrity_check().unwrap(), None);
+    }
+}
@@ -1,12 +1,22 @@
 use crate::error::{DbError, DbResult};
-use crate::sql::ast::Expr;
+use crate::sql::ast::{Expr, try_evaluate_expression_typed};
 use crate::sql::functions::FunctionEvaluator;
-use crate::storage::row::ColumnValue;
+use crate::sql::parser::parse_constant_arithmetic;
+use crate::storage::row::{ColumnType, ColumnValue};
+use std::collections::HashMap;
 
 pub struct DefaultConstraint;
 
 impl DefaultConstraint {
     pub fn evaluate(expr: &Expr) -> DbResult<String> {
+        Self::evaluate_for(expr, ColumnType::Text)
+    }
+
+    /// Render the default `expr` for a column of type `col_type`. Clock
+    /// functions are converted to the column's type first, so
+    /// `DATE DEFAULT CURRENT_TIMESTAMP` stores just the date, and constant
+    /// arithmetic such as `DEFAULT (60 * 60)` is computed.
+    pub fn evaluate_for(expr: &Expr, col_type: ColumnType) -> DbResult<String> {
         match expr {
             Expr::Literal(s) => Ok(s.clone()),
             Expr::FunctionCall { name, args } => {
@@ -18,15 +28,71 @@ impl DefaultConstraint {
                     })
                     .collect::<Result<_, _>>()?;
                 match FunctionEvaluator::evaluate_function(name, &arg_vals) {
-                    Ok(val) => Ok(val.to_string_value()),
+                    Ok(val) => Ok(Self::convert_clock_value(val, col_type).to_string_value()),
                     Err(_) => Err(DbError::InvalidValue("function error".into())),
                 }
             }
+            Expr::Add { left, right }
+            | Expr::Subtract { left, right }
+            | Expr::Multiply { left, right }
+            | Expr::Divide { left, right }
+            | Expr::Modulo { left, right } => {
+                // Nested operations are computed first, innermost outward.
+                let (left, right) = (Self::evaluate_operand(left)?, Self::evaluate_operand(right)?);
+                // Whole-number operands are declared BIGINT so `+`, `-` and
+                // `*` stay exact.
+                let types: HashMap<String, ColumnType> = [&left, &right]
+                    .into_iter()
+                    .filter(|operand| operand.parse::<i64>().is_ok())
+                    .map(|operand| (operand.clone(), ColumnType::BigInt))
+                    .collect();
+                let expr = match expr {
+                    Expr::Add { .. } => Expr::Add { left, right },
+                    Expr::Subtract { .. } => Expr::Subtract { left, right },
+                    Expr::Multiply { .. } => Expr::Multiply { left, right },
+                    Expr::Divide { .. } => Expr::Divide { left, right },
+                    _ => Expr::Modulo { left, right },
+                };
+                Ok(try_evaluate_expression_typed(&expr, &HashMap::new(), &types)?.to_string_value())
+            }
             _ => Err(DbError::InvalidValue(
                 "unsupported default expression".into(),
             )),
         }
     }
+
+    /// The value of an arithmetic operand: a parenthesized operation is
+    /// computed, a number is taken as it is.
+    fn evaluate_operand(operand: &str) -> DbResult<String> {
+        match operand
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(parse_constant_arithmetic)
+        {
+            Some(inner) => Self::evaluate(&inner),
+            None => Ok(operand.to_string()),
+        }
+    }
+
+    fn convert_clock_value(value: ColumnValue, col_type: ColumnType) -> ColumnValue {
+        const SECS_PER_DAY: i64 = 86_400;
+        match (value, col_type) {
+            (ColumnValue::DateTime(ts), ColumnType::Date) => {
+                ColumnValue::Date(ts.div_euclid(SECS_PER_DAY) as i32)
+            }
+            (ColumnValue::DateTime(ts), ColumnType::Time) => {
+                ColumnValue::Time(ts.rem_euclid(SECS_PER_DAY) as i32)
+            }
+            (ColumnValue::DateTime(ts), ColumnType::Timestamp) => ColumnValue::Timestamp(ts),
+            (ColumnValue::Date(days), ColumnType::DateTime) => {
+                ColumnValue::DateTime(days as i64 * SECS_PER_DAY)
+            }
+            (ColumnValue::Date(days), ColumnType::Timestamp) => {
+                ColumnValue::Timestamp(days as i64 * SECS_PER_DAY)
+            }
+            (value, _) => value,
+        }
+    }
 }
 
 use super::Constraint;
@@ -3,7 +3,7 @@ use crate::catalog::{Catalog, TableInfo};
 use crate::error::{DbError, DbResult};
 use crate::sql::ast::ForeignKey;
 use crate::storage::btree::BTree;
-use crate::storage::row::{ColumnValue, RowData};
+use crate::storage::row::{ColumnValue, RowData, RowKey};
 use crate::transaction::Snapshot;
 
 pub struct ForeignKeyConstraint<'a> {
@@ -27,9 +27,12 @@ impl<'a> Constraint for ForeignKeyConstraint<'a> {
                 .iter()
                 .position(|(c, _)| c == &fk.columns[0])
                 .ok_or_else(|| DbError::ColumnNotFound(fk.columns[0].clone()))?;
-            let child_val = match row.0[col_idx] {
-                ColumnValue::Integer(i) => i,
-                _ => return Err(DbError::InvalidValue("FK column must be INTEGER".into())),
+            // A NULL reference points at no parent row and is always allowed.
+            if matches!(row.0[col_idx], ColumnValue::Null) {
+                continue;
+            }
+            let Some(child_val) = row.0[col_idx].as_row_key() else {
+                return Err(DbError::InvalidValue("FK column must be INTEGER".into()));
             };
             let parent_root = catalog.get_table(&fk.parent_table)?.root_page;
             let mut parent_btree = BTree::open_root(&mut catalog.pager, parent_root)?;
@@ -59,23 +62,20 @@ impl<'a> Constraint for ForeignKeyConstraint<'a> {
                         .iter()
                         .position(|(c, _)| c == &fk.parent_columns[0])
                         .unwrap();
-                    let parent_val = match row.0[parent_idx] {
-                        ColumnValue::Integer(i) => i,
-                        _ => continue,
+                    let Some(parent_val) = row.0[parent_idx].as_row_key() else {
+                        continue;
                     };
                     let child_idx = child
                         .columns
                         .iter()
                         .position(|(c, _)| c == &fk.columns[0])
                         .unwrap();
-                    let mut matches: Vec<(i32, RowData)> = Vec::new();
+                    let mut matches: Vec<(RowKey, RowData)> = Vec::new();
                     {
                         let mut scan_tree = BTree::open_root(&mut catalog.pager, child.root_page)?;
                         for crow in scan_tree.scan_visible(snapshot)? {
-                            if let ColumnValue::Integer(v) = crow.data.0[child_idx] {
-                                if v == parent_val {
-                                    matches.push((crow.key, crow.data.clone()));
-                                }
+                            if crow.data.0[child_idx].as_row_key() == Some(parent_val) {
+                                matches.push((crow.key, crow.data.clone()));
                             }
                         }
                     }
@@ -84,20 +84,15 @@ impl<'a> Constraint for ForeignKeyConstraint<'a> {
                             let tx_id = snapshot
                                 .current_tx_id
                                 .unwrap_or(crate::storage::row::COMMITTED_BOOTSTRAP_TX);
-                            let mut del_tree =
-                                BTree::open_root(&mut catalog.pager, child.root_page)?;
-                            for (k, _) in &matches {
-                                del_tree.mark_deleted_visible(*k, snapshot, tx_id)?;
-                            }
-                            let new_root_c = del_tree.root_page();
-                            drop(del_tree);
+                            catalog.with_table_tree(&child.name, |del_tree| {
+                                for (k, _) in &matches {
+                                    del_tree.mark_deleted_visible(*k, snapshot, tx_id)?;
+                                }
+                                Ok::<_, DbError>(())
+                            })?;
                             for (k, data) in &matches {
                                 catalog.remove_from_indexes(&child.name, data, *k)?;
                             }
-                            if new_root_c != child.root_page {
-                                catalog.get_table_mut(&child.name)?.root_page = new_root_c;
-                                catalog.update_catalog_root(&child.name, new_root_c)?;
-                            }
                         } else {
                             return Err(DbError::ForeignKeyViolation(format!(
                                 "Cannot delete {}: referenced by {}.{}",
@@ -17,33 +17,63 @@ impl<'a> Constraint for PrimaryKeyConstraint<'a> {
         row: &mut RowData,
         snapshot: &Snapshot,
     ) -> DbResult<()> {
+        let positions = self
+            .columns
+            .iter()
+            .map(|col| {
+                table
+                    .columns
+                    .iter()
+                    .position(|(c, _)| c == col)
+                    .ok_or_else(|| DbError::ColumnNotFound(col.clone()))
+            })
+            .collect::<DbResult<Vec<usize>>>()?;
         // ensure not null
-        for col in self.columns {
-            if let Some(idx) = table.columns.iter().position(|(c, _)| c == col) {
-                if matches!(row.0[idx], ColumnValue::Null) {
-                    return Err(DbError::NullViolation(col.clone()));
-                }
+        for (col, &idx) in self.columns.iter().zip(&positions) {
+            if matches!(row.0[idx], ColumnValue::Null) {
+                return Err(DbError::NullViolation(col.clone()));
             }
         }
-        // check uniqueness
+        // check uniqueness of the whole key tuple; for a composite key each
+        // column may repeat on its own
         let mut btree = BTree::open_root(&mut catalog.pager, table.root_page)?;
         for existing in btree.scan_visible(snapshot)? {
-            let mut equal = true;
-            for col in self.columns {
-                if let Some(idx) = table.columns.iter().position(|(c, _)| c == col) {
-                    if existing.data.0[idx] != row.0[idx] {
-                        equal = false;
-                        break;
-                    }
-                }
-            }
-            if equal {
-                if let Some(ColumnValue::Integer(i)) = row.0.get(0) {
-                    return Err(DbError::DuplicateKey(*i));
-                }
-                return Err(DbError::DuplicateKey(0));
+            if positions.iter().all(|&idx| existing.data.0[idx] == row.0[idx]) {
+                return Err(duplicate_key_error(table, self.columns, row));
             }
         }
         Ok(())
     }
 }
+
+/// Error for a row of `table` whose primary key over `columns` is already
+/// taken. A key made only of integers is reported by the row's key as
+/// `DuplicateKey`; any other key names the table and the offending values,
+/// e.g. `'a@b.c'` or `(1, 'x')` for a composite key.
+pub(crate) fn duplicate_key_error(table: &TableInfo, columns: &[String], row: &RowData) -> DbError {
+    let values: Vec<&ColumnValue> = columns
+        .iter()
+        .filter_map(|col| table.columns.iter().position(|(c, _)| c == col))
+        .map(|idx| &row.0[idx])
+        .collect();
+    let row_key = row.0.first().and_then(ColumnValue::as_row_key);
+    if let Some(key) = row_key.filter(|_| values.iter().all(|v| v.as_row_key().is_some())) {
+        return DbError::DuplicateKey(key);
+    }
+    let rendered: Vec<String> = values
+        .iter()
+        .map(|v| match v {
+            ColumnValue::Text(s) => format!("'{}'", s.replace('\'', "''")),
+            other => Catalog::value_to_string(other),
+        })
+        .collect();
+    let key = if rendered.len() == 1 {
+        rendered[0].clone()
+    } else {
+        format!("({})", rendered.join(", "))
+    };
+    DbError::DuplicateKeyValue {
+        table: table.name.clone(),
+        key,
+    }
+}
@@ -21,7 +21,7 @@ use std::sync::{Arc, Mutex, MutexGuard};
 use crate::{
     catalog::Catalog,
     error::DbResult,
-    execution::runtime::{execute_select_with_indexes, handle_statement},
+    execution::runtime::{QueryResult, execute_select_with_indexes, handle_statement},
     sql::ast::Statement,
     storage::pager::Pager,
     storage::row::Row,
@@ -82,7 +82,7 @@ impl Database {
     }
 
     /// Execute one DML/DDL statement within transaction `tx`.
-    pub fn execute(&self, tx: &TxHandle, stmt: Statement) -> DbResult<()> {
+    pub fn execute(&self, tx: &TxHandle, stmt: Statement) -> DbResult<QueryResult> {
         let mut core = self.lock();
         let Core { catalog, manager } = &mut *core;
         manager.run_with(catalog, tx.id, |catalog| handle_statement(catalog, stmt))
@@ -117,10 +117,10 @@ impl Database {
 
     /// Run one statement in its own transaction, committing on success and
     /// aborting on error.
-    pub fn autocommit(&self, stmt: Statement) -> DbResult<()> {
+    pub fn autocommit(&self, stmt: Statement) -> DbResult<QueryResult> {
         let tx = self.begin()?;
         match self.execute(&tx, stmt) {
-            Ok(()) => self.commit(tx),
+            Ok(result) => self.commit(tx).map(|()| result),
             Err(err) => {
                 let _ = self.abort(tx);
                 Err(err)
@@ -0,0 +1,95 @@
+//! Minimal CSV reader for [`Engine::import_csv`](super::Engine::import_csv).
+//!
+//! Fields are separated by commas and records by newlines (`\r\n` or `\n`).
+//! A field wrapped in double quotes may contain commas, newlines, and `""`
+//! for a literal quote. An empty unquoted field is `None` (SQL NULL); an
+//! empty quoted field is the empty string.
+
+/// One record of the file and the 1-based line it starts on.
+pub(crate) struct CsvRecord {
+    pub line: usize,
+    pub fields: Vec<Option<String>>,
+}
+
+/// Split `text` into records. Blank lines are skipped. On malformed input
+/// returns the line of the offending record and a description.
+pub(crate) fn parse_records(text: &str) -> Result<Vec<CsvRecord>, (usize, String)> {
+    let mut records = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+
+    while chars.peek().is_some() {
+        let start_line = line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut at_field_start = true;
+        while let Some(ch) = chars.next() {
+            match ch {
+                '"' if at_field_start => {
+                    quoted = true;
+                    at_field_start = false;
+                    loop {
+                        match chars.next() {
+                            Some('"') if chars.peek() == Some(&'"') => {
+                                chars.next();
+                                field.push('"');
+                            }
+                            Some('"') => break,
+                            Some(c) => {
+                                if c == '\n' {
+                                    line += 1;
+                                }
+                                field.push(c);
+                            }
+                            None => {
+                                return Err((start_line, "unterminated quoted field".to_string()));
+                            }
+                        }
+                    }
+                    match chars.peek() {
+                        None | Some(',') | Some('\n') | Some('\r') => {}
+                        Some(c) => {
+                            return Err((
+                                start_line,
+                                format!("unexpected '{}' after closing quote", c),
+                            ));
+                        }
+                    }
+                }
+                ',' => {
+                    fields.push(finish_field(&mut field, quoted));
+                    quoted = false;
+                    at_field_start = true;
+                }
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    line += 1;
+                    break;
+                }
+                c => {
+                    at_field_start = false;
+                    field.push(c);
+                }
+            }
+        }
+        if fields.is_empty() && field.is_empty() && !quoted {
+            continue;
+        }
+        fields.push(finish_field(&mut field, quoted));
+        records.push(CsvRecord {
+            line: start_line,
+            fields,
+        });
+    }
+    Ok(records)
+}
+
+fn finish_field(field: &mut String, quoted: bool) -> Option<String> {
+    let value = std::mem::take(field);
+    if value.is_empty() && !quoted {
+        None
+    } else {
+        Some(value)
+    }
+}
@@ -1,7 +1,19 @@
+mod csv;
+
 use crate::{
-    catalog::Catalog, error::DbResult, execution::runtime::handle_statement, sql::ast::Statement,
-    storage::pager::Pager, storage::vacuum::VacuumReport, transaction::TransactionManager,
+    catalog::Catalog,
+    error::{DbError, DbResult},
+    execution::runtime::{
+        QueryResult, execute_insert, format_csv, format_json, handle_export_statement, handle_statement, validate_insert,
+    },
+    sql::ast::{Expr, Statement, bind_placeholders, placeholder_count},
+    sql::parser::{parse_statement, split_statements},
+    storage::pager::Pager,
+    storage::row::{ColumnType, ColumnValue},
+    storage::vacuum::VacuumReport,
+    transaction::TransactionManager,
 };
+use thiserror::Error;
 
 pub struct Engine {
     pub catalog: Catalog,
@@ -10,18 +22,199 @@ pub struct Engine {
 
 impl Engine {
     pub fn new(filename: &str) -> Self {
-        let catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
-        Engine {
-            catalog,
+        Engine::with_pager(Pager::new(filename).unwrap()).unwrap()
+    }
+
+    /// Open an engine over an already configured pager, e.g. one with a
+    /// non-default [`SyncMode`](crate::storage::pager::SyncMode) or group
+    /// commit size.
+    pub fn with_pager(pager: Pager) -> std::io::Result<Self> {
+        Ok(Engine {
+            catalog: Catalog::open(pager)?,
             transaction_manager: TransactionManager::new(),
-        }
+        })
     }
 
-    pub fn execute(&mut self, stmt: Statement) -> DbResult<()> {
+    pub fn execute(&mut self, stmt: Statement) -> DbResult<QueryResult> {
         self.transaction_manager
             .execute(&mut self.catalog, stmt, handle_statement)
     }
 
+    /// Turn auto-commit on or off, like `SET autocommit = on | off`. While it
+    /// is off, the first mutating statement begins a transaction that stays
+    /// open until COMMIT or ROLLBACK; turning it back on commits that
+    /// transaction.
+    pub fn set_autocommit(&mut self, on: bool) -> DbResult<()> {
+        self.transaction_manager.set_autocommit(&mut self.catalog, on)?;
+        Ok(())
+    }
+
+    /// Parse `sql` as a single statement and run it with [`Engine::execute`].
+    /// A parse failure is returned as [`DbError::ParseError`].
+    pub fn execute_str(&mut self, sql: &str) -> DbResult<QueryResult> {
+        let stmt = parse_statement(sql).map_err(DbError::ParseError)?;
+        self.execute(stmt)
+    }
+
+    /// Run every statement of `script`, split at top-level semicolons, in
+    /// order, as if each were passed to [`Engine::execute`]. Stops at the
+    /// first statement that fails to parse or execute; statements before it
+    /// keep their effects.
+    pub fn run_script(&mut self, script: &str) -> Result<Vec<QueryResult>, ScriptError> {
+        let mut results = Vec::new();
+        for (idx, sql) in split_statements(script).into_iter().enumerate() {
+            let result = parse_statement(sql)
+                .map_err(DbError::ParseError)
+                .and_then(|stmt| self.execute(stmt))
+                .map_err(|error| ScriptError {
+                    statement: idx + 1,
+                    error,
+                })?;
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    /// Insert every record of the CSV file at `path` into `table_name` through
+    /// the same conversions and constraint checks as `INSERT`. With
+    /// `has_header`, the first record names the columns each field goes to;
+    /// otherwise fields map to the table's columns in order. An empty unquoted
+    /// field is NULL. The import runs in one transaction (or the open one), so
+    /// the first failing record aborts it and, under auto-commit, rolls back
+    /// every row before it. Returns the number of rows inserted.
+    pub fn import_csv(
+        &mut self,
+        table_name: &str,
+        path: &str,
+        has_header: bool,
+    ) -> Result<usize, CsvImportError> {
+        let text = std::fs::read_to_string(path).map_err(DbError::from)?;
+        let mut records = csv::parse_records(&text).map_err(|(line, msg)| {
+            CsvImportError::Record {
+                line,
+                error: DbError::ParseError(msg),
+            }
+        })?;
+        if self.catalog.get_table(table_name).is_err() {
+            return Err(DbError::TableNotFound(table_name.to_string()).into());
+        }
+        let columns = if has_header && !records.is_empty() {
+            let header = records.remove(0);
+            let mut names = Vec::with_capacity(header.fields.len());
+            for name in header.fields {
+                let name = name.ok_or_else(|| CsvImportError::Record {
+                    line: header.line,
+                    error: DbError::ParseError("empty column name in header".into()),
+                })?;
+                names.push(name.trim().to_string());
+            }
+            Some(names)
+        } else {
+            None
+        };
+
+        let mut failed_line = 0;
+        self.transaction_manager
+            .execute_write(&mut self.catalog, |catalog| {
+                let mut inserted = 0;
+                for record in records {
+                    failed_line = record.line;
+                    let row = record
+                        .fields
+                        .into_iter()
+                        .map(|field| field.map_or(Expr::Null, Expr::Literal))
+                        .collect();
+                    inserted += execute_insert(catalog, table_name, columns.clone(), vec![row])?;
+                }
+                Ok(inserted)
+            })
+            .map_err(|error| CsvImportError::Record {
+                line: failed_line,
+                error,
+            })
+    }
+
+    /// Run a `SELECT` and return its result as a JSON array of objects keyed
+    /// by column name; see [`format_json`] for how values are encoded.
+    pub fn query_to_json(&mut self, sql: &str) -> DbResult<String> {
+        let (json, _) = self.format_query(sql, "query_to_json", format_json)?;
+        Ok(json)
+    }
+
+    /// Run a `SELECT` and write its result to `path` as CSV with a header
+    /// row; see [`format_csv`] for quoting. Returns the number of data rows
+    /// written.
+    pub fn export_csv(&mut self, sql: &str, path: &str) -> DbResult<usize> {
+        let (csv, rows) = self.format_query(sql, "export_csv", format_csv)?;
+        std::fs::write(path, csv)?;
+        Ok(rows)
+    }
+
+    /// Parse and run `sql`, which must be a query, for the export named by
+    /// `caller`, and render the result with `format`. Returns the rendering
+    /// and the number of rows.
+    fn format_query<F>(&mut self, sql: &str, caller: &str, format: F) -> DbResult<(String, usize)>
+    where
+        F: Fn(&[(String, ColumnType)], &[Vec<String>]) -> String,
+    {
+        let stmt = parse_statement(sql).map_err(DbError::ParseError)?;
+        if !matches!(stmt, Statement::Select { .. } | Statement::SetOp { .. } | Statement::Values(_)) {
+            return Err(DbError::InvalidValue(format!(
+                "{} expects a SELECT statement",
+                caller
+            )));
+        }
+        let result = self
+            .transaction_manager
+            .execute(&mut self.catalog, stmt, handle_export_statement)?;
+        match result {
+            QueryResult::Rows { header, rows } => Ok((format(&header, &rows), rows.len())),
+            _ => Ok((format(&[], &[]), 0)),
+        }
+    }
+
+    /// Parse `sql` once for repeated execution. `?` marks a parameter; the
+    /// values are supplied to [`Prepared::execute`] in order.
+    pub fn prepare(&mut self, sql: &str) -> DbResult<Prepared<'_>> {
+        let stmt = parse_statement(sql).map_err(DbError::ParseError)?;
+        let params = placeholder_count(&stmt);
+        Ok(Prepared {
+            engine: self,
+            stmt,
+            params,
+        })
+    }
+
+    /// Insert typed rows into `table_name`, auto-committing like a SQL
+    /// `INSERT` unless an explicit transaction is open.
+    pub fn insert_typed(
+        &mut self,
+        table_name: &str,
+        rows: Vec<Vec<ColumnValue>>,
+    ) -> DbResult<usize> {
+        self.transaction_manager
+            .execute_write(&mut self.catalog, |catalog| {
+                catalog.insert_typed(table_name, rows)
+            })
+    }
+
+    /// Check an `INSERT` statement without running it: every row goes through
+    /// the conversions and constraint checks of a real insert, but nothing is
+    /// written. Returns the number of rows that would be inserted or the first
+    /// error.
+    pub fn validate_insert(&mut self, stmt: Statement) -> DbResult<usize> {
+        match stmt {
+            Statement::Insert {
+                table_name,
+                columns,
+                rows,
+            } => validate_insert(&mut self.catalog, &table_name, columns, rows),
+            _ => Err(DbError::InvalidValue(
+                "validate_insert expects an INSERT statement".into(),
+            )),
+        }
+    }
+
     /// Physically prune obsolete MVCC versions from `table_name`. The vacuum
     /// cutoff (`global_xmin`) comes from the transaction manager, which owns the
     /// set of currently-live transactions.
@@ -30,3 +223,57 @@ impl Engine {
         self.catalog.vacuum_table(table_name, global_xmin)
     }
 }
+
+/// The statement of a script that failed in [`Engine::run_script`],
+/// numbered from 1.
+#[derive(Debug, Error)]
+#[error("statement {statement}: {error}")]
+pub struct ScriptError {
+    pub statement: usize,
+    #[source]
+    pub error: DbError,
+}
+
+/// Why [`Engine::import_csv`] stopped.
+#[derive(Debug, Error)]
+pub enum CsvImportError {
+    /// The file could not be read or the table does not exist.
+    #[error(transparent)]
+    Db(#[from] DbError),
+    /// The record starting on `line` (1-based, counting the header) is
+    /// malformed or was rejected by the insert.
+    #[error("line {line}: {error}")]
+    Record {
+        line: usize,
+        #[source]
+        error: DbError,
+    },
+}
+
+/// A parsed statement with `?` parameters, bound to the engine that prepared
+/// it. Each execution substitutes new values into the stored AST; the SQL
+/// text is never parsed again and values are never quoted into it.
+pub struct Prepared<'a> {
+    engine: &'a mut Engine,
+    stmt: Statement,
+    params: usize,
+}
+
+impl Prepared<'_> {
+    /// Number of parameters the statement expects.
+    pub fn param_count(&self) -> usize {
+        self.params
+    }
+
+    pub fn execute(&mut self, params: &[ColumnValue]) -> DbResult<QueryResult> {
+        if params.len() != self.params {
+            return Err(DbError::InvalidValue(format!(
+                "Statement expects {} parameters, got {}",
+                self.params,
+                params.len()
+            )));
+        }
+        let stmt = bind_placeholders(&self.stmt, params).map_err(DbError::InvalidValue)?;
+        self.engine.execute(stmt)
+    }
+}
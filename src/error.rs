@@ -8,7 +8,9 @@ pub enum DbError {
     #[error("column '{0}' not found")]
     ColumnNotFound(String),
     #[error("duplicate primary key {0}")]
-    DuplicateKey(i32),
+    DuplicateKey(i64),
+    #[error("duplicate primary key {key} in table '{table}'")]
+    DuplicateKeyValue { table: String, key: String },
     #[error("null value in column '{0}' violates not-null constraint")]
     NullViolation(String),
     #[error("value out of range")]
@@ -24,7 +26,7 @@ pub enum DbError {
     #[error("{0} not found")]
     NotFound(String),
     #[error("write conflict on logical key {0}")]
-    WriteConflict(i32),
+    WriteConflict(i64),
     #[error(transparent)]
     Io(#[from] io::Error),
 }
@@ -4,7 +4,7 @@ pub mod runtime;
 
 pub use executor::Executor;
 pub use plan::PlanNode;
-pub use runtime::{execute_delete, execute_select_with_indexes, execute_update, handle_statement, execute_select_statement, row_to_strings};
+pub use runtime::{QueryResult, execute_delete, execute_select_with_indexes, execute_update, handle_statement, execute_select_statement, for_each_row, row_to_strings};
 
 /// Entry point for executing a plan (stub).
 pub fn execute_plan(plan: PlanNode /*, btree: &mut storage::BTree */) {
@@ -19,13 +19,13 @@ pub fn execute_plan(plan: PlanNode /*, btree: &mut storage::BTree */) {
             println!("Executing: Insert into {} {:?}", table_name, values);
             // In future: btree.insert(key, &payload).unwrap();
         }
-        PlanNode::CreateIndex { index_name, table_name, column_name } => {
+        PlanNode::CreateIndex { index_name, table_name, column_name, .. } => {
             println!(
                 "Planning create index {} on {} ({})",
                 index_name, table_name, column_name
             );
         }
-        PlanNode::DropIndex { name } => {
+        PlanNode::DropIndex { name, .. } => {
             println!("Planning drop index {}", name);
         }
         PlanNode::Select { table_name, selection, .. } => {
@@ -14,9 +14,11 @@ pub enum PlanNode {
         index_name: String,
         table_name: String,
         column_name: String,
+        if_not_exists: bool,
     },
     DropIndex {
         name: String,
+        if_exists: bool,
     },
     DropTable {
         table_name: String,
@@ -50,6 +52,8 @@ pub enum PlanNode {
 pub struct MultiJoinPlan {
     pub base_table: String,
     pub base_alias: Option<String>,
+    /// The derived query behind `base_table` when it is a FROM subquery.
+    pub base_subquery: Option<Box<Statement>>,
     pub joins: Vec<JoinClause>,
     pub projections: Vec<SelectExpr>,
     pub where_predicate: Option<Predicate>,
@@ -60,8 +64,8 @@ pub fn plan_statement(stmt: Statement) -> PlanNode {
         Statement::CreateTable { table_name, columns, primary_key, if_not_exists, .. } => {
             PlanNode::CreateTable { table_name, columns, primary_key, if_not_exists }
         }
-        Statement::CreateIndex { index_name, table_name, column_name } => {
-            PlanNode::CreateIndex { index_name, table_name, column_name }
+        Statement::CreateIndex { index_name, table_name, column_name, if_not_exists } => {
+            PlanNode::CreateIndex { index_name, table_name, column_name, if_not_exists }
         }
         Statement::Insert { table_name, rows, .. } => {
             if let Some(first) = rows.into_iter().next() {
@@ -87,18 +91,37 @@ pub fn plan_statement(stmt: Statement) -> PlanNode {
                 PlanNode::MultiJoin(MultiJoinPlan {
                     base_table: table_name,
                     base_alias,
+                    base_subquery: None,
                     joins,
                     projections: columns,
                     where_predicate,
                 })
             }
         }
-        Statement::DropIndex { name } => PlanNode::DropIndex { name },
+        Statement::DropIndex { name, if_exists } => PlanNode::DropIndex { name, if_exists },
+        Statement::Reindex { .. } | Statement::Vacuum { .. } | Statement::Checkpoint => PlanNode::Exit,
         Statement::DropTable { table_name, if_exists } => PlanNode::DropTable { table_name, if_exists },
         Statement::Delete { table_name, selection } => PlanNode::Delete { table_name, selection },
+        Statement::Truncate { table_name, .. } => PlanNode::Delete { table_name, selection: None },
         Statement::Update { table_name, assignments, selection } => PlanNode::Update { table_name, assignments, selection },
         Statement::CreateSequence(_) => PlanNode::Exit,
-        Statement::BeginTransaction { .. } | Statement::Commit | Statement::Rollback => PlanNode::Exit,
+        Statement::SetOp { .. } | Statement::Values(_) => PlanNode::Exit,
+        Statement::RenameTable { .. }
+        | Statement::RenameColumn { .. }
+        | Statement::RenameIndex { .. } => PlanNode::Exit,
+        Statement::CreateView { .. } | Statement::DropView { .. } => PlanNode::Exit,
+        Statement::BeginTransaction { .. }
+        | Statement::Commit
+        | Statement::Rollback
+        | Statement::SetAutocommit(_)
+        | Statement::SetConstraints(_) => PlanNode::Exit,
+        Statement::ShowTables
+        | Statement::ShowIndexes { .. }
+        | Statement::Describe { .. }
+        | Statement::Pragma { .. } => {
+            PlanNode::Exit
+        }
+        Statement::Explain(_) => PlanNode::Exit,
         Statement::Exit => PlanNode::Exit,
     }
 }
@@ -1,17 +1,19 @@
-use crate::catalog::Catalog;
+use crate::catalog::{Catalog, IndexInfo, TableInfo};
 use crate::constraints::{
     Constraint, default::DefaultConstraint, foreign_key::ForeignKeyConstraint,
-    not_null::NotNullConstraint, primary_key::PrimaryKeyConstraint,
+    not_null::NotNullConstraint, primary_key::{duplicate_key_error, PrimaryKeyConstraint},
 };
 use crate::error::{DbError, DbResult};
 use crate::planner::aggregate;
-use crate::sql::ast::{Expr, Statement, expr_to_string};
+use crate::sql::ast::{Expr, Statement, expr_to_string, literal_operand, resolve_operand};
 use crate::storage::btree::BTree;
 use crate::storage::row::{
-    COMMITTED_BOOTSTRAP_TX, ColumnType, ColumnValue, Row, RowData, build_row_data,
+    COMMITTED_BOOTSTRAP_TX, ColumnType, ColumnValue, NULL_MARKER, Row, RowData, RowKey,
+    build_row_data, build_typed_row_data, typed_column_value,
 };
-use crate::transaction::Snapshot;
+use crate::transaction::{Snapshot, TransactionStatus};
 use std::collections::{HashMap, HashSet};
+use std::io;
 
 fn parse_index_lookup_value(value: &str, col_type: ColumnType) -> ColumnValue {
     match col_type {
@@ -19,6 +21,10 @@ fn parse_index_lookup_value(value: &str, col_type: ColumnType) -> ColumnValue {
             .parse::<i32>()
             .map(ColumnValue::Integer)
             .unwrap_or_else(|_| ColumnValue::Text(value.to_string())),
+        ColumnType::BigInt => value
+            .parse::<i64>()
+            .map(ColumnValue::BigInt)
+            .unwrap_or_else(|_| ColumnValue::Text(value.to_string())),
         ColumnType::Boolean => match value.to_ascii_lowercase().as_str() {
             "true" => ColumnValue::Boolean(true),
             "false" => ColumnValue::Boolean(false),
@@ -29,6 +35,12 @@ fn parse_index_lookup_value(value: &str, col_type: ColumnType) -> ColumnValue {
             .parse::<f64>()
             .map(ColumnValue::Double)
             .unwrap_or_else(|_| ColumnValue::Text(value.to_string())),
+        ColumnType::Decimal { scale, .. } => crate::storage::row::parse_decimal(value, scale)
+            .map(|v| ColumnValue::Decimal {
+                value: v,
+                scale: scale as u8,
+            })
+            .unwrap_or_else(|| ColumnValue::Text(value.to_string())),
         ColumnType::Date => crate::storage::row::parse_date(value)
             .map(ColumnValue::Date)
             .unwrap_or_else(|| ColumnValue::Text(value.to_string())),
@@ -44,7 +56,10 @@ fn parse_index_lookup_value(value: &str, col_type: ColumnType) -> ColumnValue {
         ColumnType::Year => crate::storage::row::parse_year(value)
             .map(ColumnValue::Year)
             .unwrap_or_else(|| ColumnValue::Text(value.to_string())),
-        ColumnType::Text => ColumnValue::Text(value.to_string()),
+        ColumnType::Blob => crate::storage::row::parse_blob_hex(value)
+            .map(ColumnValue::Blob)
+            .unwrap_or_else(|| ColumnValue::Text(value.to_string())),
+        ColumnType::Text | ColumnType::Varchar(_) => ColumnValue::Text(value.to_string()),
     }
 }
 
@@ -63,7 +78,7 @@ fn current_tx_id(catalog: &Catalog) -> u64 {
 
 fn ensure_no_write_conflict(
     table_btree: &mut BTree<'_>,
-    key: i32,
+    key: RowKey,
     visible_created_tx: u64,
     snapshot: &Snapshot,
 ) -> DbResult<()> {
@@ -73,6 +88,142 @@ fn ensure_no_write_conflict(
     Ok(())
 }
 
+/// Header type of a literal in the select list: INTEGER for a whole number
+/// that fits, a double for any other signed decimal number, otherwise TEXT.
+fn literal_column_type(value: &str) -> ColumnType {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if value.parse::<i32>().is_ok() {
+        ColumnType::Integer
+    } else if digits.bytes().all(|b| b.is_ascii_digit() || b == b'.') && digits.parse::<f64>().is_ok() {
+        ColumnType::Double {
+            precision: 8,
+            scale: 2,
+            unsigned: false,
+        }
+    } else {
+        ColumnType::Text
+    }
+}
+
+/// Header type of a projected expression: the return type of a clock
+/// function, the type of the column `DATE_ADD`/`DATE_SUB` shifts, the type
+/// of the first column `GREATEST`/`LEAST` compares, otherwise a generic number.
+fn expr_column_type(expr: &Expr, columns: &[(String, ColumnType)]) -> ColumnType {
+    match expr {
+        Expr::FunctionCall { name, args }
+            if name.eq_ignore_ascii_case("DATE_ADD") || name.eq_ignore_ascii_case("DATE_SUB") =>
+        {
+            match args.first() {
+                Some(Expr::Literal(base)) => columns
+                    .iter()
+                    .find(|(c, _)| c == base)
+                    .map(|(_, ty)| *ty)
+                    .or(Some(ColumnType::Date)),
+                _ => Some(ColumnType::Date),
+            }
+        }
+        Expr::FunctionCall { name, args }
+            if name.eq_ignore_ascii_case("GREATEST") || name.eq_ignore_ascii_case("LEAST") =>
+        {
+            args.iter().find_map(|arg| match arg {
+                Expr::Literal(token) => columns.iter().find(|(c, _)| c == token).map(|(_, ty)| *ty),
+                _ => None,
+            })
+        }
+        Expr::FunctionCall { name, .. } => {
+            crate::sql::functions::FunctionEvaluator::return_type(name)
+        }
+        Expr::Add { left, right } | Expr::Subtract { left, right } | Expr::Multiply { left, right } => {
+            // An integer column with an integer column or literal gives an
+            // integer, a DECIMAL operand keeps its type, and anything else is
+            // a double.
+            let column_type = |token: &str| {
+                let name = token.rsplit('.').next().unwrap_or(token);
+                columns.iter().find(|(c, _)| c == name).map(|(_, ty)| *ty)
+            };
+            let integer_literal = |token: &str| literal_operand(token).parse::<i64>().is_ok();
+            match (column_type(left), column_type(right)) {
+                (Some(l), Some(r)) if l.is_integer() && r.is_integer() => Some(ColumnType::BigInt),
+                (Some(l), None) if l.is_integer() && integer_literal(right) => Some(ColumnType::BigInt),
+                (None, Some(r)) if r.is_integer() && integer_literal(left) => Some(ColumnType::BigInt),
+                (Some(d @ ColumnType::Decimal { .. }), _) | (_, Some(d @ ColumnType::Decimal { .. })) => Some(d),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+    .unwrap_or(ColumnType::Double {
+        precision: 8,
+        scale: 2,
+        unsigned: false,
+    })
+}
+
+/// Column name to declared type, for typed expression evaluation.
+fn column_types(columns: &[(String, ColumnType)]) -> HashMap<String, ColumnType> {
+    columns.iter().cloned().collect()
+}
+
+/// Names under which a predicate sees the `COLLATE NOCASE` columns of
+/// `table`: bare, and as `qualifier.column` when there is a qualifier.
+fn nocase_columns(table: &TableInfo, qualifier: Option<&str>) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for (column, _) in table.columns.iter().filter(|(c, _)| table.is_nocase(c)) {
+        if let Some(q) = qualifier {
+            names.insert(format!("{}.{}", q, column));
+        }
+        names.insert(column.clone());
+    }
+    names
+}
+
+fn decimal_sum(rows: &[Row], idx: usize) -> i128 {
+    rows.iter()
+        .filter_map(|r| match r.data.0[idx] {
+            ColumnValue::Decimal { value, .. } => Some(value as i128),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Render an aggregated decimal, falling back to plain formatting when the
+/// result no longer fits a stored DECIMAL.
+fn decimal_string(value: i128, scale: u8) -> String {
+    match i64::try_from(value) {
+        Ok(value) => ColumnValue::Decimal { value, scale }.to_string_value(),
+        Err(_) => crate::storage::row::format_decimal(value, scale),
+    }
+}
+
+fn generated_expr(table_info: &TableInfo, idx: usize) -> Option<&Expr> {
+    table_info.generated.get(idx).and_then(|g| g.as_ref())
+}
+
+/// Fill the generated columns of `vals` from the other values in the row.
+fn compute_generated_columns(table_info: &TableInfo, vals: &mut [String]) -> DbResult<()> {
+    let values: HashMap<String, String> = table_info
+        .columns
+        .iter()
+        .zip(vals.iter())
+        .map(|((name, _), v)| (name.clone(), v.clone()))
+        .collect();
+    let types = column_types(&table_info.columns);
+    for (idx, slot) in vals.iter_mut().enumerate() {
+        if let Some(expr) = generated_expr(table_info, idx) {
+            let value = crate::sql::ast::try_evaluate_expression_typed(expr, &values, &types)?;
+            *slot = value.to_predicate_string();
+        }
+    }
+    Ok(())
+}
+
+fn generated_assignment_error(table_info: &TableInfo, col_name: &str) -> DbError {
+    DbError::InvalidValue(format!(
+        "Cannot assign to generated column '{}' of table '{}'",
+        col_name, table_info.name
+    ))
+}
+
 pub fn execute_delete(
     catalog: &mut Catalog,
     table_name: &str,
@@ -81,6 +232,7 @@ pub fn execute_delete(
     if let Ok(table_info) = catalog.get_table(table_name).map(Clone::clone) {
         let root_page = table_info.root_page;
         let columns = table_info.columns.clone();
+        let types = column_types(&columns);
         let rows_to_delete = {
             let snapshot = dml_snapshot(catalog);
             let mut scan_tree = BTree::open_root(&mut catalog.pager, root_page)?;
@@ -90,11 +242,11 @@ pub fn execute_delete(
                 if let Some(ref expr) = selection {
                     let mut values = HashMap::new();
                     for ((col, _), val) in columns.iter().zip(row.data.0.iter()) {
-                        let v = val.to_string_value();
+                        let v = val.to_predicate_string();
                         values.insert(col.clone(), v);
                     }
                     if matches!(
-                        crate::sql::ast::evaluate_expression(expr, &values),
+                        crate::sql::ast::evaluate_expression_typed(expr, &values, &types),
                         ColumnValue::Boolean(true)
                     ) {
                         collected.push(row);
@@ -118,25 +270,16 @@ pub fn execute_delete(
 
             let snapshot = dml_snapshot(catalog);
             let tx_id = current_tx_id(catalog);
-            let mut table_btree = BTree::open_root(&mut catalog.pager, root_page)?;
-            for r in &rows_to_delete {
-                ensure_no_write_conflict(&mut table_btree, r.key, r.created_tx, &snapshot)?;
-                table_btree.mark_deleted_visible(r.key, &snapshot, tx_id)?;
-            }
-            let new_root = table_btree.root_page();
-            drop(table_btree);
-            if new_root != root_page {
-                if let Ok(t) = catalog.get_table_mut(table_name) {
-                    t.root_page = new_root;
+            catalog.with_table_tree(table_name, |table_btree| -> DbResult<()> {
+                for r in &rows_to_delete {
+                    ensure_no_write_conflict(table_btree, r.key, r.created_tx, &snapshot)?;
+                    table_btree.mark_deleted_visible(r.key, &snapshot, tx_id)?;
                 }
-                catalog.update_catalog_root(table_name, new_root)?;
-            }
+                Ok(())
+            })?;
 
             // Record write intents so commit can re-validate first-committer-wins.
-            let final_root = catalog
-                .get_table(table_name)
-                .map(|t| t.root_page)
-                .unwrap_or(new_root);
+            let final_root = catalog.get_table(table_name)?.root_page;
             for r in &rows_to_delete {
                 catalog
                     .pager
@@ -161,6 +304,7 @@ pub fn execute_update(
     if let Ok(table_info) = catalog.get_table(table_name).map(Clone::clone) {
         let root_page = table_info.root_page;
         let columns = table_info.columns.clone();
+        let types = column_types(&columns);
         let mut col_pos = HashMap::new();
         for (i, (c, _)) in columns.iter().enumerate() {
             col_pos.insert(c.clone(), i);
@@ -170,12 +314,23 @@ pub fn execute_update(
             let idx = *col_pos
                 .get(&col)
                 .ok_or_else(|| DbError::ColumnNotFound(col.clone()))?;
+            if generated_expr(&table_info, idx).is_some() {
+                return Err(generated_assignment_error(&table_info, &col));
+            }
+            if val == NULL_MARKER {
+                parsed.push((idx, ColumnValue::Null));
+                continue;
+            }
             let ty = columns[idx].1;
             let cv = match ty {
                 ColumnType::Integer => ColumnValue::Integer(
                     val.parse::<i32>()
                         .map_err(|_| DbError::InvalidValue("Invalid INTEGER".into()))?,
                 ),
+                ColumnType::BigInt => ColumnValue::BigInt(
+                    val.parse::<i64>()
+                        .map_err(|_| DbError::InvalidValue("Invalid BIGINT".into()))?,
+                ),
                 ColumnType::Text => ColumnValue::Text(val.clone()),
                 ColumnType::Boolean => match val.to_ascii_lowercase().as_str() {
                     "true" => ColumnValue::Boolean(true),
@@ -195,6 +350,15 @@ pub fn execute_update(
                     }
                     ColumnValue::Char(s)
                 }
+                ColumnType::Varchar(len) => {
+                    if val.chars().count() > len {
+                        return Err(DbError::InvalidValue(format!(
+                            "Value '{}' for column '{}' exceeds length {}",
+                            val, col, len
+                        )));
+                    }
+                    ColumnValue::Text(val.clone())
+                }
                 ColumnType::SmallInt { unsigned, .. } => {
                     let i = val
                         .parse::<i32>()
@@ -230,6 +394,17 @@ pub fn execute_update(
                     }
                     ColumnValue::Double(f)
                 }
+                ColumnType::Decimal { precision, scale } => {
+                    let value = crate::storage::row::parse_decimal(&val, scale)
+                        .ok_or_else(|| DbError::ParseError("Invalid DECIMAL".into()))?;
+                    if value.unsigned_abs() >= 10u64.pow(precision as u32) {
+                        return Err(DbError::Overflow);
+                    }
+                    ColumnValue::Decimal {
+                        value,
+                        scale: scale as u8,
+                    }
+                }
                 ColumnType::Date => match crate::storage::row::parse_date(&val) {
                     Some(d) => ColumnValue::Date(d),
                     None => {
@@ -252,6 +427,10 @@ pub fn execute_update(
                     Some(y) => ColumnValue::Year(y),
                     None => return Err(DbError::ParseError("Invalid YEAR".into())),
                 },
+                ColumnType::Blob => match crate::storage::row::parse_blob_hex(&val) {
+                    Some(b) => ColumnValue::Blob(b),
+                    None => return Err(DbError::ParseError("Invalid BLOB".into())),
+                },
             };
             parsed.push((idx, cv));
         }
@@ -265,11 +444,11 @@ pub fn execute_update(
                 if let Some(ref expr) = selection {
                     let mut values = HashMap::new();
                     for ((col, _), val) in columns.iter().zip(row.data.0.iter()) {
-                        let v = val.to_string_value();
+                        let v = val.to_predicate_string();
                         values.insert(col.clone(), v);
                     }
                     if matches!(
-                        crate::sql::ast::evaluate_expression(expr, &values),
+                        crate::sql::ast::evaluate_expression_typed(expr, &values, &types),
                         ColumnValue::Boolean(true)
                     ) {
                         collected.push(row);
@@ -283,10 +462,20 @@ pub fn execute_update(
 
         if !rows_to_update.is_empty() {
             let count = rows_to_update.len();
+            // An assigned AUTO_INCREMENT value raises the sequence like an
+            // explicit insert does, so later DEFAULT values stay above it.
+            for (idx, val) in &parsed {
+                if table_info.auto_increment.get(*idx).copied().unwrap_or(false)
+                    && let Some(v) = val.as_row_key()
+                {
+                    let seq = format!("{}_{}", table_name, columns[*idx].0);
+                    catalog.update_sequence_current(&seq, v)?;
+                }
+            }
             struct UpdateOp {
-                old_key: i32,
+                old_key: RowKey,
                 old_created_tx: u64,
-                new_key: i32,
+                new_key: RowKey,
                 new_data: RowData,
             }
             let mut ops = Vec::new();
@@ -295,10 +484,18 @@ pub fn execute_update(
                 for (idx, val) in &parsed {
                     new_data.0[*idx] = val.clone();
                 }
-                let new_key = match new_data.0[0] {
-                    ColumnValue::Integer(i) => i,
-                    _ => row.key,
-                };
+                if table_info.generated.iter().any(Option::is_some) {
+                    let mut vals: Vec<String> = new_data.0.iter().map(ColumnValue::to_predicate_string).collect();
+                    compute_generated_columns(&table_info, &mut vals)?;
+                    for (idx, col) in columns.iter().enumerate() {
+                        if generated_expr(&table_info, idx).is_some() {
+                            let typed = build_row_data(&vals[idx..=idx], std::slice::from_ref(col))
+                                .map_err(DbError::InvalidValue)?;
+                            new_data.0[idx] = typed.0.into_iter().next().unwrap();
+                        }
+                    }
+                }
+                let new_key = new_data.0[0].as_row_key().unwrap_or(row.key);
                 ops.push(UpdateOp {
                     old_key: row.key,
                     old_created_tx: row.created_tx,
@@ -307,6 +504,18 @@ pub fn execute_update(
                 });
             }
 
+            // Only foreign keys over an assigned column can newly point at a
+            // missing parent row.
+            let changed_fks: Vec<_> = table_info
+                .fks
+                .iter()
+                .filter(|fk| {
+                    fk.columns
+                        .iter()
+                        .any(|c| parsed.iter().any(|(idx, _)| &columns[*idx].0 == c))
+                })
+                .cloned()
+                .collect();
             for op in &mut ops {
                 let nn = NotNullConstraint;
                 nn.validate_insert(
@@ -316,9 +525,7 @@ pub fn execute_update(
                     &dml_snapshot(catalog),
                 )?;
 
-                let fk_cons = ForeignKeyConstraint {
-                    fks: &table_info.fks,
-                };
+                let fk_cons = ForeignKeyConstraint { fks: &changed_fks };
                 fk_cons.validate_insert(
                     catalog,
                     &table_info,
@@ -328,6 +535,14 @@ pub fn execute_update(
             }
 
             if let Some(ref pk_cols) = table_info.primary_key {
+                for op in &ops {
+                    for col in pk_cols {
+                        let pos = table_info.columns.iter().position(|(c, _)| c == col);
+                        if pos.is_some_and(|pos| matches!(op.new_data.0[pos], ColumnValue::Null)) {
+                            return Err(DbError::NullViolation(col.clone()));
+                        }
+                    }
+                }
                 let snapshot = dml_snapshot(catalog);
                 let mut existing_rows = {
                     let mut scan_tree = BTree::open_root(&mut catalog.pager, root_page)?;
@@ -349,7 +564,7 @@ pub fn execute_update(
                                 .unwrap_or(false)
                         });
                         if equal {
-                            return Err(DbError::DuplicateKey(op.new_key));
+                            return Err(duplicate_key_error(&table_info, pk_cols, &op.new_data));
                         }
                     }
 
@@ -363,7 +578,7 @@ pub fn execute_update(
                                 .unwrap_or(false)
                         });
                         if equal {
-                            return Err(DbError::DuplicateKey(op.new_key));
+                            return Err(duplicate_key_error(&table_info, pk_cols, &op.new_data));
                         }
                     }
                 }
@@ -371,34 +586,25 @@ pub fn execute_update(
 
             let snapshot = dml_snapshot(catalog);
             let tx_id = current_tx_id(catalog);
-            let mut table_btree = BTree::open_root(&mut catalog.pager, root_page)?;
-            for op in &ops {
-                ensure_no_write_conflict(
-                    &mut table_btree,
-                    op.old_key,
-                    op.old_created_tx,
-                    &snapshot,
-                )?;
-                table_btree.mark_deleted_visible(op.old_key, &snapshot, tx_id)?;
-                let mut new_row = Row::new(op.new_key, op.new_data.clone());
-                new_row.created_tx = tx_id;
-                new_row.deleted_tx = None;
-                table_btree.insert_version(new_row)?;
-            }
-            let new_root = table_btree.root_page();
-            drop(table_btree);
-            if new_root != root_page {
-                if let Ok(t) = catalog.get_table_mut(table_name) {
-                    t.root_page = new_root;
+            catalog.with_table_tree(table_name, |table_btree| -> DbResult<()> {
+                for op in &ops {
+                    ensure_no_write_conflict(
+                        table_btree,
+                        op.old_key,
+                        op.old_created_tx,
+                        &snapshot,
+                    )?;
+                    table_btree.mark_deleted_visible(op.old_key, &snapshot, tx_id)?;
+                    let mut new_row = Row::new(op.new_key, op.new_data.clone());
+                    new_row.created_tx = tx_id;
+                    new_row.deleted_tx = None;
+                    table_btree.insert_version(new_row)?;
                 }
-                catalog.update_catalog_root(table_name, new_root)?;
-            }
+                Ok(())
+            })?;
 
             // Record write intents so commit can re-validate first-committer-wins.
-            let final_root = catalog
-                .get_table(table_name)
-                .map(|t| t.root_page)
-                .unwrap_or(new_root);
+            let final_root = catalog.get_table(table_name)?.root_page;
             for op in &ops {
                 catalog
                     .pager
@@ -423,16 +629,55 @@ pub fn execute_insert(
     table_name: &str,
     columns: Option<Vec<String>>,
     rows: Vec<Vec<Expr>>,
+) -> DbResult<usize> {
+    insert_rows(catalog, table_name, columns, rows, false)
+}
+
+/// Run every conversion and constraint check [`execute_insert`] would for
+/// `rows` without writing anything, returning the number of rows that would
+/// be inserted or the first error. Auto-increment values are drawn from a
+/// private copy of the sequences, and a row key repeated within the batch or
+/// already present in the table is reported as `DuplicateKey`.
+pub fn validate_insert(
+    catalog: &mut Catalog,
+    table_name: &str,
+    columns: Option<Vec<String>>,
+    rows: Vec<Vec<Expr>>,
+) -> DbResult<usize> {
+    insert_rows(catalog, table_name, columns, rows, true)
+}
+
+fn insert_rows(
+    catalog: &mut Catalog,
+    table_name: &str,
+    columns: Option<Vec<String>>,
+    rows: Vec<Vec<Expr>>,
+    validate_only: bool,
 ) -> DbResult<usize> {
     let table_info = catalog.get_table(table_name)?.clone();
-    let root_page = table_info.root_page;
     let columns_meta = table_info.columns.clone();
-    let fks = table_info.fks.clone();
+    // With deferred foreign keys the rows are checked without them, and the
+    // keys once every row is in (see `check_deferred_foreign_keys`).
+    let defer_fks = catalog.defer_foreign_keys() && !table_info.fks.is_empty();
+    let checked_info = if defer_fks {
+        TableInfo { fks: Vec::new(), ..table_info.clone() }
+    } else {
+        table_info.clone()
+    };
+    let mut deferred_rows = Vec::new();
+    let mut sequences = if validate_only {
+        SequenceDraws::Simulated(HashMap::new())
+    } else {
+        SequenceDraws::Catalog
+    };
+    let mut batch = ValidatedBatch::default();
 
     let mut inserted = 0usize;
     let mut result: DbResult<()> = Ok(());
 
-    for row_vals in rows {
+    for (row_idx, row_vals) in rows.into_iter().enumerate() {
+        // Errors name the 1-based position of the offending VALUES tuple.
+        let row_no = row_idx + 1;
         if let Err(e) = (|| {
             let mut vals = Vec::new();
             if let Some(ref cols) = columns {
@@ -450,17 +695,26 @@ pub fn execute_insert(
                 }
                 for (idx, (col_name, _)) in columns_meta.iter().enumerate() {
                     let auto = table_info.auto_increment.get(idx).copied().unwrap_or(false);
-                    if let Some(pos) = cols.iter().position(|c| c == col_name) {
+                    if generated_expr(&table_info, idx).is_some() {
+                        let explicit = cols.iter().position(|c| c == col_name);
+                        if explicit.is_some_and(|pos| !matches!(row_vals[pos], Expr::DefaultValue))
+                        {
+                            return Err(generated_assignment_error(&table_info, col_name));
+                        }
+                        // Placeholder; computed once the whole row is known.
+                        vals.push("NULL".into());
+                    } else if let Some(pos) = cols.iter().position(|c| c == col_name) {
                         let expr = &row_vals[pos];
                         if auto {
-                            if matches!(expr, Expr::DefaultValue) {
+                            if matches!(expr, Expr::DefaultValue | Expr::Null) {
                                 let seq = format!("{}_{}", table_name, col_name);
-                                let next = catalog.next_sequence_value(&seq)?;
+                                let next = sequences.next(catalog, &seq)?;
                                 vals.push(next.to_string());
                             } else {
                                 let s = expr_to_string(expr);
                                 if let Ok(v) = s.parse::<i64>() {
-                                    catalog.update_sequence_current(
+                                    sequences.observe(
+                                        catalog,
                                         &format!("{}_{}", table_name, col_name),
                                         v,
                                     )?;
@@ -471,13 +725,16 @@ pub fn execute_insert(
                             if let Some(def) =
                                 table_info.default_values.get(idx).and_then(|o| o.as_ref())
                             {
-                                vals.push(DefaultConstraint::evaluate(def)?);
+                                vals.push(DefaultConstraint::evaluate_for(
+                                    def,
+                                    columns_meta[idx].1,
+                                )?);
                             } else if !table_info.not_null[idx] {
                                 vals.push("NULL".into());
                             } else {
                                 return Err(DbError::InvalidValue(format!(
-                                    "Cannot use DEFAULT for column '{}' - no default value defined",
-                                    col_name
+                                    "row {}: cannot use DEFAULT for column '{}' - no default value defined",
+                                    row_no, col_name
                                 )));
                             }
                         } else {
@@ -486,18 +743,18 @@ pub fn execute_insert(
                     } else {
                         if auto {
                             let seq = format!("{}_{}", table_name, col_name);
-                            let next = catalog.next_sequence_value(&seq)?;
+                            let next = sequences.next(catalog, &seq)?;
                             vals.push(next.to_string());
                         } else if let Some(def) =
                             table_info.default_values.get(idx).and_then(|o| o.as_ref())
                         {
-                            vals.push(DefaultConstraint::evaluate(def)?);
+                            vals.push(DefaultConstraint::evaluate_for(def, columns_meta[idx].1)?);
                         } else if !table_info.not_null[idx] {
                             vals.push("NULL".into());
                         } else {
                             return Err(DbError::InvalidValue(format!(
-                                "Column '{}' requires a value or DEFAULT",
-                                col_name
+                                "row {}: column '{}' requires a value",
+                                row_no, col_name
                             )));
                         }
                     }
@@ -512,15 +769,24 @@ pub fn execute_insert(
                 }
                 for (idx, expr) in row_vals.iter().enumerate() {
                     let auto = table_info.auto_increment.get(idx).copied().unwrap_or(false);
-                    if auto {
-                        if matches!(expr, Expr::DefaultValue) {
+                    if generated_expr(&table_info, idx).is_some() {
+                        if !matches!(expr, Expr::DefaultValue) {
+                            return Err(generated_assignment_error(
+                                &table_info,
+                                &columns_meta[idx].0,
+                            ));
+                        }
+                        vals.push("NULL".into());
+                    } else if auto {
+                        if matches!(expr, Expr::DefaultValue | Expr::Null) {
                             let seq = format!("{}_{}", table_name, columns_meta[idx].0);
-                            let next = catalog.next_sequence_value(&seq)?;
+                            let next = sequences.next(catalog, &seq)?;
                             vals.push(next.to_string());
                         } else {
                             let s = expr_to_string(expr);
                             if let Ok(v) = s.parse::<i64>() {
-                                catalog.update_sequence_current(
+                                sequences.observe(
+                                    catalog,
                                     &format!("{}_{}", table_name, columns_meta[idx].0),
                                     v,
                                 )?;
@@ -531,13 +797,13 @@ pub fn execute_insert(
                         if let Some(def) =
                             table_info.default_values.get(idx).and_then(|o| o.as_ref())
                         {
-                            vals.push(DefaultConstraint::evaluate(def)?);
+                            vals.push(DefaultConstraint::evaluate_for(def, columns_meta[idx].1)?);
                         } else if !table_info.not_null[idx] {
                             vals.push("NULL".into());
                         } else {
                             return Err(DbError::InvalidValue(format!(
-                                "Cannot use DEFAULT for column '{}' - no default value defined",
-                                columns_meta[idx].0
+                                "row {}: cannot use DEFAULT for column '{}' - no default value defined",
+                                row_no, columns_meta[idx].0
                             )));
                         }
                     } else {
@@ -546,39 +812,43 @@ pub fn execute_insert(
                 }
             }
 
+            compute_generated_columns(&table_info, &mut vals)?;
+            // Values bound to parameters keep their type instead of being
+            // re-parsed from their string form.
+            let mut bound = Vec::new();
+            for (idx, (col_name, _)) in columns_meta.iter().enumerate() {
+                let pos = match &columns {
+                    Some(cols) => cols.iter().position(|c| c == col_name),
+                    None => Some(idx),
+                };
+                match pos.map(|p| &row_vals[p]) {
+                    Some(Expr::Value(v)) => {
+                        vals[idx] = NULL_MARKER.into();
+                        bound.push((idx, v.clone()));
+                    }
+                    // The keyword is `Expr::Null`, so this was the quoted text.
+                    Some(Expr::Literal(s)) if s.eq_ignore_ascii_case("NULL") => {
+                        vals[idx] = NULL_MARKER.into();
+                        bound.push((idx, ColumnValue::Text(s.clone())));
+                    }
+                    _ => {}
+                }
+            }
             let mut row_data =
                 build_row_data(&vals, &columns_meta).map_err(|e| DbError::InvalidValue(e))?;
+            for (idx, v) in bound {
+                let (name, ty) = &columns_meta[idx];
+                row_data.0[idx] = typed_column_value(v, name, ty).map_err(DbError::InvalidValue)?;
+            }
 
-            let nn = NotNullConstraint;
-            nn.validate_insert(catalog, &table_info, &mut row_data, &dml_snapshot(catalog))?;
-
-            let fk_cons = ForeignKeyConstraint { fks: &fks };
-            fk_cons.validate_insert(catalog, &table_info, &mut row_data, &dml_snapshot(catalog))?;
-            if let Some(ref pk_cols) = table_info.primary_key {
-                let pk_cons = PrimaryKeyConstraint { columns: pk_cols };
-                pk_cons.validate_insert(
-                    catalog,
-                    &table_info,
-                    &mut row_data,
-                    &dml_snapshot(catalog),
-                )?;
+            if defer_fks {
+                deferred_rows.push(row_data.clone());
+            }
+            if validate_only {
+                batch.check(catalog, &checked_info, row_data)?;
+            } else {
+                store_inserted_row(catalog, table_name, &checked_info, row_data)?;
             }
-            let key = match row_data.0.get(0) {
-                Some(ColumnValue::Integer(i)) => *i,
-                _ => {
-                    return Err(DbError::InvalidValue(
-                        "First column must be an INTEGER key".into(),
-                    ));
-                }
-            };
-            let mut table_btree = BTree::open_root(&mut catalog.pager, root_page)?;
-            table_btree.insert(key, row_data.clone())?;
-            let new_root = table_btree.root_page();
-            drop(table_btree);
-            if new_root != root_page {
-                catalog.get_table_mut(table_name)?.root_page = new_root;
-            }
-            catalog.insert_into_indexes(table_name, &row_data)?;
             inserted += 1;
             Ok(())
         })() {
@@ -588,222 +858,674 @@ pub fn execute_insert(
     }
 
     if result.is_ok() {
+        check_deferred_foreign_keys(catalog, &table_info, deferred_rows, &batch.keys)?;
         Ok(inserted)
     } else {
         Err(result.unwrap_err())
     }
 }
 
-pub fn execute_select_with_indexes(
+/// Check the foreign keys of an INSERT's `rows` once all of them are in, so a
+/// row may reference one later in the same statement. Stored rows are found
+/// in the table; `batch_keys` are the keys of rows only validated, which
+/// satisfy a key referencing their own table in the same way.
+fn check_deferred_foreign_keys(
     catalog: &mut Catalog,
-    table_name: &str,
-    selection: Option<Expr>,
-    out: &mut Vec<Row>,
-) -> DbResult<bool> {
-    let table_info = catalog.get_table(table_name)?.clone();
-    let root_page = table_info.root_page;
-    let columns = table_info.columns.clone();
-    let snapshot = dml_snapshot(catalog);
-
-    if let Some(Expr::Equals { left, right }) = selection.clone() {
-        let (col_name, value) = if columns.iter().any(|(c, _)| c == &left) {
-            (left, right)
-        } else if columns.iter().any(|(c, _)| c == &right) {
-            (right, left)
-        } else {
-            ("".into(), String::new())
-        };
-        if !col_name.is_empty() {
-            if let Some(index) = catalog.find_index(table_name, &col_name).cloned() {
-                let col_type = columns
+    table_info: &TableInfo,
+    rows: Vec<RowData>,
+    batch_keys: &HashSet<RowKey>,
+) -> DbResult<()> {
+    for mut row in rows {
+        for fk in &table_info.fks {
+            let in_batch = fk.parent_table == table_info.name
+                && table_info
+                    .columns
                     .iter()
-                    .find(|(c, _)| c == &col_name)
-                    .map(|(_, ty)| *ty)
-                    .unwrap_or(ColumnType::Text);
-                let val_cv = parse_index_lookup_value(&value, col_type);
-                let expected = Catalog::value_to_string(&val_cv);
-                let hash = Catalog::hash_value(&val_cv);
-                let mut index_tree = BTree::open_root(&mut catalog.pager, index.root_page)?;
-                if let Some(row) = index_tree.find(hash)? {
-                    if let ColumnValue::Text(ref stored) = row.data.0[0] {
-                        if stored == &expected {
-                            let candidate_keys: Vec<i32> = row
-                                .data
-                                .0
-                                .iter()
-                                .skip(1)
-                                .filter_map(|val| match val {
-                                    ColumnValue::Integer(k) => Some(*k),
-                                    _ => None,
-                                })
-                                .collect();
-                            drop(index_tree);
-
-                            let mut table_tree = BTree::open_root(&mut catalog.pager, root_page)?;
-                            let mut seen_keys = HashSet::new();
-                            for key in candidate_keys {
-                                if !seen_keys.insert(key) {
-                                    continue;
-                                }
-                                // Index entries store logical base-row keys and may be stale
-                                // until vacuum. Always resolve each candidate through the
-                                // base table's MVCC visibility rules before returning it.
-                                if let Some(r) = table_tree.find_visible(key, &snapshot)? {
-                                    let mut values = HashMap::new();
-                                    for ((col, _), val) in columns.iter().zip(r.data.0.iter()) {
-                                        values.insert(col.clone(), val.to_string_value());
-                                    }
-                                    if selection
-                                        .as_ref()
-                                        .map(|expr| {
-                                            matches!(
-                                                crate::sql::ast::evaluate_expression(expr, &values),
-                                                ColumnValue::Boolean(true)
-                                            )
-                                        })
-                                        .unwrap_or(true)
-                                    {
-                                        out.push(r);
-                                    }
-                                }
-                            }
-                            return Ok(true);
-                        }
-                    }
-                }
-                return Ok(true);
+                    .position(|(c, _)| fk.columns.first() == Some(c))
+                    .and_then(|idx| row.0[idx].as_row_key())
+                    .is_some_and(|key| batch_keys.contains(&key));
+            if in_batch {
+                continue;
             }
+            let fk_cons = ForeignKeyConstraint {
+                fks: std::slice::from_ref(fk),
+            };
+            fk_cons.validate_insert(catalog, table_info, &mut row, &dml_snapshot(catalog))?;
         }
     }
+    Ok(())
+}
 
-    let mut table_btree = BTree::open_root(&mut catalog.pager, root_page)?;
-    for row in table_btree.scan_visible(&snapshot)? {
-        if let Some(ref expr) = selection {
-            let mut values = HashMap::new();
-            for ((col, _), val) in columns.iter().zip(row.data.0.iter()) {
-                let v = val.to_string_value();
-                values.insert(col.clone(), v);
+/// Insert rows of already-typed values into `table_name`. Each row supplies
+/// one value per column; unlike [`execute_insert`] nothing is rendered to a
+/// string and re-parsed, so text containing quotes or separators is stored
+/// exactly. A NULL in an auto-increment column draws the next sequence value
+/// and generated columns must be given as NULL.
+pub fn execute_insert_typed(
+    catalog: &mut Catalog,
+    table_name: &str,
+    rows: Vec<Vec<ColumnValue>>,
+) -> DbResult<usize> {
+    let table_info = catalog.get_table(table_name)?.clone();
+    let mut inserted = 0usize;
+    for mut values in rows {
+        if values.len() != table_info.columns.len() {
+            return Err(DbError::InvalidValue(format!(
+                "INSERT has wrong number of values: expected {}, got {}",
+                table_info.columns.len(),
+                values.len()
+            )));
+        }
+        for (idx, (col_name, _)) in table_info.columns.iter().enumerate() {
+            let seq = format!("{}_{}", table_name, col_name);
+            if generated_expr(&table_info, idx).is_some() {
+                if values[idx] != ColumnValue::Null {
+                    return Err(generated_assignment_error(&table_info, col_name));
+                }
+            } else if table_info.auto_increment.get(idx).copied().unwrap_or(false) {
+                match values[idx].as_row_key() {
+                    Some(v) => catalog.update_sequence_current(&seq, v)?,
+                    None if values[idx] == ColumnValue::Null => {
+                        values[idx] = ColumnValue::BigInt(catalog.next_sequence_value(&seq)?);
+                    }
+                    None => {}
+                }
             }
-            if matches!(
-                crate::sql::ast::evaluate_expression(expr, &values),
-                ColumnValue::Boolean(true)
-            ) {
-                out.push(row);
+        }
+        if table_info.generated.iter().any(Option::is_some) {
+            let mut vals: Vec<String> = values.iter().map(ColumnValue::to_predicate_string).collect();
+            compute_generated_columns(&table_info, &mut vals)?;
+            for (idx, col) in table_info.columns.iter().enumerate() {
+                if generated_expr(&table_info, idx).is_some() {
+                    let typed = build_row_data(&vals[idx..=idx], std::slice::from_ref(col))
+                        .map_err(DbError::InvalidValue)?;
+                    values[idx] = typed.0.into_iter().next().unwrap();
+                }
             }
-        } else {
-            out.push(row);
         }
+        let row_data =
+            build_typed_row_data(values, &table_info.columns).map_err(DbError::InvalidValue)?;
+        store_inserted_row(catalog, table_name, &table_info, row_data)?;
+        inserted += 1;
     }
-    Ok(false)
+    Ok(inserted)
 }
 
-pub fn execute_multi_join(
-    plan: &crate::execution::plan::MultiJoinPlan,
-    catalog: &mut Catalog,
-    out: &mut Vec<Vec<String>>,
-) -> DbResult<()> {
-    use crate::sql::ast::evaluate_expression;
-    let mut result_rows: Vec<std::collections::HashMap<String, ColumnValue>> = Vec::new();
-    let mut result_columns = Vec::new();
+/// Where an insert draws its auto-increment values: the catalog's sequences,
+/// or for a validating insert a private copy seeded from them on first use.
+enum SequenceDraws {
+    Catalog,
+    Simulated(HashMap<String, (i64, i64)>),
+}
 
-    // base table scan
-    {
-        let base_info = catalog.get_table(&plan.base_table)?.clone();
-        let snapshot = dml_snapshot(catalog);
-        let mut tree = BTree::open_root(&mut catalog.pager, base_info.root_page)?;
-        for row in tree.scan_visible(&snapshot)? {
-            let mut map = std::collections::HashMap::new();
-            let alias = plan.base_alias.as_deref().unwrap_or(&plan.base_table);
-            for ((c, _), v) in base_info.columns.iter().zip(row.data.0.iter()) {
-                map.insert(format!("{alias}.{c}"), v.clone());
-            }
-            result_rows.push(map);
+impl SequenceDraws {
+    fn simulated<'a>(
+        catalog: &Catalog,
+        draws: &'a mut HashMap<String, (i64, i64)>,
+        name: &str,
+    ) -> io::Result<&'a mut (i64, i64)> {
+        if !draws.contains_key(name) {
+            let info = catalog.sequence(name).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Sequence '{}' not found", name),
+                )
+            })?;
+            draws.insert(name.to_string(), (info.current_value, info.increment));
         }
-        let alias = plan.base_alias.as_deref().unwrap_or(&plan.base_table);
-        for (c, _) in base_info.columns.iter() {
-            result_columns.push(format!("{alias}.{c}"));
+        Ok(draws.get_mut(name).unwrap())
+    }
+
+    fn next(&mut self, catalog: &mut Catalog, name: &str) -> io::Result<i64> {
+        match self {
+            SequenceDraws::Catalog => catalog.next_sequence_value(name),
+            SequenceDraws::Simulated(draws) => {
+                let (current, increment) = Self::simulated(catalog, draws, name)?;
+                *current += *increment;
+                Ok(*current)
+            }
         }
     }
 
-    for jc in &plan.joins {
-        let alias = jc.alias.as_ref().unwrap_or(&jc.table);
-        let info = catalog.get_table(&jc.table)?.clone();
-        let snapshot = dml_snapshot(catalog);
-        let mut tree = BTree::open_root(&mut catalog.pager, info.root_page)?;
-        let rows: Vec<_> = {
-            let mut tmp = Vec::new();
-            for r in tree.scan_visible(&snapshot)? {
-                let mut m = std::collections::HashMap::new();
-                for ((c, _), v) in info.columns.iter().zip(r.data.0.iter()) {
-                    m.insert(format!("{alias}.{c}"), v.clone());
-                }
-                tmp.push(m);
+    fn observe(&mut self, catalog: &mut Catalog, name: &str, value: i64) -> io::Result<()> {
+        match self {
+            SequenceDraws::Catalog => catalog.update_sequence_current(name, value),
+            SequenceDraws::Simulated(draws) => {
+                let (current, _) = Self::simulated(catalog, draws, name)?;
+                *current = (*current).max(value);
+                Ok(())
             }
-            tmp
-        };
+        }
+    }
+}
 
-        let mut new_rows = Vec::new();
-        let mut matched_right = vec![false; rows.len()];
-        let right_columns: Vec<String> = info
-            .columns
-            .iter()
-            .map(|(c, _)| format!("{alias}.{c}"))
-            .collect();
+/// Rows accepted so far by a validating insert. Nothing is written, so the
+/// checks against the table cannot see earlier rows of the batch; keys and
+/// primary-key values are compared against them here instead.
+#[derive(Default)]
+struct ValidatedBatch {
+    keys: HashSet<RowKey>,
+    primary_keys: Vec<Vec<ColumnValue>>,
+}
 
-        for left in &result_rows {
-            let mut matched_left = false;
-            for (idx_row, r) in rows.iter().enumerate() {
-                let mut candidate = left.clone();
-                for (k, v) in r {
-                    candidate.insert(k.clone(), v.clone());
-                }
-                let matches = match jc.join_type {
-                    crate::sql::ast::JoinType::Cross => true,
-                    _ => {
-                        if let Some(ref predicate) = jc.predicate {
-                            let mut str_map = std::collections::HashMap::new();
-                            for (k, v) in &candidate {
-                                str_map.insert(k.clone(), v.to_string_value());
-                            }
-                            matches!(
-                                evaluate_expression(predicate, &str_map),
-                                ColumnValue::Boolean(true)
-                            )
-                        } else {
-                            true
-                        }
-                    }
-                };
-                if matches {
-                    matched_left = true;
-                    matched_right[idx_row] = true;
-                    new_rows.push(candidate);
-                }
-            }
-            if !matched_left
-                && matches!(
-                    jc.join_type,
-                    crate::sql::ast::JoinType::Left | crate::sql::ast::JoinType::Full
-                )
-            {
-                let mut merged = left.clone();
-                for col in &right_columns {
-                    merged.insert(col.clone(), ColumnValue::Null);
-                }
-                new_rows.push(merged);
+impl ValidatedBatch {
+    fn check(
+        &mut self,
+        catalog: &mut Catalog,
+        table_info: &TableInfo,
+        mut row_data: RowData,
+    ) -> DbResult<()> {
+        let key = check_inserted_row(catalog, table_info, &mut row_data)?;
+        if self.keys.contains(&key) {
+            return Err(DbError::DuplicateKey(key));
+        }
+        if let Some(ref pk_cols) = table_info.primary_key {
+            let values: Vec<ColumnValue> = pk_cols
+                .iter()
+                .filter_map(|c| table_info.columns.iter().position(|(n, _)| n == c))
+                .map(|idx| row_data.0[idx].clone())
+                .collect();
+            if self.primary_keys.contains(&values) {
+                return Err(duplicate_key_error(table_info, pk_cols, &row_data));
             }
+            self.primary_keys.push(values);
         }
+        self.keys.insert(key);
+        Ok(())
+    }
+}
 
-        if matches!(
-            jc.join_type,
-            crate::sql::ast::JoinType::Right | crate::sql::ast::JoinType::Full
-        ) {
-            for (idx_row, r) in rows.iter().enumerate() {
+/// Check constraints for one new row and return its row key.
+fn check_inserted_row(
+    catalog: &mut Catalog,
+    table_info: &TableInfo,
+    row_data: &mut RowData,
+) -> DbResult<RowKey> {
+    let nn = NotNullConstraint;
+    nn.validate_insert(catalog, table_info, row_data, &dml_snapshot(catalog))?;
+
+    let fk_cons = ForeignKeyConstraint {
+        fks: &table_info.fks,
+    };
+    fk_cons.validate_insert(catalog, table_info, row_data, &dml_snapshot(catalog))?;
+    if let Some(ref pk_cols) = table_info.primary_key {
+        let pk_cons = PrimaryKeyConstraint { columns: pk_cols };
+        pk_cons.validate_insert(catalog, table_info, row_data, &dml_snapshot(catalog))?;
+    }
+    let key = match row_data.0.first().and_then(ColumnValue::as_row_key) {
+        Some(key) => key,
+        None => {
+            return Err(DbError::InvalidValue(
+                "First column must be an INTEGER key".into(),
+            ))
+        }
+    };
+    // The row key stays the first column even when the primary key spans
+    // several columns, so a unique tuple can still collide on it.
+    let mut table_btree = BTree::open_root(&mut catalog.pager, table_info.root_page)?;
+    if table_btree.find(key)?.is_some() {
+        return Err(DbError::DuplicateKey(key));
+    }
+    Ok(key)
+}
+
+/// Check constraints for one new row and write it to the table and its indexes.
+fn store_inserted_row(
+    catalog: &mut Catalog,
+    table_name: &str,
+    table_info: &TableInfo,
+    mut row_data: RowData,
+) -> DbResult<()> {
+    // An earlier row of this statement may have split the root.
+    let root_page = catalog.get_table(table_name)?.root_page;
+    let current = TableInfo { root_page, ..table_info.clone() };
+    let key = check_inserted_row(catalog, &current, &mut row_data)?;
+    catalog.with_table_tree(table_name, |tree| tree.insert(key, row_data.clone()))?;
+    catalog.insert_into_indexes(table_name, &row_data)?;
+    Ok(())
+}
+
+/// Find an `indexed_col = value` term usable for an index lookup. The term may
+/// be the whole predicate or any conjunct of a top-level `AND` chain; the caller
+/// re-applies the full predicate to the fetched rows, so the remaining conjuncts
+/// act as a residual filter.
+fn indexed_equality(
+    catalog: &Catalog,
+    table_name: &str,
+    columns: &[(String, ColumnType)],
+    expr: &Expr,
+) -> Option<(String, String, IndexInfo)> {
+    match expr {
+        Expr::Equals { left, right } => {
+            let (col_name, value) = if columns.iter().any(|(c, _)| c == left) {
+                (left, right)
+            } else if columns.iter().any(|(c, _)| c == right) {
+                (right, left)
+            } else {
+                return None;
+            };
+            catalog
+                .find_index(table_name, col_name)
+                .map(|index| (col_name.clone(), literal_operand(value).to_string(), index.clone()))
+        }
+        Expr::And(a, b) => indexed_equality(catalog, table_name, columns, a)
+            .or_else(|| indexed_equality(catalog, table_name, columns, b)),
+        _ => None,
+    }
+}
+
+/// Find a `indexed_col != value` term, either the whole predicate or a
+/// conjunct of a top-level `AND` chain, so a row holding `value` in that
+/// column fails the predicate outright.
+fn indexed_inequality(
+    catalog: &Catalog,
+    table_name: &str,
+    columns: &[(String, ColumnType)],
+    expr: &Expr,
+) -> Option<(String, String, IndexInfo)> {
+    match expr {
+        Expr::NotEquals { left, right } => indexed_equality(
+            catalog,
+            table_name,
+            columns,
+            &Expr::Equals {
+                left: left.clone(),
+                right: right.clone(),
+            },
+        ),
+        Expr::And(a, b) => indexed_inequality(catalog, table_name, columns, a)
+            .or_else(|| indexed_inequality(catalog, table_name, columns, b)),
+        _ => None,
+    }
+}
+
+/// Bounds on the row key, each `(key, inclusive)`.
+type KeyRange = (Option<(RowKey, bool)>, Option<(RowKey, bool)>);
+
+/// Collect the row-key range implied by comparisons of the first column
+/// against integer literals, either the whole predicate or conjuncts of a
+/// top-level `AND` chain. Returns `None` when no term bounds the key, so
+/// callers fall back to a full scan.
+fn key_range(columns: &[(String, ColumnType)], expr: &Expr) -> Option<KeyRange> {
+    let (key_col, key_type) = columns.first()?;
+    if !matches!(key_type, ColumnType::Integer | ColumnType::BigInt) {
+        return None;
+    }
+    let mut range = (None, None);
+    collect_key_bounds(key_col, expr, &mut range);
+    (range.0.is_some() || range.1.is_some()).then_some(range)
+}
+
+fn collect_key_bounds(key_col: &str, expr: &Expr, range: &mut KeyRange) {
+    // `(is_lower, inclusive)` for `key_col <op> literal`.
+    let (left, right, mut bound) = match expr {
+        Expr::And(a, b) => {
+            collect_key_bounds(key_col, a, range);
+            collect_key_bounds(key_col, b, range);
+            return;
+        }
+        Expr::Between { expr, low, high, negated: false } if expr == key_col => {
+            if let (Ok(low), Ok(high)) = (
+                literal_operand(low).parse::<RowKey>(),
+                literal_operand(high).parse::<RowKey>(),
+            ) {
+                tighten_lower(&mut range.0, (low, true));
+                tighten_upper(&mut range.1, (high, true));
+            }
+            return;
+        }
+        Expr::Equals { left, right } => (left, right, None),
+        Expr::GreaterThan { left, right } => (left, right, Some((true, false))),
+        Expr::GreaterOrEquals { left, right } => (left, right, Some((true, true))),
+        Expr::LessThan { left, right } => (left, right, Some((false, false))),
+        Expr::LessOrEquals { left, right } => (left, right, Some((false, true))),
+        _ => return,
+    };
+    let value = if left == key_col {
+        right
+    } else if right == key_col {
+        // `5 < id` bounds the key from the other side.
+        bound = bound.map(|(is_lower, inclusive)| (!is_lower, inclusive));
+        left
+    } else {
+        return;
+    };
+    let Ok(value) = literal_operand(value).parse::<RowKey>() else {
+        return;
+    };
+    match bound {
+        Some((true, inclusive)) => tighten_lower(&mut range.0, (value, inclusive)),
+        Some((false, inclusive)) => tighten_upper(&mut range.1, (value, inclusive)),
+        None => {
+            tighten_lower(&mut range.0, (value, true));
+            tighten_upper(&mut range.1, (value, true));
+        }
+    }
+}
+
+/// Rebuild a range as the comparisons on `key_col` it came from.
+fn key_range_expr(key_col: &str, (lower, upper): KeyRange) -> Expr {
+    let term = |key: RowKey, inclusive: bool, is_lower: bool| {
+        let (left, right) = (key_col.to_string(), key.to_string());
+        match (is_lower, inclusive) {
+            (true, true) => Expr::GreaterOrEquals { left, right },
+            (true, false) => Expr::GreaterThan { left, right },
+            (false, true) => Expr::LessOrEquals { left, right },
+            (false, false) => Expr::LessThan { left, right },
+        }
+    };
+    let lower = lower.map(|(key, inclusive)| term(key, inclusive, true));
+    let upper = upper.map(|(key, inclusive)| term(key, inclusive, false));
+    match (lower, upper) {
+        (Some(lower), Some(upper)) => Expr::And(Box::new(lower), Box::new(upper)),
+        (Some(term), None) | (None, Some(term)) => term,
+        (None, None) => unreachable!("key_range returns at least one bound"),
+    }
+}
+
+fn tighten_lower(lower: &mut Option<(RowKey, bool)>, bound: (RowKey, bool)) {
+    // At equal keys the exclusive bound is the tighter one.
+    if lower.is_none_or(|(key, inclusive)| (bound.0, !bound.1) > (key, !inclusive)) {
+        *lower = Some(bound);
+    }
+}
+
+fn tighten_upper(upper: &mut Option<(RowKey, bool)>, bound: (RowKey, bool)) {
+    if upper.is_none_or(|(key, inclusive)| (bound.0, bound.1) < (key, inclusive)) {
+        *upper = Some(bound);
+    }
+}
+
+/// Look up the row keys `index` holds for `value`. Returns the value as the
+/// index stores it (lowercased for a `nocase` column), plus the keys (`None`
+/// when the index has no entry).
+fn index_candidates(
+    catalog: &mut Catalog,
+    index: &IndexInfo,
+    value: &str,
+    col_type: ColumnType,
+    nocase: bool,
+) -> DbResult<(String, Option<Vec<RowKey>>)> {
+    let val_cv = Catalog::collate_value(parse_index_lookup_value(value, col_type), nocase);
+    let expected = Catalog::value_to_string(&val_cv);
+    let hash = Catalog::hash_value(&val_cv);
+    let mut index_tree = BTree::open_root(&mut catalog.pager, index.root_page)?;
+    // Distinct values whose hashes collide share a cell; match the exact one.
+    let keys = index_tree.find(hash)?.and_then(|row| {
+        Catalog::decode_index_buckets(&row.data)
+            .into_iter()
+            .find(|(stored, _)| *stored == expected)
+            .map(|(_, keys)| keys)
+    });
+    Ok((expected, keys))
+}
+
+pub fn execute_select_with_indexes(
+    catalog: &mut Catalog,
+    table_name: &str,
+    selection: Option<Expr>,
+    out: &mut Vec<Row>,
+) -> DbResult<bool> {
+    select_rows(catalog, table_name, selection, None, out)
+}
+
+/// Like [`execute_select_with_indexes`], but a table or key-range scan
+/// decodes only the columns whose index is marked in `projection`; the others are left as
+/// `ColumnValue::Null` in each row. `projection` must cover every column the
+/// selection reads.
+pub fn execute_select_projected(
+    catalog: &mut Catalog,
+    table_name: &str,
+    selection: Option<Expr>,
+    projection: &[bool],
+    out: &mut Vec<Row>,
+) -> DbResult<bool> {
+    select_rows(catalog, table_name, selection, Some(projection), out)
+}
+
+fn select_rows(
+    catalog: &mut Catalog,
+    table_name: &str,
+    selection: Option<Expr>,
+    projection: Option<&[bool]>,
+    out: &mut Vec<Row>,
+) -> DbResult<bool> {
+    let table_info = catalog.get_table(table_name)?.clone();
+    let root_page = table_info.root_page;
+    let columns = table_info.columns.clone();
+    let types = column_types(&columns);
+    let snapshot = dml_snapshot(catalog);
+    let column_type = |name: &str| {
+        columns
+            .iter()
+            .find(|(c, _)| c == name)
+            .map(|(_, ty)| *ty)
+            .unwrap_or(ColumnType::Text)
+    };
+
+    if let Some((col_name, value, index)) = selection
+        .as_ref()
+        .and_then(|expr| indexed_equality(catalog, table_name, &columns, expr))
+    {
+        let nocase = table_info.is_nocase(&col_name);
+        let (expected, candidates) =
+            index_candidates(catalog, &index, &value, column_type(&col_name), nocase)?;
+        let Some(candidate_keys) = candidates else {
+            return Ok(true);
+        };
+        let col_idx = columns.iter().position(|(c, _)| c == &col_name).unwrap();
+        let mut table_tree = BTree::open_root(&mut catalog.pager, root_page)?;
+        let mut seen_keys = HashSet::new();
+        for key in candidate_keys {
+            if !seen_keys.insert(key) {
+                continue;
+            }
+            // Index entries store logical base-row keys and may be stale
+            // until vacuum. Always resolve each candidate through the
+            // base table's MVCC visibility rules before returning it.
+            if let Some(r) = table_tree.find_visible(key, &snapshot)? {
+                // The case-sensitive predicate would reject rows that match a
+                // NOCASE column only by folding; check the folded value
+                // instead and leave the rest of the predicate to the caller.
+                if nocase {
+                    let folded = Catalog::collate_value(r.data.0[col_idx].clone(), true);
+                    if Catalog::value_to_string(&folded) == expected {
+                        out.push(r);
+                    }
+                    continue;
+                }
+                let mut values = HashMap::new();
+                for ((col, _), val) in columns.iter().zip(r.data.0.iter()) {
+                    values.insert(col.clone(), val.to_predicate_string());
+                }
+                if selection
+                    .as_ref()
+                    .map(|expr| {
+                        matches!(
+                            crate::sql::ast::evaluate_expression_typed(expr, &values, &types),
+                            ColumnValue::Boolean(true)
+                        )
+                    })
+                    .unwrap_or(true)
+                {
+                    out.push(r);
+                }
+            }
+        }
+        return Ok(true);
+    }
+
+    // `!=` still needs a full scan. With index exclusion enabled, the rows the
+    // index lists for the excluded value are dropped without evaluating the
+    // predicate; entries can be stale, so the column is re-checked first.
+    let mut exclusion = None;
+    if catalog.index_exclusion() {
+        if let Some((col_name, value, index)) = selection
+            .as_ref()
+            .and_then(|expr| indexed_inequality(catalog, table_name, &columns, expr))
+        {
+            let nocase = table_info.is_nocase(&col_name);
+            let (expected, candidates) =
+                index_candidates(catalog, &index, &value, column_type(&col_name), nocase)?;
+            let col_idx = columns.iter().position(|(c, _)| c == &col_name).unwrap();
+            if let Some(keys) = candidates {
+                let keys = keys.into_iter().collect::<HashSet<_>>();
+                exclusion = Some((col_idx, nocase, expected, keys));
+            }
+        }
+    }
+
+    // Comparisons on the row key start the scan at the lower bound and stop
+    // past the upper one instead of walking every leaf.
+    let range = selection
+        .as_ref()
+        .and_then(|expr| key_range(&columns, expr));
+    let mut table_btree = BTree::open_root(&mut catalog.pager, root_page)?;
+    let rows = match (range, projection) {
+        (Some((lower, upper)), Some(projection)) => {
+            table_btree.scan_visible_range_projected(lower, upper, &snapshot, projection)?
+        }
+        (Some((lower, upper)), None) => table_btree.scan_visible_range(lower, upper, &snapshot)?,
+        (None, Some(projection)) => table_btree.scan_visible_projected(&snapshot, projection)?,
+        (None, None) => table_btree.scan_visible(&snapshot)?,
+    };
+    for row in rows {
+        if let Some((col_idx, nocase, expected, keys)) = &exclusion {
+            let value = Catalog::collate_value(row.data.0[*col_idx].clone(), *nocase);
+            if keys.contains(&row.key) && Catalog::value_to_string(&value) == *expected {
+                continue;
+            }
+        }
+        if let Some(ref expr) = selection {
+            let mut values = HashMap::new();
+            for ((col, _), val) in columns.iter().zip(row.data.0.iter()) {
+                let v = val.to_predicate_string();
+                values.insert(col.clone(), v);
+            }
+            if matches!(
+                crate::sql::ast::evaluate_expression_typed(expr, &values, &types),
+                ColumnValue::Boolean(true)
+            ) {
+                out.push(row);
+            }
+        } else {
+            out.push(row);
+        }
+    }
+    Ok(exclusion.is_some())
+}
+
+/// Qualified `alias.column` names of a join with their types, and one value
+/// map per joined row.
+type JoinedRows = (Vec<(String, ColumnType)>, Vec<HashMap<String, ColumnValue>>);
+
+/// Run the joins of `plan` in clause order, with NULLs padding the unmatched
+/// side of outer joins.
+fn join_rows(
+    plan: &crate::execution::plan::MultiJoinPlan,
+    catalog: &mut Catalog,
+) -> DbResult<JoinedRows> {
+    use crate::sql::ast::evaluate_expression_typed;
+    let mut result_rows: Vec<std::collections::HashMap<String, ColumnValue>> = Vec::new();
+    let mut result_columns = Vec::new();
+
+    // base table scan
+    {
+        let (columns, rows) =
+            join_source(catalog, &plan.base_table, plan.base_subquery.as_deref())?;
+        let alias = plan.base_alias.as_deref().unwrap_or(&plan.base_table);
+        for values in rows {
+            let mut map = std::collections::HashMap::new();
+            for ((c, _), v) in columns.iter().zip(values) {
+                map.insert(format!("{alias}.{c}"), v);
+            }
+            result_rows.push(map);
+        }
+        for (c, ty) in columns.iter() {
+            result_columns.push((format!("{alias}.{c}"), *ty));
+        }
+    }
+
+    for jc in &plan.joins {
+        let alias = jc.alias.as_ref().unwrap_or(&jc.table);
+        let (columns, source_rows) = join_source(catalog, &jc.table, jc.subquery.as_deref())?;
+        let rows: Vec<_> = source_rows
+            .into_iter()
+            .map(|values| {
+                let mut m = std::collections::HashMap::new();
+                for ((c, _), v) in columns.iter().zip(values) {
+                    m.insert(format!("{alias}.{c}"), v);
+                }
+                m
+            })
+            .collect();
+
+        let mut new_rows = Vec::new();
+        let mut matched_right = vec![false; rows.len()];
+        let right_columns: Vec<(String, ColumnType)> = columns
+            .iter()
+            .map(|(c, ty)| (format!("{alias}.{c}"), *ty))
+            .collect();
+        let types: HashMap<String, ColumnType> = result_columns
+            .iter()
+            .chain(&right_columns)
+            .cloned()
+            .collect();
+
+        for left in &result_rows {
+            let mut matched_left = false;
+            for (idx_row, r) in rows.iter().enumerate() {
+                let mut candidate = left.clone();
+                for (k, v) in r {
+                    candidate.insert(k.clone(), v.clone());
+                }
+                let matches = match jc.join_type {
+                    crate::sql::ast::JoinType::Cross => true,
+                    _ => {
+                        if let Some(ref predicate) = jc.predicate {
+                            let mut str_map = std::collections::HashMap::new();
+                            for (k, v) in &candidate {
+                                str_map.insert(k.clone(), v.to_predicate_string());
+                            }
+                            matches!(
+                                evaluate_expression_typed(predicate, &str_map, &types),
+                                ColumnValue::Boolean(true)
+                            )
+                        } else {
+                            true
+                        }
+                    }
+                };
+                if matches {
+                    matched_left = true;
+                    matched_right[idx_row] = true;
+                    new_rows.push(candidate);
+                }
+            }
+            if !matched_left
+                && matches!(
+                    jc.join_type,
+                    crate::sql::ast::JoinType::Left | crate::sql::ast::JoinType::Full
+                )
+            {
+                let mut merged = left.clone();
+                for (col, _) in &right_columns {
+                    merged.insert(col.clone(), ColumnValue::Null);
+                }
+                new_rows.push(merged);
+            }
+        }
+
+        if matches!(
+            jc.join_type,
+            crate::sql::ast::JoinType::Right | crate::sql::ast::JoinType::Full
+        ) {
+            for (idx_row, r) in rows.iter().enumerate() {
                 if matched_right[idx_row] {
                     continue;
                 }
                 let mut merged = std::collections::HashMap::new();
-                for col in &result_columns {
+                for (col, _) in &result_columns {
                     merged.insert(col.clone(), ColumnValue::Null);
                 }
                 for (k, v) in r {
@@ -816,26 +1538,115 @@ pub fn execute_multi_join(
         result_rows = new_rows;
         result_columns.extend(right_columns);
     }
+    Ok((result_columns, result_rows))
+}
+
+type JoinSource = (Vec<(String, ColumnType)>, Vec<Vec<ColumnValue>>);
+
+/// Columns and rows of a joined relation: the visible rows of `table`, or
+/// the result of a derived `subquery`, its values parsed by header type.
+fn join_source(
+    catalog: &mut Catalog,
+    table: &str,
+    subquery: Option<&Statement>,
+) -> DbResult<JoinSource> {
+    let Some(query) = subquery else {
+        let info = catalog.get_table(table)?.clone();
+        let snapshot = dml_snapshot(catalog);
+        let mut tree = BTree::open_root(&mut catalog.pager, info.root_page)?;
+        let rows = tree
+            .scan_visible(&snapshot)?
+            .into_iter()
+            .map(|r| r.data.0)
+            .collect();
+        return Ok((info.columns, rows));
+    };
+    let mut result = Vec::new();
+    let header = execute_select_statement(catalog, query, &mut result, None)?;
+    let rows = result
+        .into_iter()
+        .map(|row| {
+            row.iter()
+                .zip(&header)
+                .map(|(v, (_, ty))| derived_value(v, *ty))
+                .collect()
+        })
+        .collect();
+    Ok((header, rows))
+}
 
+/// A result cell of a derived relation read back as a value of its column type.
+fn derived_value(cell: &str, ty: ColumnType) -> ColumnValue {
+    if cell == "NULL" || cell == NULL_MARKER {
+        ColumnValue::Null
+    } else {
+        parse_index_lookup_value(cell, ty)
+    }
+}
+
+pub fn execute_multi_join(
+    plan: &crate::execution::plan::MultiJoinPlan,
+    catalog: &mut Catalog,
+    out: &mut Vec<Vec<String>>,
+) -> DbResult<()> {
+    execute_ordered_join(plan, catalog, None, out)
+}
+
+/// [`execute_multi_join`] with the joined rows sorted by `order_by` before
+/// they are projected, so the key need not be in the select list. The key is
+/// a qualified column, a bare one when unambiguous, or a select-list alias.
+fn execute_ordered_join(
+    plan: &crate::execution::plan::MultiJoinPlan,
+    catalog: &mut Catalog,
+    order_by: Option<&crate::sql::ast::OrderBy>,
+    out: &mut Vec<Vec<String>>,
+) -> DbResult<()> {
+    let (columns, result_rows) = join_rows(plan, catalog)?;
     let projections = expand_join_projections(plan, catalog)?;
+    let order_key = order_by
+        .map(|order| {
+            let key = resolve_select_alias(&order.column, &columns, &plan.projections);
+            let bare = |c: &str| c.split_once('.').is_some_and(|(_, b)| b == key);
+            columns
+                .iter()
+                .find(|(c, _)| *c == key)
+                .or_else(|| {
+                    let mut named = columns.iter().filter(|(c, _)| bare(c));
+                    named.next().filter(|_| named.next().is_none())
+                })
+                .map(|(c, _)| (c.clone(), order.descending))
+                .ok_or_else(|| DbError::ColumnNotFound(order.column.clone()))
+        })
+        .transpose()?;
+    // Typed by qualified name, so `b.price > 100` compares numerically.
+    let types: HashMap<String, ColumnType> = columns.into_iter().collect();
+    let mut kept = Vec::new();
     for row in result_rows {
         let mut str_map = std::collections::HashMap::new();
         for (k, v) in &row {
-            let s = v.to_string_value();
-            str_map.insert(k.clone(), s);
+            str_map.insert(k.clone(), v.to_predicate_string());
         }
         if let Some(ref pred) = plan.where_predicate {
-            if !matches!(
-                evaluate_expression(pred, &str_map),
-                ColumnValue::Boolean(true)
-            ) {
+            if !evaluate_with_catalog(pred, &str_map, &types, &HashSet::new(), catalog)? {
                 continue;
             }
         }
+        kept.push(row);
+    }
+    if let Some((key, descending)) = &order_key {
+        kept.sort_by(|a, b| {
+            let ord = match (a.get(key), b.get(key)) {
+                (Some(x), Some(y)) => x.compare(y),
+                (x, y) => x.is_some().cmp(&y.is_some()),
+            };
+            if *descending { ord.reverse() } else { ord }
+        });
+    }
+    for row in kept {
         let mut projected = Vec::new();
         for p in &projections {
-            if let Some(v) = str_map.get(p) {
-                projected.push(v.clone());
+            if let Some(v) = row.get(p) {
+                projected.push(value_cell(catalog, v));
             }
         }
         out.push(projected);
@@ -843,6 +1654,42 @@ pub fn execute_multi_join(
     Ok(())
 }
 
+/// Aggregate the joined rows of `plan`, optionally grouped by join columns;
+/// `GROUP BY`, `HAVING` and the select list may name columns qualified or,
+/// when unambiguous, bare.
+pub fn execute_join_group_query(
+    plan: &crate::execution::plan::MultiJoinPlan,
+    group_by: Option<&[String]>,
+    having: Option<Expr>,
+    catalog: &mut Catalog,
+    out: &mut Vec<Vec<String>>,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    let (columns, maps) = join_rows(plan, catalog)?;
+    let rows = maps
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut map)| {
+            let values = columns
+                .iter()
+                .map(|(n, _)| map.remove(n).unwrap_or(ColumnValue::Null))
+                .collect();
+            Row::new(i as RowKey, RowData(values))
+        })
+        .collect();
+    group_rows(
+        catalog,
+        None,
+        &columns,
+        rows,
+        &plan.projections,
+        group_by,
+        having,
+        plan.where_predicate.clone(),
+        out,
+        None,
+    )
+}
+
 pub fn execute_group_query(
     catalog: &mut Catalog,
     table_name: &str,
@@ -856,16 +1703,106 @@ pub fn execute_group_query(
     let mut rows = Vec::new();
     execute_select_with_indexes(catalog, table_name, None, &mut rows)?;
     let table_info = catalog.get_table(table_name)?.clone();
-    aggregate::validate_group_by(projections, group_by, having.as_ref(), &table_info.columns)?;
+    group_rows(
+        catalog,
+        Some(table_name),
+        &table_info.columns,
+        rows,
+        projections,
+        group_by,
+        having,
+        selection,
+        out,
+        context,
+    )
+}
+
+/// The column a GROUP BY or ORDER BY key names: the key itself when it is a
+/// column, or bare and unique among joined ones, otherwise the column behind a
+/// select-list alias, as `dept` for `GROUP BY d` in `SELECT dept AS d`.
+fn resolve_select_alias(
+    key: &str,
+    columns: &[(String, ColumnType)],
+    projections: &[crate::sql::ast::SelectExpr],
+) -> String {
+    let is_column = columns.iter().any(|(c, _)| {
+        c.eq_ignore_ascii_case(key)
+            || c.split_once('.').is_some_and(|(_, bare)| bare.eq_ignore_ascii_case(key))
+    });
+    if is_column {
+        return key.to_string();
+    }
+    projections
+        .iter()
+        .find_map(|p| match (&p.expr, &p.alias) {
+            (crate::sql::ast::SelectItem::Column(c), Some(alias)) if alias == key => {
+                Some(c.clone())
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| key.to_string())
+}
 
+/// The computed expression the select list names `alias`, as `score * 2` for
+/// `ORDER BY dbl` in `SELECT score * 2 AS dbl`.
+fn projected_expr<'e>(
+    projections: &'e [crate::sql::ast::SelectExpr],
+    alias: &str,
+) -> Option<&'e Expr> {
+    projections.iter().find_map(|p| match (&p.expr, &p.alias) {
+        (crate::sql::ast::SelectItem::Expr(e), Some(a)) if a == alias => Some(e.as_ref()),
+        _ => None,
+    })
+}
+
+/// Filter, group and aggregate `rows`, whose values follow `columns`. With a
+/// `qualifier`, WHERE and HAVING also see each column as `qualifier.column`.
+#[allow(clippy::too_many_arguments)]
+fn group_rows(
+    catalog: &mut Catalog,
+    qualifier: Option<&str>,
+    columns: &[(String, ColumnType)],
+    rows: Vec<Row>,
+    projections: &[crate::sql::ast::SelectExpr],
+    group_by: Option<&[String]>,
+    having: Option<Expr>,
+    selection: Option<Expr>,
+    out: &mut Vec<Vec<String>>,
+    context: Option<&std::collections::HashMap<String, String>>,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    let group_by: Option<Vec<String>> = group_by.map(|keys| {
+        keys.iter()
+            .map(|key| resolve_select_alias(key, columns, projections))
+            .collect()
+    });
+    let group_by = group_by.as_deref();
+    aggregate::validate_group_by(projections, group_by, having.as_ref(), columns)?;
+    let types = column_types(columns);
+    let nocase = match qualifier {
+        Some(table) => nocase_columns(catalog.get_table(table)?, qualifier),
+        None => HashSet::new(),
+    };
+
+    use crate::sql::ast::SelectItem;
     let mut groups: std::collections::HashMap<Vec<String>, Vec<crate::storage::row::Row>> =
         std::collections::HashMap::new();
     let mut col_pos = std::collections::HashMap::new();
-    for (i, (c, _)) in table_info.columns.iter().enumerate() {
+    for (i, (c, _)) in columns.iter().enumerate() {
         col_pos.insert(c.clone(), i);
         col_pos.insert(c.to_uppercase(), i);
     }
-    let mut get_idx = |name: &str| -> DbResult<usize> {
+    // Joined columns are qualified; a bare name resolves when it is unique.
+    for (i, (c, _)) in columns.iter().enumerate() {
+        if let Some((_, bare)) = c.split_once('.') {
+            let same_name =
+                |(o, _): &&(String, ColumnType)| o.split_once('.').is_some_and(|(_, b)| b == bare);
+            if columns.iter().filter(same_name).count() == 1 {
+                col_pos.entry(bare.to_string()).or_insert(i);
+                col_pos.entry(bare.to_uppercase()).or_insert(i);
+            }
+        }
+    }
+    let get_idx = |name: &str| -> DbResult<usize> {
         col_pos
             .get(name)
             .copied()
@@ -883,14 +1820,15 @@ pub fn execute_group_query(
                 values.insert(k.clone(), v.clone());
             }
         }
-        for ((c, _), val) in table_info.columns.iter().zip(row.data.0.iter()) {
-            let s = val.to_string_value();
-            values.insert(c.clone(), s.clone());
-            let qual = format!("{}.{}", table_name, c);
-            values.insert(qual, s);
+        for ((c, _), val) in columns.iter().zip(row.data.0.iter()) {
+            let s = val.to_predicate_string();
+            if let Some(q) = qualifier {
+                values.insert(format!("{}.{}", q, c), s.clone());
+            }
+            values.insert(c.clone(), s);
         }
         if let Some(ref sel) = selection {
-            if !evaluate_with_catalog(sel, &values, catalog)? {
+            if !evaluate_with_catalog(sel, &values, &types, &nocase, catalog)? {
                 continue;
             }
         }
@@ -898,7 +1836,7 @@ pub fn execute_group_query(
             let mut parts = Vec::new();
             for c in gb {
                 let idx = get_idx(c)?;
-                parts.push(row.data.0[idx].to_string_value());
+                parts.push(row.data.0[idx].to_predicate_string());
             }
             parts
         } else {
@@ -906,17 +1844,27 @@ pub fn execute_group_query(
         };
         groups.entry(key).or_default().push(row);
     }
+    // Aggregates without GROUP BY form one group even when no row is left,
+    // so `COUNT(*)` gives 0 and HAVING still has a group to test.
+    if group_by.is_none()
+        && groups.is_empty()
+        && projections
+            .iter()
+            .all(|p| matches!(p.expr, SelectItem::Aggregate { .. } | SelectItem::Literal(_)))
+    {
+        groups.insert(Vec::new(), Vec::new());
+    }
+    let mut having_aggs = Vec::new();
+    if let Some(pred) = &having {
+        having_aggregates(pred, &mut having_aggs);
+    }
 
     let mut header = Vec::new();
-    use crate::sql::ast::SelectItem;
     for expr in projections {
         match &expr.expr {
             SelectItem::Column(c) => {
                 let idx = get_idx(c)?;
-                header.push((
-                    expr.alias.clone().unwrap_or(c.clone()),
-                    table_info.columns[idx].1,
-                ));
+                header.push((expr.alias.clone().unwrap_or(c.clone()), columns[idx].1));
             }
             SelectItem::Aggregate { func, column } => {
                 let name = format!(
@@ -924,10 +1872,24 @@ pub fn execute_group_query(
                     func.as_str(),
                     column.clone().unwrap_or("*".into())
                 );
-                header.push((expr.alias.clone().unwrap_or(name), ColumnType::Integer));
+                let ty = match (func, column) {
+                    (
+                        crate::sql::ast::AggFunc::Min | crate::sql::ast::AggFunc::Max,
+                        Some(c),
+                    ) => columns[get_idx(c)?].1,
+                    // SUM and AVG of a DECIMAL keep its scale.
+                    (
+                        crate::sql::ast::AggFunc::Sum | crate::sql::ast::AggFunc::Avg,
+                        Some(c),
+                    ) if matches!(columns[get_idx(c)?].1, ColumnType::Decimal { .. }) => {
+                        columns[get_idx(c)?].1
+                    }
+                    _ => ColumnType::Integer,
+                };
+                header.push((expr.alias.clone().unwrap_or(name), ty));
             }
-            SelectItem::All => {
-                for (c, ty) in &table_info.columns {
+            SelectItem::All | SelectItem::AllFrom(_) => {
+                for (c, ty) in columns {
                     header.push((c.clone(), *ty));
                 }
             }
@@ -938,11 +1900,7 @@ pub fn execute_group_query(
                 ));
             }
             SelectItem::Literal(val) => {
-                let ty = if val.parse::<i32>().is_ok() {
-                    ColumnType::Integer
-                } else {
-                    ColumnType::Text
-                };
+                let ty = literal_column_type(val);
                 header.push((expr.alias.clone().unwrap_or_else(|| val.clone()), ty));
             }
             SelectItem::Expr(_) => {
@@ -962,68 +1920,11 @@ pub fn execute_group_query(
                 SelectItem::Column(c) => {
                     let idx = get_idx(c)?;
                     let val = &grows[0].data.0[idx];
-                    let s = val.to_string_value();
-                    value_map.insert(c.clone(), s.clone());
-                    result_row.push(s);
+                    value_map.insert(c.clone(), val.to_predicate_string());
+                    result_row.push(value_cell(catalog, val));
                 }
                 SelectItem::Aggregate { func, column } => {
-                    let val = match func {
-                        crate::sql::ast::AggFunc::Count => grows.len().to_string(),
-                        crate::sql::ast::AggFunc::Sum => {
-                            let idx = get_idx(column.as_ref().unwrap())?;
-                            match table_info.columns[idx].1 {
-                                ColumnType::Double { .. } => {
-                                    let mut sum = 0.0;
-                                    for r in &grows {
-                                        if let ColumnValue::Double(f) = r.data.0[idx] {
-                                            sum += f;
-                                        }
-                                    }
-                                    sum.to_string()
-                                }
-                                _ => {
-                                    let mut sum = 0i64;
-                                    for r in &grows {
-                                        if let ColumnValue::Integer(i) = r.data.0[idx] {
-                                            sum += i as i64;
-                                        }
-                                    }
-                                    sum.to_string()
-                                }
-                            }
-                        }
-                        crate::sql::ast::AggFunc::Min => {
-                            let idx = get_idx(column.as_ref().unwrap())?;
-                            let mut min_val: Option<i32> = None;
-                            for r in &grows {
-                                if let ColumnValue::Integer(i) = r.data.0[idx] {
-                                    min_val = Some(min_val.map_or(i, |m| m.min(i)));
-                                }
-                            }
-                            min_val.unwrap_or(0).to_string()
-                        }
-                        crate::sql::ast::AggFunc::Max => {
-                            let idx = get_idx(column.as_ref().unwrap())?;
-                            let mut max_val: Option<i32> = None;
-                            for r in &grows {
-                                if let ColumnValue::Integer(i) = r.data.0[idx] {
-                                    max_val = Some(max_val.map_or(i, |m| m.max(i)));
-                                }
-                            }
-                            max_val.unwrap_or(0).to_string()
-                        }
-                        crate::sql::ast::AggFunc::Avg => {
-                            let idx = get_idx(column.as_ref().unwrap())?;
-                            let mut sum = 0i64;
-                            for r in &grows {
-                                if let ColumnValue::Integer(i) = r.data.0[idx] {
-                                    sum += i as i64;
-                                }
-                            }
-                            let avg = sum as f64 / grows.len() as f64;
-                            avg.to_string()
-                        }
-                    };
+                    let val = aggregate_value(func, column.as_deref(), &grows, columns, &get_idx)?;
                     let name = format!(
                         "{}({})",
                         func.as_str(),
@@ -1034,22 +1935,21 @@ pub fn execute_group_query(
                     if expr.alias.is_some() {
                         value_map.insert(name, val.clone());
                     }
-                    result_row.push(val);
+                    result_row.push(if val == NULL_MARKER { null_cell(catalog) } else { val });
                 }
-                SelectItem::All => {
-                    for (i, _) in &table_info.columns {
+                SelectItem::All | SelectItem::AllFrom(_) => {
+                    for (i, _) in columns {
                         let idx = get_idx(i)?;
                         let v = &grows[0].data.0[idx];
-                        let s = v.to_string_value();
-                        value_map.insert(i.clone(), s.clone());
-                        result_row.push(s);
+                        value_map.insert(i.clone(), v.to_predicate_string());
+                        result_row.push(value_cell(catalog, v));
                     }
                 }
                 SelectItem::Subquery(sub) => {
                     let mut inner_rows = Vec::new();
                     let mut ctx = std::collections::HashMap::new();
-                    for ((c, _), v) in table_info.columns.iter().zip(grows[0].data.0.iter()) {
-                        let val = v.to_string_value();
+                    for ((c, _), v) in columns.iter().zip(grows[0].data.0.iter()) {
+                        let val = v.to_predicate_string();
                         ctx.insert(c.clone(), val);
                     }
                     execute_select_statement(catalog, sub, &mut inner_rows, Some(&ctx))?;
@@ -1065,22 +1965,43 @@ pub fn execute_group_query(
                     result_row.push(val.clone());
                 }
                 SelectItem::Expr(expr) => {
-                    let map = table_info
-                        .columns
+                    let map = columns
                         .iter()
                         .zip(grows[0].data.0.iter())
-                        .map(|((c, _), v)| (c.clone(), v.to_string_value()))
+                        .map(|((c, _), v)| (c.clone(), v.to_predicate_string()))
                         .collect::<std::collections::HashMap<_, _>>();
-                    let val = crate::sql::ast::evaluate_expression(expr, &map).to_string_value();
-                    result_row.push(val);
+                    let val = crate::sql::ast::try_evaluate_expression_typed(expr, &map, &types)?;
+                    result_row.push(value_cell(catalog, &val));
                 }
             }
         }
+        // HAVING may name an aggregate however it is spelled, or one the
+        // select list leaves out; bind each call it compares as written.
+        for (token, func, column) in &having_aggs {
+            let val = aggregate_value(func, column.as_deref(), &grows, columns, &get_idx)?;
+            value_map.insert(token.clone(), val);
+        }
         if let Some(ref pred) = having {
-            if !matches!(
-                crate::sql::ast::evaluate_expression(pred, &value_map),
-                ColumnValue::Boolean(true)
-            ) {
+            let keep = if expr_has_subquery(pred) {
+                // Correlated subqueries see the aggregate values plus the
+                // group's representative row, both bare and table-qualified.
+                let mut ctx = context.cloned().unwrap_or_default();
+                for ((c, _), v) in columns.iter().zip(grows[0].data.0.iter()) {
+                    let s = v.to_predicate_string();
+                    if let Some(q) = qualifier {
+                        ctx.insert(format!("{}.{}", q, c), s.clone());
+                    }
+                    ctx.insert(c.clone(), s);
+                }
+                ctx.extend(value_map.clone());
+                evaluate_with_catalog(pred, &ctx, &types, &nocase, catalog)?
+            } else {
+                matches!(
+                    crate::sql::ast::evaluate_expression(pred, &value_map),
+                    ColumnValue::Boolean(true)
+                )
+            };
+            if !keep {
                 continue;
             }
         }
@@ -1089,8 +2010,170 @@ pub fn execute_group_query(
     Ok(header)
 }
 
-pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()> {
-    match stmt {
+/// The value of `func` over one group's rows as a predicate string. SUM, AVG,
+/// MIN, and MAX of an empty group are NULL.
+fn aggregate_value(
+    func: &crate::sql::ast::AggFunc,
+    column: Option<&str>,
+    grows: &[Row],
+    columns: &[(String, ColumnType)],
+    get_idx: &dyn Fn(&str) -> DbResult<usize>,
+) -> DbResult<String> {
+    if grows.is_empty() && !matches!(func, crate::sql::ast::AggFunc::Count) {
+        return Ok(NULL_MARKER.to_string());
+    }
+    Ok(match func {
+        crate::sql::ast::AggFunc::Count => grows.len().to_string(),
+        crate::sql::ast::AggFunc::Sum => {
+            let idx = get_idx(column.unwrap_or_default())?;
+            match columns[idx].1 {
+                ColumnType::Double { .. } => {
+                    let mut sum = 0.0;
+                    for r in grows {
+                        if let ColumnValue::Double(f) = r.data.0[idx] {
+                            sum += f;
+                        }
+                    }
+                    sum.to_string()
+                }
+                ColumnType::Decimal { scale, .. } => {
+                    decimal_string(decimal_sum(grows, idx), scale as u8)
+                }
+                _ => {
+                    let mut sum = 0i64;
+                    for r in grows {
+                        match r.data.0[idx] {
+                            ColumnValue::Integer(i) => sum += i as i64,
+                            ColumnValue::BigInt(i) => sum += i,
+                            _ => {}
+                        }
+                    }
+                    sum.to_string()
+                }
+            }
+        }
+        crate::sql::ast::AggFunc::Min | crate::sql::ast::AggFunc::Max => {
+            // Compare in the column's own type and return the value
+            // as stored; NULLs are skipped.
+            let idx = get_idx(column.unwrap_or_default())?;
+            let mut best: Option<&ColumnValue> = None;
+            for v in grows.iter().map(|r| &r.data.0[idx]) {
+                if matches!(v, ColumnValue::Null) {
+                    continue;
+                }
+                let ord = best.map(|b| v.compare(b));
+                let better = match func {
+                    crate::sql::ast::AggFunc::Min => ord.is_none_or(|o| o.is_lt()),
+                    _ => ord.is_none_or(|o| o.is_gt()),
+                };
+                if better {
+                    best = Some(v);
+                }
+            }
+            best.map_or_else(|| NULL_MARKER.to_string(), |v| v.to_string_value())
+        }
+        crate::sql::ast::AggFunc::Avg => {
+            let idx = get_idx(column.unwrap_or_default())?;
+            if let ColumnType::Decimal { scale, .. } = columns[idx].1 {
+                let count = grows.len().max(1) as i128;
+                let sum = decimal_sum(grows, idx);
+                // Round half away from zero at the column's scale.
+                let avg = (2 * sum + sum.signum() * count) / (2 * count);
+                decimal_string(avg, scale as u8)
+            } else {
+                let mut sum = 0i64;
+                for r in grows {
+                    match r.data.0[idx] {
+                        ColumnValue::Integer(i) => sum += i as i64,
+                        ColumnValue::BigInt(i) => sum += i,
+                        _ => {}
+                    }
+                }
+                let avg = sum as f64 / grows.len() as f64;
+                avg.to_string()
+            }
+        }
+    })
+}
+
+/// Aggregate calls such as `count(*)` or `SUM(x)` compared in `having`, as
+/// written, with the function and column they name.
+fn having_aggregates(
+    having: &Expr,
+    out: &mut Vec<(String, crate::sql::ast::AggFunc, Option<String>)>,
+) {
+    match having {
+        Expr::And(a, b) | Expr::Or(a, b) => {
+            having_aggregates(a, out);
+            having_aggregates(b, out);
+        }
+        _ => {
+            for token in crate::sql::ast::operand_tokens(having) {
+                if let Some((func, column)) = aggregate_call(token) {
+                    out.push((token.clone(), func, column));
+                }
+            }
+        }
+    }
+}
+
+/// Parse an operand such as `count(*)` or `SUM(t.x)` as an aggregate call.
+fn aggregate_call(token: &str) -> Option<(crate::sql::ast::AggFunc, Option<String>)> {
+    use crate::sql::ast::AggFunc;
+    let (name, rest) = token.split_once('(')?;
+    let func = match name.trim().to_uppercase().as_str() {
+        "COUNT" => AggFunc::Count,
+        "SUM" => AggFunc::Sum,
+        "AVG" => AggFunc::Avg,
+        "MIN" => AggFunc::Min,
+        "MAX" => AggFunc::Max,
+        _ => return None,
+    };
+    let arg = rest.strip_suffix(')')?.trim();
+    let column = (arg != "*").then(|| arg.to_string());
+    Some((func, column))
+}
+
+/// Outcome of [`handle_statement`], for the REPL or an embedding program to
+/// present as it sees fit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryResult {
+    /// A result set: column names with their types, then the formatted rows.
+    Rows {
+        header: Vec<(String, ColumnType)>,
+        rows: Vec<Vec<String>>,
+    },
+    /// Number of rows inserted, updated or deleted.
+    Affected(usize),
+    /// A status line such as `Table t created` or a PRAGMA's value.
+    Message(String),
+    /// The statement completed with nothing to report.
+    Ack,
+}
+
+pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<QueryResult> {
+    let mut result = execute_statement(catalog, stmt)?;
+    if let (QueryResult::Rows { header, rows }, Some(places)) =
+        (&mut result, catalog.float_precision())
+    {
+        apply_float_precision(header, rows, places);
+    }
+    Ok(result)
+}
+
+/// Run `stmt` like [`handle_statement`] for [`format_json`] or [`format_csv`]:
+/// SQL NULL comes back as [`NULL_MARKER`], so it is not mistaken for the text
+/// `'NULL'`.
+pub fn handle_export_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<QueryResult> {
+    catalog.set_mark_nulls(true);
+    let result = handle_statement(catalog, stmt);
+    catalog.set_mark_nulls(false);
+    result
+}
+
+fn execute_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<QueryResult> {
+    let stmt = expand_views(catalog, &stmt)?.unwrap_or(stmt);
+    let result = match stmt {
         Statement::CreateTable {
             table_name,
             columns,
@@ -1104,6 +2187,8 @@ pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()>
                     "Only one AUTO_INCREMENT column allowed per table".into(),
                 ));
             }
+            let generated: Vec<_> = columns.iter().map(|c| c.generated.clone()).collect();
+            let nocase: Vec<_> = columns.iter().map(|c| c.nocase).collect();
             let cols: Vec<_> = columns
                 .into_iter()
                 .map(|c| {
@@ -1116,46 +2201,84 @@ pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()>
                     )
                 })
                 .collect();
-            match catalog.create_table_with_fks(&table_name, cols.clone(), fks, primary_key.clone())
-            {
-                Ok(()) => println!("Table {} created", table_name),
-                Err(e) => {
-                    if if_not_exists && e.to_string().contains("already exists") {
-                        println!("Table {} already exists", table_name);
-                    } else {
-                        return Err(DbError::from(e));
-                    }
-                }
+            if if_not_exists && catalog.table_exists(&table_name) {
+                return Ok(QueryResult::Message(format!(
+                    "Table {} already exists",
+                    table_name
+                )));
             }
+            catalog.create_table_with_collations(
+                &table_name,
+                cols.clone(),
+                fks,
+                primary_key.clone(),
+                generated,
+                nocase,
+            )?;
+            let message = format!("Table {} created", table_name);
             for (name, _, _, _, ai) in cols {
                 if ai {
                     let seq_name = format!("{}_{}", table_name, name);
                     catalog.create_sequence(&seq_name, 1, 1)?;
                 }
             }
+            QueryResult::Message(message)
         }
         Statement::CreateIndex {
             index_name,
             table_name,
             column_name,
+            if_not_exists,
         } => {
-            catalog.create_index(&index_name, &table_name, &column_name)?;
-            println!("Index {} created", index_name);
+            if if_not_exists && catalog.index_exists(&index_name) {
+                QueryResult::Message(format!("Index {} already exists", index_name))
+            } else {
+                catalog.create_index(&index_name, &table_name, &column_name)?;
+                QueryResult::Message(format!("Index {} created", index_name))
+            }
+        }
+        Statement::DropIndex { name, if_exists } => {
+            if catalog.drop_index(&name)? {
+                QueryResult::Message(format!("Index {} dropped", name))
+            } else if if_exists {
+                QueryResult::Message(format!("Index {} does not exist", name))
+            } else {
+                return Err(DbError::NotFound(format!("index '{}' not found", name)));
+            }
         }
-        Statement::DropIndex { name } => {
-            if catalog.drop_index(&name)? {
-                println!("Index {} dropped", name);
+        Statement::Reindex { index, table } => {
+            let rebuilt = catalog.reindex(index.as_deref(), table.as_deref())?;
+            QueryResult::Message(format!("{} index(es) rebuilt", rebuilt))
+        }
+        Statement::CreateView { name, sql } => {
+            catalog.create_view(&name, &sql)?;
+            QueryResult::Message(format!("View {} created", name))
+        }
+        Statement::DropView { name, if_exists } => {
+            if catalog.drop_view(&name)? {
+                QueryResult::Message(format!("View {} dropped", name))
+            } else if if_exists {
+                QueryResult::Message(format!("View {} does not exist", name))
             } else {
-                return Err(DbError::NotFound(format!("index '{}' not found", name)));
+                return Err(DbError::NotFound(format!("view '{}' not found", name)));
             }
         }
+        Statement::Checkpoint => {
+            catalog.checkpoint()?;
+            QueryResult::Message("Checkpoint complete".into())
+        }
+        // `TransactionManager::execute` runs VACUUM itself; reaching here
+        // means the statement came through a session's transaction.
+        Statement::Vacuum { .. } => {
+            return Err(DbError::InvalidValue(
+                "VACUUM cannot run inside a transaction".into(),
+            ));
+        }
         Statement::Insert {
             table_name,
             columns: col_list,
             rows,
-        } => {
-            execute_insert(catalog, &table_name, col_list, rows)?;
-        }
+        } => QueryResult::Affected(execute_insert(catalog, &table_name, col_list, rows)?),
         Statement::Select {
             columns,
             from,
@@ -1167,16 +2290,21 @@ pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()>
             limit,
             offset,
         } => {
-            let has_subquery = from
-                .iter()
-                .any(|t| matches!(t, crate::sql::ast::TableRef::Subquery { .. }))
+            // A derived table joined to others goes through the join plan below.
+            let has_subquery = (joins.is_empty()
+                && from
+                    .iter()
+                    .any(|t| matches!(t, crate::sql::ast::TableRef::Subquery { .. })))
                 || columns
                     .iter()
                     .any(|c| matches!(c.expr, crate::sql::ast::SelectItem::Subquery(_)))
                 || where_predicate
                     .as_ref()
                     .map_or(false, |e| expr_has_subquery(e));
-            if has_subquery {
+            let system_source = from.iter().any(|t| {
+                matches!(t, crate::sql::ast::TableRef::Named { name, .. } if is_system_table(name))
+            });
+            if has_subquery || system_source {
                 let stmt = crate::sql::ast::Statement::Select {
                     columns: columns.clone(),
                     from: from.clone(),
@@ -1188,13 +2316,9 @@ pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()>
                     limit,
                     offset,
                 };
-                let mut results = Vec::new();
-                let header = execute_select_statement(catalog, &stmt, &mut results, None)?;
-                println!("{}", format_header(&header));
-                for row in results {
-                    println!("{}", format_values(&row));
-                }
-                return Ok(());
+                let mut rows = Vec::new();
+                let header = execute_select_statement(catalog, &stmt, &mut rows, None)?;
+                return Ok(QueryResult::Rows { header, rows });
             }
             if from.is_empty() {
                 let stmt = crate::sql::ast::Statement::Select {
@@ -1208,114 +2332,151 @@ pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()>
                     limit,
                     offset,
                 };
-                let mut results = Vec::new();
-                let header = execute_select_statement(catalog, &stmt, &mut results, None)?;
-                println!("{}", format_header(&header));
-                for row in results {
-                    println!("{}", format_values(&row));
-                }
-                return Ok(());
+                let mut rows = Vec::new();
+                let header = execute_select_statement(catalog, &stmt, &mut rows, None)?;
+                return Ok(QueryResult::Rows { header, rows });
             }
-            let (from_table, base_alias) = match from.first().unwrap() {
-                crate::sql::ast::TableRef::Named { name, alias } => (name.clone(), alias.clone()),
-                _ => unreachable!(),
+            let (from_table, base_alias, base_subquery) = match from.first().unwrap() {
+                crate::sql::ast::TableRef::Named { name, alias } => {
+                    (name.clone(), alias.clone(), None)
+                }
+                crate::sql::ast::TableRef::Subquery { query, alias } => {
+                    (alias.clone(), Some(alias.clone()), Some(query.clone()))
+                }
             };
             if joins.is_empty() {
-                if group_by.is_some()
-                    || columns
-                        .iter()
-                        .any(|c| matches!(c.expr, crate::sql::ast::SelectItem::Aggregate { .. }))
-                {
-                    let mut results = Vec::new();
-                    let header = execute_group_query(
-                        catalog,
-                        &from_table,
-                        &columns,
-                        group_by.as_deref(),
-                        having.clone(),
-                        where_predicate,
-                        &mut results,
-                        None,
-                    )?;
-                    println!("{}", format_header(&header));
-                    for row in results {
-                        println!("{}", format_values(&row));
-                    }
-                } else {
-                    let table_info = catalog.get_table(&from_table)?.clone();
-                    let (idxs, meta) = select_projection_indices(&table_info.columns, &columns)?;
-                    println!("{}", format_header(&meta));
-                    let mut results = Vec::new();
-                    execute_select_with_indexes(
-                        catalog,
-                        &from_table,
-                        where_predicate,
-                        &mut results,
-                    )?;
-                    for row in results {
-                        let vals = row_to_strings(&row);
-                        let mut val_map = std::collections::HashMap::new();
-                        for ((c, _), v) in table_info.columns.iter().zip(vals.iter()) {
-                            val_map.insert(c.clone(), v.clone());
-                        }
-                        let projected: Vec<_> = idxs
-                            .iter()
-                            .map(|p| match p {
-                                Projection::Index(i) => vals[*i].clone(),
-                                Projection::Literal(s) => s.clone(),
-                                Projection::Subquery(_) => String::new(),
-                                Projection::Expr(expr) => {
-                                    crate::sql::ast::evaluate_expression(expr, &val_map)
-                                        .to_string_value()
-                                }
-                            })
-                            .collect();
-                        println!("{}", format_values(&projected));
-                    }
-                }
+                let stmt = crate::sql::ast::Statement::Select {
+                    columns,
+                    from,
+                    joins,
+                    where_predicate,
+                    group_by,
+                    having,
+                    order_by,
+                    limit,
+                    offset,
+                };
+                let mut rows = Vec::new();
+                let header = execute_select_statement(catalog, &stmt, &mut rows, None)?;
+                QueryResult::Rows { header, rows }
             } else {
                 let plan = crate::execution::plan::MultiJoinPlan {
                     base_table: from_table,
                     base_alias,
+                    base_subquery,
                     joins,
                     projections: columns.clone(),
                     where_predicate,
                 };
-                let projections = expand_join_projections(&plan, catalog)?;
-                let header_meta = join_header(&plan, catalog, &projections)?;
-                println!("{}", format_header(&header_meta));
-                let mut results = Vec::new();
-                execute_multi_join(&plan, catalog, &mut results)?;
-                for row in results {
-                    println!("{}", format_values(&row));
-                }
+                let mut rows = Vec::new();
+                let aggregated = columns
+                    .iter()
+                    .any(|c| matches!(c.expr, crate::sql::ast::SelectItem::Aggregate { .. }));
+                let header = if group_by.is_some() || aggregated {
+                    let header = execute_join_group_query(
+                        &plan,
+                        group_by.as_deref(),
+                        having,
+                        catalog,
+                        &mut rows,
+                    )?;
+                    if let Some(order) = &order_by {
+                        sort_grouped(&mut rows, &header, &columns, &order.column, order.descending)?;
+                    }
+                    header
+                } else {
+                    let columns = join_projection_columns(&plan, catalog)?;
+                    let projections: Vec<String> =
+                        columns.iter().map(|(c, _)| c.clone()).collect();
+                    let mut header = join_header(&plan, catalog, &projections)?;
+                    for ((name, _), (_, alias)) in header.iter_mut().zip(columns) {
+                        if let Some(alias) = alias {
+                            *name = alias;
+                        }
+                    }
+                    execute_ordered_join(&plan, catalog, order_by.as_ref(), &mut rows)?;
+                    header
+                };
+                // OFFSET and LIMIT (or FETCH FIRST) apply to the joined result.
+                let rows = rows
+                    .into_iter()
+                    .skip(offset.unwrap_or(0))
+                    .take(limit.unwrap_or(usize::MAX))
+                    .collect();
+                QueryResult::Rows { header, rows }
             }
         }
         Statement::DropTable { table_name, .. } => {
             if catalog.drop_table(&table_name)? {
-                println!("Table {} dropped", table_name);
+                QueryResult::Message(format!("Table {} dropped", table_name))
+            } else {
+                QueryResult::Ack
             }
         }
+        Statement::RenameTable { old_name, new_name } => {
+            catalog.rename_table(&old_name, &new_name)?;
+            QueryResult::Message(format!("Table {} renamed to {}", old_name, new_name))
+        }
+        Statement::RenameIndex { old_name, new_name } => {
+            catalog.rename_index(&old_name, &new_name)?;
+            QueryResult::Message(format!("Index {} renamed to {}", old_name, new_name))
+        }
+        Statement::RenameColumn {
+            table_name,
+            old_name,
+            new_name,
+        } => {
+            catalog.rename_column(&table_name, &old_name, &new_name)?;
+            QueryResult::Message(format!(
+                "Column {} of table {} renamed to {}",
+                old_name, table_name, new_name
+            ))
+        }
         Statement::Delete {
             table_name,
             selection,
+        } => QueryResult::Affected(execute_delete(catalog, &table_name, selection)?),
+        Statement::Truncate {
+            table_name,
+            restart_identity,
         } => {
-            let count = execute_delete(catalog, &table_name, selection)?;
-            println!("{} row(s) deleted", count);
+            let deleted = execute_delete(catalog, &table_name, None)?;
+            if restart_identity {
+                let table_info = catalog.get_table(&table_name)?.clone();
+                for (idx, (col_name, _)) in table_info.columns.iter().enumerate() {
+                    if table_info.auto_increment.get(idx).copied().unwrap_or(false) {
+                        catalog.restart_sequence(&format!("{}_{}", table_name, col_name))?;
+                    }
+                }
+            }
+            QueryResult::Affected(deleted)
         }
         Statement::Update {
             table_name,
             assignments,
             selection,
-        } => {
-            let count = execute_update(catalog, &table_name, assignments, selection)?;
-            println!("{} row(s) updated", count);
-        }
+        } => QueryResult::Affected(execute_update(
+            catalog,
+            &table_name,
+            assignments,
+            selection,
+        )?),
         Statement::CreateSequence(seq) => {
             catalog.create_sequence(&seq.name, seq.start, seq.increment)?;
-            println!("Sequence '{}' created successfully", seq.name);
+            QueryResult::Message(format!("Sequence '{}' created successfully", seq.name))
         }
-        Statement::BeginTransaction { .. } | Statement::Commit | Statement::Rollback => {
+        Statement::SetConstraints(deferred) => {
+            catalog.set_defer_foreign_keys(deferred);
+            QueryResult::Message(if deferred {
+                "Foreign keys are checked at the end of each INSERT".into()
+            } else {
+                "Foreign keys are checked row by row".into()
+            })
+        }
+        Statement::BeginTransaction { .. }
+        | Statement::Commit
+        | Statement::Rollback
+        | Statement::SetAutocommit(_) => {
             // Transaction control is owned by `TransactionManager::execute`, which
             // intercepts these before dispatching here. Reaching this arm means a
             // caller bypassed the manager.
@@ -1323,15 +2484,383 @@ pub fn handle_statement(catalog: &mut Catalog, stmt: Statement) -> DbResult<()>
                 "transaction control statements must go through the transaction manager".into(),
             ));
         }
-        Statement::Exit => {}
+        Statement::SetOp { .. } | Statement::Values(_) => {
+            let mut rows = Vec::new();
+            let header = execute_select_statement(catalog, &stmt, &mut rows, None)?;
+            QueryResult::Rows { header, rows }
+        }
+        Statement::ShowTables | Statement::ShowIndexes { .. } | Statement::Describe { .. } => {
+            let mut rows = Vec::new();
+            let header = execute_show_statement(catalog, &stmt, &mut rows)?;
+            QueryResult::Rows { header, rows }
+        }
+        Statement::Pragma { name, value } => {
+            QueryResult::Message(execute_pragma(catalog, &name, value.as_deref())?)
+        }
+        Statement::Explain(inner) => QueryResult::Rows {
+            header: vec![("QUERY PLAN".into(), ColumnType::Text)],
+            rows: explain_statement(catalog, &inner)?
+                .into_iter()
+                .map(|line| vec![line])
+                .collect(),
+        },
+        Statement::Exit => QueryResult::Ack,
+    };
+    Ok(result)
+}
+
+/// Describe how `stmt` (a SELECT or UNION) would run, one plan step per line.
+/// Steps nest under the step that consumes them; nothing is executed.
+pub fn explain_statement(catalog: &Catalog, stmt: &Statement) -> DbResult<Vec<String>> {
+    let mut lines = Vec::new();
+    explain_into(catalog, stmt, 0, &mut lines)?;
+    Ok(lines)
+}
+
+fn explain_into(
+    catalog: &Catalog,
+    stmt: &Statement,
+    depth: usize,
+    out: &mut Vec<String>,
+) -> DbResult<()> {
+    use crate::execution::plan::{PlanNode, plan_statement};
+    use crate::sql::ast::{JoinType, SelectItem, SetOp, TableRef};
+    if let Some(expanded) = expand_views(catalog, stmt)? {
+        return explain_into(catalog, &expanded, depth, out);
+    }
+    let mut depth = depth;
+    let (columns, from, joins, where_predicate, group_by, having, order_by, limit, offset) =
+        match stmt {
+            Statement::SetOp { op, left, right } => {
+                let label = match op {
+                    SetOp::Union => "Union",
+                    SetOp::UnionAll => "Union All",
+                };
+                plan_step(out, &mut depth, label.to_string());
+                explain_into(catalog, left, depth, out)?;
+                return explain_into(catalog, right, depth, out);
+            }
+            Statement::Values(rows) => {
+                plan_step(out, &mut depth, format!("Values Scan ({} rows)", rows.len()));
+                return Ok(());
+            }
+            Statement::Select {
+                columns,
+                from,
+                joins,
+                where_predicate,
+                group_by,
+                having,
+                order_by,
+                limit,
+                offset,
+            } => (
+                columns,
+                from,
+                joins,
+                where_predicate,
+                group_by,
+                having,
+                order_by,
+                limit,
+                offset,
+            ),
+            _ => {
+                return Err(DbError::InvalidValue(
+                    "EXPLAIN supports only SELECT statements".into(),
+                ));
+            }
+        };
+
+    let Some(source) = from.first() else {
+        plan_step(out, &mut depth, "Result".to_string());
+        return Ok(());
+    };
+    match (limit, offset) {
+        (Some(l), Some(o)) => plan_step(out, &mut depth, format!("Limit ({l} rows, offset {o})")),
+        (Some(l), None) => plan_step(out, &mut depth, format!("Limit ({l} rows)")),
+        (None, Some(o)) => plan_step(out, &mut depth, format!("Limit (offset {o})")),
+        (None, None) => {}
+    }
+    if let Some(order) = order_by {
+        let dir = if order.descending { " DESC" } else { "" };
+        plan_step(out, &mut depth, format!("Sort ({}{dir})", order.column));
+    }
+    let aggregated = columns
+        .iter()
+        .any(|c| matches!(c.expr, SelectItem::Aggregate { .. }));
+    if let Some(keys) = group_by {
+        let mut line = format!("Group Aggregate (group by {})", keys.join(", "));
+        if having.is_some() {
+            line.push_str(" with HAVING filter");
+        }
+        plan_step(out, &mut depth, line);
+    } else if aggregated {
+        plan_step(out, &mut depth, "Aggregate".to_string());
+    }
+
+    if !joins.is_empty() {
+        let PlanNode::MultiJoin(plan) = plan_statement(stmt.clone()) else {
+            return Err(DbError::InvalidValue("Unsupported query".into()));
+        };
+        let label = |table: &str, alias: Option<&str>| match alias {
+            Some(a) => format!("{table} {a}"),
+            None => table.to_string(),
+        };
+        let order: Vec<String> =
+            std::iter::once(label(&plan.base_table, plan.base_alias.as_deref()))
+                .chain(
+                    plan.joins
+                        .iter()
+                        .map(|j| label(&j.table, j.alias.as_deref())),
+                )
+                .collect();
+        let filter = if plan.where_predicate.is_some() {
+            " with filter"
+        } else {
+            ""
+        };
+        plan_step(
+            out,
+            &mut depth,
+            format!("Nested Loop Join (order: {}){filter}", order.join(", ")),
+        );
+        let indent = "  ".repeat(depth);
+        out.push(format!("{indent}-> Seq Scan on {}", order[0]));
+        for (jc, name) in plan.joins.iter().zip(order.iter().skip(1)) {
+            let kind = match jc.join_type {
+                JoinType::Inner => "Inner",
+                JoinType::Left => "Left",
+                JoinType::Right => "Right",
+                JoinType::Full => "Full",
+                JoinType::Cross => "Cross",
+            };
+            out.push(format!("{indent}-> {kind} Join: Seq Scan on {name}"));
+        }
+        return Ok(());
+    }
+
+    let filter = if where_predicate.is_some() {
+        " with filter"
+    } else {
+        ""
+    };
+    match source {
+        TableRef::Subquery { query, alias } => {
+            plan_step(out, &mut depth, format!("Subquery Scan on {alias}{filter}"));
+            explain_into(catalog, query, depth, out)
+        }
+        TableRef::Named { name, .. } if is_system_table(name) => {
+            plan_step(
+                out,
+                &mut depth,
+                format!("System Table Scan on {name}{filter}"),
+            );
+            Ok(())
+        }
+        TableRef::Named { name, alias } => {
+            let scope = ColumnScope {
+                table: name,
+                alias: alias.as_deref(),
+                context: None,
+            };
+            let predicate = where_predicate
+                .as_ref()
+                .map(|e| scope.expr(e))
+                .transpose()?;
+            let info = catalog.get_table(name)?;
+            // Mirrors the index hint `select_each_row` computes.
+            let index = predicate
+                .as_ref()
+                .filter(|pred| !expr_has_subquery(pred))
+                .and_then(|pred| indexed_equality(catalog, name, &info.columns, pred));
+            let line = match index {
+                Some((col, value, idx)) => {
+                    let residual = match &predicate {
+                        Some(Expr::Equals { .. }) => "",
+                        _ => " with filter",
+                    };
+                    format!(
+                        "Index Scan using {} on {name} ({col} = {value}){residual}",
+                        idx.name
+                    )
+                }
+                None => format!("Seq Scan on {name}{filter}"),
+            };
+            plan_step(out, &mut depth, line);
+            Ok(())
+        }
+    }
+}
+
+/// Append a plan line at `depth` and nest the following steps under it.
+fn plan_step(out: &mut Vec<String>, depth: &mut usize, line: String) {
+    if *depth == 0 {
+        out.push(line);
+    } else {
+        out.push(format!("{}-> {}", "  ".repeat(*depth), line));
+    }
+    *depth += 1;
+}
+
+/// Produce the rows for `SHOW TABLES` / `SHOW INDEXES [FROM t]`, sorted by name,
+/// or for `DESCRIBE t`, one row per column in declaration order.
+pub fn execute_show_statement(
+    catalog: &Catalog,
+    stmt: &Statement,
+    out: &mut Vec<Vec<String>>,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    match stmt {
+        Statement::ShowTables => {
+            let mut names: Vec<String> = catalog.all_tables().into_iter().map(|t| t.name).collect();
+            names.sort();
+            out.extend(names.into_iter().map(|n| vec![n]));
+            Ok(vec![("table_name".into(), ColumnType::Text)])
+        }
+        Statement::ShowIndexes { table } => {
+            if let Some(t) = table {
+                catalog.get_table(t)?;
+            }
+            let mut indexes: Vec<IndexInfo> = catalog
+                .all_indexes()
+                .into_iter()
+                .filter(|idx| table.as_ref().is_none_or(|t| &idx.table_name == t))
+                .collect();
+            indexes.sort_by(|a, b| a.name.cmp(&b.name));
+            for idx in indexes {
+                out.push(vec![
+                    idx.name,
+                    idx.table_name,
+                    idx.column_name,
+                    idx.root_page.to_string(),
+                ]);
+            }
+            Ok(vec![
+                ("index_name".into(), ColumnType::Text),
+                ("table_name".into(), ColumnType::Text),
+                ("column_name".into(), ColumnType::Text),
+                ("root_page".into(), ColumnType::Integer),
+            ])
+        }
+        Statement::Describe { table } => {
+            let info = catalog.get_table(table)?;
+            let pk = info.primary_key.clone().unwrap_or_default();
+            for (idx, (name, ty)) in info.columns.iter().enumerate() {
+                let default = match info.default_values.get(idx).and_then(Option::as_ref) {
+                    Some(expr) => crate::sql::ast::default_expr_to_string(expr),
+                    None => "NULL".to_string(),
+                };
+                out.push(vec![
+                    name.clone(),
+                    ty.as_str(),
+                    (!info.not_null.get(idx).copied().unwrap_or(false)).to_string(),
+                    default,
+                    info.auto_increment.get(idx).copied().unwrap_or(false).to_string(),
+                    pk.contains(name).to_string(),
+                ]);
+            }
+            Ok(vec![
+                ("column_name".into(), ColumnType::Text),
+                ("type".into(), ColumnType::Text),
+                ("nullable".into(), ColumnType::Boolean),
+                ("default".into(), ColumnType::Text),
+                ("auto_increment".into(), ColumnType::Boolean),
+                ("primary_key".into(), ColumnType::Boolean),
+            ])
+        }
+        _ => Err(DbError::InvalidValue("Not a SHOW statement".into())),
+    }
+}
+
+/// Largest accepted `PRAGMA float_precision`; `f64` carries ~17 significant
+/// digits, so more places would only print noise.
+const MAX_FLOAT_PRECISION: usize = 17;
+
+fn execute_pragma(catalog: &mut Catalog, name: &str, value: Option<&str>) -> DbResult<String> {
+    match name {
+        "integrity_check" => {
+            if value.is_some() {
+                return Err(DbError::InvalidValue(
+                    "integrity_check takes no value".into(),
+                ));
+            }
+            Ok(catalog
+                .integrity_check()?
+                .unwrap_or_else(|| "OK".to_string()))
+        }
+        "float_precision" => {
+            match value {
+                None => {}
+                Some(v) if v.eq_ignore_ascii_case("DEFAULT") => {
+                    catalog.set_float_precision(None);
+                }
+                Some(v) => {
+                    let places = v
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|p| *p <= MAX_FLOAT_PRECISION)
+                        .ok_or_else(|| {
+                            DbError::InvalidValue(format!(
+                                "float_precision must be between 0 and {} or DEFAULT",
+                                MAX_FLOAT_PRECISION
+                            ))
+                        })?;
+                    catalog.set_float_precision(Some(places));
+                }
+            }
+            Ok(match catalog.float_precision() {
+                Some(places) => format!("float_precision = {}", places),
+                None => "float_precision = DEFAULT".to_string(),
+            })
+        }
+        _ => Err(DbError::InvalidValue(format!("Unknown pragma '{}'", name))),
+    }
+}
+
+/// Show the DOUBLE and DECIMAL columns of a result with `places` decimal
+/// places, as `PRAGMA float_precision` asks. Only finished results are
+/// reformatted, so comparisons and sorting always see exact values.
+fn apply_float_precision(header: &[(String, ColumnType)], rows: &mut [Vec<String>], places: usize) {
+    for (idx, (_, ty)) in header.iter().enumerate() {
+        let decimal = match ty {
+            ColumnType::Decimal { .. } => true,
+            ColumnType::Double { .. } => false,
+            _ => continue,
+        };
+        for cell in rows.iter_mut().filter_map(|row| row.get_mut(idx)) {
+            let shown = match crate::storage::row::parse_decimal_literal(cell) {
+                Some((value, scale)) if decimal => {
+                    let places = places as u8;
+                    let rescaled = crate::storage::row::rescale_decimal(value as i128, scale, places);
+                    Some(crate::storage::row::format_decimal(rescaled, places))
+                }
+                _ => cell.parse::<f64>().ok().map(|f| format!("{:.*}", places, f)),
+            };
+            if let Some(shown) = shown {
+                *cell = shown;
+            }
+        }
     }
-    Ok(())
 }
 
 pub fn row_to_strings(row: &Row) -> Vec<String> {
     row.data.0.iter().map(|v| v.to_string_value()).collect()
 }
 
+/// How a query result shows SQL NULL: `NULL`, or [`NULL_MARKER`] while
+/// [`Catalog::marks_nulls`].
+fn null_cell(catalog: &Catalog) -> String {
+    if catalog.marks_nulls() { NULL_MARKER.into() } else { "NULL".into() }
+}
+
+/// `value` as a query result cell, with NULL shown by [`null_cell`].
+fn value_cell(catalog: &Catalog, value: &ColumnValue) -> String {
+    match value {
+        ColumnValue::Null => null_cell(catalog),
+        other => other.to_string_value(),
+    }
+}
+
 pub enum Projection {
     Index(usize),
     Literal(String),
@@ -1376,7 +2905,7 @@ pub fn select_projection_indices(
                     let header = p.alias.clone().unwrap_or(name);
                     meta.push((header, ColumnType::Integer));
                 }
-                SelectItem::All => {
+                SelectItem::All | SelectItem::AllFrom(_) => {
                     for (i, (n, ty)) in columns.iter().enumerate() {
                         idxs.push(Projection::Index(i));
                         meta.push((n.clone(), *ty));
@@ -1390,22 +2919,14 @@ pub fn select_projection_indices(
                     idxs.push(Projection::Subquery(q.clone()));
                 }
                 SelectItem::Literal(val) => {
-                    let ty = if val.parse::<i32>().is_ok() {
-                        ColumnType::Integer
-                    } else {
-                        ColumnType::Text
-                    };
+                    let ty = literal_column_type(val);
                     meta.push((p.alias.clone().unwrap_or_else(|| val.clone()), ty));
                     idxs.push(Projection::Literal(val.clone()));
                 }
                 SelectItem::Expr(expr) => {
                     meta.push((
                         p.alias.clone().unwrap_or("EXPR".into()),
-                        ColumnType::Double {
-                            precision: 8,
-                            scale: 2,
-                            unsigned: false,
-                        },
+                        expr_column_type(expr, columns),
                     ));
                     idxs.push(Projection::Expr(expr.clone()));
                 }
@@ -1417,49 +2938,85 @@ pub fn select_projection_indices(
 
 pub fn expand_join_projections(
     plan: &crate::execution::plan::MultiJoinPlan,
-    catalog: &Catalog,
+    catalog: &mut Catalog,
 ) -> DbResult<Vec<String>> {
-    use crate::sql::ast::{SelectExpr, SelectItem};
-    if plan.projections.len() == 1 && matches!(plan.projections[0].expr, SelectItem::All) {
-        let mut list = Vec::new();
-        let base_info = catalog.get_table(&plan.base_table)?;
-        let base_alias = plan.base_alias.as_deref().unwrap_or(&plan.base_table);
-        for (c, _) in &base_info.columns {
-            list.push(format!("{base_alias}.{}", c));
-        }
-        for jc in &plan.joins {
-            let alias = jc.alias.as_ref().unwrap_or(&jc.table);
-            let info = catalog.get_table(&jc.table)?;
-            for (c, _) in &info.columns {
-                list.push(format!("{alias}.{}", c));
-            }
-        }
-        Ok(list)
-    } else {
-        let mut out = Vec::new();
-        for p in &plan.projections {
-            if let SelectItem::Column(c) = &p.expr {
-                out.push(c.clone());
+    Ok(join_projection_columns(plan, catalog)?
+        .into_iter()
+        .map(|(column, _)| column)
+        .collect())
+}
+
+/// The qualified columns a join's select list produces, each with the alias
+/// the select list gives it.
+fn join_projection_columns(
+    plan: &crate::execution::plan::MultiJoinPlan,
+    catalog: &mut Catalog,
+) -> DbResult<Vec<(String, Option<String>)>> {
+    use crate::sql::ast::SelectItem;
+    let base_alias = plan.base_alias.as_deref().unwrap_or(&plan.base_table);
+    let base_columns = join_base_columns(plan, catalog)?;
+    let mut sources = vec![(base_alias.to_string(), base_columns)];
+    for jc in &plan.joins {
+        let alias = jc.alias.as_ref().unwrap_or(&jc.table);
+        let (columns, _) = join_source(catalog, &jc.table, jc.subquery.as_deref())?;
+        sources.push((alias.clone(), columns));
+    }
+    let qualified = |alias: &str, columns: &[(String, ColumnType)]| {
+        columns
+            .iter()
+            .map(|(c, _)| (format!("{alias}.{c}"), None))
+            .collect::<Vec<_>>()
+    };
+    // `*` and `t.*` expand in place, so they mix freely with named columns.
+    let mut out = Vec::new();
+    for p in &plan.projections {
+        match &p.expr {
+            SelectItem::All => {
+                for (alias, columns) in &sources {
+                    out.extend(qualified(alias, columns));
+                }
             }
+            SelectItem::AllFrom(source) => {
+                let Some((alias, columns)) = sources
+                    .iter()
+                    .find(|(alias, _)| alias.eq_ignore_ascii_case(source))
+                else {
+                    return Err(DbError::TableNotFound(source.clone()));
+                };
+                out.extend(qualified(alias, columns));
+            }
+            SelectItem::Column(c) => out.push((c.clone(), p.alias.clone())),
+            _ => {}
         }
-        Ok(out)
+    }
+    Ok(out)
+}
+
+/// Columns of the base relation of `plan`: its table's, or the header of its
+/// derived subquery.
+fn join_base_columns(
+    plan: &crate::execution::plan::MultiJoinPlan,
+    catalog: &mut Catalog,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    match plan.base_subquery.as_deref() {
+        Some(query) => Ok(join_source(catalog, &plan.base_table, Some(query))?.0),
+        None => Ok(catalog.get_table(&plan.base_table)?.columns.clone()),
     }
 }
 
 pub fn join_header(
     plan: &crate::execution::plan::MultiJoinPlan,
-    catalog: &Catalog,
+    catalog: &mut Catalog,
     projections: &[String],
 ) -> DbResult<Vec<(String, ColumnType)>> {
     use std::collections::HashMap;
     let mut alias_map = HashMap::new();
     let base_alias = plan.base_alias.as_ref().unwrap_or(&plan.base_table);
-    alias_map.insert(base_alias.clone(), plan.base_table.clone());
+    let base_columns = join_base_columns(plan, catalog)?;
+    alias_map.insert(base_alias.clone(), base_columns);
     for jc in &plan.joins {
-        alias_map.insert(
-            jc.alias.clone().unwrap_or_else(|| jc.table.clone()),
-            jc.table.clone(),
-        );
+        let (columns, _) = join_source(catalog, &jc.table, jc.subquery.as_deref())?;
+        alias_map.insert(jc.alias.clone().unwrap_or_else(|| jc.table.clone()), columns);
     }
 
     let mut out = Vec::new();
@@ -1471,12 +3028,10 @@ pub fn join_header(
         let col = parts
             .next()
             .ok_or_else(|| DbError::ParseError("Bad column".into()))?;
-        let table = alias_map
+        let columns = alias_map
             .get(alias)
             .ok_or_else(|| DbError::ParseError("Bad alias".into()))?;
-        let info = catalog.get_table(table)?;
-        let ty = info
-            .columns
+        let ty = columns
             .iter()
             .find(|(c, _)| c == col)
             .ok_or_else(|| DbError::ColumnNotFound(col.to_string()))?
@@ -1486,73 +3041,95 @@ pub fn join_header(
     Ok(out)
 }
 
+/// Both operands lowercased when either names a column in `nocase`, so the
+/// comparison follows `COLLATE NOCASE`; `None` for a case-sensitive one.
+fn nocase_operands(
+    left: &str,
+    right: &str,
+    values: &HashMap<String, String>,
+    nocase: &HashSet<String>,
+) -> Option<(String, String)> {
+    if !nocase.contains(left) && !nocase.contains(right) {
+        return None;
+    }
+    let folded = |token: &str| resolve_operand(token, values).to_lowercase();
+    Some((folded(left), folded(right)))
+}
+
+/// Order `left` against `right`, ignoring case when either is a `nocase` column.
+fn compare_collated(
+    left: &str,
+    right: &str,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+    nocase: &HashSet<String>,
+) -> std::cmp::Ordering {
+    match nocase_operands(left, right, values, nocase) {
+        Some((l, r)) => l.cmp(&r),
+        None => crate::sql::ast::compare_operands(left, right, values, column_types),
+    }
+}
+
+/// Evaluate `expr` for one row. Comparisons involving a column named in
+/// `nocase` ignore case.
 fn evaluate_with_catalog(
     expr: &crate::sql::ast::Expr,
     values: &std::collections::HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+    nocase: &HashSet<String>,
     catalog: &mut Catalog,
 ) -> DbResult<bool> {
     use crate::sql::ast::Expr;
+    if let Some(bound) = crate::sql::ast::bind_function_operands(expr, values, column_types) {
+        return evaluate_with_catalog(expr, &bound, column_types, nocase, catalog);
+    }
+    if crate::sql::ast::null_operand(expr, values) {
+        // UNKNOWN filters the row out just like FALSE.
+        return Ok(false);
+    }
+    let equal = |left: &str, right: &str| match nocase_operands(left, right, values, nocase) {
+        Some((l, r)) => l == r,
+        None => crate::sql::ast::operands_equal(left, right, values, column_types),
+    };
+    if matches!(expr, Expr::Add { .. } | Expr::Subtract { .. } | Expr::Multiply { .. }) {
+        // Integer overflow is an error here too, not an inexact truth value.
+        crate::sql::ast::try_evaluate_expression_typed(expr, values, column_types)?;
+    }
     match expr {
-        Expr::Equals { left, right } => Ok(values.get(left).map(String::as_str).unwrap_or(left)
-            == values.get(right).map(String::as_str).unwrap_or(right)),
-        Expr::NotEquals { left, right } => Ok(values.get(left).map(String::as_str).unwrap_or(left)
-            != values.get(right).map(String::as_str).unwrap_or(right)),
+        Expr::Equals { left, right } => Ok(equal(left, right)),
+        Expr::NotEquals { left, right } => Ok(!equal(left, right)),
         Expr::Add { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
             Ok((l + r) != 0.0)
         }
         Expr::Subtract { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
             Ok((l - r) != 0.0)
         }
         Expr::Multiply { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
             Ok((l * r) != 0.0)
         }
         Expr::Divide { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<f64>()
                 .unwrap_or(1.0);
             if r == 0.0 {
@@ -1562,16 +3139,10 @@ fn evaluate_with_catalog(
             }
         }
         Expr::Modulo { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<f64>()
                 .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<f64>()
                 .unwrap_or(1.0);
             if r == 0.0 {
@@ -1581,179 +3152,584 @@ fn evaluate_with_catalog(
             }
         }
         Expr::BitwiseAnd { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<i64>()
                 .unwrap_or(0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<i64>()
                 .unwrap_or(0);
             Ok((l & r) != 0)
         }
         Expr::BitwiseOr { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<i64>()
                 .unwrap_or(0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<i64>()
                 .unwrap_or(0);
             Ok((l | r) != 0)
         }
         Expr::BitwiseXor { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
+            let l = resolve_operand(left, values)
                 .parse::<i64>()
                 .unwrap_or(0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
+            let r = resolve_operand(right, values)
                 .parse::<i64>()
                 .unwrap_or(0);
             Ok((l ^ r) != 0)
         }
-        Expr::Between { expr, low, high } => {
-            let v = values
-                .get(expr)
-                .map(String::as_str)
-                .unwrap_or(expr)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            let l = values
-                .get(low)
-                .map(String::as_str)
-                .unwrap_or(low)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            let h = values
-                .get(high)
-                .map(String::as_str)
-                .unwrap_or(high)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            Ok(v >= l && v <= h)
+        Expr::Between {
+            expr,
+            low,
+            high,
+            negated,
+        } => {
+            let within = compare_collated(expr, low, values, column_types, nocase).is_ge()
+                && compare_collated(expr, high, values, column_types, nocase).is_le();
+            Ok(within != *negated)
         }
         Expr::GreaterThan { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            Ok(l > r)
+            Ok(compare_collated(left, right, values, column_types, nocase).is_gt())
         }
         Expr::GreaterOrEquals { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            Ok(l >= r)
+            Ok(compare_collated(left, right, values, column_types, nocase).is_ge())
         }
         Expr::LessThan { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            Ok(l < r)
+            Ok(compare_collated(left, right, values, column_types, nocase).is_lt())
         }
         Expr::LessOrEquals { left, right } => {
-            let l = values
-                .get(left)
-                .map(String::as_str)
-                .unwrap_or(left)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            let r = values
-                .get(right)
-                .map(String::as_str)
-                .unwrap_or(right)
-                .parse::<f64>()
-                .unwrap_or(0.0);
-            Ok(l <= r)
+            Ok(compare_collated(left, right, values, column_types, nocase).is_le())
         }
-        Expr::And(a, b) => Ok(evaluate_with_catalog(a, values, catalog)?
-            && evaluate_with_catalog(b, values, catalog)?),
-        Expr::Or(a, b) => Ok(evaluate_with_catalog(a, values, catalog)?
-            || evaluate_with_catalog(b, values, catalog)?),
+        Expr::IsTruth { .. } | Expr::IsNull { .. } => Ok(matches!(
+            crate::sql::ast::evaluate_expression_typed(expr, values, column_types),
+            ColumnValue::Boolean(true)
+        )),
+        Expr::And(a, b) => Ok(evaluate_with_catalog(a, values, column_types, nocase, catalog)?
+            && evaluate_with_catalog(b, values, column_types, nocase, catalog)?),
+        Expr::Or(a, b) => Ok(evaluate_with_catalog(a, values, column_types, nocase, catalog)?
+            || evaluate_with_catalog(b, values, column_types, nocase, catalog)?),
         Expr::InSubquery { left, query } => {
             let mut rows = Vec::new();
             let header = execute_select_statement(catalog, query, &mut rows, Some(values))?;
             if header.len() != 1 {
                 return Err(DbError::InvalidValue(
-                    "Subquery must return one column".into(),
+                    "Subquery must return one column".into(),
+                ));
+            }
+            let val = resolve_operand(left, values);
+            for r in rows {
+                if r.get(0).map(|s| s.as_str()) == Some(val) {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        Expr::ExistsSubquery { query } => {
+            // One row settles EXISTS, so the inner scan stops at the first match.
+            let mut rows = Vec::new();
+            let _ = execute_select_limited(catalog, query, &mut rows, Some(values), 1)?;
+            Ok(!rows.is_empty())
+        }
+        Expr::ScalarSubquery { left, op, query } => {
+            let mut rows = Vec::new();
+            let header = execute_select_statement(catalog, query, &mut rows, Some(values))?;
+            if header.len() != 1 {
+                return Err(DbError::InvalidValue(
+                    "Subquery must return one column".into(),
+                ));
+            }
+            if rows.len() > 1 {
+                return Err(DbError::InvalidValue(
+                    "Scalar subquery returned more than one row".into(),
                 ));
             }
-            let val = values.get(left).map(String::as_str).unwrap_or(left);
-            for r in rows {
-                if r.get(0).map(|s| s.as_str()) == Some(val) {
-                    return Ok(true);
+            // An empty result is NULL. The value is bound under a key no column
+            // can have so it is never mistaken for a column reference.
+            const SCALAR_KEY: &str = "\u{0}SCALAR";
+            let scalar = rows
+                .pop()
+                .and_then(|mut r| r.pop())
+                .unwrap_or_else(|| crate::storage::row::NULL_MARKER.to_string());
+            let mut bound = values.clone();
+            bound.insert(SCALAR_KEY.to_string(), scalar);
+            let (left, right) = (left.clone(), SCALAR_KEY.to_string());
+            let cmp = match op.as_str() {
+                "=" => Expr::Equals { left, right },
+                "!=" => Expr::NotEquals { left, right },
+                "<" => Expr::LessThan { left, right },
+                "<=" => Expr::LessOrEquals { left, right },
+                ">" => Expr::GreaterThan { left, right },
+                ">=" => Expr::GreaterOrEquals { left, right },
+                _ => {
+                    return Err(DbError::InvalidValue(format!(
+                        "Unsupported scalar subquery operator '{}'",
+                        op
+                    )));
                 }
-            }
-            Ok(false)
+            };
+            evaluate_with_catalog(&cmp, &bound, column_types, nocase, catalog)
         }
-        Expr::ExistsSubquery { query } => {
-            let mut rows = Vec::new();
-            let _ = execute_select_statement(catalog, query, &mut rows, Some(values))?;
-            Ok(!rows.is_empty())
+        Expr::Subquery(_)
+        | Expr::Literal(_)
+        | Expr::FunctionCall { .. }
+        | Expr::DefaultValue
+        | Expr::Null
+        | Expr::Placeholder(_)
+        | Expr::Value(_) => Ok(false),
+    }
+}
+
+/// The columns a single-table scan has to decode: those projected by index,
+/// the ORDER BY key, and any the WHERE clause may read. `None` (decode every
+/// column) when a projected expression, a subquery, or an enclosing query's
+/// row could refer to any of them.
+fn scan_projection(
+    columns: &[(String, ColumnType)],
+    projections: &[Projection],
+    where_predicate: Option<&Expr>,
+    order_key: Option<&str>,
+    context: Option<&std::collections::HashMap<String, String>>,
+) -> Option<Vec<bool>> {
+    if context.is_some() || where_predicate.is_some_and(expr_has_subquery) {
+        return None;
+    }
+    let mut wanted = vec![false; columns.len()];
+    for p in projections {
+        match p {
+            Projection::Index(i) => wanted[*i] = true,
+            Projection::Literal(_) => {}
+            Projection::Subquery(_) | Projection::Expr(_) => return None,
         }
-        Expr::Subquery(_) | Expr::Literal(_) | Expr::FunctionCall { .. } | Expr::DefaultValue => {
-            Ok(false)
+    }
+    for (i, (name, _)) in columns.iter().enumerate() {
+        if order_key == Some(name.as_str())
+            || where_predicate.is_some_and(|pred| expr_mentions(pred, name))
+        {
+            wanted[i] = true;
         }
     }
+    Some(wanted)
+}
+
+/// Whether `expr` may read `column`. Operands are matched as text, so this
+/// errs towards `true`.
+fn expr_mentions(expr: &Expr, column: &str) -> bool {
+    let column = column.to_ascii_lowercase();
+    let text = |s: &str| s.to_ascii_lowercase().contains(&column);
+    match expr {
+        Expr::Equals { left, right }
+        | Expr::NotEquals { left, right }
+        | Expr::Add { left, right }
+        | Expr::Subtract { left, right }
+        | Expr::Multiply { left, right }
+        | Expr::Divide { left, right }
+        | Expr::Modulo { left, right }
+        | Expr::BitwiseAnd { left, right }
+        | Expr::BitwiseOr { left, right }
+        | Expr::BitwiseXor { left, right }
+        | Expr::GreaterThan { left, right }
+        | Expr::GreaterOrEquals { left, right }
+        | Expr::LessThan { left, right }
+        | Expr::LessOrEquals { left, right } => text(left) || text(right),
+        Expr::Between { expr, low, high, .. } => text(expr) || text(low) || text(high),
+        Expr::IsTruth { expr, .. } | Expr::IsNull { expr, .. } => text(expr),
+        Expr::And(a, b) | Expr::Or(a, b) => expr_mentions(a, &column) || expr_mentions(b, &column),
+        Expr::FunctionCall { args, .. } => args.iter().any(|a| expr_mentions(a, &column)),
+        Expr::Literal(s) => text(s),
+        Expr::InSubquery { .. }
+        | Expr::ExistsSubquery { .. }
+        | Expr::ScalarSubquery { .. }
+        | Expr::Subquery(_) => true,
+        Expr::DefaultValue | Expr::Null | Expr::Placeholder(_) | Expr::Value(_) => false,
+    }
 }
 
 fn expr_has_subquery(expr: &crate::sql::ast::Expr) -> bool {
     use crate::sql::ast::Expr;
     match expr {
-        Expr::InSubquery { .. } | Expr::ExistsSubquery { .. } | Expr::Subquery(_) => true,
+        Expr::InSubquery { .. }
+        | Expr::ExistsSubquery { .. }
+        | Expr::ScalarSubquery { .. }
+        | Expr::Subquery(_) => true,
         Expr::And(a, b) | Expr::Or(a, b) => expr_has_subquery(a) || expr_has_subquery(b),
         _ => false,
     }
 }
 
+/// Column references of a single-table SELECT. `table.col` and `alias.col`
+/// resolve to the bare `col`; references the enclosing query binds through
+/// `context` are left for it to resolve, and any other qualifier is rejected.
+struct ColumnScope<'a> {
+    table: &'a str,
+    alias: Option<&'a str>,
+    context: Option<&'a std::collections::HashMap<String, String>>,
+}
+
+impl ColumnScope<'_> {
+    fn column(&self, name: &str) -> DbResult<String> {
+        let is_ident = |s: &str| {
+            s.chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        let Some((qualifier, column)) = name.split_once('.') else {
+            return Ok(name.to_string());
+        };
+        if !is_ident(qualifier) || !is_ident(column) {
+            return Ok(name.to_string());
+        }
+        if qualifier.eq_ignore_ascii_case(self.table)
+            || self
+                .alias
+                .is_some_and(|a| qualifier.eq_ignore_ascii_case(a))
+        {
+            Ok(column.to_string())
+        } else if self.context.is_some_and(|ctx| ctx.contains_key(name)) {
+            Ok(name.to_string())
+        } else {
+            Err(DbError::ColumnNotFound(name.to_string()))
+        }
+    }
+
+    /// An ORDER BY key: a column, or an aggregate such as `SUM(t.price)`
+    /// whose argument is resolved like a column.
+    fn order_key(&self, key: &str) -> DbResult<String> {
+        match key.strip_suffix(')').and_then(|k| k.split_once('(')) {
+            Some((func, arg)) if arg != "*" => Ok(format!("{}({})", func, self.column(arg)?)),
+            _ => self.column(key),
+        }
+    }
+
+    /// Rewrite the column references of `expr`. Subqueries keep their own scope.
+    fn expr(&self, expr: &Expr) -> DbResult<Expr> {
+        let c = |s: &String| self.column(s);
+        Ok(match expr {
+            Expr::Equals { left, right } => Expr::Equals {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::NotEquals { left, right } => Expr::NotEquals {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::Add { left, right } => Expr::Add {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::Subtract { left, right } => Expr::Subtract {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::Multiply { left, right } => Expr::Multiply {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::Divide { left, right } => Expr::Divide {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::Modulo { left, right } => Expr::Modulo {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::BitwiseAnd { left, right } => Expr::BitwiseAnd {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::BitwiseOr { left, right } => Expr::BitwiseOr {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::BitwiseXor { left, right } => Expr::BitwiseXor {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::GreaterThan { left, right } => Expr::GreaterThan {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::GreaterOrEquals { left, right } => Expr::GreaterOrEquals {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::LessThan { left, right } => Expr::LessThan {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::LessOrEquals { left, right } => Expr::LessOrEquals {
+                left: c(left)?,
+                right: c(right)?,
+            },
+            Expr::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => Expr::Between {
+                expr: c(expr)?,
+                low: c(low)?,
+                high: c(high)?,
+                negated: *negated,
+            },
+            Expr::IsTruth {
+                expr,
+                truth,
+                negated,
+            } => Expr::IsTruth {
+                expr: c(expr)?,
+                truth: *truth,
+                negated: *negated,
+            },
+            Expr::IsNull { expr, negated } => Expr::IsNull {
+                expr: c(expr)?,
+                negated: *negated,
+            },
+            Expr::InSubquery { left, query } => Expr::InSubquery {
+                left: c(left)?,
+                query: query.clone(),
+            },
+            Expr::ScalarSubquery { left, op, query } => Expr::ScalarSubquery {
+                left: c(left)?,
+                op: op.clone(),
+                query: query.clone(),
+            },
+            Expr::And(a, b) => Expr::And(Box::new(self.expr(a)?), Box::new(self.expr(b)?)),
+            Expr::Or(a, b) => Expr::Or(Box::new(self.expr(a)?), Box::new(self.expr(b)?)),
+            Expr::FunctionCall { name, args } => Expr::FunctionCall {
+                name: name.clone(),
+                args: args.iter().map(|a| self.expr(a)).collect::<DbResult<_>>()?,
+            },
+            Expr::Literal(s) => Expr::Literal(c(s)?),
+            Expr::ExistsSubquery { .. }
+            | Expr::Subquery(_)
+            | Expr::DefaultValue
+            | Expr::Null
+            | Expr::Placeholder(_)
+            | Expr::Value(_) => expr.clone(),
+        })
+    }
+
+    fn select_exprs(
+        &self,
+        columns: &[crate::sql::ast::SelectExpr],
+    ) -> DbResult<Vec<crate::sql::ast::SelectExpr>> {
+        use crate::sql::ast::{SelectExpr, SelectItem};
+        columns
+            .iter()
+            .map(|p| {
+                let (expr, alias) = match &p.expr {
+                    SelectItem::Column(col) => {
+                        (SelectItem::Column(self.column(col)?), p.alias.clone())
+                    }
+                    SelectItem::Aggregate {
+                        func,
+                        column: Some(col),
+                    } => {
+                        // Keep the header as written, e.g. `COUNT(u.id)`.
+                        let alias = p
+                            .alias
+                            .clone()
+                            .unwrap_or_else(|| format!("{}({})", func.as_str(), col));
+                        let item = SelectItem::Aggregate {
+                            func: func.clone(),
+                            column: Some(self.column(col)?),
+                        };
+                        (item, Some(alias))
+                    }
+                    SelectItem::Expr(e) => {
+                        (SelectItem::Expr(Box::new(self.expr(e)?)), p.alias.clone())
+                    }
+                    SelectItem::AllFrom(source) => {
+                        if !source.eq_ignore_ascii_case(self.table)
+                            && !self.alias.is_some_and(|a| source.eq_ignore_ascii_case(a))
+                        {
+                            return Err(DbError::TableNotFound(source.clone()));
+                        }
+                        (SelectItem::All, None)
+                    }
+                    other => (other.clone(), p.alias.clone()),
+                };
+                Ok(SelectExpr { expr, alias })
+            })
+            .collect()
+    }
+}
+
+/// Virtual tables exposing the in-memory catalog through plain `SELECT`.
+const INFORMATION_SCHEMA_TABLES: &str = "information_schema.tables";
+const INFORMATION_SCHEMA_COLUMNS: &str = "information_schema.columns";
+
+/// Substitute each view named in the FROM list or a JOIN of `stmt` with its
+/// stored query, as a derived table named after the view unless aliased.
+/// `None` when `stmt` reads no view.
+fn expand_views(catalog: &Catalog, stmt: &Statement) -> DbResult<Option<Statement>> {
+    use crate::sql::ast::TableRef;
+    let Statement::Select { from, joins, .. } = stmt else {
+        return Ok(None);
+    };
+    let is_view = |name: &str| catalog.view_sql(name).is_some();
+    if !from
+        .iter()
+        .any(|t| matches!(t, TableRef::Named { name, .. } if is_view(name)))
+        && !joins.iter().any(|j| j.subquery.is_none() && is_view(&j.table))
+    {
+        return Ok(None);
+    }
+    let view_query = |name: &str| -> DbResult<Box<Statement>> {
+        let sql = catalog.view_sql(name).unwrap_or_default();
+        crate::sql::parser::parse_statement(sql)
+            .map(Box::new)
+            .map_err(DbError::ParseError)
+    };
+    let mut expanded = stmt.clone();
+    if let Statement::Select { from, joins, .. } = &mut expanded {
+        for table in from.iter_mut() {
+            if let TableRef::Named { name, alias } = table
+                && is_view(name)
+            {
+                let query = view_query(name)?;
+                let alias = alias.take().unwrap_or_else(|| name.clone());
+                *table = TableRef::Subquery { query, alias };
+            }
+        }
+        for join in joins.iter_mut() {
+            if join.subquery.is_none() && is_view(&join.table) {
+                join.subquery = Some(view_query(&join.table)?);
+                if let Some(alias) = &join.alias {
+                    join.table = alias.clone();
+                } else {
+                    join.alias = Some(join.table.clone());
+                }
+            }
+        }
+    }
+    Ok(Some(expanded))
+}
+
+fn is_system_table(name: &str) -> bool {
+    name.eq_ignore_ascii_case(INFORMATION_SCHEMA_TABLES)
+        || name.eq_ignore_ascii_case(INFORMATION_SCHEMA_COLUMNS)
+}
+
+/// Materialize the rows of an `information_schema` view from the catalog.
+/// Tables are listed in name order so results are deterministic.
+fn system_table_rows(
+    catalog: &Catalog,
+    name: &str,
+    out: &mut Vec<Vec<String>>,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    let mut tables = catalog.all_tables();
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    if name.eq_ignore_ascii_case(INFORMATION_SCHEMA_TABLES) {
+        for t in &tables {
+            out.push(vec![
+                t.name.clone(),
+                t.columns.len().to_string(),
+                t.root_page.to_string(),
+            ]);
+        }
+        Ok(vec![
+            ("table_name".into(), ColumnType::Text),
+            ("column_count".into(), ColumnType::Integer),
+            ("root_page".into(), ColumnType::Integer),
+        ])
+    } else if name.eq_ignore_ascii_case(INFORMATION_SCHEMA_COLUMNS) {
+        for t in &tables {
+            for (pos, (col, ty)) in t.columns.iter().enumerate() {
+                let not_null = t.not_null.get(pos).copied().unwrap_or(false);
+                let in_pk = t
+                    .primary_key
+                    .as_ref()
+                    .is_some_and(|pk| pk.iter().any(|c| c == col));
+                out.push(vec![
+                    t.name.clone(),
+                    col.clone(),
+                    (pos + 1).to_string(),
+                    ty.as_str(),
+                    if not_null { "NO" } else { "YES" }.to_string(),
+                    in_pk.to_string(),
+                ]);
+            }
+        }
+        Ok(vec![
+            ("table_name".into(), ColumnType::Text),
+            ("column_name".into(), ColumnType::Text),
+            ("ordinal_position".into(), ColumnType::Integer),
+            ("data_type".into(), ColumnType::Text),
+            ("is_nullable".into(), ColumnType::Text),
+            ("is_primary_key".into(), ColumnType::Boolean),
+        ])
+    } else {
+        Err(DbError::TableNotFound(name.to_string()))
+    }
+}
+
+/// Answer an unfiltered `COUNT(*)` on `table` from the B-Tree's maintained
+/// row count. Returns `None` when the count may differ from what a scan would
+/// see: a transaction is open, one is still in flight or was aborted, or the
+/// table is empty (the grouped path produces no row for it).
+fn maintained_row_count(catalog: &mut Catalog, table: &str) -> DbResult<Option<usize>> {
+    if catalog.transaction_active()
+        || catalog
+            .pager
+            .transaction_table()
+            .values()
+            .any(|status| !matches!(status, TransactionStatus::Committed(_)))
+    {
+        return Ok(None);
+    }
+    let root_page = catalog.get_table(table)?.root_page;
+    let mut tree = BTree::open_root(&mut catalog.pager, root_page)?;
+    Ok(Some(tree.row_count()?).filter(|&count| count > 0))
+}
+
 pub fn execute_select_statement(
     catalog: &mut Catalog,
     stmt: &crate::sql::ast::Statement,
     out: &mut Vec<Vec<String>>,
     context: Option<&std::collections::HashMap<String, String>>,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    select_each_row(catalog, stmt, context, None, &mut |row| out.push(row))
+}
+
+/// Like [`execute_select_statement`] but produces at most `max_rows` rows,
+/// stopping a table scan as soon as that many rows passed its WHERE clause.
+pub fn execute_select_limited(
+    catalog: &mut Catalog,
+    stmt: &crate::sql::ast::Statement,
+    out: &mut Vec<Vec<String>>,
+    context: Option<&std::collections::HashMap<String, String>>,
+    max_rows: usize,
+) -> DbResult<Vec<(String, ColumnType)>> {
+    select_each_row(catalog, stmt, context, Some(max_rows), &mut |row| out.push(row))
+}
+
+/// Run a SELECT and hand each projected row to `f` as it is produced instead
+/// of collecting the whole result. Returns the result header.
+pub fn for_each_row<F>(
+    catalog: &mut Catalog,
+    stmt: &crate::sql::ast::Statement,
+    mut f: F,
+) -> DbResult<Vec<(String, ColumnType)>>
+where
+    F: FnMut(Vec<String>),
+{
+    select_each_row(catalog, stmt, None, None, &mut f)
+}
+
+fn select_each_row(
+    catalog: &mut Catalog,
+    stmt: &crate::sql::ast::Statement,
+    context: Option<&std::collections::HashMap<String, String>>,
+    max_rows: Option<usize>,
+    emit: &mut dyn FnMut(Vec<String>),
 ) -> DbResult<Vec<(String, ColumnType)>> {
     use crate::sql::ast::{SelectExpr, SelectItem, TableRef};
     use crate::storage::row::ColumnType;
+    if let Some(expanded) = expand_views(catalog, stmt)? {
+        return select_each_row(catalog, &expanded, context, max_rows, emit);
+    }
     match stmt {
         crate::sql::ast::Statement::Select {
             columns,
@@ -1783,27 +3759,21 @@ pub fn execute_select_statement(
                     match &expr.expr {
                         SelectItem::Literal(v) => {
                             let name = expr.alias.clone().unwrap_or_else(|| v.clone());
-                            let ty = if v.parse::<i32>().is_ok() {
-                                ColumnType::Integer
-                            } else {
-                                ColumnType::Text
-                            };
+                            let ty = literal_column_type(v);
                             header.push((name, ty));
                             row.push(v.clone());
                         }
                         SelectItem::Expr(e) => {
-                            let val = crate::sql::ast::evaluate_expression(
-                                e,
-                                &std::collections::HashMap::new(),
-                            )
-                            .to_string_value();
+                            let val = value_cell(
+                                catalog,
+                                &crate::sql::ast::evaluate_expression(
+                                    e,
+                                    &std::collections::HashMap::new(),
+                                ),
+                            );
                             header.push((
                                 expr.alias.clone().unwrap_or("EXPR".into()),
-                                ColumnType::Double {
-                                    precision: 8,
-                                    scale: 2,
-                                    unsigned: false,
-                                },
+                                expr_column_type(e, &[]),
                             ));
                             row.push(val);
                         }
@@ -1826,59 +3796,213 @@ pub fn execute_select_statement(
                         _ => return Err(DbError::InvalidValue("Unsupported projection".into())),
                     }
                 }
-                out.push(row);
+                emit(row);
                 return Ok(header);
             }
             if !joins.is_empty() {
                 return Err(DbError::InvalidValue("Unsupported query".into()));
             }
+            // Apply OFFSET and LIMIT to the rows as they are produced.
+            let mut skip = offset.unwrap_or(0);
+            let mut remaining = match (*limit, max_rows) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            // A plain table scan stops once it has produced this many rows.
+            let scan_cap = remaining.map(|n| n + skip);
+            let mut limited = |row: Vec<String>| {
+                if skip > 0 {
+                    skip -= 1;
+                    return;
+                }
+                match remaining.as_mut() {
+                    Some(0) => return,
+                    Some(n) => *n -= 1,
+                    None => {}
+                }
+                emit(row);
+            };
+            let emit: &mut dyn FnMut(Vec<String>) = &mut limited;
             let source = from
                 .first()
                 .ok_or_else(|| DbError::ParseError("Missing FROM".into()))?;
             match source {
+                TableRef::Named { name, .. } if is_system_table(name) => {
+                    let mut inner_rows = Vec::new();
+                    let inner_header = system_table_rows(catalog, name, &mut inner_rows)?;
+                    project_derived_rows(
+                        catalog,
+                        inner_header,
+                        inner_rows,
+                        columns,
+                        where_predicate.as_ref(),
+                        group_by.as_deref(),
+                        having.as_ref(),
+                        order_by.as_ref(),
+                        context,
+                        emit,
+                    )
+                }
                 TableRef::Named { name, alias } => {
+                    let scope = ColumnScope {
+                        table: name,
+                        alias: alias.as_deref(),
+                        context,
+                    };
+                    let columns = &scope.select_exprs(columns)?;
+                    let where_predicate = &where_predicate
+                        .as_ref()
+                        .map(|e| scope.expr(e))
+                        .transpose()?;
+                    let group_by = &group_by
+                        .as_ref()
+                        .map(|g| {
+                            g.iter()
+                                .map(|c| scope.column(c))
+                                .collect::<DbResult<Vec<_>>>()
+                        })
+                        .transpose()?;
+                    let having = having.as_ref().map(|e| scope.expr(e)).transpose()?;
+                    let order_by = order_by
+                        .as_ref()
+                        .map(|o| scope.order_key(&o.column).map(|c| (c, o.descending)))
+                        .transpose()?;
+                    if let [
+                        SelectExpr {
+                            expr:
+                                SelectItem::Aggregate {
+                                    func: crate::sql::ast::AggFunc::Count,
+                                    column: None,
+                                },
+                            alias,
+                        },
+                    ] = columns.as_slice()
+                        && where_predicate.is_none()
+                        && group_by.is_none()
+                        && having.is_none()
+                        && let Some(count) = maintained_row_count(catalog, name)?
+                    {
+                        emit(vec![count.to_string()]);
+                        let name = alias.clone().unwrap_or_else(|| "COUNT(*)".into());
+                        return Ok(vec![(name, ColumnType::Integer)]);
+                    }
                     if group_by.is_some()
                         || columns
                             .iter()
                             .any(|c| matches!(c.expr, SelectItem::Aggregate { .. }))
                     {
-                        return execute_group_query(
+                        let mut grouped = Vec::new();
+                        let header = execute_group_query(
                             catalog,
                             name,
                             columns,
                             group_by.as_deref(),
-                            having.clone(),
+                            having,
                             where_predicate.clone(),
-                            out,
+                            &mut grouped,
                             context,
-                        );
+                        )?;
+                        if let Some((key, descending)) = &order_by {
+                            sort_grouped(&mut grouped, &header, columns, key, *descending)?;
+                        }
+                        grouped.into_iter().for_each(&mut *emit);
+                        return Ok(header);
                     }
                     let info = catalog.get_table(name)?.clone();
+                    let order_by = order_by.map(|(key, descending)| {
+                        (resolve_select_alias(&key, &info.columns, columns), descending)
+                    });
+                    let types = column_types(&info.columns);
+                    let nocase = nocase_columns(&info, Some(alias.as_deref().unwrap_or(name)));
                     let (idxs, header) = select_projection_indices(&info.columns, columns)?;
+                    // Narrow the scan through an index when the WHERE clause has an
+                    // indexed equality, or to a key range when it bounds the first
+                    // column; the full predicate is still checked below.
+                    let index_hint = match (context, where_predicate) {
+                        (None, Some(pred)) if !expr_has_subquery(pred) => {
+                            indexed_equality(catalog, name, &info.columns, pred)
+                                .map(|(left, right, _)| Expr::Equals { left, right })
+                                .or_else(|| {
+                                    key_range(&info.columns, pred)
+                                        .map(|range| key_range_expr(&info.columns[0].0, range))
+                                })
+                        }
+                        _ => None,
+                    };
+                    let projection = scan_projection(
+                        &info.columns,
+                        &idxs,
+                        where_predicate.as_ref(),
+                        order_by.as_ref().map(|(key, _)| key.as_str()),
+                        context,
+                    );
                     let mut rows = Vec::new();
-                    execute_select_with_indexes(catalog, name, None, &mut rows)?;
+                    select_rows(catalog, name, index_hint, projection.as_deref(), &mut rows)?;
+                    if let Some((key, descending)) = &order_by
+                        && !info.columns.iter().any(|(c, _)| c == key)
+                        && let Some(expr) = projected_expr(columns, key)
+                    {
+                        // An alias of a computed column sorts by its value.
+                        let mut keyed = rows
+                            .into_iter()
+                            .map(|row| {
+                                let map = info
+                                    .columns
+                                    .iter()
+                                    .zip(row.data.0.iter())
+                                    .map(|((c, _), v)| (c.clone(), v.to_predicate_string()))
+                                    .collect();
+                                let value = crate::sql::ast::try_evaluate_expression_typed(
+                                    expr, &map, &types,
+                                )?;
+                                Ok((value, row))
+                            })
+                            .collect::<DbResult<Vec<_>>>()?;
+                        keyed.sort_by(|(a, _), (b, _)| {
+                            let ord = a.compare(b);
+                            if *descending { ord.reverse() } else { ord }
+                        });
+                        rows = keyed.into_iter().map(|(_, row)| row).collect();
+                    } else if let Some((key, descending)) = &order_by {
+                        let idx = info
+                            .columns
+                            .iter()
+                            .position(|(c, _)| c == key)
+                            .ok_or_else(|| DbError::ColumnNotFound(key.clone()))?;
+                        rows.sort_by(|a, b| {
+                            let ord = a.data.0[idx].compare(&b.data.0[idx]);
+                            if *descending { ord.reverse() } else { ord }
+                        });
+                    }
+                    let mut produced = 0;
                     for row in rows {
-                        let vals = row_to_strings(&row);
+                        if scan_cap == Some(produced) {
+                            break;
+                        }
+                        catalog.note_row_visited();
                         let mut map = std::collections::HashMap::new();
                         if let Some(ctx) = context {
                             for (k, v) in ctx {
                                 map.insert(k.clone(), v.clone());
                             }
                         }
-                        for ((c, _), v) in info.columns.iter().zip(vals.iter()) {
+                        for ((c, _), v) in info.columns.iter().zip(row.data.0.iter()) {
+                            let v = v.to_predicate_string();
                             map.insert(c.clone(), v.clone());
                             let qual = format!("{}.{}", alias.as_deref().unwrap_or(name), c);
-                            map.insert(qual, v.clone());
+                            map.insert(qual, v);
                         }
                         if let Some(pred) = where_predicate {
-                            if !evaluate_with_catalog(pred, &map, catalog)? {
+                            if !evaluate_with_catalog(pred, &map, &types, &nocase, catalog)? {
                                 continue;
                             }
                         }
                         let mut projected = Vec::new();
                         for p in idxs.iter() {
                             match p {
-                                Projection::Index(i) => projected.push(vals[*i].clone()),
+                                Projection::Index(i) => {
+                                    projected.push(value_cell(catalog, &row.data.0[*i]))
+                                }
                                 Projection::Literal(s) => projected.push(s.clone()),
                                 Projection::Subquery(q) => {
                                     let mut inner_rows = Vec::new();
@@ -1896,14 +4020,16 @@ pub fn execute_select_statement(
                                     projected.push(val);
                                 }
                                 Projection::Expr(expr) => {
-                                    let val = crate::sql::ast::evaluate_expression(expr, &map)
-                                        .to_string_value();
-                                    projected.push(val);
+                                    let val = crate::sql::ast::try_evaluate_expression_typed(
+                                        expr, &map, &types,
+                                    )?;
+                                    projected.push(value_cell(catalog, &val));
                                 }
                             }
                         }
 
-                        out.push(projected);
+                        produced += 1;
+                        emit(projected);
                     }
                     Ok(header)
                 }
@@ -1911,65 +4037,258 @@ pub fn execute_select_statement(
                     let mut inner_rows = Vec::new();
                     let inner_header =
                         execute_select_statement(catalog, query, &mut inner_rows, context)?;
-                    let mut filtered = Vec::new();
-                    for row in inner_rows {
-                        let mut values = std::collections::HashMap::new();
-                        if let Some(ctx) = context {
-                            for (k, v) in ctx {
-                                values.insert(k.clone(), v.clone());
-                            }
-                        }
-                        for ((col, _), val) in inner_header.iter().zip(row.iter()) {
-                            values.insert(col.clone(), val.clone());
-                        }
-                        if let Some(pred) = where_predicate {
-                            if !evaluate_with_catalog(pred, &values, catalog)? {
-                                continue;
-                            }
-                        }
-                        filtered.push(row);
-                    }
-                    if columns.len() == 1 && matches!(columns[0].expr, SelectItem::All) {
-                        out.extend(filtered.clone());
-                        Ok(inner_header)
+                    project_derived_rows(
+                        catalog,
+                        inner_header,
+                        inner_rows,
+                        columns,
+                        where_predicate.as_ref(),
+                        group_by.as_deref(),
+                        having.as_ref(),
+                        order_by.as_ref(),
+                        context,
+                        emit,
+                    )
+                }
+            }
+        }
+        crate::sql::ast::Statement::SetOp { op, left, right } => {
+            let mut left_rows = Vec::new();
+            let header = execute_select_statement(catalog, left, &mut left_rows, context)?;
+            let mut right_rows = Vec::new();
+            let right_header = execute_select_statement(catalog, right, &mut right_rows, context)?;
+            if header.len() != right_header.len() {
+                return Err(DbError::InvalidValue(format!(
+                    "UNION operands have different column counts ({} and {})",
+                    header.len(),
+                    right_header.len()
+                )));
+            }
+            let mut seen = std::collections::HashSet::new();
+            for row in left_rows.into_iter().chain(right_rows) {
+                if *op == crate::sql::ast::SetOp::UnionAll || seen.insert(row.clone()) {
+                    emit(row);
+                }
+            }
+            Ok(header)
+        }
+        crate::sql::ast::Statement::Values(rows) => {
+            let rows = values_rows(catalog, rows)?;
+            let header = values_header(&rows);
+            let cap = max_rows.unwrap_or(usize::MAX);
+            for row in rows.into_iter().take(cap) {
+                emit(row);
+            }
+            Ok(header)
+        }
+        _ => Err(DbError::InvalidValue("Not a SELECT".into())),
+    }
+}
+
+/// The rows of a `VALUES` statement as result strings.
+fn values_rows(catalog: &Catalog, rows: &[Vec<Expr>]) -> DbResult<Vec<Vec<String>>> {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|expr| match expr {
+                    Expr::Literal(v) => Ok(v.clone()),
+                    Expr::Value(v) => Ok(value_cell(catalog, v)),
+                    Expr::Null => Ok(null_cell(catalog)),
+                    Expr::DefaultValue => Err(DbError::InvalidValue(
+                        "DEFAULT is only allowed in INSERT".into(),
+                    )),
+                    other => Err(DbError::InvalidValue(format!(
+                        "Unsupported value in VALUES: {:?}",
+                        other
+                    ))),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Header of a `VALUES` result: `column1`, `column2`, ..., each typed from
+/// the first non-NULL value in its column.
+fn values_header(rows: &[Vec<String>]) -> Vec<(String, ColumnType)> {
+    let width = rows.first().map_or(0, Vec::len);
+    (0..width)
+        .map(|i| {
+            let ty = rows
+                .iter()
+                .map(|row| row[i].as_str())
+                .find(|v| !v.eq_ignore_ascii_case("NULL") && *v != NULL_MARKER)
+                .map_or(ColumnType::Text, literal_column_type);
+            (format!("column{}", i + 1), ty)
+        })
+        .collect()
+}
+
+/// Order grouped result rows by `key`: a header name (an alias or an
+/// aggregate's display name), a grouped column or an aggregate call.
+fn sort_grouped(
+    grouped: &mut [Vec<String>],
+    header: &[(String, ColumnType)],
+    columns: &[crate::sql::ast::SelectExpr],
+    key: &str,
+    descending: bool,
+) -> DbResult<()> {
+    use crate::sql::ast::SelectItem;
+    let idx = header
+        .iter()
+        .position(|(h, _)| h == key)
+        .or_else(|| {
+            columns.iter().position(|c| match &c.expr {
+                SelectItem::Column(n) => n == key,
+                SelectItem::Aggregate { func, column } => format!(
+                    "{}({})",
+                    func.as_str(),
+                    column.as_deref().unwrap_or("*")
+                )
+                .eq_ignore_ascii_case(key),
+                _ => false,
+            })
+        })
+        .ok_or_else(|| DbError::ColumnNotFound(key.to_string()))?;
+    grouped.sort_by(|a, b| {
+        let ord = match (a[idx].parse::<f64>(), b[idx].parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.total_cmp(&y),
+            _ => a[idx].cmp(&b[idx]),
+        };
+        if descending { ord.reverse() } else { ord }
+    });
+    Ok(())
+}
+
+/// Filter, group, order and project rows produced by a derived source (a FROM
+/// subquery, a view or a system catalog view) whose columns are described by
+/// `inner_header`. Column references may carry the source's qualifier.
+#[allow(clippy::too_many_arguments)]
+fn project_derived_rows(
+    catalog: &mut Catalog,
+    inner_header: Vec<(String, ColumnType)>,
+    inner_rows: Vec<Vec<String>>,
+    columns: &[crate::sql::ast::SelectExpr],
+    where_predicate: Option<&Expr>,
+    group_by: Option<&[String]>,
+    having: Option<&Expr>,
+    order_by: Option<&crate::sql::ast::OrderBy>,
+    context: Option<&std::collections::HashMap<String, String>>,
+    emit: &mut dyn FnMut(Vec<String>),
+) -> DbResult<Vec<(String, ColumnType)>> {
+    use crate::sql::ast::SelectItem;
+    let bare = |c: &str| c.rsplit('.').next().unwrap_or(c).to_string();
+    if group_by.is_some()
+        || columns
+            .iter()
+            .any(|c| matches!(c.expr, SelectItem::Aggregate { .. }))
+    {
+        let rows = inner_rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let values = row
+                    .iter()
+                    .zip(&inner_header)
+                    .map(|(v, (_, ty))| derived_value(v, *ty))
+                    .collect();
+                Row::new(i as RowKey, RowData(values))
+            })
+            .collect();
+        let group_by: Option<Vec<String>> =
+            group_by.map(|keys| keys.iter().map(|k| bare(k)).collect());
+        let mut grouped = Vec::new();
+        let header = group_rows(
+            catalog,
+            None,
+            &inner_header,
+            rows,
+            columns,
+            group_by.as_deref(),
+            having.cloned(),
+            where_predicate.cloned(),
+            &mut grouped,
+            context,
+        )?;
+        if let Some(order) = order_by {
+            sort_grouped(&mut grouped, &header, columns, &order.column, order.descending)?;
+        }
+        grouped.into_iter().for_each(emit);
+        return Ok(header);
+    }
+    let types = column_types(&inner_header);
+    let mut filtered = Vec::new();
+    for row in inner_rows {
+        let mut values = std::collections::HashMap::new();
+        if let Some(ctx) = context {
+            for (k, v) in ctx {
+                values.insert(k.clone(), v.clone());
+            }
+        }
+        for ((col, _), val) in inner_header.iter().zip(row.iter()) {
+            values.insert(col.clone(), val.clone());
+        }
+        if let Some(pred) = where_predicate {
+            if !evaluate_with_catalog(pred, &values, &types, &HashSet::new(), catalog)? {
+                continue;
+            }
+        }
+        filtered.push(row);
+    }
+    if let Some(order) = order_by {
+        let key = resolve_select_alias(&bare(&order.column), &inner_header, columns);
+        let (idx, ty) = inner_header
+            .iter()
+            .enumerate()
+            .find(|(_, (n, _))| *n == bare(&key))
+            .map(|(i, (_, ty))| (i, *ty))
+            .ok_or_else(|| DbError::ColumnNotFound(order.column.clone()))?;
+        filtered.sort_by(|a, b| {
+            let ord = derived_value(&a[idx], ty).compare(&derived_value(&b[idx], ty));
+            if order.descending { ord.reverse() } else { ord }
+        });
+    }
+    if columns.len() == 1 && matches!(columns[0].expr, SelectItem::All) {
+        filtered.into_iter().for_each(emit);
+        Ok(inner_header)
+    } else {
+        let mut header = Vec::new();
+        let mut idxs = Vec::new();
+        for expr in columns {
+            match &expr.expr {
+                SelectItem::Column(c) => {
+                    let base = c.split('.').last().unwrap_or(c);
+                    if let Some((i, (_, ty))) = inner_header
+                        .iter()
+                        .enumerate()
+                        .find(|(_, (n, _))| n == base)
+                    {
+                        idxs.push(i);
+                        header.push((
+                            expr.alias.clone().unwrap_or(base.to_string()),
+                            *ty,
+                        ));
                     } else {
-                        let mut header = Vec::new();
-                        let mut idxs = Vec::new();
-                        for expr in columns {
-                            match &expr.expr {
-                                SelectItem::Column(c) => {
-                                    let base = c.split('.').last().unwrap_or(c);
-                                    if let Some((i, (_, ty))) = inner_header
-                                        .iter()
-                                        .enumerate()
-                                        .find(|(_, (n, _))| n == base)
-                                    {
-                                        idxs.push(i);
-                                        header.push((
-                                            expr.alias.clone().unwrap_or(base.to_string()),
-                                            *ty,
-                                        ));
-                                    } else {
-                                        return Err(DbError::ColumnNotFound(c.clone()));
-                                    }
-                                }
-                                _ => {
-                                    return Err(DbError::InvalidValue(
-                                        "Unsupported projection".into(),
-                                    ));
-                                }
-                            }
-                        }
-                        for row in filtered {
-                            let projected: Vec<_> = idxs.iter().map(|&i| row[i].clone()).collect();
-                            out.push(projected);
-                        }
-                        Ok(header)
+                        return Err(DbError::ColumnNotFound(c.clone()));
                     }
                 }
+                SelectItem::All | SelectItem::AllFrom(_) => {
+                    for (i, col) in inner_header.iter().enumerate() {
+                        idxs.push(i);
+                        header.push(col.clone());
+                    }
+                }
+                _ => {
+                    return Err(DbError::InvalidValue(
+                        "Unsupported projection".into(),
+                    ));
+                }
             }
         }
-        _ => Err(DbError::InvalidValue("Not a SELECT".into())),
+        for row in filtered {
+            let projected: Vec<_> = idxs.iter().map(|&i| row[i].clone()).collect();
+            emit(projected);
+        }
+        Ok(header)
     }
 }
 
@@ -1994,6 +4313,111 @@ pub fn format_header(columns: &[(String, ColumnType)]) -> String {
         .join(" | ")
 }
 
+/// Render a result set from [`handle_export_statement`] as a JSON array with
+/// one object per row, keyed by column name in header order. Each cell is
+/// read back as its column's `ColumnValue`, so numbers and booleans stay
+/// unquoted and NULL is `null`; the text `'NULL'`, dates, times, and blobs
+/// are strings in their display form.
+pub fn format_json(header: &[(String, ColumnType)], rows: &[Vec<String>]) -> String {
+    let mut out = String::from("[");
+    for (row_idx, row) in rows.iter().enumerate() {
+        if row_idx > 0 {
+            out.push(',');
+        }
+        out.push('{');
+        for (col_idx, (col, cell)) in header.iter().zip(row).enumerate() {
+            if col_idx > 0 {
+                out.push(',');
+            }
+            push_json_string(&mut out, &col.0);
+            out.push(':');
+            let value = if cell == NULL_MARKER {
+                ColumnValue::Null
+            } else if cell.eq_ignore_ascii_case("NULL") {
+                // Not SQL NULL, which comes marked; `build_row_data` would
+                // read it as one.
+                ColumnValue::Text(cell.clone())
+            } else {
+                build_row_data(std::slice::from_ref(cell), std::slice::from_ref(col))
+                    .ok()
+                    .and_then(|data| data.0.into_iter().next())
+                    .unwrap_or_else(|| ColumnValue::Text(cell.clone()))
+            };
+            push_json_value(&mut out, &value);
+        }
+        out.push('}');
+    }
+    out.push(']');
+    out
+}
+
+/// Render a result set from [`handle_export_statement`] as RFC 4180 CSV: a
+/// header row of column names, then one record per row, each line ending in
+/// CRLF. A field containing a comma, quote, or line break, and the empty
+/// string, is quoted with `""` escaping; NULL is an empty unquoted field, as
+/// [`Engine::import_csv`] reads it.
+///
+/// [`Engine::import_csv`]: crate::engine::Engine::import_csv
+pub fn format_csv(header: &[(String, ColumnType)], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    let names: Vec<String> = header.iter().map(|(name, _)| csv_field(name)).collect();
+    out.push_str(&names.join(","));
+    out.push_str("\r\n");
+    for row in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                if cell == NULL_MARKER {
+                    String::new()
+                } else {
+                    csv_field(cell)
+                }
+            })
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.is_empty() || value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn push_json_value(out: &mut String, value: &ColumnValue) {
+    match value {
+        ColumnValue::Null => out.push_str("null"),
+        ColumnValue::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+        ColumnValue::Integer(i) => out.push_str(&i.to_string()),
+        ColumnValue::BigInt(i) => out.push_str(&i.to_string()),
+        ColumnValue::Year(y) => out.push_str(&y.to_string()),
+        ColumnValue::Double(f) if f.is_finite() => out.push_str(&value.to_string_value()),
+        ColumnValue::Double(_) => out.push_str("null"),
+        ColumnValue::Decimal { .. } => out.push_str(&value.to_string_value()),
+        _ => push_json_string(out, &value.to_string_value()),
+    }
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,15 +10,17 @@ mod error;
 mod planner;
 
 use std::io::{self, Write};
+use std::time::{Duration, Instant};
 use log::{debug, info, warn};
 
 use crate::storage::pager::Pager;
 use crate::storage::btree::BTree;
 use crate::storage::row::{RowData, ColumnValue, ColumnType, build_row_data};
 use crate::catalog::Catalog;
-use crate::sql::parser::parse_statement;
+use crate::sql::parser::{parse_statement, split_statements};
 use crate::sql::ast::{Statement, Expr, expr_to_string};
-use crate::execution::{execute_delete, execute_select_with_indexes, handle_statement};
+use crate::execution::{QueryResult, execute_delete, execute_select_with_indexes, handle_statement};
+use crate::execution::runtime::{format_csv, format_header, format_json, format_values, handle_export_statement};
 use crate::error::DbError;
 
 // const DATABASE_FILE: &str = "data.aerodb";
@@ -32,6 +34,21 @@ fn main() -> io::Result<()> {
     let mut catalog = Catalog::open(Pager::new(DATABASE_FILE)?)?;
     let mut transaction_manager = crate::transaction::TransactionManager::new();
 
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--file") {
+        let Some(path) = args.get(pos + 1) else {
+            eprintln!("Usage: aerodb --file <path.sql>");
+            std::process::exit(2);
+        };
+        let script = std::fs::read_to_string(path)?;
+        if let Err(message) = run_script(&mut catalog, &mut transaction_manager, &script) {
+            eprintln!("{}: {}", path, message);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut timer = StatementTimer::default();
     loop {
         print!("aerodb> ");
         io::stdout().flush()?;
@@ -48,28 +65,72 @@ fn main() -> io::Result<()> {
             break;
         }
 
+        if let Some(setting) = trimmed.strip_prefix(".timer") {
+            match setting.trim().to_ascii_lowercase().as_str() {
+                "on" => timer.enabled = true,
+                "off" => timer.enabled = false,
+                _ => println!("Usage: .timer on|off"),
+            }
+            continue;
+        }
+
+        if let Some(query) = trimmed.strip_prefix(".json ") {
+            match parse_statement(query) {
+                Ok(stmt @ (Statement::Select { .. } | Statement::SetOp { .. } | Statement::Values(_))) => {
+                    match transaction_manager.execute(&mut catalog, stmt, handle_export_statement) {
+                        Ok(QueryResult::Rows { header, rows }) => println!("{}", format_json(&header, &rows)),
+                        Ok(_) => println!("[]"),
+                        Err(e) => println!("{}", describe_error(&e)),
+                    }
+                }
+                Ok(_) => println!("Error: .json expects a SELECT statement"),
+                Err(e) => warn!("Parse error: {}", e),
+            }
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix(".csv ") {
+            let Some((path, query)) = rest.trim_start().split_once(char::is_whitespace) else {
+                println!("Usage: .csv <path> SELECT ...");
+                continue;
+            };
+            match parse_statement(query) {
+                Ok(stmt @ (Statement::Select { .. } | Statement::SetOp { .. } | Statement::Values(_))) => {
+                    match transaction_manager.execute(&mut catalog, stmt, handle_export_statement) {
+                        Ok(QueryResult::Rows { header, rows }) => {
+                            match std::fs::write(path, format_csv(&header, &rows)) {
+                                Ok(()) => println!("{} row(s) exported to {}", rows.len(), path),
+                                Err(e) => println!("IO error: {}", e),
+                            }
+                        }
+                        Ok(_) => println!("0 row(s) exported to {}", path),
+                        Err(e) => println!("{}", describe_error(&e)),
+                    }
+                }
+                Ok(_) => println!("Error: .csv expects a SELECT statement"),
+                Err(e) => warn!("Parse error: {}", e),
+            }
+            continue;
+        }
+
         match parse_statement(trimmed) {
             Ok(stmt) => {
                 if let Statement::Exit = stmt {
                     break;
                 }
-                if let Err(e) =
+                let verb = match stmt {
+                    Statement::Delete { .. } => "deleted",
+                    Statement::Update { .. } => "updated",
+                    _ => "inserted",
+                };
+                let result = timer.time(|| {
                     transaction_manager.execute(&mut catalog, stmt, handle_statement)
-                {
-                    match e {
-                        DbError::TableNotFound(t) => println!("Error: table '{}' not found", t),
-                        DbError::DuplicateKey(k) => println!("Error: duplicate primary key {}", k),
-                        DbError::Overflow => println!("Error: value out of range"),
-                        DbError::ParseError(m) | DbError::InvalidValue(m) => println!("Error: {}", m),
-                        DbError::ColumnNotFound(c) => println!("Error: column '{}' not found", c),
-                        DbError::GroupByMismatch(c) => println!("Error: column '{}' must appear in GROUP BY or be aggregated", c),
-                        DbError::NotFound(m) => println!("Error: {}", m),
-                        DbError::NullViolation(c) => println!("Error: column '{}' cannot be NULL", c),
-                        DbError::ForeignKeyViolation(m) => println!("Error: {}", m),
-                        DbError::WriteConflict(k) => println!("Error: write conflict on logical key {}", k),
-                        DbError::Io(err) => println!("IO error: {}", err),
-                    }
+                });
+                match result {
+                    Ok(result) => print_result(&result, verb),
+                    Err(e) => println!("{}", describe_error(&e)),
                 }
+                timer.report();
             }
             Err(e) => warn!("Parse error: {}", e),
         }
@@ -79,6 +140,118 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Wall-clock timing of REPL statements, switched by `.timer on|off` and off
+/// by default.
+#[derive(Default)]
+struct StatementTimer {
+    enabled: bool,
+    /// Duration of the last statement run while enabled.
+    last: Option<Duration>,
+}
+
+impl StatementTimer {
+    /// Run `statement`, recording how long it took when the timer is on.
+    fn time<T>(&mut self, statement: impl FnOnce() -> T) -> T {
+        self.last = None;
+        if !self.enabled {
+            return statement();
+        }
+        let start = Instant::now();
+        let result = statement();
+        self.last = Some(start.elapsed());
+        result
+    }
+
+    /// Print the last recorded duration, if any.
+    fn report(&self) {
+        if let Some(elapsed) = self.last {
+            println!("Run Time: {:.3} ms", elapsed.as_secs_f64() * 1000.0);
+        }
+    }
+}
+
+/// Execute each statement of a `--file` script in order, printing results as
+/// the REPL does. Stops at the first failure, naming the statement by its
+/// 1-based position in the script.
+fn run_script(
+    catalog: &mut Catalog,
+    transaction_manager: &mut crate::transaction::TransactionManager,
+    script: &str,
+) -> Result<(), String> {
+    for (idx, sql) in split_statements(script).into_iter().enumerate() {
+        let stmt = parse_statement(sql)
+            .map_err(|e| format!("statement {}: Parse error: {}", idx + 1, e))?;
+        if let Statement::Exit = stmt {
+            break;
+        }
+        let verb = match stmt {
+            Statement::Delete { .. } => "deleted",
+            Statement::Update { .. } => "updated",
+            _ => "inserted",
+        };
+        match transaction_manager.execute(catalog, stmt, handle_statement) {
+            Ok(result) => print_result(&result, verb),
+            Err(e) => return Err(format!("statement {}: {}", idx + 1, describe_error(&e))),
+        }
+    }
+    Ok(())
+}
+
+/// Render an execution error the way the REPL prints it.
+fn describe_error(e: &DbError) -> String {
+    match e {
+        DbError::TableNotFound(t) => format!("Error: table '{}' not found", t),
+        DbError::DuplicateKey(k) => format!("Error: duplicate primary key {}", k),
+        DbError::DuplicateKeyValue { table, key } => {
+            format!("Error: duplicate primary key {} in table '{}'", key, table)
+        }
+        DbError::Overflow => "Error: value out of range".to_string(),
+        DbError::ParseError(m) | DbError::InvalidValue(m) => format!("Error: {}", m),
+        DbError::ColumnNotFound(c) => format!("Error: column '{}' not found", c),
+        DbError::GroupByMismatch(c) => format!("Error: column '{}' must appear in GROUP BY or be aggregated", c),
+        DbError::NotFound(m) => format!("Error: {}", m),
+        DbError::NullViolation(c) => format!("Error: column '{}' cannot be NULL", c),
+        DbError::ForeignKeyViolation(m) => format!("Error: {}", m),
+        DbError::WriteConflict(k) => format!("Error: write conflict on logical key {}", k),
+        DbError::Io(err) => format!("IO error: {}", err),
+    }
+}
+
+/// Print a statement's outcome the way the REPL shows it. `verb` names what
+/// happened to an affected row count ("deleted", "updated" or "inserted").
+fn print_result(result: &QueryResult, verb: &str) {
+    match result {
+        QueryResult::Rows { header, rows } => {
+            println!("{}", format_header(header));
+            for row in rows {
+                println!("{}", format_values(row));
+            }
+        }
+        QueryResult::Affected(count) => println!("{} row(s) {}", count, verb),
+        QueryResult::Message(message) => println!("{}", message),
+        QueryResult::Ack => {}
+    }
+}
+
+#[cfg(test)]
+mod timer_tests {
+    use super::StatementTimer;
+
+    #[test]
+    fn timer_records_only_when_enabled() {
+        let mut timer = StatementTimer::default();
+        assert_eq!(timer.time(|| 7), 7);
+        assert!(timer.last.is_none());
+
+        timer.enabled = true;
+        timer.time(|| std::thread::sleep(std::time::Duration::from_millis(2)));
+        assert!(timer.last.unwrap() >= std::time::Duration::from_millis(2));
+
+        timer.enabled = false;
+        timer.time(|| ());
+        assert!(timer.last.is_none());
+    }
+}
 
 #[cfg(all(test, feature = "main-tests"))]
 mod tests {
@@ -600,10 +773,11 @@ mod tests {
     fn parse_create_index() {
         let stmt = parse_statement("CREATE INDEX idx_name ON users (name)").unwrap();
         match stmt {
-            Statement::CreateIndex { index_name, table_name, column_name } => {
+            Statement::CreateIndex { index_name, table_name, column_name, if_not_exists } => {
                 assert_eq!(index_name, "idx_name");
                 assert_eq!(table_name, "users");
                 assert_eq!(column_name, "name");
+                assert!(!if_not_exists);
             }
             _ => panic!("Expected create index"),
         }
@@ -775,7 +949,7 @@ mod tests {
                 let mut results = Vec::new();
                 let used = crate::execution::execute_select_with_indexes(&mut catalog, &table_name, selection, &mut results).unwrap();
                 assert!(used, "index should be used for duplicate values");
-                let keys: Vec<i32> = results.iter().map(|r| r.key).collect();
+                let keys: Vec<i64> = results.iter().map(|r| r.key).collect();
                 assert_eq!(keys, vec![1, 2, 3]);
             }
             _ => panic!("Expected select"),
@@ -876,7 +1050,7 @@ mod tests {
                 let mut results = Vec::new();
                 let used = crate::execution::execute_select_with_indexes(&mut catalog, &table_name, selection, &mut results).unwrap();
                 assert!(!used, "index should not be used for inequality");
-                let keys: Vec<i32> = results.iter().map(|r| r.key).collect();
+                let keys: Vec<i64> = results.iter().map(|r| r.key).collect();
                 assert_eq!(keys, vec![2]);
             }
             _ => panic!("Expected select"),
@@ -992,19 +1166,19 @@ mod tests {
     fn parse_transaction_statements() {
         let stmt = parse_statement("BEGIN TRANSACTION tx1").unwrap();
         match stmt {
-            Statement::BeginTransaction { name } => assert_eq!(name, Some("tx1".into())),
+            Statement::BeginTransaction { name, .. } => assert_eq!(name, Some("tx1".into())),
             _ => panic!("Expected begin transaction"),
         }
 
         let stmt = parse_statement("BEGIN TRANSACTION").unwrap();
         match stmt {
-            Statement::BeginTransaction { name } => assert_eq!(name, None),
+            Statement::BeginTransaction { name, .. } => assert_eq!(name, None),
             _ => panic!("Expected begin transaction"),
         }
 
         let stmt = parse_statement("BEGIN").unwrap();
         match stmt {
-            Statement::BeginTransaction { name } => assert_eq!(name, None),
+            Statement::BeginTransaction { name, .. } => assert_eq!(name, None),
             _ => panic!("Expected begin transaction"),
         }
 
@@ -38,10 +38,18 @@ fn collect_expr_columns(expr: &Expr, cols: &HashSet<String>, out: &mut HashSet<S
                 }
             }
         }
-        Expr::Between { expr, low, high } => {
+        Expr::Between { expr, low, high, .. } => {
             collect_expr_columns(&Expr::GreaterOrEquals { left: expr.clone(), right: low.clone() }, cols, out, aggs);
             collect_expr_columns(&Expr::LessOrEquals { left: expr.clone(), right: high.clone() }, cols, out, aggs);
         }
+        Expr::IsTruth { expr, .. }
+        | Expr::IsNull { expr, .. }
+        | Expr::ScalarSubquery { left: expr, .. } => {
+            let n = normalize(expr);
+            if cols.contains(&n) {
+                out.insert(n);
+            }
+        }
         Expr::And(a, b) | Expr::Or(a, b) => {
             collect_expr_columns(a, cols, out, aggs);
             collect_expr_columns(b, cols, out, aggs);
@@ -56,7 +64,7 @@ fn collect_expr_columns(expr: &Expr, cols: &HashSet<String>, out: &mut HashSet<S
             }
         }
         Expr::InSubquery { .. } | Expr::ExistsSubquery { .. } | Expr::Subquery(_) => {}
-        Expr::Literal(_) | Expr::DefaultValue => {}
+        Expr::Literal(_) | Expr::DefaultValue | Expr::Null | Expr::Placeholder(_) | Expr::Value(_) => {}
     }
 }
 
@@ -94,7 +102,7 @@ pub fn validate_group_by(
                 collect_expr_columns(e, &col_set, &mut cols, &mut agg_present);
                 select_cols.extend(cols);
             }
-            SelectItem::All => {
+            SelectItem::All | SelectItem::AllFrom(_) => {
                 for c in &col_set {
                     select_cols.insert(c.clone());
                 }
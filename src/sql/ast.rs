@@ -1,5 +1,7 @@
 // src/sql/ast.rs
-use crate::storage::row::ColumnType;
+use crate::error::{DbError, DbResult};
+use crate::storage::row::{ColumnType, ColumnValue};
+use crate::transaction::IsolationLevel;
 
 #[derive(Debug, Clone)]
 pub enum Expr {
@@ -13,19 +15,35 @@ pub enum Expr {
     BitwiseAnd { left: String, right: String },
     BitwiseOr { left: String, right: String },
     BitwiseXor { left: String, right: String },
-    Between { expr: String, low: String, high: String },
+    /// `expr [NOT] BETWEEN low AND high`, inclusive of both bounds.
+    Between { expr: String, low: String, high: String, negated: bool },
     GreaterThan { left: String, right: String },
     GreaterOrEquals { left: String, right: String },
     LessThan { left: String, right: String },
     LessOrEquals { left: String, right: String },
+    /// `expr IS [NOT] {TRUE | FALSE | UNKNOWN}`; `truth: None` is UNKNOWN.
+    IsTruth { expr: String, truth: Option<bool>, negated: bool },
+    /// `expr IS [NOT] NULL`
+    IsNull { expr: String, negated: bool },
     InSubquery { left: String, query: Box<Statement> },
     ExistsSubquery { query: Box<Statement> },
+    /// `left <op> (SELECT ...)` where the subquery yields a single value;
+    /// `op` is one of `=`, `!=`, `<`, `<=`, `>` or `>=`.
+    ScalarSubquery { left: String, op: String, query: Box<Statement> },
     And(Box<Expr>, Box<Expr>),
     Or(Box<Expr>, Box<Expr>),
     Subquery(Box<Statement>),
     Literal(String),
+    /// The `NULL` keyword in a VALUES tuple.
+    Null,
     DefaultValue,
     FunctionCall { name: String, args: Vec<Expr> },
+    /// The `n`th `?` parameter (1-based) in an INSERT value position. Inside
+    /// other expressions a parameter is the operand token `?n`.
+    Placeholder(usize),
+    /// A typed value bound to a [`Expr::Placeholder`]; never produced by the
+    /// parser.
+    Value(ColumnValue),
 }
 
 #[derive(Debug, Clone)]
@@ -52,9 +70,12 @@ pub struct ForeignKey {
 #[derive(Debug, Clone)]
 pub struct JoinClause {
     pub join_type: JoinType,
+    /// The joined table, or the alias of a derived `subquery`.
     pub table: String,
     pub alias: Option<String>,
     pub predicate: Option<Expr>,
+    /// A parenthesized query joined in place of a table.
+    pub subquery: Option<Box<Statement>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -81,6 +102,13 @@ pub enum AggFunc {
     Avg,
 }
 
+/// Set operation combining the results of two queries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetOp {
+    Union,
+    UnionAll,
+}
+
 /// SQL literal used in DEFAULT clauses and elsewhere.
 pub type Literal = String;
 
@@ -92,6 +120,10 @@ pub struct ColumnDef {
     pub default_value: Option<Expr>,
     pub auto_increment: bool,
     pub primary_key: bool,
+    /// Expression of a `GENERATED ALWAYS AS (expr) STORED` column.
+    pub generated: Option<Expr>,
+    /// Declared `COLLATE NOCASE`: text compares ignoring case.
+    pub nocase: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -116,6 +148,8 @@ impl AggFunc {
 #[derive(Debug, Clone)]
 pub enum SelectItem {
     All,
+    /// Every column of one FROM source, written `t.*`.
+    AllFrom(String),
     Column(String),
     Aggregate { func: AggFunc, column: Option<String> },
     Expr(Box<Expr>),
@@ -143,14 +177,57 @@ pub enum Statement {
         index_name: String,
         table_name: String,
         column_name: String,
+        if_not_exists: bool,
     },
     DropIndex {
         name: String,
+        if_exists: bool,
+    },
+    /// `REINDEX`, `REINDEX INDEX <name>` or `REINDEX TABLE <table>`; with
+    /// neither name every index is rebuilt.
+    Reindex {
+        index: Option<String>,
+        table: Option<String>,
     },
+    /// `VACUUM [[TABLE] <table>]`; rewrites the table's B-tree and indexes
+    /// densely packed, or every table's without a name.
+    Vacuum {
+        table: Option<String>,
+    },
+    /// `CHECKPOINT`: write committed WAL contents to the database file and
+    /// truncate the WAL.
+    Checkpoint,
     DropTable {
         table_name: String,
         if_exists: bool,
     },
+    /// `CREATE VIEW <name> AS <query>`; `sql` is the query's text, which is
+    /// parsed again wherever the view is read.
+    CreateView {
+        name: String,
+        sql: String,
+    },
+    /// `DROP VIEW [IF EXISTS] <name>`
+    DropView {
+        name: String,
+        if_exists: bool,
+    },
+    /// `ALTER TABLE <old_name> RENAME TO <new_name>`
+    RenameTable {
+        old_name: String,
+        new_name: String,
+    },
+    /// `ALTER INDEX <old_name> RENAME TO <new_name>`
+    RenameIndex {
+        old_name: String,
+        new_name: String,
+    },
+    /// `ALTER TABLE <table_name> RENAME COLUMN <old_name> TO <new_name>`
+    RenameColumn {
+        table_name: String,
+        old_name: String,
+        new_name: String,
+    },
     Insert {
         table_name: String,
         columns: Option<Vec<String>>, // None for unqualified
@@ -167,19 +244,55 @@ pub enum Statement {
         limit: Option<usize>,
         offset: Option<usize>,
     },
+    /// `<left> UNION [ALL] <right>`; chains of unions nest on the left.
+    SetOp {
+        op: SetOp,
+        left: Box<Statement>,
+        right: Box<Statement>,
+    },
+    /// `VALUES (..), (..)`: constant rows with columns `column1`, `column2`, ...
+    Values(Vec<Vec<Expr>>),
     Delete {
         table_name: String,
         selection: Option<Expr>,
     },
+    /// `TRUNCATE [TABLE] <table> [RESTART | CONTINUE IDENTITY]`; deletes every
+    /// row, and unless `CONTINUE IDENTITY` restarts the table's AUTO_INCREMENT
+    /// sequences.
+    Truncate {
+        table_name: String,
+        restart_identity: bool,
+    },
     Update {
         table_name: String,
         assignments: Vec<(String, String)>,
         selection: Option<Expr>,
     },
     CreateSequence(CreateSequence),
-    BeginTransaction { name: Option<String> },
+    /// `BEGIN [TRANSACTION] [SNAPSHOT | ISOLATION LEVEL SNAPSHOT] [name]`;
+    /// `isolation` is `None` when the statement names no level.
+    BeginTransaction {
+        name: Option<String>,
+        isolation: Option<IsolationLevel>,
+    },
     Commit,
     Rollback,
+    /// `SET autocommit = on | off`.
+    SetAutocommit(bool),
+    /// `SET CONSTRAINTS [ALL] DEFERRED | IMMEDIATE`; `true` when deferred.
+    SetConstraints(bool),
+    ShowTables,
+    ShowIndexes { table: Option<String> },
+    /// `DESCRIBE t` / `DESC t`; lists the columns of `table`.
+    Describe { table: String },
+    /// `PRAGMA name [= value]`; without a value the current setting is shown.
+    Pragma {
+        name: String,
+        value: Option<String>,
+    },
+    /// `EXPLAIN <query>`; describes the plan for a SELECT or UNION without
+    /// running it.
+    Explain(Box<Statement>),
     Exit,
 }
 
@@ -188,27 +301,154 @@ use std::collections::HashMap;
 /// Evaluate an expression against a map of column values. If an operand
 /// matches a column name, the corresponding value is used; otherwise the
 /// operand itself is treated as a literal string.
-use crate::storage::row::ColumnValue;
+use crate::storage::row::{NULL_MARKER, parse_decimal_literal, rescale_decimal};
 
 pub fn evaluate_expression(expr: &Expr, values: &HashMap<String, String>) -> ColumnValue {
+    evaluate_expression_typed(expr, values, &HashMap::new())
+}
+
+// Whole-number operands when at least one names an integer column and
+// neither names another type, so `+`, `-` and `*` over integer columns
+// stay exact instead of going through f64.
+fn integer_operands(
+    left: &str,
+    right: &str,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> Option<(i64, i64)> {
+    if !column_types.contains_key(left) && !column_types.contains_key(right) {
+        return None;
+    }
+    let operand = |token: &str| {
+        if column_types.get(token).is_some_and(|ty| !ty.is_integer()) {
+            return None;
+        }
+        resolve_operand(token, values).parse::<i64>().ok()
+    };
+    Some((operand(left)?, operand(right)?))
+}
+
+/// Like [`evaluate_expression_typed`], but integer `+`, `-` and `*` that
+/// overflow an `i64` fail with [`DbError::Overflow`] instead of falling back to
+/// an inexact double.
+pub fn try_evaluate_expression_typed(
+    expr: &Expr,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> DbResult<ColumnValue> {
+    if let Some(bound) = bind_function_operands(expr, values, column_types) {
+        return try_evaluate_expression_typed(expr, &bound, column_types);
+    }
+    let checked = match expr {
+        Expr::Add { left, right } => integer_operands(left, right, values, column_types)
+            .map(|(l, r)| l.checked_add(r)),
+        Expr::Subtract { left, right } => integer_operands(left, right, values, column_types)
+            .map(|(l, r)| l.checked_sub(r)),
+        Expr::Multiply { left, right } => integer_operands(left, right, values, column_types)
+            .map(|(l, r)| l.checked_mul(r)),
+        _ => None,
+    };
+    if checked == Some(None) {
+        return Err(DbError::Overflow);
+    }
+    Ok(evaluate_expression_typed(expr, values, column_types))
+}
+
+/// Like [`evaluate_expression`], but operands naming a column in
+/// `column_types` are read as that column's type: `+`, `-` and `*` use exact
+/// fixed-point arithmetic on DECIMAL columns and integer arithmetic on integer
+/// columns, and ordering comparisons follow [`compare_operands`].
+pub fn evaluate_expression_typed(
+    expr: &Expr,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> ColumnValue {
     fn get_value<'a>(token: &'a str, values: &'a HashMap<String, String>) -> &'a str {
-        values.get(token).map(String::as_str).unwrap_or(token)
+        resolve_operand(token, values)
+    }
+    fn decimal_operands(
+        left: &str,
+        right: &str,
+        values: &HashMap<String, String>,
+        column_types: &HashMap<String, ColumnType>,
+    ) -> Option<((i128, u8), (i128, u8))> {
+        let declared_scale = |token: &str| match column_types.get(token) {
+            Some(ColumnType::Decimal { scale, .. }) => Some(*scale as u8),
+            _ => None,
+        };
+        if declared_scale(left).is_none() && declared_scale(right).is_none() {
+            return None;
+        }
+        let operand = |token: &str| {
+            let (value, scale) = parse_decimal_literal(get_value(token, values))?;
+            let declared = declared_scale(token).unwrap_or(scale);
+            let target = scale.max(declared);
+            Some((rescale_decimal(value as i128, scale, target), target))
+        };
+        Some((operand(left)?, operand(right)?))
+    }
+    fn decimal_result(value: i128, scale: u8) -> ColumnValue {
+        match i64::try_from(value) {
+            Ok(value) => ColumnValue::Decimal { value, scale },
+            Err(_) => ColumnValue::Double(value as f64 / 10f64.powi(scale as i32)),
+        }
     }
 
+    if let Some(bound) = bind_function_operands(expr, values, column_types) {
+        return evaluate_expression_typed(expr, &bound, column_types);
+    }
+    if null_operand(expr, values) {
+        // A comparison with NULL is UNKNOWN and arithmetic on NULL is NULL.
+        return ColumnValue::Null;
+    }
     match expr {
-        Expr::Equals { left, right } => ColumnValue::Boolean(get_value(left, values) == get_value(right, values)),
-        Expr::NotEquals { left, right } => ColumnValue::Boolean(get_value(left, values) != get_value(right, values)),
+        Expr::Equals { left, right } => ColumnValue::Boolean(operands_equal(left, right, values, column_types)),
+        Expr::NotEquals { left, right } => ColumnValue::Boolean(!operands_equal(left, right, values, column_types)),
         Expr::Add { left, right } => {
+            if let Some(((l, ls), (r, rs))) = decimal_operands(left, right, values, column_types) {
+                let scale = ls.max(rs);
+                return decimal_result(
+                    rescale_decimal(l, ls, scale) + rescale_decimal(r, rs, scale),
+                    scale,
+                );
+            }
+            if let Some(v) = integer_operands(left, right, values, column_types)
+                .and_then(|(l, r)| l.checked_add(r))
+            {
+                return ColumnValue::BigInt(v);
+            }
             let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
             let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
             ColumnValue::Double(l + r)
         }
         Expr::Subtract { left, right } => {
+            if let Some(((l, ls), (r, rs))) = decimal_operands(left, right, values, column_types) {
+                let scale = ls.max(rs);
+                return decimal_result(
+                    rescale_decimal(l, ls, scale) - rescale_decimal(r, rs, scale),
+                    scale,
+                );
+            }
+            if let Some(v) = integer_operands(left, right, values, column_types)
+                .and_then(|(l, r)| l.checked_sub(r))
+            {
+                return ColumnValue::BigInt(v);
+            }
             let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
             let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
             ColumnValue::Double(l - r)
         }
         Expr::Multiply { left, right } => {
+            if let Some(((l, ls), (r, rs))) = decimal_operands(left, right, values, column_types) {
+                // Keep the wider operand scale rather than the full product scale.
+                let scale = ls.max(rs);
+                return decimal_result(rescale_decimal(l * r, ls + rs, scale), scale);
+            }
+            if let Some(v) = integer_operands(left, right, values, column_types)
+                .and_then(|(l, r)| l.checked_mul(r))
+            {
+                return ColumnValue::BigInt(v);
+            }
             let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
             let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
             ColumnValue::Double(l * r)
@@ -238,60 +478,333 @@ pub fn evaluate_expression(expr: &Expr, values: &HashMap<String, String>) -> Col
             let r = get_value(right, values).parse::<i32>().unwrap_or(0);
             ColumnValue::Integer(l ^ r)
         }
-        Expr::Between { expr: v, low, high } => {
-            let val = get_value(v, values).parse::<f64>().unwrap_or(0.0);
-            let l = get_value(low, values).parse::<f64>().unwrap_or(0.0);
-            let h = get_value(high, values).parse::<f64>().unwrap_or(0.0);
-            ColumnValue::Boolean(val >= l && val <= h)
+        Expr::Between { expr: v, low, high, negated } => {
+            let within = compare_operands(v, low, values, column_types).is_ge()
+                && compare_operands(v, high, values, column_types).is_le();
+            ColumnValue::Boolean(within != *negated)
         }
         Expr::GreaterThan { left, right } => {
-            let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
-            let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
-            ColumnValue::Boolean(l > r)
+            ColumnValue::Boolean(compare_operands(left, right, values, column_types).is_gt())
         }
         Expr::GreaterOrEquals { left, right } => {
-            let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
-            let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
-            ColumnValue::Boolean(l >= r)
+            ColumnValue::Boolean(compare_operands(left, right, values, column_types).is_ge())
         }
         Expr::LessThan { left, right } => {
-            let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
-            let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
-            ColumnValue::Boolean(l < r)
+            ColumnValue::Boolean(compare_operands(left, right, values, column_types).is_lt())
         }
         Expr::LessOrEquals { left, right } => {
-            let l = get_value(left, values).parse::<f64>().unwrap_or(0.0);
-            let r = get_value(right, values).parse::<f64>().unwrap_or(0.0);
-            ColumnValue::Boolean(l <= r)
+            ColumnValue::Boolean(compare_operands(left, right, values, column_types).is_le())
+        }
+        Expr::IsTruth { expr: v, truth, negated } => {
+            // NULL (or any non-boolean value) is UNKNOWN in three-valued logic.
+            let actual = match get_value(v, values).to_ascii_lowercase().as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            };
+            ColumnValue::Boolean((actual == *truth) != *negated)
+        }
+        Expr::IsNull { expr: v, negated } => {
+            ColumnValue::Boolean((get_value(v, values) == NULL_MARKER) != *negated)
         }
         Expr::FunctionCall { name, args } => {
-            let arg_vals: Vec<ColumnValue> = args.iter().map(|a| evaluate_expression(a, values)).collect();
+            let arg_vals: Vec<ColumnValue> = args
+                .iter()
+                .map(|a| match a {
+                    Expr::Literal(token) => operand_value(token, values, column_types),
+                    _ => evaluate_expression_typed(a, values, column_types),
+                })
+                .collect();
             match crate::sql::functions::FunctionEvaluator::evaluate_function(name, &arg_vals) {
                 Ok(v) => v,
                 Err(_) => ColumnValue::Null,
             }
         }
-        Expr::InSubquery { .. } | Expr::ExistsSubquery { .. } => ColumnValue::Boolean(false),
+        Expr::InSubquery { .. } | Expr::ExistsSubquery { .. } | Expr::ScalarSubquery { .. } => {
+            ColumnValue::Boolean(false)
+        }
         Expr::And(a, b) => {
-            match (evaluate_expression(a, values), evaluate_expression(b, values)) {
-                (ColumnValue::Boolean(l), ColumnValue::Boolean(r)) => ColumnValue::Boolean(l && r),
-                _ => ColumnValue::Boolean(false),
+            match (evaluate_expression_typed(a, values, column_types), evaluate_expression_typed(b, values, column_types)) {
+                (ColumnValue::Boolean(false), _) | (_, ColumnValue::Boolean(false)) => ColumnValue::Boolean(false),
+                (ColumnValue::Boolean(true), ColumnValue::Boolean(true)) => ColumnValue::Boolean(true),
+                _ => ColumnValue::Null,
             }
         }
         Expr::Or(a, b) => {
-            match (evaluate_expression(a, values), evaluate_expression(b, values)) {
-                (ColumnValue::Boolean(l), ColumnValue::Boolean(r)) => ColumnValue::Boolean(l || r),
-                _ => ColumnValue::Boolean(false),
+            match (evaluate_expression_typed(a, values, column_types), evaluate_expression_typed(b, values, column_types)) {
+                (ColumnValue::Boolean(true), _) | (_, ColumnValue::Boolean(true)) => ColumnValue::Boolean(true),
+                (ColumnValue::Boolean(false), ColumnValue::Boolean(false)) => ColumnValue::Boolean(false),
+                _ => ColumnValue::Null,
+            }
+        }
+        Expr::Value(v) => v.clone(),
+        Expr::Null => ColumnValue::Null,
+        Expr::Subquery(_) | Expr::Literal(_) | Expr::FunctionCall { .. } | Expr::DefaultValue | Expr::Placeholder(_) => ColumnValue::Boolean(false),
+    }
+}
+
+/// Prefix of an operand bound to a `?` parameter. No column name starts with
+/// it, so a bound value is never read as a column reference, whatever its
+/// text.
+pub const PARAM_MARKER: char = '\u{1}';
+
+/// The literal text of operand `token`, without its [`PARAM_MARKER`].
+pub fn literal_operand(token: &str) -> &str {
+    token.strip_prefix(PARAM_MARKER).unwrap_or(token)
+}
+
+/// The value operand `token` stands for: that of the column it names in
+/// `values`, or else the token itself as a literal.
+pub fn resolve_operand<'a>(token: &'a str, values: &'a HashMap<String, String>) -> &'a str {
+    values.get(token).map_or_else(|| literal_operand(token), String::as_str)
+}
+
+/// The type both comparison operands are read as: that of the operand naming
+/// a column, or the left column's when both do. A DATE compared with a
+/// DATETIME or TIMESTAMP column is read as the latter, so the date means
+/// midnight.
+fn comparison_type(
+    left: &str,
+    right: &str,
+    column_types: &HashMap<String, ColumnType>,
+) -> Option<ColumnType> {
+    match (column_types.get(left), column_types.get(right)) {
+        (Some(ColumnType::Date), Some(ty @ (ColumnType::DateTime | ColumnType::Timestamp)))
+        | (Some(ty @ (ColumnType::DateTime | ColumnType::Timestamp)), Some(ColumnType::Date)) => Some(*ty),
+        (l, r) => l.or(r).copied(),
+    }
+}
+
+/// Order the comparison operands `left` and `right`. When either names a
+/// column in `column_types`, both are read as the [`comparison_type`]: TEXT,
+/// CHAR and VARCHAR compare lexicographically, DATE, DATETIME, TIMESTAMP,
+/// TIME and YEAR chronologically, and everything else numerically. Untyped
+/// operands compare numerically when both parse as numbers and as text
+/// otherwise.
+pub fn compare_operands(
+    left: &str,
+    right: &str,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> std::cmp::Ordering {
+    let l = resolve_operand(left, values);
+    let r = resolve_operand(right, values);
+    let ordering = match comparison_type(left, right, column_types) {
+        Some(ColumnType::Text | ColumnType::Char(_) | ColumnType::Varchar(_)) => Some(l.cmp(r)),
+        _ => temporal_ordering(left, right, values, column_types),
+    };
+    ordering.unwrap_or_else(|| match (l.parse::<f64>(), r.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => l.cmp(r),
+    })
+}
+
+/// The integer a DATE, DATETIME, TIMESTAMP, TIME or YEAR column stores for
+/// `value`: days, seconds since the epoch, seconds, or the year. A DATETIME
+/// also accepts a bare date, read as midnight. `None` for other column types
+/// or when `value` does not parse.
+pub fn temporal_key(ty: &ColumnType, value: &str) -> Option<i64> {
+    use crate::storage::row::{parse_date, parse_datetime, parse_time, parse_year};
+    match ty {
+        ColumnType::Date => parse_date(value).map(i64::from),
+        ColumnType::DateTime | ColumnType::Timestamp => parse_datetime(value)
+            .or_else(|| parse_date(value).map(|days| i64::from(days) * 86_400)),
+        ColumnType::Time => parse_time(value).map(i64::from),
+        ColumnType::Year => parse_year(value).map(i64::from),
+        _ => None,
+    }
+}
+
+/// Order `left` and `right` chronologically when either names a temporal
+/// column, comparing both as the [`comparison_type`]'s stored integer (see
+/// [`temporal_key`]) rather than as text or floats.
+pub fn temporal_ordering(
+    left: &str,
+    right: &str,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> Option<std::cmp::Ordering> {
+    let ty = comparison_type(left, right, column_types)?;
+    let l = resolve_operand(left, values);
+    let r = resolve_operand(right, values);
+    Some(temporal_key(&ty, l)?.cmp(&temporal_key(&ty, r)?))
+}
+
+/// `=` on two operands: chronological for temporal columns, so
+/// `at = '2024-01-01'` matches a DATETIME at midnight, numeric for numeric
+/// columns, so a DECIMAL `1.50` equals a DOUBLE `1.5`, by truth value for
+/// BOOLEAN columns, so `active = TRUE` and `active = 1` match a stored `true`,
+/// and textual otherwise. Like [`compare_operands`], untyped operands are
+/// equal numerically when both parse as numbers.
+pub fn operands_equal(
+    left: &str,
+    right: &str,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> bool {
+    if let Some(ordering) = temporal_ordering(left, right, values, column_types) {
+        return ordering.is_eq();
+    }
+    let l = resolve_operand(left, values);
+    let r = resolve_operand(right, values);
+    let numeric = match comparison_type(left, right, column_types) {
+        Some(ColumnType::Boolean) => {
+            if let (Some(a), Some(b)) = (boolean_operand(l), boolean_operand(r)) {
+                return a == b;
             }
+            false
         }
-        Expr::Subquery(_) | Expr::Literal(_) | Expr::FunctionCall { .. } | Expr::DefaultValue => ColumnValue::Boolean(false),
+        Some(ty) => ty.is_numeric(),
+        None => true,
+    };
+    if numeric
+        && let (Ok(a), Ok(b)) = (l.parse::<f64>(), r.parse::<f64>())
+        && a == b
+    {
+        return true;
     }
+    l == r
+}
+
+/// The truth value of a BOOLEAN operand: `true`/`false` in any case, or
+/// `1`/`0`.
+fn boolean_operand(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") || value == "1" {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") || value == "0" {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Read a function argument token as a typed value: a column of a temporal
+/// type keeps its stored integer form, and bare literals become numbers when
+/// they parse as one and text otherwise.
+fn operand_value(
+    token: &str,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> ColumnValue {
+    let value = resolve_operand(token, values);
+    if value == NULL_MARKER {
+        return ColumnValue::Null;
+    }
+    match column_types.get(token) {
+        Some(ty @ ColumnType::Date) => temporal_key(ty, value).map(|d| ColumnValue::Date(d as i32)),
+        Some(ty @ ColumnType::DateTime) => temporal_key(ty, value).map(ColumnValue::DateTime),
+        Some(ty @ ColumnType::Timestamp) => temporal_key(ty, value).map(ColumnValue::Timestamp),
+        _ => None,
+    }
+    .or_else(|| value.parse::<i64>().ok().map(ColumnValue::BigInt))
+    .or_else(|| value.parse::<f64>().ok().map(ColumnValue::Double))
+    .unwrap_or_else(|| ColumnValue::Text(value.to_string()))
+}
+
+/// Functions a comparison or arithmetic operand may call, as in
+/// `WHERE GREATEST(a, b) > 10`.
+const OPERAND_FUNCTIONS: &[&str] = &["GREATEST", "LEAST"];
+
+/// Evaluate each operand of `expr` that calls one of [`OPERAND_FUNCTIONS`]
+/// and bind the result under the operand's own text, so the comparison reads
+/// it like a column value. `None` when there is nothing left to bind.
+pub fn bind_function_operands(
+    expr: &Expr,
+    values: &HashMap<String, String>,
+    column_types: &HashMap<String, ColumnType>,
+) -> Option<HashMap<String, String>> {
+    let calls: Vec<_> = operand_tokens(expr)
+        .into_iter()
+        .filter(|token| !values.contains_key(*token))
+        .filter_map(|token| operand_call(token).map(|(name, args)| (token, name, args)))
+        .collect();
+    if calls.is_empty() {
+        return None;
+    }
+    let mut bound = values.clone();
+    for (token, name, args) in calls {
+        let args: Vec<ColumnValue> = args
+            .iter()
+            .map(|arg| match arg.strip_prefix('\'').and_then(|a| a.strip_suffix('\'')) {
+                Some(text) => ColumnValue::Text(text.replace("''", "'")),
+                None => operand_value(arg, values, column_types),
+            })
+            .collect();
+        let value = crate::sql::functions::FunctionEvaluator::evaluate_function(&name, &args)
+            .unwrap_or(ColumnValue::Null);
+        bound.insert(token.clone(), value.to_predicate_string());
+    }
+    Some(bound)
+}
+
+/// Split an operand such as `GREATEST(a, 'b')` into the upper-cased function
+/// name and its argument tokens; `None` unless it calls an operand function.
+fn operand_call(token: &str) -> Option<(String, Vec<String>)> {
+    let (name, rest) = token.split_once('(')?;
+    let name = name.trim().to_uppercase();
+    if !OPERAND_FUNCTIONS.contains(&name.as_str()) {
+        return None;
+    }
+    let inner = rest.trim_end().strip_suffix(')')?;
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quote = false;
+    for ch in inner.chars() {
+        match ch {
+            '\'' => {
+                in_quote = !in_quote;
+                current.push(ch);
+            }
+            ',' if !in_quote => args.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(ch),
+        }
+    }
+    args.push(current.trim().to_string());
+    Some((name, args))
+}
+
+/// Tokens `expr` reads as comparison or arithmetic operands.
+pub(crate) fn operand_tokens(expr: &Expr) -> Vec<&String> {
+    match expr {
+        Expr::Equals { left, right }
+        | Expr::NotEquals { left, right }
+        | Expr::Add { left, right }
+        | Expr::Subtract { left, right }
+        | Expr::Multiply { left, right }
+        | Expr::Divide { left, right }
+        | Expr::Modulo { left, right }
+        | Expr::BitwiseAnd { left, right }
+        | Expr::BitwiseOr { left, right }
+        | Expr::BitwiseXor { left, right }
+        | Expr::GreaterThan { left, right }
+        | Expr::GreaterOrEquals { left, right }
+        | Expr::LessThan { left, right }
+        | Expr::LessOrEquals { left, right } => vec![left, right],
+        Expr::Between { expr, low, high, .. } => vec![expr, low, high],
+        Expr::InSubquery { left, .. } | Expr::ScalarSubquery { left, .. } => vec![left],
+        _ => Vec::new(),
+    }
+}
+
+/// True when a comparison or arithmetic operand of `expr` is the `NULL`
+/// keyword or resolves to NULL.
+pub fn null_operand(expr: &Expr, values: &HashMap<String, String>) -> bool {
+    operand_tokens(expr)
+        .into_iter()
+        .any(|t| t == NULL_MARKER || values.get(t).map(String::as_str) == Some(NULL_MARKER))
 }
 
 pub fn expr_to_string(expr: &Expr) -> String {
     match expr {
         Expr::Literal(s) => s.clone(),
         Expr::DefaultValue => "DEFAULT".into(),
+        Expr::Null => NULL_MARKER.into(),
+        Expr::Value(v) => v.to_predicate_string(),
+        Expr::Add { left, right } => format!("{} + {}", left, right),
+        Expr::Subtract { left, right } => format!("{} - {}", left, right),
+        Expr::Multiply { left, right } => format!("{} * {}", left, right),
+        Expr::Divide { left, right } => format!("{} / {}", left, right),
+        Expr::Modulo { left, right } => format!("{} % {}", left, right),
         Expr::FunctionCall { name, args } => {
             if args.is_empty() {
                 format!("{}{}", name, if name.ends_with("()") { "" } else { "()" })
@@ -304,15 +817,221 @@ pub fn expr_to_string(expr: &Expr) -> String {
     }
 }
 
+/// The 1-based position of a `?n` parameter token.
+fn placeholder_index(token: &str) -> Option<usize> {
+    token.strip_prefix('?')?.parse().ok().filter(|n| *n > 0)
+}
+
+/// Number of parameters `stmt` expects: the highest `?n` it contains.
+pub fn placeholder_count(stmt: &Statement) -> usize {
+    let mut count = 0;
+    let _ = visit_placeholders(&mut stmt.clone(), &mut |n| {
+        count = count.max(n);
+        Ok(ColumnValue::Null)
+    });
+    count
+}
+
+/// Substitute `params` for the placeholders of `stmt`; `?n` takes
+/// `params[n - 1]`. INSERT values receive the typed value itself and UPDATE
+/// assignments its predicate string. Other operands receive the predicate
+/// string behind a [`PARAM_MARKER`], so a value spelled like a column name is
+/// still compared as a value.
+pub fn bind_placeholders(stmt: &Statement, params: &[ColumnValue]) -> Result<Statement, String> {
+    let mut bound = stmt.clone();
+    visit_placeholders(&mut bound, &mut |n| {
+        params
+            .get(n - 1)
+            .cloned()
+            .ok_or_else(|| format!("No value bound for parameter ?{}", n))
+    })?;
+    Ok(bound)
+}
+
+type BindFn<'a> = dyn FnMut(usize) -> Result<ColumnValue, String> + 'a;
+
+fn visit_placeholders(stmt: &mut Statement, bind: &mut BindFn) -> Result<(), String> {
+    match stmt {
+        Statement::Insert { rows, .. } | Statement::Values(rows) => {
+            for expr in rows.iter_mut().flatten() {
+                visit_expr_placeholders(expr, bind)?;
+            }
+        }
+        Statement::Select {
+            columns,
+            from,
+            joins,
+            where_predicate,
+            having,
+            ..
+        } => {
+            for column in columns {
+                match &mut column.expr {
+                    SelectItem::Expr(expr) => visit_expr_placeholders(expr, bind)?,
+                    SelectItem::Subquery(query) => visit_placeholders(query, bind)?,
+                    _ => {}
+                }
+            }
+            for table in from {
+                if let TableRef::Subquery { query, .. } = table {
+                    visit_placeholders(query, bind)?;
+                }
+            }
+            for join in joins {
+                if let Some(expr) = &mut join.predicate {
+                    visit_expr_placeholders(expr, bind)?;
+                }
+                if let Some(query) = &mut join.subquery {
+                    visit_placeholders(query, bind)?;
+                }
+            }
+            for expr in [where_predicate, having].into_iter().flatten() {
+                visit_expr_placeholders(expr, bind)?;
+            }
+        }
+        Statement::SetOp { left, right, .. } => {
+            visit_placeholders(left, bind)?;
+            visit_placeholders(right, bind)?;
+        }
+        Statement::Update {
+            assignments,
+            selection,
+            ..
+        } => {
+            for (_, value) in assignments {
+                if let Some(n) = placeholder_index(value) {
+                    *value = bind(n)?.to_predicate_string();
+                }
+            }
+            if let Some(expr) = selection {
+                visit_expr_placeholders(expr, bind)?;
+            }
+        }
+        Statement::Delete {
+            selection: Some(expr),
+            ..
+        } => visit_expr_placeholders(expr, bind)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn visit_operand_placeholder(token: &mut String, bind: &mut BindFn) -> Result<(), String> {
+    if let Some(n) = placeholder_index(token) {
+        *token = match bind(n)? {
+            ColumnValue::Null => NULL_MARKER.to_string(),
+            value => format!("{}{}", PARAM_MARKER, value.to_predicate_string()),
+        };
+    }
+    Ok(())
+}
+
+fn visit_expr_placeholders(expr: &mut Expr, bind: &mut BindFn) -> Result<(), String> {
+    match expr {
+        Expr::Placeholder(n) => *expr = Expr::Value(bind(*n)?),
+        Expr::Equals { left, right }
+        | Expr::NotEquals { left, right }
+        | Expr::Add { left, right }
+        | Expr::Subtract { left, right }
+        | Expr::Multiply { left, right }
+        | Expr::Divide { left, right }
+        | Expr::Modulo { left, right }
+        | Expr::BitwiseAnd { left, right }
+        | Expr::BitwiseOr { left, right }
+        | Expr::BitwiseXor { left, right }
+        | Expr::GreaterThan { left, right }
+        | Expr::GreaterOrEquals { left, right }
+        | Expr::LessThan { left, right }
+        | Expr::LessOrEquals { left, right } => {
+            visit_operand_placeholder(left, bind)?;
+            visit_operand_placeholder(right, bind)?;
+        }
+        Expr::Between { expr, low, high, .. } => {
+            for token in [expr, low, high] {
+                visit_operand_placeholder(token, bind)?;
+            }
+        }
+        Expr::IsTruth { expr, .. } | Expr::IsNull { expr, .. } => {
+            visit_operand_placeholder(expr, bind)?
+        }
+        Expr::InSubquery { left, query } | Expr::ScalarSubquery { left, query, .. } => {
+            visit_operand_placeholder(left, bind)?;
+            visit_placeholders(query, bind)?;
+        }
+        Expr::ExistsSubquery { query } | Expr::Subquery(query) => {
+            visit_placeholders(query, bind)?
+        }
+        Expr::And(a, b) | Expr::Or(a, b) => {
+            visit_expr_placeholders(a, bind)?;
+            visit_expr_placeholders(b, bind)?;
+        }
+        Expr::Literal(token) => visit_operand_placeholder(token, bind)?,
+        Expr::FunctionCall { args, .. } => {
+            for arg in args {
+                visit_expr_placeholders(arg, bind)?;
+            }
+        }
+        Expr::DefaultValue | Expr::Null | Expr::Value(_) => {}
+    }
+    Ok(())
+}
+
 pub fn parse_default_expr(s: &str) -> Expr {
     let upper = s.to_ascii_uppercase();
     if upper == "CURRENT_TIMESTAMP" || upper == "CURRENT_TIMESTAMP()" {
         Expr::FunctionCall { name: "CURRENT_TIMESTAMP".into(), args: Vec::new() }
+    } else if upper == "NOW()" {
+        Expr::FunctionCall { name: "NOW".into(), args: Vec::new() }
+    } else if upper == "CURRENT_DATE" || upper == "CURRENT_DATE()" {
+        Expr::FunctionCall { name: "CURRENT_DATE".into(), args: Vec::new() }
     } else if upper == "GETDATE()" {
         Expr::FunctionCall { name: "GETDATE".into(), args: Vec::new() }
     } else if upper == "GETUTCDATE()" {
         Expr::FunctionCall { name: "GETUTCDATE".into(), args: Vec::new() }
+    } else if let Some(text) = s.strip_prefix('\'').and_then(|s| s.strip_suffix('\''))
+        && text.starts_with('(')
+    {
+        Expr::Literal(text.to_string())
+    } else if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        crate::sql::parser::parse_constant_arithmetic(inner)
+            .unwrap_or_else(|| Expr::Literal(s.to_string()))
     } else {
         Expr::Literal(s.to_string())
     }
 }
+
+/// Whether `expr` is arithmetic on numeric literals and parenthesized
+/// constant arithmetic, the form a parenthesized default such as
+/// `DEFAULT (60 * 60)` or `DEFAULT (2 * (3 + 1))` may take.
+pub fn is_constant_arithmetic(expr: &Expr) -> bool {
+    match expr {
+        Expr::Add { left, right }
+        | Expr::Subtract { left, right }
+        | Expr::Multiply { left, right }
+        | Expr::Divide { left, right }
+        | Expr::Modulo { left, right } => {
+            is_constant_operand(left) && is_constant_operand(right)
+        }
+        _ => false,
+    }
+}
+
+fn is_constant_operand(operand: &str) -> bool {
+    match operand.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => crate::sql::parser::parse_constant_arithmetic(inner)
+            .is_some_and(|expr| is_constant_arithmetic(&expr)),
+        None => operand.parse::<f64>().is_ok(),
+    }
+}
+
+/// The text a column default is stored and shown as: that of
+/// [`expr_to_string`], with arithmetic parenthesized so
+/// [`parse_default_expr`] reads it back as an expression, and text that
+/// starts with `(` quoted so it reads back as text.
+pub fn default_expr_to_string(expr: &Expr) -> String {
+    match expr {
+        Expr::Literal(s) if s.starts_with('(') => format!("'{}'", s),
+        _ if is_constant_arithmetic(expr) => format!("({})", expr_to_string(expr)),
+        _ => expr_to_string(expr),
+    }
+}
@@ -1,14 +1,24 @@
 use chrono::{Local, Utc};
-use crate::storage::row::ColumnValue;
+use crate::storage::row::{ColumnType, ColumnValue, parse_date, parse_datetime};
 
 pub enum EvalError {
     UnknownFunction(String),
     InvalidArgumentCount,
+    InvalidArgument,
 }
 
 pub struct FunctionEvaluator;
 
 impl FunctionEvaluator {
+    /// Type of the value `name` returns, when it does not depend on the arguments.
+    pub fn return_type(name: &str) -> Option<ColumnType> {
+        match name.to_uppercase().as_str() {
+            "CURRENT_TIMESTAMP" | "GETDATE" | "GETUTCDATE" | "NOW" => Some(ColumnType::DateTime),
+            "CURRENT_DATE" => Some(ColumnType::Date),
+            _ => None,
+        }
+    }
+
     pub fn evaluate_function(name: &str, args: &[ColumnValue]) -> Result<ColumnValue, EvalError> {
         match name.to_uppercase().as_str() {
             "CURRENT_TIMESTAMP" | "GETDATE" => {
@@ -17,12 +27,79 @@ impl FunctionEvaluator {
                 }
                 Ok(ColumnValue::DateTime(Local::now().timestamp()))
             }
+            "NOW" => {
+                if !args.is_empty() {
+                    return Err(EvalError::InvalidArgumentCount);
+                }
+                Ok(ColumnValue::DateTime(Local::now().timestamp()))
+            }
+            "CURRENT_DATE" => {
+                if !args.is_empty() {
+                    return Err(EvalError::InvalidArgumentCount);
+                }
+                // The date part of CURRENT_TIMESTAMP, in days since the epoch.
+                Ok(ColumnValue::Date(
+                    Local::now().timestamp().div_euclid(86_400) as i32,
+                ))
+            }
             "GETUTCDATE" => {
                 if !args.is_empty() {
                     return Err(EvalError::InvalidArgumentCount);
                 }
                 Ok(ColumnValue::DateTime(Utc::now().timestamp()))
             }
+            "DATE_ADD" | "DATE_SUB" => {
+                let [base, days] = args else {
+                    return Err(EvalError::InvalidArgumentCount);
+                };
+                let days = match days {
+                    ColumnValue::Integer(n) => *n as i64,
+                    ColumnValue::BigInt(n) => *n,
+                    ColumnValue::Null => return Ok(ColumnValue::Null),
+                    _ => return Err(EvalError::InvalidArgument),
+                };
+                let days = if name.eq_ignore_ascii_case("DATE_SUB") {
+                    -days
+                } else {
+                    days
+                };
+                // Dates move by whole days, date-times by 86 400 seconds a day.
+                Ok(match base {
+                    ColumnValue::Date(d) => ColumnValue::Date(d + days as i32),
+                    ColumnValue::DateTime(ts) => ColumnValue::DateTime(ts + days * 86_400),
+                    ColumnValue::Timestamp(ts) => ColumnValue::Timestamp(ts + days * 86_400),
+                    ColumnValue::Text(s) => match (parse_date(s), parse_datetime(s)) {
+                        (Some(d), _) => ColumnValue::Date(d + days as i32),
+                        (None, Some(ts)) => ColumnValue::DateTime(ts + days * 86_400),
+                        (None, None) => ColumnValue::Null,
+                    },
+                    _ => ColumnValue::Null,
+                })
+            }
+            "GREATEST" | "LEAST" => {
+                if args.is_empty() {
+                    return Err(EvalError::InvalidArgumentCount);
+                }
+                if args.iter().any(|a| matches!(a, ColumnValue::Null)) {
+                    return Ok(ColumnValue::Null);
+                }
+                // Numbers compare numerically; once any argument is not a
+                // number, every argument compares as text.
+                let numeric = args.iter().all(|a| a.as_f64().is_some());
+                let order = |a: &&ColumnValue, b: &&ColumnValue| {
+                    if numeric {
+                        a.compare(b)
+                    } else {
+                        a.to_string_value().cmp(&b.to_string_value())
+                    }
+                };
+                let pick = if name.eq_ignore_ascii_case("GREATEST") {
+                    args.iter().max_by(order)
+                } else {
+                    args.iter().min_by(order)
+                };
+                Ok(pick.cloned().unwrap_or(ColumnValue::Null))
+            }
             _ => Err(EvalError::UnknownFunction(name.to_string())),
         }
     }
@@ -1,5 +1,6 @@
-use crate::sql::ast::{Expr, Statement, OrderBy, ForeignKey, Action, ColumnDef};
+use crate::sql::ast::{Expr, Statement, OrderBy, ForeignKey, Action, ColumnDef, SetOp};
 use crate::storage::row::ColumnType;
+use crate::transaction::IsolationLevel;
 
 fn tokenize(input: &str) -> Result<Vec<String>, String> {
     let mut tokens = Vec::new();
@@ -10,8 +11,18 @@ fn tokenize(input: &str) -> Result<Vec<String>, String> {
     while let Some(ch) = chars.next() {
         if let Some(quote) = in_quote {
             current.push(ch);
+            // A doubled quote is an escaped quote inside the literal; it stays
+            // doubled in the token and `unquote_token` collapses it.
+            if ch == quote && chars.peek() == Some(&quote) {
+                current.push(quote);
+                chars.next();
+                continue;
+            }
             if ch == quote {
                 in_quote = None;
+                if hex_literal(&current).is_some() {
+                    current.make_ascii_uppercase();
+                }
                 tokens.push(current.clone());
                 current.clear();
             }
@@ -20,7 +31,9 @@ fn tokenize(input: &str) -> Result<Vec<String>, String> {
 
         match ch {
             '\'' | '"' => {
-                if !current.is_empty() {
+                // Keep the prefix of a hex literal (`X'..'`) in the same token.
+                let hex_prefix = ch == '\'' && current.eq_ignore_ascii_case("X");
+                if !current.is_empty() && !hex_prefix {
                     tokens.push(current.clone());
                     current.clear();
                 }
@@ -95,15 +108,64 @@ fn tokenize(input: &str) -> Result<Vec<String>, String> {
     if !current.is_empty() {
         tokens.push(current);
     }
-    Ok(tokens)
+    Ok(merge_qualified_identifiers(tokens))
+}
+
+/// Whether `token` is a `"quoted"` identifier.
+fn is_quoted_identifier(token: &str) -> bool {
+    token.len() >= 2 && token.starts_with('"') && token.ends_with('"')
+}
+
+/// Join a qualifier and a column split by quoting, as in `t."order"`,
+/// `"my table"."order"`, or `"my table".id`, into one quoted token holding
+/// the dotted name.
+fn merge_qualified_identifiers(tokens: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let merged = match out.last() {
+            Some(last) if is_quoted_identifier(&token) && last == "." => {
+                let prev = out.len().checked_sub(2).map(|idx| &out[idx]);
+                match prev {
+                    Some(prev) if is_quoted_identifier(prev) => {
+                        let name = format!("\"{}.{}\"", unquote_token(prev), unquote_token(&token));
+                        out.truncate(out.len() - 2);
+                        Some(name)
+                    }
+                    _ => None,
+                }
+            }
+            Some(last) if is_quoted_identifier(&token) && last.ends_with('.') && is_identifier_token(last) => {
+                let name = format!("\"{}{}\"", last, unquote_token(&token));
+                out.pop();
+                Some(name)
+            }
+            Some(last) if token.len() > 1 && token.starts_with('.') && is_quoted_identifier(last) => {
+                let name = format!("\"{}{}\"", unquote_token(last), token);
+                out.pop();
+                Some(name)
+            }
+            _ => None,
+        };
+        out.push(merged.unwrap_or(token));
+    }
+    out
 }
 
 fn split_top_level(s: &str) -> Vec<String> {
     let mut parts = Vec::new();
     let mut current = String::new();
     let mut depth = 0;
+    let mut in_quote: Option<char> = None;
     for ch in s.chars() {
+        if let Some(quote) = in_quote {
+            if ch == quote {
+                in_quote = None;
+            }
+            current.push(ch);
+            continue;
+        }
         match ch {
+            '\'' | '"' => { in_quote = Some(ch); current.push(ch); }
             '(' => { depth += 1; current.push(ch); }
             ')' => { depth -= 1; current.push(ch); }
             ',' if depth == 0 => {
@@ -148,12 +210,113 @@ fn split_top_level_tokens(tokens: &[String]) -> Vec<Vec<String>> {
     parts
 }
 
-fn unquote_token(token: &str) -> &str {
-    if (token.starts_with('"') && token.ends_with('"')) || (token.starts_with('\'') && token.ends_with('\'')) {
-        &token[1..token.len() - 1]
-    } else {
-        token
+/// Arguments of `DATE_ADD(base, n)` or `DATE_SUB(base, n)`: a column or
+/// date literal, then a day count optionally written `INTERVAL n DAY`.
+fn parse_date_shift_args(tokens: &[String]) -> Result<Vec<crate::sql::ast::Expr>, String> {
+    let parts = split_top_level_tokens(tokens);
+    let [base, amount] = parts.as_slice() else {
+        return Err("DATE_ADD expects a date and a number of days".into());
+    };
+    let [base] = base.as_slice() else {
+        return Err("DATE_ADD expects a column or date literal".into());
+    };
+    let mut amount: Vec<&str> = amount.iter().map(String::as_str).collect();
+    if amount.first().is_some_and(|t| t.eq_ignore_ascii_case("INTERVAL")) {
+        amount.remove(0);
+    }
+    if amount.last().is_some_and(|t| t.eq_ignore_ascii_case("DAY") || t.eq_ignore_ascii_case("DAYS")) {
+        amount.pop();
+    }
+    let days = amount.concat();
+    if days.parse::<i64>().is_err() {
+        return Err(format!("Invalid day count '{}' in DATE_ADD", days));
+    }
+    Ok(vec![
+        crate::sql::ast::Expr::Literal(unquote_token(base).to_string()),
+        crate::sql::ast::Expr::Literal(days),
+    ])
+}
+
+/// Arguments of `GREATEST(a, b, ...)` or `LEAST(a, b, ...)`: one or more
+/// columns, literals, or nested `GREATEST`/`LEAST` calls. A quoted literal
+/// stays text even if it names a column.
+fn parse_extremum_args(name: &str, tokens: &[String]) -> Result<Vec<crate::sql::ast::Expr>, String> {
+    if tokens.is_empty() {
+        return Err(format!("{} expects at least one argument", name));
+    }
+    split_top_level_tokens(tokens)
+        .iter()
+        .map(|part| match part.as_slice() {
+            [token] if token.starts_with('\'') => Ok(crate::sql::ast::Expr::Value(
+                crate::storage::row::ColumnValue::Text(unquote_token(token).to_string()),
+            )),
+            [token] => Ok(crate::sql::ast::Expr::Literal(unquote_token(token).to_string())),
+            [func, open, args @ .., close]
+                if open == "("
+                    && close == ")"
+                    && (func.eq_ignore_ascii_case("GREATEST") || func.eq_ignore_ascii_case("LEAST")) =>
+            {
+                let func = func.to_uppercase();
+                let args = parse_extremum_args(&func, args)?;
+                Ok(crate::sql::ast::Expr::FunctionCall { name: func, args })
+            }
+            // A signed number arrives as a sign token and the digits.
+            tokens if join_tokens(tokens).replace(' ', "").parse::<f64>().is_ok() => {
+                Ok(crate::sql::ast::Expr::Literal(join_tokens(tokens).replace(' ', "")))
+            }
+            _ => Err(format!("{} expects columns or literals", name)),
+        })
+        .collect()
+}
+
+/// A non-negative row count for LIMIT, OFFSET, or FETCH.
+fn parse_row_count(raw: &str, clause: &str) -> Result<usize, String> {
+    if raw.starts_with('-') {
+        return Err(format!("{} must not be negative", clause));
+    }
+    raw.parse::<usize>().map_err(|_| format!("Invalid {} value", clause))
+}
+
+/// A comparison or arithmetic operand as stored in an [`Expr`]: unquoted, or
+/// [`NULL_MARKER`] for the `NULL` keyword so it differs from the text 'NULL'.
+///
+/// [`NULL_MARKER`]: crate::storage::row::NULL_MARKER
+fn operand_text(token: &str) -> String {
+    let token = token.trim_end_matches(';');
+    if token.eq_ignore_ascii_case("NULL") {
+        return crate::storage::row::NULL_MARKER.to_string();
+    }
+    unquote_token(token).into_owned()
+}
+
+fn is_row_keyword(token: &str) -> bool {
+    token.eq_ignore_ascii_case("ROW") || token.eq_ignore_ascii_case("ROWS")
+}
+
+/// The text of a token with its quotes removed. Inside the quotes a doubled
+/// quote character (`'it''s'`) stands for one.
+fn unquote_token(token: &str) -> std::borrow::Cow<'_, str> {
+    if let Some(hex) = hex_literal(token) {
+        return hex.into();
+    }
+    for quote in ['"', '\''] {
+        if token.len() >= 2 && token.starts_with(quote) && token.ends_with(quote) {
+            let inner = &token[1..token.len() - 1];
+            let doubled = [quote, quote].iter().collect::<String>();
+            return if inner.contains(&doubled) {
+                inner.replace(&doubled, &quote.to_string()).into()
+            } else {
+                inner.into()
+            };
+        }
     }
+    token.into()
+}
+
+/// The hex digits of an `X'..'` literal token. The tokenizer uppercases these
+/// so they compare equal to the rendering of a BLOB value.
+fn hex_literal(token: &str) -> Option<&str> {
+    token.strip_prefix(['X', 'x'])?.strip_prefix('\'')?.strip_suffix('\'')
 }
 
 fn is_identifier_token(token: &str) -> bool {
@@ -169,6 +332,15 @@ fn is_identifier_token(token: &str) -> bool {
     token.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.')
 }
 
+/// Whether `token` is a number literal such as `7`, `-5` or `-2.5`; the
+/// tokenizer keeps a `-` that directly precedes a digit on the number.
+fn is_number_token(token: &str) -> bool {
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+        && digits.parse::<f64>().is_ok()
+}
+
 fn is_operator_token(token: &str) -> bool {
     matches!(
         token.to_uppercase().as_str(),
@@ -176,6 +348,64 @@ fn is_operator_token(token: &str) -> bool {
     )
 }
 
+/// Parse the `(v, ...), (v, ...)` tuples after `VALUES` in an INSERT or a
+/// standalone VALUES statement.
+fn parse_value_rows(tokens: &[String]) -> Result<Vec<Vec<Expr>>, String> {
+    // Split on the tokens rather than the rejoined text so commas and
+    // parentheses inside quoted values stay part of the value.
+    let tuples = split_top_level_tokens(tokens);
+    if tuples.is_empty() { return Err("Missing values".into()); }
+
+    let mut rows = Vec::new();
+    for tup in tuples {
+        if tup.len() < 2 || tup[0] != "(" || tup[tup.len() - 1] != ")" {
+            return Err("Values must be in parentheses".to_string());
+        }
+        let vals: Vec<Expr> = split_top_level_tokens(&tup[1..tup.len() - 1])
+            .into_iter()
+            .map(|parts| {
+                let v = join_tokens(&parts);
+                let v = v.as_str();
+                if v.eq_ignore_ascii_case("DEFAULT") {
+                    Expr::DefaultValue
+                } else if parts.len() == 1 && v.eq_ignore_ascii_case("NULL") {
+                    Expr::Null
+                } else if let Some(n) = v.strip_prefix('?').and_then(|n| n.parse().ok()) {
+                    Expr::Placeholder(n)
+                } else if let Some(hex) = hex_literal(v) {
+                    Expr::Literal(hex.to_string())
+                } else if parts.len() == 1 && (v.starts_with('"') || v.starts_with('\'')) {
+                    Expr::Literal(unquote_token(v).into_owned())
+                } else {
+                    Expr::Literal(v.to_string())
+                }
+            })
+            .collect();
+        if vals.is_empty() {
+            return Err("At least one value required".to_string());
+        }
+        rows.push(vals);
+    }
+    Ok(rows)
+}
+
+/// Parse the parenthesized query starting at `tokens[idx]`, as used for a
+/// derived table in FROM or JOIN. Returns it and the index after `)`.
+fn parse_derived_table(tokens: &[String], idx: usize) -> Result<(Statement, usize), String> {
+    let mut depth = tokens[idx].matches('(').count() as i32 - tokens[idx].matches(')').count() as i32;
+    let mut end = idx;
+    while depth > 0 {
+        end += 1;
+        if end >= tokens.len() { return Err("Unclosed subquery".into()); }
+        depth += tokens[end].matches('(').count() as i32 - tokens[end].matches(')').count() as i32;
+    }
+    let sub_tokens = join_tokens(&tokens[idx..=end]);
+    // Strip only the outer pair so a trailing `(...)` inside the query survives.
+    let inner = sub_tokens.strip_prefix('(').unwrap_or(&sub_tokens);
+    let inner = inner.strip_suffix(')').unwrap_or(inner);
+    Ok((parse_statement(inner)?, end + 1))
+}
+
 fn join_tokens(tokens: &[String]) -> String {
     let mut out = String::new();
     let mut prev: Option<&str> = None;
@@ -238,6 +468,54 @@ fn parse_column_def(chunk: &str) -> Result<ColumnDef, String> {
         return Err("Column definitions must be <name> <type>".to_string());
     }
     let name = unquote_token(&parts.remove(0)).to_string();
+    let mut generated = None;
+    if let Some(pos) = parts.iter().position(|s| s.eq_ignore_ascii_case("GENERATED")) {
+        if parts.len() < pos + 4
+            || !parts[pos + 1].eq_ignore_ascii_case("ALWAYS")
+            || !parts[pos + 2].eq_ignore_ascii_case("AS")
+            || parts[pos + 3] != "("
+        {
+            return Err("Expected GENERATED ALWAYS AS (expr)".into());
+        }
+        let mut depth = 0i32;
+        let mut close = None;
+        for (idx, token) in parts.iter().enumerate().skip(pos + 3) {
+            if token == "(" {
+                depth += 1;
+            } else if token == ")" {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(idx);
+                    break;
+                }
+            }
+        }
+        let close = close.ok_or("Unclosed generated column expression")?;
+        let (expr, used) = parse_expression(&parts[pos + 4..close])?;
+        if pos + 4 + used != close {
+            return Err("Unexpected tokens in generated column expression".into());
+        }
+        generated = Some(expr);
+        let mut end = close + 1;
+        if end < parts.len() && parts[end].eq_ignore_ascii_case("STORED") {
+            end += 1;
+        }
+        parts.drain(pos..end);
+        if parts.is_empty() {
+            // The type may be omitted; arithmetic results are DOUBLE values.
+            parts.push("DOUBLE".into());
+        }
+    }
+    let mut nocase = false;
+    if let Some(pos) = parts.iter().position(|s| s.eq_ignore_ascii_case("COLLATE")) {
+        match parts.get(pos + 1) {
+            Some(name) if name.eq_ignore_ascii_case("NOCASE") => nocase = true,
+            Some(name) if name.eq_ignore_ascii_case("BINARY") => {}
+            Some(name) => return Err(format!("Unknown collation {}", name)),
+            None => return Err("COLLATE requires a collation name".into()),
+        }
+        parts.drain(pos..pos + 2);
+    }
     let mut not_null = false;
     if let Some(pos) = parts.iter().position(|s| s.eq_ignore_ascii_case("NOT")) {
         if pos + 1 < parts.len() && parts[pos + 1].eq_ignore_ascii_case("NULL") {
@@ -255,12 +533,23 @@ fn parse_column_def(chunk: &str) -> Result<ColumnDef, String> {
         }
         let literal = join_tokens(&parts[pos+1..]);
         let lit = literal.trim();
-        let lit = if (lit.starts_with('"') && lit.ends_with('"')) || (lit.starts_with('\'') && lit.ends_with('\'')) {
-            &lit[1..lit.len()-1]
+        let default = if (lit.starts_with('"') && lit.ends_with('"')) || (lit.starts_with('\'') && lit.ends_with('\'')) {
+            let text = &lit[1..lit.len()-1];
+            // A quoted default is text even when it looks like `(1 + 2)`.
+            if text.starts_with('(') { Expr::Literal(text.to_string()) } else { crate::sql::ast::parse_default_expr(text) }
         } else {
-            lit
+            let default = crate::sql::ast::parse_default_expr(lit);
+            if matches!(default, Expr::Literal(_)) {
+                match parse_constant_arithmetic(lit) {
+                    Some(Expr::Literal(_)) => {}
+                    Some(_) => return Err(format!("DEFAULT {} must be parenthesized, as in DEFAULT ({})", lit, lit)),
+                    None if lit.starts_with('(') => return Err(format!("DEFAULT {} is not a constant expression", lit)),
+                    None => {}
+                }
+            }
+            default
         };
-        default_value = Some(crate::sql::ast::parse_default_expr(lit));
+        default_value = Some(default);
         parts.truncate(pos);
     }
     let mut auto_increment = false;
@@ -287,7 +576,13 @@ fn parse_column_def(chunk: &str) -> Result<ColumnDef, String> {
             return Err("AUTO_INCREMENT columns must be NOT NULL".into());
         }
     }
-    Ok(ColumnDef { name: name.to_string(), col_type: ctype, not_null, default_value, auto_increment, primary_key })
+    if generated.is_some() && (default_value.is_some() || auto_increment) {
+        return Err("Generated columns cannot have DEFAULT or AUTO_INCREMENT".into());
+    }
+    if nocase && !matches!(ctype, ColumnType::Text | ColumnType::Char(_) | ColumnType::Varchar(_)) {
+        return Err("COLLATE NOCASE can only be used with text columns".into());
+    }
+    Ok(ColumnDef { name: name.to_string(), col_type: ctype, not_null, default_value, auto_increment, primary_key, generated, nocase })
 }
 
 /// Parse a simple boolean expression consisting of identifiers, =, !=, AND, OR.
@@ -315,7 +610,7 @@ fn parse_expression(tokens: &[String]) -> Result<(Expr, usize), String> {
             }
             return Err("Unclosed function call".into());
         }
-        Ok((unquote_token(&tokens[0]).to_string(), 1))
+        Ok((operand_text(&tokens[0]), 1))
     }
 
     if tokens[0].eq_ignore_ascii_case("EXISTS") {
@@ -332,17 +627,24 @@ fn parse_expression(tokens: &[String]) -> Result<(Expr, usize), String> {
         let sub_tokens = join_tokens(&tokens[1..=end]);
         let inner = sub_tokens.trim_start_matches('(').trim_end_matches(')');
         let substmt = parse_statement(inner)?;
-        let mut expr = Expr::ExistsSubquery { query: Box::new(substmt) };
-        let mut consumed = end + 1;
-        while tokens.len() > consumed {
-            let logic = tokens[consumed].to_uppercase();
-            if logic != "AND" && logic != "OR" { break; }
-            consumed += 1;
-            let (next, used) = parse_expression(&tokens[consumed..])?;
-            expr = if logic == "AND" { Expr::And(Box::new(expr), Box::new(next)) } else { Expr::Or(Box::new(expr), Box::new(next)) };
-            consumed += used;
+        let expr = Expr::ExistsSubquery { query: Box::new(substmt) };
+        return parse_logic_tail(tokens, expr, end + 1);
+    }
+    if tokens[0] == "(" {
+        // A parenthesized condition, such as `(a = 1 OR b = 2) AND c = 3`.
+        let mut depth = 0i32;
+        let close = tokens
+            .iter()
+            .position(|token| {
+                depth += (token == "(") as i32 - (token == ")") as i32;
+                depth == 0
+            })
+            .ok_or("Unclosed parenthesis in expression")?;
+        let (expr, used) = parse_expression(&tokens[1..close])?;
+        if used + 1 != close {
+            return Err("Unexpected tokens in parenthesized expression".into());
         }
-        return Ok((expr, consumed));
+        return parse_logic_tail(tokens, expr, close + 1);
     }
     if tokens.len() < 3 {
         return Err("Incomplete expression".into());
@@ -353,111 +655,164 @@ fn parse_expression(tokens: &[String]) -> Result<(Expr, usize), String> {
     }
     let op = tokens[idx].as_str();
     idx += 1;
-    let mut consumed;
-    let mut expr = match op.to_uppercase().as_str() {
-        "IN" => {
-            if idx >= tokens.len() || !tokens[idx].starts_with('(') {
-                return Err("Expected '(' after IN".into());
-            }
-            let mut depth = tokens[idx].matches('(').count() as i32 - tokens[idx].matches(')').count() as i32;
-            let mut end = idx;
-            while depth > 0 {
-                end += 1;
-                if end >= tokens.len() { return Err("Unclosed subquery".into()); }
-                depth += tokens[end].matches('(').count() as i32 - tokens[end].matches(')').count() as i32;
-            }
-            let sub_tokens = join_tokens(&tokens[idx..=end]);
-            let inner = sub_tokens.trim_start_matches('(').trim_end_matches(')');
-            let substmt = parse_statement(inner)?;
-            consumed = end + 1;
-            Expr::InSubquery { left, query: Box::new(substmt) }
-        }
-        "=" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::Equals { left, right }
-        }
-        "!=" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::NotEquals { left, right }
-        }
-        "<>" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::NotEquals { left, right }
-        }
-        "+" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::Add { left, right }
-        }
-        "-" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::Subtract { left, right }
-        }
-        "*" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::Multiply { left, right }
-        }
-        "/" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::Divide { left, right }
-        }
-        "%" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::Modulo { left, right }
-        }
-        "&" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::BitwiseAnd { left, right }
-        }
-        "|" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::BitwiseOr { left, right }
-        }
-        "^" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::BitwiseXor { left, right }
-        }
-        "BETWEEN" => {
-            if idx + 2 >= tokens.len() || !tokens[idx + 1].eq_ignore_ascii_case("AND") {
-                return Err("BETWEEN requires syntax: <expr> BETWEEN <low> AND <high>".into());
-            }
-            let low = unquote_token(&tokens[idx]).to_string();
-            let high = unquote_token(&tokens[idx + 2]).trim_end_matches(';').to_string();
-            consumed = idx + 3;
-            Expr::Between { expr: left, low, high }
-        }
-        ">" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::GreaterThan { left, right }
-        }
-        ">=" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::GreaterOrEquals { left, right }
-        }
-        "<" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::LessThan { left, right }
+    let consumed;
+    // A comparison whose right side is `(SELECT ...)` is a scalar subquery.
+    let scalar_op = match op {
+        "=" | "!=" | "<" | "<=" | ">" | ">=" => Some(op),
+        "<>" => Some("!="),
+        _ => None,
+    }
+    .filter(|_| {
+        tokens.get(idx).map(String::as_str) == Some("(")
+            && tokens.get(idx + 1).is_some_and(|t| t.eq_ignore_ascii_case("SELECT"))
+    });
+    let expr = if let Some(scalar_op) = scalar_op {
+        let mut depth = 0i32;
+        let mut end = idx;
+        loop {
+            if end >= tokens.len() { return Err("Unclosed subquery".into()); }
+            depth += tokens[end].matches('(').count() as i32 - tokens[end].matches(')').count() as i32;
+            if depth == 0 { break; }
+            end += 1;
         }
-        "<=" => {
-            let right = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-            consumed = idx + 1;
-            Expr::LessOrEquals { left, right }
+        let sub_tokens = join_tokens(&tokens[idx..=end]);
+        let inner = sub_tokens.trim_start_matches('(').trim_end_matches(')');
+        let substmt = parse_statement(inner)?;
+        consumed = end + 1;
+        Expr::ScalarSubquery { left, op: scalar_op.to_string(), query: Box::new(substmt) }
+    } else {
+        match op.to_uppercase().as_str() {
+            "IN" => {
+                if idx >= tokens.len() || !tokens[idx].starts_with('(') {
+                    return Err("Expected '(' after IN".into());
+                }
+                let mut depth = tokens[idx].matches('(').count() as i32 - tokens[idx].matches(')').count() as i32;
+                let mut end = idx;
+                while depth > 0 {
+                    end += 1;
+                    if end >= tokens.len() { return Err("Unclosed subquery".into()); }
+                    depth += tokens[end].matches('(').count() as i32 - tokens[end].matches(')').count() as i32;
+                }
+                let sub_tokens = join_tokens(&tokens[idx..=end]);
+                let inner = sub_tokens.trim_start_matches('(').trim_end_matches(')');
+                let substmt = parse_statement(inner)?;
+                consumed = end + 1;
+                Expr::InSubquery { left, query: Box::new(substmt) }
+            }
+            "=" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::Equals { left, right }
+            }
+            "!=" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::NotEquals { left, right }
+            }
+            "<>" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::NotEquals { left, right }
+            }
+            "+" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::Add { left, right }
+            }
+            "-" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::Subtract { left, right }
+            }
+            "*" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::Multiply { left, right }
+            }
+            "/" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::Divide { left, right }
+            }
+            "%" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::Modulo { left, right }
+            }
+            "&" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::BitwiseAnd { left, right }
+            }
+            "|" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::BitwiseOr { left, right }
+            }
+            "^" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::BitwiseXor { left, right }
+            }
+            "BETWEEN" | "NOT" => {
+                let negated = op.eq_ignore_ascii_case("NOT");
+                if negated {
+                    if !tokens.get(idx).is_some_and(|t| t.eq_ignore_ascii_case("BETWEEN")) {
+                        return Err(format!("Unknown operator '{}'", op));
+                    }
+                    idx += 1;
+                }
+                if idx + 2 >= tokens.len() || !tokens[idx + 1].eq_ignore_ascii_case("AND") {
+                    return Err("BETWEEN requires syntax: <expr> [NOT] BETWEEN <low> AND <high>".into());
+                }
+                let low = operand_text(&tokens[idx]);
+                let high = operand_text(&tokens[idx + 2]);
+                consumed = idx + 3;
+                Expr::Between { expr: left, low, high, negated }
+            }
+            "IS" => {
+                let negated = tokens[idx].eq_ignore_ascii_case("NOT");
+                if negated {
+                    idx += 1;
+                }
+                consumed = idx + 1;
+                match tokens.get(idx).map(|t| t.trim_end_matches(';').to_uppercase()).as_deref() {
+                    Some("TRUE") => Expr::IsTruth { expr: left, truth: Some(true), negated },
+                    Some("FALSE") => Expr::IsTruth { expr: left, truth: Some(false), negated },
+                    Some("UNKNOWN") => Expr::IsTruth { expr: left, truth: None, negated },
+                    Some("NULL") => Expr::IsNull { expr: left, negated },
+                    _ => return Err("Expected TRUE, FALSE, UNKNOWN or NULL after IS".into()),
+                }
+            }
+            ">" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::GreaterThan { left, right }
+            }
+            ">=" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::GreaterOrEquals { left, right }
+            }
+            "<" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::LessThan { left, right }
+            }
+            "<=" => {
+                let right = operand_text(&tokens[idx]);
+                consumed = idx + 1;
+                Expr::LessOrEquals { left, right }
+            }
+            _ => return Err(format!("Unknown operator '{}'", op)),
         }
-        _ => return Err(format!("Unknown operator '{}'", op)),
     };
+    parse_logic_tail(tokens, expr, consumed)
+}
+
+/// Combine `expr`, which ends before `tokens[consumed]`, with any conditions
+/// that follow it joined by AND or OR.
+fn parse_logic_tail(tokens: &[String], mut expr: Expr, mut consumed: usize) -> Result<(Expr, usize), String> {
     while tokens.len() > consumed {
         let logic = tokens[consumed].to_uppercase();
         if logic != "AND" && logic != "OR" {
@@ -503,39 +858,421 @@ fn parse_create_sequence(tokens: &[String]) -> Result<Statement, String> {
     Ok(Statement::CreateSequence(crate::sql::ast::CreateSequence { name, start, increment }))
 }
 
+/// Parse a standalone expression such as a stored generated-column definition.
+pub fn parse_expression_str(input: &str) -> Result<Expr, String> {
+    let tokens = tokenize(input)?;
+    let (expr, used) = parse_expression(&tokens)?;
+    if used != tokens.len() {
+        return Err(format!("Unexpected tokens in expression: {}", input));
+    }
+    Ok(expr)
+}
+
+/// Parse `input` as constant arithmetic: `+ - * / %` over numeric literals
+/// (negative ones included) with the usual precedence and parentheses. An
+/// operation nested in another becomes its parenthesized operand, so
+/// `2 * 3 + 1` is `Add { left: "(2 * 3)", right: "1" }`, and a lone number is
+/// a literal. `None` when `input` is anything else.
+pub fn parse_constant_arithmetic(input: &str) -> Option<Expr> {
+    let tokens = tokenize(input).ok()?;
+    let (expr, used) = constant_sum(&tokens)?;
+    (used == tokens.len()).then_some(expr)
+}
+
+fn constant_sum(tokens: &[String]) -> Option<(Expr, usize)> {
+    let (mut expr, mut idx) = constant_product(tokens)?;
+    while let Some(op @ ("+" | "-")) = tokens.get(idx).map(String::as_str) {
+        let (right, used) = constant_product(&tokens[idx + 1..])?;
+        expr = constant_operation(op, &expr, &right);
+        idx += 1 + used;
+    }
+    Some((expr, idx))
+}
+
+fn constant_product(tokens: &[String]) -> Option<(Expr, usize)> {
+    let (mut expr, mut idx) = constant_factor(tokens)?;
+    while let Some(op @ ("*" | "/" | "%")) = tokens.get(idx).map(String::as_str) {
+        let (right, used) = constant_factor(&tokens[idx + 1..])?;
+        expr = constant_operation(op, &expr, &right);
+        idx += 1 + used;
+    }
+    Some((expr, idx))
+}
+
+fn constant_factor(tokens: &[String]) -> Option<(Expr, usize)> {
+    let token = tokens.first()?;
+    if token == "(" {
+        let (expr, used) = constant_sum(&tokens[1..])?;
+        return (tokens.get(used + 1)? == ")").then_some((expr, used + 2));
+    }
+    // The tokenizer keeps the sign of a negative literal with its digits.
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    let numeric = digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && token.parse::<f64>().is_ok();
+    numeric.then(|| (Expr::Literal(token.clone()), 1))
+}
+
+fn constant_operation(op: &str, left: &Expr, right: &Expr) -> Expr {
+    let operand = |expr: &Expr| match expr {
+        Expr::Literal(number) => number.clone(),
+        nested => format!("({})", crate::sql::ast::expr_to_string(nested)),
+    };
+    let (left, right) = (operand(left), operand(right));
+    match op {
+        "+" => Expr::Add { left, right },
+        "-" => Expr::Subtract { left, right },
+        "*" => Expr::Multiply { left, right },
+        "/" => Expr::Divide { left, right },
+        _ => Expr::Modulo { left, right },
+    }
+}
+
+/// Parse `CREATE VIEW <name> AS <query>` from the raw input, so the query
+/// keeps its text and a UNION inside it is not split off the statement.
+/// `None` when the input is not a CREATE VIEW.
+fn parse_create_view(input: &str) -> Option<Result<Statement, String>> {
+    fn next_word(s: &str) -> (&str, &str) {
+        let s = s.trim_start();
+        let end = s.find(char::is_whitespace).unwrap_or(s.len());
+        s.split_at(end)
+    }
+    let (create, rest) = next_word(input);
+    let (view, rest) = next_word(rest);
+    if !create.eq_ignore_ascii_case("CREATE") || !view.eq_ignore_ascii_case("VIEW") {
+        return None;
+    }
+    let usage = || Err("Usage: CREATE VIEW <name> AS <query>".to_string());
+    let (name, rest) = next_word(rest);
+    let (as_kw, rest) = next_word(rest);
+    if name.is_empty() || !as_kw.eq_ignore_ascii_case("AS") {
+        return Some(usage());
+    }
+    let sql = rest.trim().trim_end_matches(';').trim_end();
+    match parse_statement(sql) {
+        Ok(Statement::Select { .. } | Statement::SetOp { .. } | Statement::Values(_)) => {
+            Some(Ok(Statement::CreateView { name: unquote_token(name).to_string(), sql: sql.to_string() }))
+        }
+        Ok(_) => Some(Err("A view must be defined by a SELECT or VALUES query".to_string())),
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// Locate the last `UNION [ALL]` keyword outside parentheses and quotes.
+/// Returns the byte range of the keyword(s) and the operation.
+fn find_top_level_union(input: &str) -> Option<(usize, usize, SetOp)> {
+    fn is_word_char(c: char) -> bool {
+        c.is_ascii_alphanumeric() || c == '_'
+    }
+    fn starts_with_word(s: &str, word: &str) -> bool {
+        s.len() >= word.len()
+            && s.is_char_boundary(word.len())
+            && s[..word.len()].eq_ignore_ascii_case(word)
+            && !s[word.len()..].starts_with(is_word_char)
+    }
+
+    let mut found = None;
+    let mut depth = 0i32;
+    let mut in_quote: Option<char> = None;
+    let mut prev: Option<char> = None;
+    for (i, ch) in input.char_indices() {
+        if let Some(quote) = in_quote {
+            if ch == quote {
+                in_quote = None;
+            }
+        } else {
+            match ch {
+                '\'' | '"' => in_quote = Some(ch),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if depth == 0
+                    && !prev.is_some_and(is_word_char)
+                    && starts_with_word(&input[i..], "UNION") =>
+                {
+                    let after = &input[i + "UNION".len()..];
+                    let trimmed = after.trim_start();
+                    if starts_with_word(trimmed, "ALL") {
+                        let end = input.len() - trimmed.len() + "ALL".len();
+                        found = Some((i, end, SetOp::UnionAll));
+                    } else {
+                        found = Some((i, i + "UNION".len(), SetOp::Union));
+                    }
+                }
+                _ => {}
+            }
+        }
+        prev = Some(ch);
+    }
+    found
+}
+
+/// Number each bare `?` parameter outside quotes as `?1`, `?2`, ... so its
+/// position survives into the AST. Explicitly numbered `?n` tokens are kept.
+fn number_placeholders(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut in_quote: Option<char> = None;
+    let mut next = 0;
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        out.push(ch);
+        match in_quote {
+            Some(q) if ch == q => in_quote = None,
+            Some(_) => {}
+            None if ch == '\'' || ch == '"' => in_quote = Some(ch),
+            None if ch == '?' => {
+                if chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    continue;
+                }
+                next += 1;
+                out.push_str(&next.to_string());
+            }
+            None => {}
+        }
+    }
+    out
+}
+
+/// Replace `-- ...` line comments and `/* ... */` block comments with a
+/// space, leaving quoted strings and identifiers untouched.
+fn strip_comments(input: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(input.len());
+    let mut in_quote: Option<char> = None;
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match in_quote {
+            Some(q) if ch == q => in_quote = None,
+            Some(_) => {}
+            None if matches!(ch, '\'' | '"' | '`') => in_quote = Some(ch),
+            None if ch == '-' && chars.peek() == Some(&'-') => {
+                // The newline ending the comment is kept as whitespace.
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                out.push(' ');
+                continue;
+            }
+            None if ch == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == '*' => break,
+                        Some(c) => prev = c,
+                        None => return Err("Unterminated block comment".to_string()),
+                    }
+                }
+                out.push(' ');
+                continue;
+            }
+            None => {}
+        }
+        out.push(ch);
+    }
+    Ok(out)
+}
+
+/// Split a script into statements at top-level semicolons, ignoring those
+/// inside quotes and comments. Pieces holding only whitespace and comments
+/// are dropped; the rest keep their comments for [`parse_statement`].
+pub fn split_statements(script: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    let mut in_quote: Option<char> = None;
+    let mut chars = script.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        match in_quote {
+            Some(q) if ch == q => in_quote = None,
+            Some(_) => {}
+            None if ch == '-' && chars.peek().is_some_and(|&(_, c)| c == '-') => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            None if ch == '/' && chars.peek().is_some_and(|&(_, c)| c == '*') => {
+                chars.next();
+                let mut prev = '\0';
+                let mut closed = false;
+                for (_, c) in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        closed = true;
+                        break;
+                    }
+                    prev = c;
+                }
+                // Keep an unterminated comment so parsing reports it.
+                has_code |= !closed;
+            }
+            None if ch == ';' => {
+                if has_code {
+                    statements.push(script[start..idx].trim());
+                }
+                start = idx + 1;
+                has_code = false;
+            }
+            None => {
+                if matches!(ch, '\'' | '"' | '`') {
+                    in_quote = Some(ch);
+                }
+                has_code |= !ch.is_whitespace();
+            }
+        }
+    }
+    if has_code {
+        statements.push(script[start..].trim());
+    }
+    statements
+}
+
+/// Rewrite MySQL-style `` `name` `` identifiers as `"name"` so the rest of
+/// the parser only has to know one identifier quote.
+fn normalize_backticks(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut in_quote: Option<char> = None;
+    for ch in input.chars() {
+        match in_quote {
+            Some(q) if ch == q => {
+                in_quote = None;
+                out.push(if ch == '`' { '"' } else { ch });
+            }
+            Some(_) => out.push(ch),
+            None if matches!(ch, '\'' | '"' | '`') => {
+                in_quote = Some(ch);
+                out.push(if ch == '`' { '"' } else { ch });
+            }
+            None => out.push(ch),
+        }
+    }
+    out
+}
+
 pub fn parse_statement(input: &str) -> Result<Statement, String> {
+    let stripped;
+    let input = if input.contains("--") || input.contains("/*") {
+        stripped = strip_comments(input)?;
+        stripped.as_str()
+    } else {
+        input
+    };
+    let unticked;
+    let input = if input.contains('`') {
+        unticked = normalize_backticks(input);
+        unticked.as_str()
+    } else {
+        input
+    };
+    let numbered;
+    let input = if input.contains('?') {
+        numbered = number_placeholders(input);
+        numbered.as_str()
+    } else {
+        input
+    };
+    let trimmed = input.trim_start();
+    if trimmed.len() > 7
+        && trimmed[..7].eq_ignore_ascii_case("EXPLAIN")
+        && trimmed[7..].starts_with(char::is_whitespace)
+    {
+        let inner = parse_statement(&trimmed[7..])?;
+        if !matches!(inner, Statement::Select { .. } | Statement::SetOp { .. }) {
+            return Err("EXPLAIN supports only SELECT statements".to_string());
+        }
+        return Ok(Statement::Explain(Box::new(inner)));
+    }
+    if let Some(view) = parse_create_view(input) {
+        return view;
+    }
+    if let Some((start, end, op)) = find_top_level_union(input) {
+        let left = parse_statement(&input[..start])?;
+        let right = parse_statement(&input[end..])?;
+        if !matches!(left, Statement::Select { .. } | Statement::SetOp { .. } | Statement::Values(_))
+            || !matches!(right, Statement::Select { .. } | Statement::Values(_))
+        {
+            return Err("UNION operands must be SELECT or VALUES statements".to_string());
+        }
+        return Ok(Statement::SetOp { op, left: Box::new(left), right: Box::new(right) });
+    }
     let tokens = tokenize(input)?;
     if tokens.is_empty() {
         return Err("Empty input".to_string());
     }
     match tokens[0].to_uppercase().as_str() {
         "BEGIN" => {
-            // Support BEGIN [TRANSACTION] [name]
+            // Support BEGIN [TRANSACTION] [SNAPSHOT | ISOLATION LEVEL <level>] [name]
             let mut idx = 1;
             if tokens.get(idx).map(|s| s.eq_ignore_ascii_case("TRANSACTION")) == Some(true) {
                 idx += 1;
             }
+            let mut isolation = None;
+            if tokens.get(idx).is_some_and(|s| s.eq_ignore_ascii_case("ISOLATION")) {
+                if !tokens.get(idx + 1).is_some_and(|s| s.eq_ignore_ascii_case("LEVEL")) {
+                    return Err("Expected LEVEL after ISOLATION".to_string());
+                }
+                idx += 2;
+                match tokens.get(idx) {
+                    Some(level) if level.eq_ignore_ascii_case("SNAPSHOT") => {}
+                    Some(level) => return Err(format!("Unsupported isolation level '{}'", level)),
+                    None => return Err("Expected an isolation level".to_string()),
+                }
+            }
+            if tokens.get(idx).is_some_and(|s| s.eq_ignore_ascii_case("SNAPSHOT")) {
+                isolation = Some(IsolationLevel::Snapshot);
+                idx += 1;
+            }
             let name = tokens.get(idx).map(|s| unquote_token(s).trim_end_matches(';').to_string());
-            Ok(Statement::BeginTransaction { name })
+            Ok(Statement::BeginTransaction { name, isolation })
         }
         "COMMIT" => Ok(Statement::Commit),
         "ROLLBACK" => Ok(Statement::Rollback),
+        "SET" if tokens.get(1).is_some_and(|s| s.eq_ignore_ascii_case("CONSTRAINTS")) => {
+            let usage = "Usage: SET CONSTRAINTS [ALL] DEFERRED | IMMEDIATE";
+            let mode = match tokens.len() {
+                3 => &tokens[2],
+                4 if tokens[2].eq_ignore_ascii_case("ALL") => &tokens[3],
+                _ => return Err(usage.to_string()),
+            };
+            match mode.trim_end_matches(';').to_ascii_uppercase().as_str() {
+                "DEFERRED" => Ok(Statement::SetConstraints(true)),
+                "IMMEDIATE" => Ok(Statement::SetConstraints(false)),
+                _ => Err(usage.to_string()),
+            }
+        }
+        "SET" => {
+            // SET autocommit = on | off
+            let usage = "Usage: SET autocommit = on | off";
+            if tokens.len() != 4
+                || !unquote_token(&tokens[1]).eq_ignore_ascii_case("autocommit")
+                || tokens[2] != "="
+            {
+                return Err(usage.to_string());
+            }
+            match unquote_token(&tokens[3]).to_ascii_lowercase().as_str() {
+                "on" | "1" | "true" => Ok(Statement::SetAutocommit(true)),
+                "off" | "0" | "false" => Ok(Statement::SetAutocommit(false)),
+                _ => Err(usage.to_string()),
+            }
+        }
         "CREATE" => {
             if tokens.len() >= 3 && tokens[1].eq_ignore_ascii_case("SEQUENCE") {
                 return parse_create_sequence(&tokens[1..]);
             }
             if tokens.len() >= 3 && tokens[1].eq_ignore_ascii_case("INDEX") {
-                if tokens.len() < 6 || !tokens[3].eq_ignore_ascii_case("ON") {
-                    return Err("Usage: CREATE INDEX <name> ON <table>(<column>)".to_string());
+                // CREATE INDEX [IF NOT EXISTS] <name> ON <table>(<column>)
+                let mut idx = 2;
+                let mut if_not_exists = false;
+                if tokens.get(idx).map(|s| s.to_uppercase()) == Some("IF".to_string())
+                    && tokens.get(idx + 1).map(|s| s.to_uppercase()) == Some("NOT".to_string())
+                    && tokens.get(idx + 2).map(|s| s.to_uppercase()) == Some("EXISTS".to_string())
+                {
+                    if_not_exists = true;
+                    idx += 3;
+                }
+                if tokens.len() < idx + 4 || !tokens[idx + 1].eq_ignore_ascii_case("ON") {
+                    return Err("Usage: CREATE INDEX [IF NOT EXISTS] <name> ON <table>(<column>)".to_string());
                 }
-                let index_name = tokens[2].to_string();
-                let table_name = unquote_token(&tokens[4]).trim_end_matches(';').to_string();
+                let index_name = unquote_token(&tokens[idx]).to_string();
+                let table_name = unquote_token(&tokens[idx + 2]).trim_end_matches(';').to_string();
                 let rest = input[input.find('(').ok_or("Missing '('")?..].trim();
                 if !rest.starts_with('(') || !rest.ends_with(')') {
                     return Err("Column must be in parentheses".to_string());
                 }
-                let col = rest[1..rest.len() - 1].trim().to_string();
-                return Ok(Statement::CreateIndex { index_name, table_name, column_name: col });
+                let col = unquote_token(rest[1..rest.len() - 1].trim()).to_string();
+                return Ok(Statement::CreateIndex { index_name, table_name, column_name: col, if_not_exists });
             }
             // Expect: CREATE TABLE [IF NOT EXISTS] table_name (col1 TYPE, ...)
             if tokens.len() < 4 || !tokens[1].eq_ignore_ascii_case("TABLE") {
@@ -573,18 +1310,18 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                     }
                     let end = rest.find(')').ok_or("Missing ) in FOREIGN KEY")?;
                     let cols_part = &rest[1..end];
-                    let cols: Vec<String> = cols_part.split(',').map(|s| s.trim().to_string()).collect();
+                    let cols: Vec<String> = cols_part.split(',').map(|s| unquote_token(s.trim()).to_string()).collect();
                     rest = rest[end + 1..].trim();
                     if !rest.to_uppercase().starts_with("REFERENCES") {
                         return Err("Expected REFERENCES".into());
                     }
                     rest = rest[10..].trim();
                     let mut parts = rest.splitn(2, '(');
-                    let parent_table = parts.next().ok_or("Missing parent table")?.trim().to_string();
+                    let parent_table = unquote_token(parts.next().ok_or("Missing parent table")?.trim()).to_string();
                     let remainder = parts.next().ok_or("Missing ( after parent table")?;
                     let end2 = remainder.find(')').ok_or("Missing ) after parent columns")?;
                     let pcols_part = &remainder[..end2];
-                    let parent_columns: Vec<String> = pcols_part.split(',').map(|s| s.trim().to_string()).collect();
+                    let parent_columns: Vec<String> = pcols_part.split(',').map(|s| unquote_token(s.trim()).to_string()).collect();
                     let mut rest2 = remainder[end2 + 1..].trim();
                     let mut on_delete = None;
                     let mut on_update = None;
@@ -618,7 +1355,7 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                         return Err("Expected column list after PRIMARY KEY".into());
                     }
                     let inner = &rest[1..rest.len()-1];
-                    let cols: Vec<String> = inner.split(',').map(|c| c.trim().to_string()).collect();
+                    let cols: Vec<String> = inner.split(',').map(|c| unquote_token(c.trim()).to_string()).collect();
                     if primary_key.is_some() {
                         return Err("Multiple primary keys defined".into());
                     }
@@ -675,40 +1412,16 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
             if idx >= tokens.len() || !tokens[idx].eq_ignore_ascii_case("VALUES") {
                 return Err("Expected VALUES".into());
             }
-            idx += 1;
-            if idx >= tokens.len() { return Err("Missing values".into()); }
-            let rest_tokens = tokens[idx..].join(" ");
-            let rest = rest_tokens.trim().trim_end_matches(';');
-            let tuple_strs = split_top_level(rest);
-            if tuple_strs.is_empty() { return Err("Missing values".into()); }
-
-            let mut rows = Vec::new();
-            for tup in tuple_strs {
-                let tup = tup.trim();
-                if !tup.starts_with('(') || !tup.ends_with(')') {
-                    return Err("Values must be in parentheses".to_string());
-                }
-                let inner = &tup[1..tup.len()-1];
-                let vals: Vec<Expr> = split_top_level(inner)
-                    .into_iter()
-                    .map(|s| {
-                        let v = s.trim();
-                        if v.eq_ignore_ascii_case("DEFAULT") {
-                            Expr::DefaultValue
-                        } else if (v.starts_with('"') && v.ends_with('"')) || (v.starts_with('\'') && v.ends_with('\'')) {
-                            Expr::Literal(v[1..v.len()-1].to_string())
-                        } else {
-                            Expr::Literal(v.to_string())
-                        }
-                    })
-                    .collect();
-                if vals.is_empty() {
-                    return Err("At least one value required".to_string());
-                }
-                rows.push(vals);
-            }
+            let rows = parse_value_rows(&tokens[idx + 1..])?;
             Ok(Statement::Insert { table_name: table, columns, rows })
         }
+        "VALUES" => {
+            let rows = parse_value_rows(&tokens[1..])?;
+            if rows.iter().any(|row| row.len() != rows[0].len()) {
+                return Err("VALUES rows must all have the same number of values".into());
+            }
+            Ok(Statement::Values(rows))
+        }
         "SELECT" => {
             if tokens.len() < 2 {
                 return Err("Incomplete SELECT".into());
@@ -767,6 +1480,9 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 let upper = expr_tokens[0].to_uppercase();
                 let item = if expr_tokens.len() == 1 && expr_tokens[0] == "*" {
                     crate::sql::ast::SelectItem::All
+                } else if expr_tokens.len() == 2 && expr_tokens[1] == "*" && expr_tokens[0].ends_with('.') {
+                    let source = unquote_token(expr_tokens[0].trim_end_matches('.'));
+                    crate::sql::ast::SelectItem::AllFrom(source.to_string())
                 } else if expr_tokens[0] == "(" && expr_tokens.last().map(|t| t.as_str()) == Some(")") {
                     let inner = join_tokens(&expr_tokens[1..expr_tokens.len() - 1]);
                     let sub = parse_statement(&inner)?;
@@ -795,16 +1511,30 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                         _ => crate::sql::ast::AggFunc::Max,
                     };
                     crate::sql::ast::SelectItem::Aggregate { func, column }
-                } else if upper == "CURRENT_TIMESTAMP"
-                    && (expr_tokens.len() == 1
+                } else if matches!(upper.as_str(), "DATE_ADD" | "DATE_SUB")
+                    && expr_tokens.len() >= 3
+                    && expr_tokens[1] == "("
+                    && expr_tokens.last().map(|t| t.as_str()) == Some(")")
+                {
+                    let args = parse_date_shift_args(&expr_tokens[2..expr_tokens.len() - 1])?;
+                    crate::sql::ast::SelectItem::Expr(Box::new(crate::sql::ast::Expr::FunctionCall { name: upper.clone(), args }))
+                } else if matches!(upper.as_str(), "GREATEST" | "LEAST")
+                    && expr_tokens.len() >= 3
+                    && expr_tokens[1] == "("
+                    && expr_tokens.last().map(|t| t.as_str()) == Some(")")
+                {
+                    let args = parse_extremum_args(&upper, &expr_tokens[2..expr_tokens.len() - 1])?;
+                    crate::sql::ast::SelectItem::Expr(Box::new(crate::sql::ast::Expr::FunctionCall { name: upper.clone(), args }))
+                } else if matches!(upper.as_str(), "CURRENT_TIMESTAMP" | "CURRENT_DATE" | "NOW")
+                    && ((expr_tokens.len() == 1 && upper != "NOW")
                         || (expr_tokens.len() == 3 && expr_tokens[1] == "(" && expr_tokens[2] == ")"))
                 {
-                    crate::sql::ast::SelectItem::Expr(Box::new(crate::sql::ast::Expr::FunctionCall { name: "CURRENT_TIMESTAMP".into(), args: Vec::new() }))
+                    crate::sql::ast::SelectItem::Expr(Box::new(crate::sql::ast::Expr::FunctionCall { name: upper.clone(), args: Vec::new() }))
                 } else if expr_tokens.len() == 1 && expr_tokens[0].starts_with('\'') && expr_tokens[0].ends_with('\'') {
                     crate::sql::ast::SelectItem::Literal(unquote_token(&expr_tokens[0]).to_string())
                 } else if expr_tokens.len() == 1 && expr_tokens[0].starts_with('"') && expr_tokens[0].ends_with('"') {
                     crate::sql::ast::SelectItem::Column(unquote_token(&expr_tokens[0]).to_string())
-                } else if expr_tokens.len() == 1 && expr_tokens[0].chars().all(|c| c.is_ascii_digit()) {
+                } else if expr_tokens.len() == 1 && is_number_token(&expr_tokens[0]) {
                     crate::sql::ast::SelectItem::Literal(expr_tokens[0].to_string())
                 } else {
                     if expr_tokens.len() >= 3 {
@@ -824,7 +1554,7 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 columns.push(crate::sql::ast::SelectExpr { expr: item, alias });
             }
             if idx >= tokens.len() {
-                if columns.iter().any(|c| matches!(c.expr, crate::sql::ast::SelectItem::Column(_) | crate::sql::ast::SelectItem::All | crate::sql::ast::SelectItem::Aggregate { .. })) {
+                if columns.iter().any(|c| matches!(c.expr, crate::sql::ast::SelectItem::Column(_) | crate::sql::ast::SelectItem::All | crate::sql::ast::SelectItem::AllFrom(_) | crate::sql::ast::SelectItem::Aggregate { .. })) {
                     return Err("Column without table".into());
                 }
                 return Ok(Statement::Select {
@@ -848,17 +1578,8 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
             }
             let mut from = Vec::new();
             if tokens[idx].starts_with('(') {
-                let mut depth = tokens[idx].matches('(').count() as i32 - tokens[idx].matches(')').count() as i32;
-                let mut end = idx;
-                while depth > 0 {
-                    end += 1;
-                    if end >= tokens.len() { return Err("Unclosed subquery".into()); }
-                    depth += tokens[end].matches('(').count() as i32 - tokens[end].matches(')').count() as i32;
-                }
-                let sub_tokens = join_tokens(&tokens[idx..=end]);
-                let inner = sub_tokens.trim_start_matches('(').trim_end_matches(')');
-                let substmt = parse_statement(inner)?;
-                idx = end + 1;
+                let (substmt, next) = parse_derived_table(&tokens, idx)?;
+                idx = next;
                 let mut alias = None;
                 if idx < tokens.len() && tokens[idx].eq_ignore_ascii_case("AS") {
                     if idx + 1 >= tokens.len() { return Err("Subquery in FROM requires alias".into()); }
@@ -894,6 +1615,9 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                     && !tokens[idx].eq_ignore_ascii_case("GROUP")
                     && !tokens[idx].eq_ignore_ascii_case("ORDER")
                     && !tokens[idx].eq_ignore_ascii_case("HAVING")
+                    && !tokens[idx].eq_ignore_ascii_case("LIMIT")
+                    && !tokens[idx].eq_ignore_ascii_case("OFFSET")
+                    && !tokens[idx].eq_ignore_ascii_case("FETCH")
                 {
                     alias = Some(unquote_token(&tokens[idx]).trim_end_matches(';').to_string());
                     idx += 1;
@@ -915,6 +1639,7 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                     || token.eq_ignore_ascii_case("HAVING")
                     || token.eq_ignore_ascii_case("LIMIT")
                     || token.eq_ignore_ascii_case("OFFSET")
+                    || token.eq_ignore_ascii_case("FETCH")
             };
             while idx < tokens.len() {
                 let join_type = if tokens[idx].eq_ignore_ascii_case("JOIN") {
@@ -971,8 +1696,16 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 if idx >= tokens.len() {
                     return Err("Expected table after JOIN".into());
                 }
-                let table = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
-                idx += 1;
+                let mut subquery = None;
+                let mut table = String::new();
+                if tokens[idx].starts_with('(') {
+                    let (substmt, next) = parse_derived_table(&tokens, idx)?;
+                    subquery = Some(Box::new(substmt));
+                    idx = next;
+                } else {
+                    table = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
+                    idx += 1;
+                }
                 let mut alias = None;
                 if idx < tokens.len() && tokens[idx].eq_ignore_ascii_case("AS") {
                     if idx + 1 < tokens.len() {
@@ -983,6 +1716,9 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                     alias = Some(unquote_token(&tokens[idx]).trim_end_matches(';').to_string());
                     idx += 1;
                 }
+                if subquery.is_some() {
+                    table = alias.clone().ok_or("Subquery in JOIN requires alias")?;
+                }
 
                 let predicate = if matches!(join_type, crate::sql::ast::JoinType::Cross) {
                     if idx < tokens.len() && tokens[idx].eq_ignore_ascii_case("ON") {
@@ -1017,7 +1753,7 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                     Some(expr)
                 };
 
-                joins.push(crate::sql::ast::JoinClause { join_type, table, alias, predicate });
+                joins.push(crate::sql::ast::JoinClause { join_type, table, alias, predicate, subquery });
             }
 
             let mut where_predicate = None;
@@ -1037,7 +1773,7 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                         continue;
                     }
                     let token = tokens[idx].trim_end_matches(',').trim_end_matches(';');
-                    if token.eq_ignore_ascii_case("ORDER") || token.eq_ignore_ascii_case("WHERE") || token.eq_ignore_ascii_case("HAVING") { break; }
+                    if ["ORDER", "WHERE", "HAVING", "LIMIT", "OFFSET", "FETCH"].iter().any(|k| token.eq_ignore_ascii_case(k)) { break; }
                     cols.push(unquote_token(token).to_string());
                     idx += 1;
                     if idx >= tokens.len() { break; }
@@ -1064,7 +1800,24 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 if tokens[idx] == "," {
                     return Err("Expected column after ORDER BY".into());
                 }
-                let column = tokens[idx].trim_end_matches(',').trim_end_matches(';');
+                // An aggregate key such as `COUNT(*)` is kept in its display form.
+                let aggregate;
+                let column = if idx + 1 < tokens.len()
+                    && tokens[idx + 1] == "("
+                    && matches!(tokens[idx].to_uppercase().as_str(), "COUNT" | "SUM" | "AVG" | "MIN" | "MAX")
+                {
+                    let close = tokens[idx..]
+                        .iter()
+                        .position(|t| t == ")")
+                        .map(|p| idx + p)
+                        .ok_or("Expected ')' in ORDER BY")?;
+                    let inner = join_tokens(&tokens[idx + 2..close]);
+                    aggregate = format!("{}({})", tokens[idx].to_uppercase(), unquote_token(inner.trim()));
+                    idx = close;
+                    aggregate.as_str()
+                } else {
+                    tokens[idx].trim_end_matches(',').trim_end_matches(';')
+                };
                 if idx + 1 < tokens.len() && tokens[idx + 1] == "," {
                     return Err("Only one ORDER BY column is supported".into());
                 }
@@ -1081,7 +1834,7 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 }
                 if idx < tokens.len() {
                     let keyword = tokens[idx].trim_end_matches(';');
-                    if !keyword.eq_ignore_ascii_case("LIMIT") && !keyword.eq_ignore_ascii_case("OFFSET") {
+                    if !["LIMIT", "OFFSET", "FETCH"].iter().any(|k| keyword.eq_ignore_ascii_case(k)) {
                         return Err("Unexpected token after ORDER BY clause".into());
                     }
                 }
@@ -1095,15 +1848,23 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
             }
 
             let mut limit = None;
-            if idx < tokens.len() && tokens[idx].trim_end_matches(';').eq_ignore_ascii_case("LIMIT") {
-                idx += 1;
-                if idx >= tokens.len() {
+            let mut saw_limit = false;
+            let parse_limit = |idx: &mut usize, limit: &mut Option<usize>| -> Result<(), String> {
+                *idx += 1;
+                if *idx >= tokens.len() {
                     return Err("Expected value after LIMIT".into());
                 }
-                let raw = tokens[idx].trim_end_matches(';');
-                let value = raw.parse::<usize>().map_err(|_| "Invalid LIMIT value".to_string())?;
-                limit = Some(value);
-                idx += 1;
+                let raw = tokens[*idx].trim_end_matches(';');
+                // `LIMIT ALL` is the same as no limit at all.
+                if !raw.eq_ignore_ascii_case("ALL") {
+                    *limit = Some(parse_row_count(raw, "LIMIT")?);
+                }
+                *idx += 1;
+                Ok(())
+            };
+            if idx < tokens.len() && tokens[idx].trim_end_matches(';').eq_ignore_ascii_case("LIMIT") {
+                parse_limit(&mut idx, &mut limit)?;
+                saw_limit = true;
             }
 
             let mut offset = None;
@@ -1112,17 +1873,53 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 if idx >= tokens.len() {
                     return Err("Expected value after OFFSET".into());
                 }
-                let raw = tokens[idx].trim_end_matches(';');
-                let value = raw.parse::<usize>().map_err(|_| "Invalid OFFSET value".to_string())?;
-                offset = Some(value);
+                offset = Some(parse_row_count(tokens[idx].trim_end_matches(';'), "OFFSET")?);
                 idx += 1;
+                // The standard spelling may follow the count with ROW or ROWS.
+                if idx < tokens.len() && is_row_keyword(&tokens[idx]) {
+                    idx += 1;
+                }
+            }
+            // Like PostgreSQL, LIMIT may also come after OFFSET.
+            if !saw_limit && idx < tokens.len() && tokens[idx].trim_end_matches(';').eq_ignore_ascii_case("LIMIT") {
+                parse_limit(&mut idx, &mut limit)?;
+                saw_limit = true;
+            }
+
+            // `FETCH {FIRST | NEXT} [n] {ROW | ROWS} ONLY` is the standard form of LIMIT.
+            if idx < tokens.len() && tokens[idx].eq_ignore_ascii_case("FETCH") {
+                if saw_limit {
+                    return Err("FETCH cannot be combined with LIMIT".into());
+                }
+                idx += 1;
+                if idx >= tokens.len() || !(tokens[idx].eq_ignore_ascii_case("FIRST") || tokens[idx].eq_ignore_ascii_case("NEXT")) {
+                    return Err("Expected FIRST or NEXT after FETCH".into());
+                }
+                idx += 1;
+                let mut count = 1;
+                if idx < tokens.len() && !is_row_keyword(&tokens[idx]) {
+                    count = parse_row_count(&tokens[idx], "FETCH")?;
+                    idx += 1;
+                }
+                if idx >= tokens.len() || !is_row_keyword(&tokens[idx]) {
+                    return Err("Expected ROWS after FETCH count".into());
+                }
+                idx += 1;
+                if idx >= tokens.len() || !tokens[idx].trim_end_matches(';').eq_ignore_ascii_case("ONLY") {
+                    return Err("Expected ONLY after FETCH ... ROWS".into());
+                }
+                idx += 1;
+                limit = Some(count);
+            }
+            if idx < tokens.len() && tokens[idx] != ";" {
+                return Err(format!("Unexpected token '{}' at end of SELECT", tokens[idx]));
             }
 
             Ok(Statement::Select { columns, from, joins, where_predicate, group_by, having, order_by, limit, offset })
         }
         "DROP" => {
             if tokens.len() < 3 {
-                return Err("Usage: DROP TABLE <name> | DROP INDEX <name>".to_string());
+                return Err("Usage: DROP TABLE <name> | DROP INDEX <name> | DROP VIEW <name>".to_string());
             }
             if tokens[1].eq_ignore_ascii_case("TABLE") {
                 let mut idx = 2;
@@ -1139,15 +1936,61 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                 let table = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
                 Ok(Statement::DropTable { table_name: table, if_exists })
             } else if tokens[1].eq_ignore_ascii_case("INDEX") {
-                if tokens.len() < 3 {
-                    return Err("Usage: DROP INDEX <name>".to_string());
+                let mut idx = 2;
+                let mut if_exists = false;
+                if tokens.get(idx).map(|s| s.to_uppercase()) == Some("IF".to_string())
+                    && tokens.get(idx + 1).map(|s| s.to_uppercase()) == Some("EXISTS".to_string())
+                {
+                    if_exists = true;
+                    idx += 2;
+                }
+                if idx >= tokens.len() {
+                    return Err("Usage: DROP INDEX [IF EXISTS] <name>".to_string());
+                }
+                let name = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
+                Ok(Statement::DropIndex { name, if_exists })
+            } else if tokens[1].eq_ignore_ascii_case("VIEW") {
+                let mut idx = 2;
+                let mut if_exists = false;
+                if tokens.get(idx).is_some_and(|s| s.eq_ignore_ascii_case("IF"))
+                    && tokens.get(idx + 1).is_some_and(|s| s.eq_ignore_ascii_case("EXISTS"))
+                {
+                    if_exists = true;
+                    idx += 2;
                 }
-                let name = unquote_token(&tokens[2]).trim_end_matches(';').to_string();
-                Ok(Statement::DropIndex { name })
+                if idx >= tokens.len() {
+                    return Err("Usage: DROP VIEW [IF EXISTS] <name>".to_string());
+                }
+                let name = unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
+                Ok(Statement::DropView { name, if_exists })
             } else {
                 Err("Usage: DROP TABLE <name>".to_string())
             }
         }
+        "ALTER" => {
+            if tokens.get(1).is_some_and(|s| s.eq_ignore_ascii_case("INDEX")) {
+                if tokens.len() != 6 || !tokens[3].eq_ignore_ascii_case("RENAME") || !tokens[4].eq_ignore_ascii_case("TO") {
+                    return Err("Usage: ALTER INDEX <name> RENAME TO <new_name>".to_string());
+                }
+                return Ok(Statement::RenameIndex {
+                    old_name: unquote_token(&tokens[2]).to_string(),
+                    new_name: unquote_token(&tokens[5]).trim_end_matches(';').to_string(),
+                });
+            }
+            let usage = "Usage: ALTER TABLE <name> RENAME TO <new_name> | ALTER TABLE <name> RENAME COLUMN <column> TO <new_name>";
+            if tokens.len() < 6 || !tokens[1].eq_ignore_ascii_case("TABLE") || !tokens[3].eq_ignore_ascii_case("RENAME") {
+                return Err(usage.to_string());
+            }
+            let table = unquote_token(&tokens[2]).to_string();
+            let name_at = |idx: usize| unquote_token(&tokens[idx]).trim_end_matches(';').to_string();
+            if tokens.len() == 6 && tokens[4].eq_ignore_ascii_case("TO") {
+                Ok(Statement::RenameTable { old_name: table, new_name: name_at(5) })
+            } else if tokens.len() == 8 && tokens[4].eq_ignore_ascii_case("COLUMN") && tokens[6].eq_ignore_ascii_case("TO") {
+                Ok(Statement::RenameColumn { table_name: table, old_name: name_at(5), new_name: name_at(7) })
+            } else {
+                Err(usage.to_string())
+            }
+        }
         "DELETE" => {
             if tokens.len() < 5 || !tokens[1].eq_ignore_ascii_case("FROM") || !tokens[3].eq_ignore_ascii_case("WHERE") {
                 return Err("Usage: DELETE FROM <table> WHERE <expr>".to_string());
@@ -1156,6 +1999,30 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
             let (expr, _) = parse_expression(&tokens[4..])?;
             Ok(Statement::Delete { table_name: table, selection: Some(expr) })
         }
+        "TRUNCATE" => {
+            let usage = "Usage: TRUNCATE [TABLE] <table> [RESTART IDENTITY | CONTINUE IDENTITY]";
+            let words: Vec<&str> = tokens[1..]
+                .iter()
+                .map(|t| t.trim_end_matches(';'))
+                .filter(|t| !t.is_empty())
+                .collect();
+            let words = match words.first() {
+                Some(w) if w.eq_ignore_ascii_case("TABLE") => &words[1..],
+                _ => &words[..],
+            };
+            let restart_identity = match words {
+                [_] => true,
+                [_, mode, identity] if identity.eq_ignore_ascii_case("IDENTITY") => {
+                    match mode.to_uppercase().as_str() {
+                        "RESTART" => true,
+                        "CONTINUE" => false,
+                        _ => return Err(usage.to_string()),
+                    }
+                }
+                _ => return Err(usage.to_string()),
+            };
+            Ok(Statement::Truncate { table_name: unquote_token(words[0]).to_string(), restart_identity })
+        }
         "UPDATE" => {
             if tokens.len() < 4 || !tokens[2].eq_ignore_ascii_case("SET") {
                 return Err("Usage: UPDATE <table> SET col = val [, ...] [WHERE <expr>]".to_string());
@@ -1181,8 +2048,12 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
                     return Err("Expected value after '='".into());
                 }
                 let mut val = tokens[idx].trim_end_matches(',').trim_end_matches(';').to_string();
-                if (val.starts_with('"') && val.ends_with('"')) || (val.starts_with('\'') && val.ends_with('\'')) {
-                    val = val[1..val.len() - 1].to_string();
+                if val.eq_ignore_ascii_case("NULL") {
+                    val = crate::storage::row::NULL_MARKER.to_string();
+                } else if let Some(hex) = hex_literal(&val) {
+                    val = hex.to_string();
+                } else if (val.starts_with('"') && val.ends_with('"')) || (val.starts_with('\'') && val.ends_with('\'')) {
+                    val = unquote_token(&val).into_owned();
                 }
                 assignments.push((col, val));
                 idx += 1;
@@ -1195,6 +2066,65 @@ pub fn parse_statement(input: &str) -> Result<Statement, String> {
             };
             Ok(Statement::Update { table_name: table, assignments, selection })
         }
+        "SHOW" => {
+            match tokens.get(1).map(|s| s.to_uppercase()).as_deref() {
+                Some("TABLES") if tokens.len() == 2 => Ok(Statement::ShowTables),
+                Some("INDEXES") | Some("INDEX") => match tokens.len() {
+                    2 => Ok(Statement::ShowIndexes { table: None }),
+                    4 if tokens[2].eq_ignore_ascii_case("FROM") => Ok(Statement::ShowIndexes {
+                        table: Some(unquote_token(&tokens[3]).to_string()),
+                    }),
+                    _ => Err("Usage: SHOW INDEXES [FROM <table>]".to_string()),
+                },
+                _ => Err("Usage: SHOW TABLES | SHOW INDEXES [FROM <table>]".to_string()),
+            }
+        }
+        "DESCRIBE" | "DESC" => match tokens.len() {
+            2 => Ok(Statement::Describe { table: unquote_token(&tokens[1]).to_string() }),
+            _ => Err("Usage: DESCRIBE <table>".to_string()),
+        },
+        "REINDEX" => {
+            let target = tokens.get(2).map(|s| unquote_token(s).to_string());
+            match (tokens.get(1).map(|s| s.to_uppercase()).as_deref(), tokens.len()) {
+                (None, _) => Ok(Statement::Reindex { index: None, table: None }),
+                (Some("INDEX"), 3) => Ok(Statement::Reindex { index: target, table: None }),
+                (Some("TABLE"), 3) => Ok(Statement::Reindex { index: None, table: target }),
+                _ => Err("Usage: REINDEX [INDEX <name> | TABLE <table>]".to_string()),
+            }
+        }
+        "CHECKPOINT" => match tokens.len() {
+            1 => Ok(Statement::Checkpoint),
+            _ => Err("Usage: CHECKPOINT".to_string()),
+        },
+        "VACUUM" => match tokens.len() {
+            1 => Ok(Statement::Vacuum { table: None }),
+            2 => Ok(Statement::Vacuum { table: Some(unquote_token(&tokens[1]).to_string()) }),
+            3 if tokens[1].eq_ignore_ascii_case("TABLE") => {
+                Ok(Statement::Vacuum { table: Some(unquote_token(&tokens[2]).to_string()) })
+            }
+            _ => Err("Usage: VACUUM [[TABLE] <table>]".to_string()),
+        },
+        "PRAGMA" => {
+            // PRAGMA <name> [= <value>]
+            let name = tokens
+                .get(1)
+                .map(|s| unquote_token(s).to_ascii_lowercase())
+                .ok_or("Usage: PRAGMA <name> [= <value>]")?;
+            let value = match tokens.get(2).map(String::as_str) {
+                None => None,
+                Some("=") => Some(
+                    tokens
+                        .get(3)
+                        .map(|s| unquote_token(s).to_string())
+                        .ok_or("Expected value after '='")?,
+                ),
+                Some(_) => return Err("Usage: PRAGMA <name> [= <value>]".to_string()),
+            };
+            if tokens.len() > 4 {
+                return Err("Unexpected token after PRAGMA value".to_string());
+            }
+            Ok(Statement::Pragma { name, value })
+        }
         "EXIT" | ".EXIT" | ".exit" => Ok(Statement::Exit),
         _ => Err(format!("Unrecognized command: {}", tokens[0])),
     }
@@ -1,9 +1,9 @@
 use crate::storage::page::{
-    HEADER_SIZE, NODE_INTERNAL, NODE_LEAF, PAGE_SIZE, get_cell_count, get_next_leaf, get_node_type,
-    get_parent, set_cell_count, set_is_root, set_next_leaf, set_node_type, set_parent,
+    HEADER_SIZE, NODE_INTERNAL, NODE_LEAF, NODE_OVERFLOW, checksum_offset, get_cell_count, get_next_leaf, get_node_type, get_parent, get_subtree_count, set_cell_count,
+    set_is_root, set_next_leaf, set_node_type, set_parent, set_subtree_count,
 };
-use crate::storage::pager::Pager;
-use crate::storage::row::{COMMITTED_BOOTSTRAP_TX, Row, RowData};
+use crate::storage::pager::{Pager, SplitStrategy};
+use crate::storage::row::{COMMITTED_BOOTSTRAP_TX, Row, RowData, RowKey};
 use crate::storage::vacuum::{aborted_creator_is_removable, deleted_version_is_removable};
 use crate::transaction::{
     Snapshot, TransactionId, TransactionStatus, TransactionTable, is_visible,
@@ -11,6 +11,91 @@ use crate::transaction::{
 use log::debug;
 use std::io;
 
+/// Width of a row key in leaf cells and internal separators.
+const KEY_SIZE: usize = std::mem::size_of::<RowKey>();
+
+fn read_key(data: &[u8], offset: usize) -> RowKey {
+    RowKey::from_le_bytes(data[offset..offset + KEY_SIZE].try_into().unwrap())
+}
+
+fn write_key(data: &mut [u8], offset: usize, key: RowKey) {
+    data[offset..offset + KEY_SIZE].copy_from_slice(&key.to_le_bytes());
+}
+
+/// Set in a leaf cell's length field when the payload lives in overflow pages.
+pub(super) const OVERFLOW_FLAG: u32 = 1 << 31;
+
+/// Body of an overflow marker cell: total payload length and first page.
+pub(super) const OVERFLOW_MARKER_SIZE: usize = 8;
+
+/// Node capacities derived from the pager's page size: every limit is a
+/// share of the bytes between the header and the checksum trailer.
+#[derive(Clone, Copy)]
+struct Layout {
+    /// End of the cell area; cells never reach the checksum trailer.
+    cells_end: usize,
+}
+
+impl Layout {
+    fn new(page_size: usize) -> Self {
+        Layout {
+            cells_end: checksum_offset(page_size),
+        }
+    }
+
+    /// Payloads longer than this are spilled to overflow pages, so every leaf
+    /// can hold at least four cells.
+    fn max_inline_payload(self) -> usize {
+        (self.cells_end - HEADER_SIZE) / 4
+    }
+
+    /// Payload bytes stored in each overflow page.
+    fn overflow_capacity(self) -> usize {
+        self.cells_end - HEADER_SIZE
+    }
+
+    /// A non-root leaf whose cells occupy fewer bytes than this after versions
+    /// are physically removed borrows from or merges with a sibling.
+    fn min_leaf_fill(self) -> usize {
+        (self.cells_end - HEADER_SIZE) / 4
+    }
+
+    /// Separator keys that fit in one internal page.
+    fn max_internal_keys(self) -> usize {
+        (self.cells_end - HEADER_SIZE - 4) / (KEY_SIZE + 4)
+    }
+
+    /// A non-root internal node with fewer keys borrows from or merges with a sibling.
+    fn min_internal_keys(self) -> usize {
+        self.max_internal_keys() / 2
+    }
+
+    /// Bytes a payload of `payload_len` occupies in a leaf, including key and length.
+    fn cell_size(self, payload_len: usize) -> usize {
+        let body = if payload_len > self.max_inline_payload() {
+            OVERFLOW_MARKER_SIZE
+        } else {
+            payload_len
+        };
+        KEY_SIZE + 4 + body
+    }
+
+    /// Bytes the cells of `rows` occupy in a leaf.
+    fn rows_size(self, rows: &[Row]) -> usize {
+        rows.iter()
+            .map(|row| self.cell_size(row.serialize_mvcc_payload().len()))
+            .sum()
+    }
+}
+
+/// Integrity-check failure on `page_num`.
+fn corrupt(page_num: u32, msg: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("page {}: {}", page_num, msg),
+    )
+}
+
 /// store multiple separator keys and child pointers.
 ///
 /// Page format is:
@@ -19,15 +104,20 @@ use std::io;
 ///
 /// LEAF body: a sequence of cells:
 ///   for each cell:
-///     [4B key][4B payload_len][payload_bytes]
+///     [8B key][4B payload_len][payload_bytes]
+///   Payloads longer than Layout::max_inline_payload are moved to a chain of
+///   overflow pages; the cell then sets OVERFLOW_FLAG in payload_len and
+///   its body is [4B total_payload_len][4B first_overflow_page].
 ///
 /// INTERNAL body:
 ///   offset = HEADER_SIZE
 ///   [4B leftmost_child_page]
 ///   for i = 0..cell_count-1:
-///     [4B separator_key_i][4B child_page_i]
+///     [8B separator_key_i][4B child_page_i]
 ///
 ///   number of children = cell_count + 1.
+///   The header's next_leaf bytes hold the subtree count: the number of
+///   live (not deleted) row versions in every leaf below the node.
 ///
 ///—————————————————————————————————————————————————————————————————————————————————————————————
 /// On insert:
@@ -48,8 +138,10 @@ use std::io;
 ///—————————————————————————————————————————————————————————————————————————————————————————————
 
 pub struct BTree<'a> {
-    root_page: u32,
-    pager: &'a mut Pager,
+    pub(super) root_page: u32,
+    pub(super) pager: &'a mut Pager,
+    /// Overflow pages released by leaf rewrites, reused before allocating.
+    spare_overflow: Vec<u32>,
 }
 
 impl<'a> BTree<'a> {
@@ -60,7 +152,7 @@ impl<'a> BTree<'a> {
         if pager.file_length_pages() == 0 {
             debug!("Initializing new database: allocating page 0 as a leaf root.");
             let new_root = pager.allocate_page()?;
-            let page = pager.get_page(new_root)?;
+            let page = pager.get_page_mut(new_root)?;
             set_node_type(&mut page.data, NODE_LEAF);
             set_is_root(&mut page.data, true);
             set_parent(&mut page.data, 0);
@@ -72,11 +164,15 @@ impl<'a> BTree<'a> {
             debug!("Opening existing database: using page 0 as root.");
             root_page = 0;
         }
-        Ok(BTree { root_page, pager })
+        Ok(BTree {
+            root_page,
+            pager,
+            spare_overflow: Vec::new(),
+        })
     }
 
     /// Public find: legacy wrapper that returns the newest non-deleted version for a logical key.
-    pub fn find(&mut self, key: i32) -> io::Result<Option<Row>> {
+    pub fn find(&mut self, key: RowKey) -> io::Result<Option<Row>> {
         debug!(
             "find() → starting at root page {} for logical key={}",
             self.root_page, key
@@ -86,7 +182,7 @@ impl<'a> BTree<'a> {
     }
 
     /// Return the newest version of `key` visible to `snapshot`.
-    pub fn find_visible(&mut self, key: i32, snapshot: &Snapshot) -> io::Result<Option<Row>> {
+    pub fn find_visible(&mut self, key: RowKey, snapshot: &Snapshot) -> io::Result<Option<Row>> {
         let rows = self.collect_all_rows()?;
         let tx_table = self.pager.transaction_table().clone();
         let mut visible = None;
@@ -110,7 +206,7 @@ impl<'a> BTree<'a> {
     /// detection before they mark the visible version as deleted.
     pub fn has_write_conflict(
         &mut self,
-        key: i32,
+        key: RowKey,
         visible_created_tx: TransactionId,
         snapshot: &Snapshot,
     ) -> io::Result<bool> {
@@ -145,7 +241,7 @@ impl<'a> BTree<'a> {
         tx_id >= snapshot.xmax || snapshot.active_tx_ids.binary_search(&tx_id).is_ok()
     }
 
-    fn find_latest_logical(&mut self, key: i32) -> io::Result<Option<Row>> {
+    fn find_latest_logical(&mut self, key: RowKey) -> io::Result<Option<Row>> {
         // Physical "newest live version" lookup used by non-snapshot callers such
         // as index probes. Anchor it to the active transaction (if any) so the
         // transaction's own in-flight versions are recognised as own writes
@@ -170,7 +266,7 @@ impl<'a> BTree<'a> {
     }
 
     /// Recursive helper to find a key starting at page `page_num`.
-    fn find_in_page(&mut self, page_num: u32, key: i32) -> io::Result<Option<Row>> {
+    fn find_in_page(&mut self, page_num: u32, key: RowKey) -> io::Result<Option<Row>> {
         let page = self.pager.get_page(page_num)?;
         let node_type = get_node_type(&page.data);
 
@@ -202,9 +298,8 @@ impl<'a> BTree<'a> {
         // For each separator key / child_i pair:
         for _ in 0..cell_count {
             // Read separator key_i
-            let key_i_bytes = &page.data[offset..offset + 4];
-            let key_i = i32::from_le_bytes(key_i_bytes.try_into().unwrap());
-            offset += 4;
+            let key_i = read_key(&page.data, offset);
+            offset += KEY_SIZE;
 
             // Read child_page_i
             let child_i_bytes = &page.data[offset..offset + 4];
@@ -230,7 +325,7 @@ impl<'a> BTree<'a> {
     }
 
     /// Public insert: adds (key, data) into the tree.
-    pub fn insert(&mut self, key: i32, data: RowData) -> io::Result<()> {
+    pub fn insert(&mut self, key: RowKey, data: RowData) -> io::Result<()> {
         debug!(
             "insert() → starting at root {} for key={}",
             self.root_page, key
@@ -251,8 +346,144 @@ impl<'a> BTree<'a> {
         self.insert_row_version_into_page(self.root_page, row)
     }
 
+    /// Build the tree from `rows` sorted by strictly increasing key, packing
+    /// leaves left to right and then building each internal level from the
+    /// one below. The tree must be empty. Returns the new root page.
+    pub fn bulk_load(&mut self, rows: Vec<(RowKey, RowData)>) -> io::Result<u32> {
+        if let Some(pair) = rows.windows(2).find(|pair| pair[0].0 >= pair[1].0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "bulk_load requires strictly increasing keys, got {} before {}",
+                    pair[0].0, pair[1].0
+                ),
+            ));
+        }
+        let created_tx = self
+            .pager
+            .transaction_id()
+            .unwrap_or(COMMITTED_BOOTSTRAP_TX);
+        let rows = rows
+            .into_iter()
+            .map(|(key, data)| {
+                let mut row = Row::new(key, data);
+                row.created_tx = created_tx;
+                row
+            })
+            .collect();
+        self.bulk_load_versions(rows)
+    }
+
+    /// Like [`BTree::bulk_load`], but from row versions sorted by key, each
+    /// keeping its MVCC fields. The versions of one key go to the same leaf.
+    pub fn bulk_load_versions(&mut self, rows: Vec<Row>) -> io::Result<u32> {
+        let root = self.pager.get_page(self.root_page)?;
+        if get_node_type(&root.data) != NODE_LEAF || get_cell_count(&root.data) != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "bulk_load requires an empty tree",
+            ));
+        }
+        if let Some(pair) = rows.windows(2).find(|pair| pair[0].key > pair[1].key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "bulk_load requires keys in order, got {} before {}",
+                    pair[0].key, pair[1].key
+                ),
+            ));
+        }
+
+        // 1) Pack rows into leaves; the existing root becomes the first leaf.
+        let layout = self.layout();
+        let mut level: Vec<(RowKey, u32)> = Vec::new();
+        let mut pending: Vec<Row> = Vec::new();
+        let mut used = 0;
+        for row in rows {
+            let size = layout.cell_size(row.serialize_mvcc_payload().len());
+            let same_key = pending.last().is_some_and(|last| last.key == row.key);
+            if !pending.is_empty() && !same_key && HEADER_SIZE + used + size > layout.cells_end {
+                self.write_bulk_leaf(&mut level, std::mem::take(&mut pending))?;
+                used = 0;
+            }
+            used += size;
+            pending.push(row);
+        }
+        if !pending.is_empty() {
+            self.write_bulk_leaf(&mut level, pending)?;
+        }
+        if level.len() <= 1 {
+            return Ok(self.root_page);
+        }
+
+        // 2) Build internal levels until a single node remains. Children are
+        //    spread evenly so the last node of a level is not left nearly empty.
+        let max_children = layout.max_internal_keys() + 1;
+        while level.len() > 1 {
+            let nodes = level.len().div_ceil(max_children);
+            let per_node = level.len().div_ceil(nodes);
+            let mut next_level = Vec::with_capacity(nodes);
+            for chunk in level.chunks(per_node) {
+                let page_num = self.pager.allocate_page()?;
+                let page = self.pager.get_page_mut(page_num)?;
+                set_node_type(&mut page.data, NODE_INTERNAL);
+                set_is_root(&mut page.data, false);
+                set_parent(&mut page.data, 0);
+                let keys: Vec<RowKey> = chunk[1..].iter().map(|&(key, _)| key).collect();
+                let children: Vec<u32> = chunk.iter().map(|&(_, child)| child).collect();
+                self.write_all_to_internal(page_num, &keys, &children)?;
+                let mut count = 0;
+                for &child in &children {
+                    count += self.subtree_count(child)?;
+                }
+                set_subtree_count(&mut self.pager.get_page_mut(page_num)?.data, count);
+                for &child in &children {
+                    let page = self.pager.get_page_mut(child)?;
+                    set_parent(&mut page.data, page_num);
+                    set_is_root(&mut page.data, false);
+                    self.pager.flush_page(child)?;
+                }
+                next_level.push((chunk[0].0, page_num));
+            }
+            level = next_level;
+        }
+
+        let root_page = level[0].1;
+        let page = self.pager.get_page_mut(root_page)?;
+        set_is_root(&mut page.data, true);
+        self.pager.flush_page(root_page)?;
+        self.root_page = root_page;
+        Ok(root_page)
+    }
+
+    /// Write one bulk-loaded leaf and link it after the previous leaf in `level`.
+    fn write_bulk_leaf(
+        &mut self,
+        level: &mut Vec<(RowKey, u32)>,
+        rows: Vec<Row>,
+    ) -> io::Result<()> {
+        let page_num = match level.last() {
+            None => self.root_page,
+            Some(&(_, prev)) => {
+                let page_num = self.pager.allocate_page()?;
+                let page = self.pager.get_page_mut(page_num)?;
+                set_node_type(&mut page.data, NODE_LEAF);
+                set_is_root(&mut page.data, false);
+                set_parent(&mut page.data, 0);
+                set_next_leaf(&mut page.data, 0);
+                let prev_page = self.pager.get_page_mut(prev)?;
+                set_next_leaf(&mut prev_page.data, page_num);
+                self.pager.flush_page(prev)?;
+                page_num
+            }
+        };
+        self.write_all_rows_to_leaf(page_num, &rows)?;
+        level.push((rows[0].key, page_num));
+        Ok(())
+    }
+
     /// Delete a logical key by marking its newest visible version as deleted.
-    pub fn delete(&mut self, key: i32) -> io::Result<bool> {
+    pub fn delete(&mut self, key: RowKey) -> io::Result<bool> {
         let deleted_tx = self
             .pager
             .transaction_id()
@@ -261,9 +492,10 @@ impl<'a> BTree<'a> {
     }
 
     /// Mark the newest visible version of `key` as deleted without physically rebuilding the tree.
-    pub fn mark_deleted(&mut self, key: i32, deleted_tx: TransactionId) -> io::Result<bool> {
-        let snapshot = Snapshot::new(TransactionId::MAX, Vec::new());
-        let Some(target) = self.find_visible(key, &snapshot)? else {
+    pub fn mark_deleted(&mut self, key: RowKey, deleted_tx: TransactionId) -> io::Result<bool> {
+        // The same newest version `insert` checks for, so a key the live
+        // transaction already rewrote can be deleted and rewritten again.
+        let Some(target) = self.find_latest_logical(key)? else {
             return Ok(false);
         };
         let leaf_page = self.find_leaf_page(self.root_page, key)?;
@@ -276,14 +508,14 @@ impl<'a> BTree<'a> {
             return Ok(false);
         };
         row.deleted_tx = Some(deleted_tx);
-        self.write_all_rows_to_leaf(leaf_page, &rows)?;
+        self.rewrite_leaf(leaf_page, rows)?;
         Ok(true)
     }
 
     /// Mark the version of `key` visible to `snapshot` as deleted.
     pub fn mark_deleted_visible(
         &mut self,
-        key: i32,
+        key: RowKey,
         snapshot: &Snapshot,
         deleted_tx: TransactionId,
     ) -> io::Result<bool> {
@@ -300,11 +532,20 @@ impl<'a> BTree<'a> {
             return Ok(false);
         };
         row.deleted_tx = Some(deleted_tx);
-        self.write_all_rows_to_leaf(leaf_page, &rows)?;
+        self.rewrite_leaf(leaf_page, rows)?;
         Ok(true)
     }
 
-    fn find_leaf_page(&mut self, page_num: u32, key: i32) -> io::Result<u32> {
+    /// Write `rows` back to leaf `page_num`, splitting it if they no longer
+    /// fit; stamping `deleted_tx` grows a cell by eight bytes.
+    fn rewrite_leaf(&mut self, page_num: u32, rows: Vec<Row>) -> io::Result<()> {
+        match self.write_all_rows_to_leaf(page_num, &rows) {
+            Err(e) if e.to_string().starts_with("Leaf overflow") => self.split_leaf(page_num, rows),
+            other => other,
+        }
+    }
+
+    fn find_leaf_page(&mut self, page_num: u32, key: RowKey) -> io::Result<u32> {
         let page = self.pager.get_page(page_num)?;
         let node_type = get_node_type(&page.data);
         if node_type == NODE_LEAF {
@@ -316,9 +557,8 @@ impl<'a> BTree<'a> {
         let mut child_page = u32::from_le_bytes(leftmost_child_bytes.try_into().unwrap());
         offset += 4;
         for _ in 0..cell_count {
-            let key_i_bytes = &page.data[offset..offset + 4];
-            let key_i = i32::from_le_bytes(key_i_bytes.try_into().unwrap());
-            offset += 4;
+            let key_i = read_key(&page.data, offset);
+            offset += KEY_SIZE;
             let child_i_bytes = &page.data[offset..offset + 4];
             let right_child = u32::from_le_bytes(child_i_bytes.try_into().unwrap());
             offset += 4;
@@ -344,10 +584,14 @@ impl<'a> BTree<'a> {
     }
 
     fn collect_all_rows(&mut self) -> io::Result<Vec<Row>> {
+        self.collect_rows(None)
+    }
+
+    fn collect_rows(&mut self, projection: Option<&[bool]>) -> io::Result<Vec<Row>> {
         let mut rows = Vec::new();
         let mut page_num = self.leftmost_leaf_page()?;
         loop {
-            rows.extend(self.read_all_rows_from_leaf(page_num)?);
+            rows.extend(self.read_leaf_rows(page_num, projection)?);
             let next = get_next_leaf(&self.pager.get_page(page_num)?.data);
             if next == 0 {
                 break;
@@ -366,13 +610,15 @@ impl<'a> BTree<'a> {
 
     /// Physically remove deleted row versions that are older than every active
     /// snapshot boundary. This is an explicit maintenance API; it is never
-    /// called automatically by DML paths.
+    /// called automatically by DML paths. Leaves left underfull are then
+    /// rebalanced against a sibling; see [`BTree::rebalance_leaf`].
     pub fn vacuum_deleted_versions(
         &mut self,
         global_xmin: TransactionId,
         tx_table: &TransactionTable,
     ) -> io::Result<usize> {
         let mut removed = 0;
+        let mut shrunk = Vec::new();
         let mut page_num = self.leftmost_leaf_page()?;
         loop {
             let rows = self.read_all_rows_from_leaf(page_num)?;
@@ -387,6 +633,7 @@ impl<'a> BTree<'a> {
             removed += before - kept.len();
             if kept.len() != before {
                 self.write_all_rows_to_leaf(page_num, &kept)?;
+                shrunk.push(page_num);
             }
 
             let next = get_next_leaf(&self.pager.get_page(page_num)?.data);
@@ -395,9 +642,258 @@ impl<'a> BTree<'a> {
             }
             page_num = next;
         }
+
+        // Rebalance once the chain walk is done; a merge unlinks a leaf that
+        // may still be queued.
+        let mut unlinked = std::collections::HashSet::new();
+        for page_num in shrunk {
+            if !unlinked.contains(&page_num) {
+                unlinked.extend(self.rebalance_leaf(page_num)?);
+            }
+        }
         Ok(removed)
     }
 
+    /// Restore the fill of leaf `page_num` if rows were removed from it: move
+    /// whole key groups from an adjacent sibling under the same parent while
+    /// the sibling stays above [`Layout::min_leaf_fill`], updating the separator
+    /// between them. Only when the sibling has nothing to spare are the two
+    /// merged into the left one, which removes a separator from the parent and
+    /// may underflow it in turn. Returns the pages unlinked from the tree.
+    fn rebalance_leaf(&mut self, page_num: u32) -> io::Result<Vec<u32>> {
+        if page_num == self.root_page {
+            return Ok(Vec::new());
+        }
+        let parent = get_parent(&self.pager.get_page(page_num)?.data);
+        let mut unlinked = Vec::new();
+        // Merging two short leaves can leave a short one, so keep going.
+        let mut short = page_num;
+        while let Some((left, right)) = self.rebalance_leaf_pair(parent, short)? {
+            unlinked.push(right);
+            short = left;
+        }
+        if !unlinked.is_empty() {
+            unlinked.extend(self.rebalance_internal(parent)?);
+        }
+        Ok(unlinked)
+    }
+
+    /// One borrow or merge step for leaf `page_num` under `parent`. Returns
+    /// the `(left, right)` pair when right was merged into left.
+    fn rebalance_leaf_pair(
+        &mut self,
+        parent: u32,
+        page_num: u32,
+    ) -> io::Result<Option<(u32, u32)>> {
+        let layout = self.layout();
+        let min_fill = layout.min_leaf_fill();
+        let rows = self.read_all_rows_from_leaf(page_num)?;
+        if layout.rows_size(&rows) >= min_fill {
+            return Ok(None);
+        }
+        let (mut keys, mut children) = self.read_all_from_internal(parent)?;
+        let Some(idx) = children.iter().position(|&child| child == page_num) else {
+            return Err(corrupt(parent, format!("page {} is not a child", page_num)));
+        };
+        if children.len() < 2 {
+            return Ok(None);
+        }
+        // `sep` indexes the separator between the pair, left to right.
+        let sep = if idx + 1 < children.len() {
+            idx
+        } else {
+            idx - 1
+        };
+        let (left, right) = (children[sep], children[sep + 1]);
+        let left_short = left == page_num;
+        let (mut left_rows, mut right_rows) = if left_short {
+            (rows, self.read_all_rows_from_leaf(right)?)
+        } else {
+            (self.read_all_rows_from_leaf(left)?, rows)
+        };
+
+        // Borrow: versions of one key always move together.
+        let mut moved = false;
+        loop {
+            let (short, lender) = if left_short {
+                (&left_rows, &right_rows)
+            } else {
+                (&right_rows, &left_rows)
+            };
+            if layout.rows_size(short) >= min_fill || lender.is_empty() {
+                break;
+            }
+            let edge = if left_short {
+                lender[0].key
+            } else {
+                lender[lender.len() - 1].key
+            };
+            let group = lender.iter().filter(|row| row.key == edge).count();
+            let rest = if left_short {
+                layout.rows_size(&lender[group..])
+            } else {
+                layout.rows_size(&lender[..lender.len() - group])
+            };
+            if rest < min_fill {
+                break;
+            }
+            if left_short {
+                left_rows.extend(right_rows.drain(..group));
+            } else {
+                let tail = left_rows.split_off(left_rows.len() - group);
+                right_rows.splice(0..0, tail);
+            }
+            moved = true;
+        }
+        if moved {
+            self.write_all_rows_to_leaf(left, &left_rows)?;
+            self.write_all_rows_to_leaf(right, &right_rows)?;
+            keys[sep] = right_rows[0].key;
+            self.write_all_to_internal(parent, &keys, &children)?;
+            return Ok(None);
+        }
+
+        // Merge right into left when both fit in one page.
+        left_rows.append(&mut right_rows);
+        if HEADER_SIZE + layout.rows_size(&left_rows) > layout.cells_end {
+            return Ok(None);
+        }
+        let right_next = get_next_leaf(&self.pager.get_page(right)?.data);
+        self.write_all_rows_to_leaf(left, &left_rows)?;
+        // Emptying the right leaf releases its overflow chains and takes its
+        // rows out of the ancestors' counts before it is unlinked.
+        self.write_all_rows_to_leaf(right, &[])?;
+        {
+            let page = self.pager.get_page_mut(left)?;
+            set_next_leaf(&mut page.data, right_next);
+            self.pager.flush_page(left)?;
+        }
+        keys.remove(sep);
+        children.remove(sep + 1);
+        self.write_all_to_internal(parent, &keys, &children)?;
+        Ok(Some((left, right)))
+    }
+
+    /// Restore internal node `page_num` after it lost a child. Below
+    /// [`Layout::min_internal_keys`] it rotates one child through the parent
+    /// separator from a sibling with keys to spare, or else merges with that
+    /// sibling, pulling the separator down. A root left with a single child
+    /// absorbs it. Returns the pages unlinked from the tree.
+    fn rebalance_internal(&mut self, page_num: u32) -> io::Result<Vec<u32>> {
+        let (keys, children) = self.read_all_from_internal(page_num)?;
+        if page_num == self.root_page {
+            if keys.is_empty() {
+                self.collapse_root(children[0])?;
+                return Ok(vec![children[0]]);
+            }
+            return Ok(Vec::new());
+        }
+        let min_keys = self.layout().min_internal_keys();
+        if keys.len() >= min_keys {
+            return Ok(Vec::new());
+        }
+        let parent = get_parent(&self.pager.get_page(page_num)?.data);
+        let (mut parent_keys, mut parent_children) = self.read_all_from_internal(parent)?;
+        let Some(idx) = parent_children.iter().position(|&child| child == page_num) else {
+            return Err(corrupt(parent, format!("page {} is not a child", page_num)));
+        };
+        if parent_children.len() < 2 {
+            return Ok(Vec::new());
+        }
+        let sep = if idx + 1 < parent_children.len() {
+            idx
+        } else {
+            idx - 1
+        };
+        let (left, right) = (parent_children[sep], parent_children[sep + 1]);
+        let left_short = left == page_num;
+        let (mut left_keys, mut left_children) = self.read_all_from_internal(left)?;
+        let (mut right_keys, mut right_children) = self.read_all_from_internal(right)?;
+
+        let lender_keys = if left_short {
+            right_keys.len()
+        } else {
+            left_keys.len()
+        };
+        if lender_keys > min_keys {
+            let (moved, receiver, lender) = if left_short {
+                left_keys.push(parent_keys[sep]);
+                parent_keys[sep] = right_keys.remove(0);
+                let moved = right_children.remove(0);
+                left_children.push(moved);
+                (moved, left, right)
+            } else {
+                right_keys.insert(0, parent_keys[sep]);
+                parent_keys[sep] = left_keys.pop().unwrap();
+                let moved = left_children.pop().unwrap();
+                right_children.insert(0, moved);
+                (moved, right, left)
+            };
+            self.write_all_to_internal(left, &left_keys, &left_children)?;
+            self.write_all_to_internal(right, &right_keys, &right_children)?;
+            self.write_all_to_internal(parent, &parent_keys, &parent_children)?;
+            {
+                let page = self.pager.get_page_mut(moved)?;
+                set_parent(&mut page.data, receiver);
+                self.pager.flush_page(moved)?;
+            }
+            let count = self.subtree_count(moved)? as i64;
+            self.shift_subtree_count(receiver, count)?;
+            self.shift_subtree_count(lender, -count)?;
+            return Ok(Vec::new());
+        }
+
+        // Merge right into left; the parent's total is unchanged.
+        left_keys.push(parent_keys[sep]);
+        left_keys.append(&mut right_keys);
+        for &child in &right_children {
+            let page = self.pager.get_page_mut(child)?;
+            set_parent(&mut page.data, left);
+            self.pager.flush_page(child)?;
+        }
+        left_children.append(&mut right_children);
+        self.write_all_to_internal(left, &left_keys, &left_children)?;
+        let count = self.subtree_count(right)? as i64;
+        self.shift_subtree_count(left, count)?;
+        parent_keys.remove(sep);
+        parent_children.remove(sep + 1);
+        self.write_all_to_internal(parent, &parent_keys, &parent_children)?;
+        let mut unlinked = self.rebalance_internal(parent)?;
+        unlinked.push(right);
+        Ok(unlinked)
+    }
+
+    /// Copy the root's only child into the root page, so the tree loses a
+    /// level while its root page number (recorded in the catalog) stays put.
+    fn collapse_root(&mut self, child: u32) -> io::Result<()> {
+        let data = self.pager.get_page(child)?.data.clone();
+        let root_page = self.root_page;
+        {
+            let root = self.pager.get_page_mut(root_page)?;
+            root.data.copy_from_slice(&data);
+            set_is_root(&mut root.data, true);
+            set_parent(&mut root.data, 0);
+        }
+        self.pager.flush_page(root_page)?;
+        if get_node_type(&data) == NODE_INTERNAL {
+            let (_, grandchildren) = self.read_all_from_internal(root_page)?;
+            for grandchild in grandchildren {
+                let page = self.pager.get_page_mut(grandchild)?;
+                set_parent(&mut page.data, root_page);
+                self.pager.flush_page(grandchild)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add `delta` to the subtree count of internal node `page_num` alone.
+    fn shift_subtree_count(&mut self, page_num: u32, delta: i64) -> io::Result<()> {
+        let page = self.pager.get_page_mut(page_num)?;
+        let count = (get_subtree_count(&page.data) as i64 + delta).max(0) as u32;
+        set_subtree_count(&mut page.data, count);
+        self.pager.flush_page(page_num)
+    }
+
     fn insert_row_version_into_page(&mut self, page_num: u32, row: Row) -> io::Result<()> {
         let page = self.pager.get_page(page_num)?;
         let node_type = get_node_type(&page.data);
@@ -421,8 +917,8 @@ impl<'a> BTree<'a> {
         let mut child_page = u32::from_le_bytes(page.data[offset..offset + 4].try_into().unwrap());
         offset += 4;
         for _ in 0..cell_count {
-            let key_i = i32::from_le_bytes(page.data[offset..offset + 4].try_into().unwrap());
-            offset += 4;
+            let key_i = read_key(&page.data, offset);
+            offset += KEY_SIZE;
             let right_child = u32::from_le_bytes(page.data[offset..offset + 4].try_into().unwrap());
             offset += 4;
             if row.key < key_i {
@@ -434,7 +930,7 @@ impl<'a> BTree<'a> {
     }
 
     /// Recursive helper to insert into page `page_num`. May split leaf or internal pages.
-    fn insert_into_page(&mut self, page_num: u32, key: i32, data: RowData) -> io::Result<()> {
+    fn insert_into_page(&mut self, page_num: u32, key: RowKey, data: RowData) -> io::Result<()> {
         let page = self.pager.get_page(page_num)?;
         let node_type = get_node_type(&page.data);
 
@@ -493,9 +989,8 @@ impl<'a> BTree<'a> {
         offset += 4;
 
         for _ in 0..cell_count {
-            let key_i_bytes = &page.data[offset..offset + 4];
-            let key_i = i32::from_le_bytes(key_i_bytes.try_into().unwrap());
-            offset += 4;
+            let key_i = read_key(&page.data, offset);
+            offset += KEY_SIZE;
 
             let child_i_bytes = &page.data[offset..offset + 4];
             let right_child = u32::from_le_bytes(child_i_bytes.try_into().unwrap());
@@ -524,15 +1019,11 @@ impl<'a> BTree<'a> {
 
     /// Split a leaf page that has overflowed. `all_rows` is the full (sorted) list of rows.
     ///
-    /// - Redistribute half to original page (`leaf_page_num`), half to newly allocated leaf.
+    /// - Redistribute half to original page (`leaf_page_num`), half to newly allocated leaf
+    ///   (see [`BTree::leaf_split_index`] for the append-optimized exception).
     /// - Let `separator_key` = first key of right half.
     /// - Call `insert_in_parent(leaf_page_num, separator_key, new_leaf_page)`.
     fn split_leaf(&mut self, leaf_page_num: u32, all_rows: Vec<Row>) -> io::Result<()> {
-        let total = all_rows.len();
-        let split_index = total / 2;
-        let left_rows = &all_rows[..split_index];
-        let right_rows = &all_rows[split_index..];
-
         // —————————————————————————
         // 1) Read the old next_leaf *after* the left page has been rewritten.
         //    (This ensures we fetch the correct successor in the chain.)
@@ -542,6 +1033,10 @@ impl<'a> BTree<'a> {
             get_next_leaf(&left_page.data)
         };
 
+        let split_index = self.leaf_split_index(&all_rows, old_next == 0);
+        let left_rows = &all_rows[..split_index];
+        let right_rows = &all_rows[split_index..];
+
         // —————————————————————————
         // 2) Allocate a new leaf page. Initialize its header properly:
         //    NODE_TYPE = LEAF, IS_ROOT = false, PARENT = 0 (temporarily),
@@ -549,7 +1044,7 @@ impl<'a> BTree<'a> {
         // —————————————————————————
         let new_leaf = self.pager.allocate_page()?;
         {
-            let mut p = self.pager.get_page(new_leaf)?;
+            let mut p = self.pager.get_page_mut(new_leaf)?;
             set_node_type(&mut p.data, NODE_LEAF);
             set_is_root(&mut p.data, false);
             set_parent(&mut p.data, 0);
@@ -578,13 +1073,13 @@ impl<'a> BTree<'a> {
         // —————————————————————————
         {
             // Link original leaf to new leaf
-            let mut left_page = self.pager.get_page(leaf_page_num)?;
+            let mut left_page = self.pager.get_page_mut(leaf_page_num)?;
             set_next_leaf(&mut left_page.data, new_leaf);
             self.pager.flush_page(leaf_page_num)?;
         }
         {
             // Link new leaf to the old successor
-            let mut new_page = self.pager.get_page(new_leaf)?;
+            let mut new_page = self.pager.get_page_mut(new_leaf)?;
             set_next_leaf(&mut new_page.data, old_next);
             self.pager.flush_page(new_leaf)?;
         }
@@ -596,6 +1091,37 @@ impl<'a> BTree<'a> {
         self.insert_in_parent(leaf_page_num, separator_key, new_leaf)
     }
 
+    /// Where to divide the sorted `rows` of an overflowing leaf. Midpoint by
+    /// default; under [`SplitStrategy::AppendOptimized`] the rightmost leaf
+    /// keeps the longest prefix that fits, ending on a key boundary so all
+    /// versions of a key stay together. Falls back to the midpoint when no
+    /// such prefix leaves a right half that fits too.
+    fn leaf_split_index(&self, rows: &[Row], rightmost: bool) -> usize {
+        let midpoint = rows.len() / 2;
+        if !rightmost || self.pager.split_strategy() != SplitStrategy::AppendOptimized {
+            return midpoint;
+        }
+        let layout = self.layout();
+        let capacity = layout.cells_end - HEADER_SIZE;
+        let sizes: Vec<usize> = rows
+            .iter()
+            .map(|row| layout.cell_size(row.serialize_mvcc_payload().len()))
+            .collect();
+        let mut split = 0;
+        let mut used = 0;
+        while split < rows.len() && used + sizes[split] <= capacity {
+            used += sizes[split];
+            split += 1;
+        }
+        while split > 0 && split < rows.len() && rows[split - 1].key == rows[split].key {
+            split -= 1;
+        }
+        if split == 0 || split == rows.len() || sizes[split..].iter().sum::<usize>() > capacity {
+            return midpoint;
+        }
+        split
+    }
+
     /// Insert a new (separator_key, new_child_page) entry into the parent of `old_page`.
     ///
     /// If `old_page` was the root, create a new root (internal) at page 0 or a newly allocated page.
@@ -603,7 +1129,7 @@ impl<'a> BTree<'a> {
     fn insert_in_parent(
         &mut self,
         old_page: u32,
-        separator_key: i32,
+        separator_key: RowKey,
         new_page: u32,
     ) -> io::Result<()> {
         // Find parent page number
@@ -617,20 +1143,23 @@ impl<'a> BTree<'a> {
 
             // old_page was the root. Create a new root internal node.
             // Allocate a fresh page to be the new root (we’ll keep old_page and new_page as children).
+            let count = self.subtree_count(old_page)? + self.subtree_count(new_page)?;
             let new_root = self.pager.allocate_page()?;
             {
-                let root = self.pager.get_page(new_root)?;
+                let root = self.pager.get_page_mut(new_root)?;
                 set_node_type(&mut root.data, NODE_INTERNAL);
                 set_is_root(&mut root.data, true);
                 set_parent(&mut root.data, 0); // root’s parent = 0
+                set_subtree_count(&mut root.data, count);
 
                 // Leftmost child pointer = old_page
                 root.data[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&old_page.to_le_bytes());
 
                 // One separator: [separator_key][new_page]
                 let offset = HEADER_SIZE + 4;
-                root.data[offset..offset + 4].copy_from_slice(&separator_key.to_le_bytes());
-                root.data[offset + 4..offset + 8].copy_from_slice(&new_page.to_le_bytes());
+                write_key(&mut root.data, offset, separator_key);
+                root.data[offset + KEY_SIZE..offset + KEY_SIZE + 4]
+                    .copy_from_slice(&new_page.to_le_bytes());
 
                 // Set cell_count = 1
                 set_cell_count(&mut root.data, 1);
@@ -638,11 +1167,11 @@ impl<'a> BTree<'a> {
 
             // Update parent pointers of both children
             {
-                let p_old = self.pager.get_page(old_page)?;
+                let p_old = self.pager.get_page_mut(old_page)?;
                 set_parent(&mut p_old.data, new_root);
             }
             {
-                let p_new = self.pager.get_page(new_page)?;
+                let p_new = self.pager.get_page_mut(new_page)?;
                 set_parent(&mut p_new.data, new_root);
             }
 
@@ -709,13 +1238,16 @@ impl<'a> BTree<'a> {
                     separator_key, parent_page
                 );
                 {
-                    let c1 = self.pager.get_page(old_page)?;
+                    let c1 = self.pager.get_page_mut(old_page)?;
                     set_parent(&mut c1.data, parent_page);
                 }
                 {
-                    let c2 = self.pager.get_page(new_page)?;
+                    let c2 = self.pager.get_page_mut(new_page)?;
                     set_parent(&mut c2.data, parent_page);
                 }
+                // The new child's rows were written before it had a parent.
+                let added = self.subtree_count(new_page)?;
+                self.add_to_subtree_counts(parent_page, added as i64)?;
                 return Ok(());
             }
             Err(e) => {
@@ -724,7 +1256,8 @@ impl<'a> BTree<'a> {
                         "  → Internal overflow at page {}! Splitting internal node.",
                         parent_page
                     );
-                    return self.split_internal(parent_page, keys, children);
+                    let appended = insert_idx + 1 == keys.len();
+                    return self.split_internal(parent_page, keys, children, appended);
                 } else {
                     return Err(e);
                 }
@@ -738,11 +1271,16 @@ impl<'a> BTree<'a> {
     ///   left_keys = keys[0..mid], right_keys = keys[mid+1..]
     ///   left_children = children[0..mid+1], right_children = children[mid+1..]
     ///   The middle key (keys[mid]) is “pushed up” to the parent via insert_in_parent.
+    ///
+    /// Under [`SplitStrategy::AppendOptimized`], when the new child was `appended`
+    /// as the last one, mid is instead the second-to-last key, so the right node
+    /// starts with a single key and two children.
     fn split_internal(
         &mut self,
         page_num: u32,
-        keys: Vec<i32>,
+        keys: Vec<RowKey>,
         children: Vec<u32>,
+        appended: bool,
     ) -> io::Result<()> {
         debug!(
             "split_internal: splitting internal page {} with {} keys.",
@@ -751,7 +1289,14 @@ impl<'a> BTree<'a> {
         );
 
         let total_keys = keys.len();
-        let mid_index = total_keys / 2;
+        let mid_index = if appended
+            && total_keys >= 3
+            && self.pager.split_strategy() == SplitStrategy::AppendOptimized
+        {
+            total_keys - 2
+        } else {
+            total_keys / 2
+        };
 
         // The separator to push up
         let separator_key = keys[mid_index];
@@ -764,8 +1309,30 @@ impl<'a> BTree<'a> {
         let right_keys = &keys[(mid_index + 1)..];
         let right_children = &children[(mid_index + 1)..];
 
+        // Recount both halves: the stored count of `page_num` does not yet
+        // include the child that caused the overflow.
+        let mut left_count = 0;
+        for &child in left_children {
+            left_count += self.subtree_count(child)?;
+        }
+        let mut right_count = 0;
+        for &child in right_children {
+            right_count += self.subtree_count(child)?;
+        }
+
         // Rewrite the current page (page_num) as an internal node containing left_keys/left_children
         self.write_all_to_internal(page_num, left_keys, left_children)?;
+        let old_count = get_subtree_count(&self.pager.get_page(page_num)?.data);
+        set_subtree_count(&mut self.pager.get_page_mut(page_num)?.data, left_count);
+        self.pager.flush_page(page_num)?;
+        if page_num != self.root_page {
+            let parent = get_parent(&self.pager.get_page(page_num)?.data);
+            self.add_to_subtree_counts(parent, left_count as i64 - old_count as i64)?;
+        }
+        for &child in left_children {
+            let c = self.pager.get_page_mut(child)?;
+            set_parent(&mut c.data, page_num);
+        }
 
         debug!(
             "  → Wrote {} keys to left internal {}.",
@@ -776,11 +1343,12 @@ impl<'a> BTree<'a> {
         // Allocate a new internal page for the right half
         let new_internal = self.pager.allocate_page()?;
         {
-            let ni = self.pager.get_page(new_internal)?;
+            let ni = self.pager.get_page_mut(new_internal)?;
             set_node_type(&mut ni.data, NODE_INTERNAL);
             set_is_root(&mut ni.data, false);
             set_parent(&mut ni.data, 0); // will fix below
             set_cell_count(&mut ni.data, 0);
+            set_subtree_count(&mut ni.data, right_count);
         }
         self.write_all_to_internal(new_internal, right_keys, right_children)?;
 
@@ -792,7 +1360,7 @@ impl<'a> BTree<'a> {
 
         // Update parent pointers for all children of new_internal
         for &child in right_children {
-            let c = self.pager.get_page(child)?;
+            let c = self.pager.get_page_mut(child)?;
             set_parent(&mut c.data, new_internal);
         }
 
@@ -807,27 +1375,29 @@ impl<'a> BTree<'a> {
             // Create a brand-new root (internal) at a fresh page
             let new_root = self.pager.allocate_page()?;
             {
-                let nr = self.pager.get_page(new_root)?;
+                let nr = self.pager.get_page_mut(new_root)?;
                 set_node_type(&mut nr.data, NODE_INTERNAL);
                 set_is_root(&mut nr.data, true);
                 set_parent(&mut nr.data, 0); // root’s parent = 0
                 set_cell_count(&mut nr.data, 1);
+                set_subtree_count(&mut nr.data, left_count + right_count);
 
                 // Leftmost child = old root (page_num)
                 nr.data[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&page_num.to_le_bytes());
 
                 // One separator: [separator_key][new_internal]
                 let off = HEADER_SIZE + 4;
-                nr.data[off..off + 4].copy_from_slice(&separator_key.to_le_bytes());
-                nr.data[off + 4..off + 8].copy_from_slice(&new_internal.to_le_bytes());
+                write_key(&mut nr.data, off, separator_key);
+                nr.data[off + KEY_SIZE..off + KEY_SIZE + 4]
+                    .copy_from_slice(&new_internal.to_le_bytes());
             }
             // Update parent pointers of the two children
             {
-                let p_old = self.pager.get_page(page_num)?;
+                let p_old = self.pager.get_page_mut(page_num)?;
                 set_parent(&mut p_old.data, new_root);
             }
             {
-                let p_new = self.pager.get_page(new_internal)?;
+                let p_new = self.pager.get_page_mut(new_internal)?;
                 set_parent(&mut p_new.data, new_root);
             }
             // Flush pages
@@ -856,6 +1426,16 @@ impl<'a> BTree<'a> {
 
     /// Read back every row from a leaf page.  We assume `HEADER_SIZE=12`.
     fn read_all_rows_from_leaf(&mut self, page_num: u32) -> io::Result<Vec<Row>> {
+        self.read_leaf_rows(page_num, None)
+    }
+
+    /// Read back every row from a leaf page, decoding only the columns
+    /// marked in `projection` when one is given.
+    fn read_leaf_rows(
+        &mut self,
+        page_num: u32,
+        projection: Option<&[bool]>,
+    ) -> io::Result<Vec<Row>> {
         let page = self.pager.get_page(page_num)?;
         if get_node_type(&page.data) != NODE_LEAF {
             return Err(io::Error::new(
@@ -869,90 +1449,302 @@ impl<'a> BTree<'a> {
         let mut offset = HEADER_SIZE; // 12
 
         for _ in 0..cell_count {
-            // 1) 4 bytes key
-            let key = i32::from_le_bytes(page.data[offset..offset + 4].try_into().unwrap());
-            // 2) 4 bytes payload length
-            let payload_len =
-                u32::from_le_bytes(page.data[offset + 4..offset + 8].try_into().unwrap()) as usize;
-            // 3) payload bytes
-            let start = offset + 8;
-            let end = start + payload_len;
-            if end > PAGE_SIZE {
+            let (row, end) = self.read_cell(page_num, offset, projection)?;
+            rows.push(row);
+            offset = end;
+        }
+
+        Ok(rows)
+    }
+
+    /// Decode the leaf cell at `offset`, following its overflow chain if the
+    /// payload was spilled. Returns the row and the offset of the next cell.
+    fn read_cell(
+        &mut self,
+        page_num: u32,
+        offset: usize,
+        projection: Option<&[bool]>,
+    ) -> io::Result<(Row, usize)> {
+        let page = self.pager.get_page(page_num)?;
+        // 1) 8 bytes key
+        let key = read_key(&page.data, offset);
+        // 2) 4 bytes payload length, with the overflow flag in the top bit
+        let len_at = offset + KEY_SIZE;
+        let len_field = u32::from_le_bytes(page.data[len_at..len_at + 4].try_into().unwrap());
+        let body_len = (len_field & !OVERFLOW_FLAG) as usize;
+        // 3) payload bytes, or the overflow marker
+        let start = len_at + 4;
+        let end = start + body_len;
+        if end > page.data.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "read_all_rows_from_leaf: corrupt payload length",
+            ));
+        }
+        if len_field & OVERFLOW_FLAG == 0 {
+            let (row, decoded) =
+                Row::deserialize_mvcc_payload_projected(key, &page.data[start..end], projection)?;
+            self.pager.note_bytes_decoded(decoded);
+            return Ok((row, end));
+        }
+        if body_len != OVERFLOW_MARKER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "read_all_rows_from_leaf: corrupt overflow marker",
+            ));
+        }
+        let total = u32::from_le_bytes(page.data[start..start + 4].try_into().unwrap()) as usize;
+        let first = u32::from_le_bytes(page.data[start + 4..end].try_into().unwrap());
+        let bytes = self.read_overflow(first, total)?;
+        let (row, decoded) = Row::deserialize_mvcc_payload_projected(key, &bytes, projection)?;
+        self.pager.note_bytes_decoded(decoded);
+        Ok((row, end))
+    }
+
+    /// Reassemble `total` payload bytes from the overflow chain starting at `first`.
+    pub(super) fn read_overflow(&mut self, first: u32, total: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(total);
+        let mut page_num = first;
+        while bytes.len() < total {
+            if page_num == 0 || page_num >= self.pager.num_pages() {
                 return Err(io::Error::new(
                     io::ErrorKind::Other,
-                    "read_all_rows_from_leaf: corrupt payload length",
+                    format!(
+                        "overflow chain ends after {} of {} bytes",
+                        bytes.len(),
+                        total
+                    ),
                 ));
             }
-            let bytes = &page.data[start..end];
-            let row = Row::deserialize_mvcc_payload(key, bytes)?;
-
-            rows.push(row);
-            offset = end;
+            let page = self.pager.get_page(page_num)?;
+            let chunk = get_cell_count(&page.data) as usize;
+            if get_node_type(&page.data) != NODE_OVERFLOW
+                || chunk > Layout::new(page.data.len()).overflow_capacity()
+                || bytes.len() + chunk > total
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("page {} is not a valid overflow page", page_num),
+                ));
+            }
+            bytes.extend_from_slice(&page.data[HEADER_SIZE..HEADER_SIZE + chunk]);
+            page_num = get_next_leaf(&page.data);
         }
+        Ok(bytes)
+    }
 
-        Ok(rows)
+    /// Store `payload` in a chain of overflow pages and return the first page.
+    pub(super) fn write_overflow(&mut self, payload: &[u8]) -> io::Result<u32> {
+        let chunks: Vec<&[u8]> = payload.chunks(self.layout().overflow_capacity()).collect();
+        let mut pages = Vec::with_capacity(chunks.len());
+        for _ in 0..chunks.len() {
+            let page_num = match self.spare_overflow.pop() {
+                Some(page_num) => page_num,
+                None => self.pager.allocate_page()?,
+            };
+            pages.push(page_num);
+        }
+        for (i, chunk) in chunks.iter().enumerate() {
+            let page = self.pager.get_page_mut(pages[i])?;
+            page.data.fill(0);
+            set_node_type(&mut page.data, NODE_OVERFLOW);
+            set_cell_count(&mut page.data, chunk.len() as u16);
+            set_next_leaf(&mut page.data, pages.get(i + 1).copied().unwrap_or(0));
+            page.data[HEADER_SIZE..HEADER_SIZE + chunk.len()].copy_from_slice(chunk);
+            self.pager.flush_page(pages[i])?;
+        }
+        Ok(pages[0])
     }
 
-    /// Write a complete sorted list of rows into a leaf page.
-    fn write_all_rows_to_leaf(&mut self, page_num: u32, rows: &[Row]) -> io::Result<()> {
+    /// Pages of every overflow chain referenced from the cells of leaf `page_num`.
+    fn overflow_pages(&mut self, page_num: u32) -> io::Result<Vec<u32>> {
         let page = self.pager.get_page(page_num)?;
+        if get_node_type(&page.data) != NODE_LEAF {
+            return Ok(Vec::new());
+        }
+        let mut firsts = Vec::new();
+        let mut offset = HEADER_SIZE;
+        for _ in 0..get_cell_count(&page.data) {
+            let len_at = offset + KEY_SIZE;
+            if len_at + 4 > page.data.len() {
+                break;
+            }
+            let len_field = u32::from_le_bytes(page.data[len_at..len_at + 4].try_into().unwrap());
+            let start = len_at + 4;
+            offset = start + (len_field & !OVERFLOW_FLAG) as usize;
+            if offset > page.data.len() {
+                break;
+            }
+            if len_field & OVERFLOW_FLAG != 0 && offset - start == OVERFLOW_MARKER_SIZE {
+                firsts.push(u32::from_le_bytes(
+                    page.data[start + 4..offset].try_into().unwrap(),
+                ));
+            }
+        }
 
-        // 1) Compute total size of all row‐bodies (4B key + 4B length + payload.len())
-        let mut total_size: usize = 0;
-        for row in rows {
-            let bytes = row.serialize_mvcc_payload();
-            total_size += 4; // key
-            total_size += 4; // length
-            total_size += bytes.len();
+        let mut pages = Vec::new();
+        for mut next in firsts {
+            while next != 0 && next < self.pager.num_pages() && !pages.contains(&next) {
+                let page = self.pager.get_page(next)?;
+                if get_node_type(&page.data) != NODE_OVERFLOW {
+                    break;
+                }
+                pages.push(next);
+                next = get_next_leaf(&page.data);
+            }
         }
+        Ok(pages)
+    }
 
-        // 2) Check overflow: if HEADER_SIZE + total_size > PAGE_SIZE, it truly won't fit
-        if HEADER_SIZE + total_size > PAGE_SIZE {
+    /// Write a complete sorted list of rows into a leaf page.
+    fn write_all_rows_to_leaf(&mut self, page_num: u32, rows: &[Row]) -> io::Result<()> {
+        // 1) Compute total size of all cells (8B key + 4B length + inline body)
+        let payloads: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|row| row.serialize_mvcc_payload())
+            .collect();
+        let layout = self.layout();
+        let total_size: usize = payloads.iter().map(|bytes| layout.cell_size(bytes.len())).sum();
+
+        // 2) Check overflow: cells must end before the checksum trailer
+        if HEADER_SIZE + total_size > layout.cells_end {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "Leaf overflow: not enough space",
             ));
         }
 
-        // 3) Zero‐out everything AFTER the 12B header (we do NOT touch offsets [0..12))
-        for idx in HEADER_SIZE..PAGE_SIZE {
-            page.data[idx] = 0;
+        // 3) The cells being replaced give up their overflow chains; spill
+        //    long payloads into those pages first. Count their live rows
+        //    before the chains are reused.
+        let old_live = self.live_cells(page_num)?;
+        let released = self.overflow_pages(page_num)?;
+        self.spare_overflow.extend(released);
+        let mut cells = Vec::with_capacity(rows.len());
+        for (row, bytes) in rows.iter().zip(payloads) {
+            if bytes.len() > layout.max_inline_payload() {
+                let first = self.write_overflow(&bytes)?;
+                let mut marker = Vec::with_capacity(OVERFLOW_MARKER_SIZE);
+                marker.extend(&(bytes.len() as u32).to_le_bytes());
+                marker.extend(&first.to_le_bytes());
+                cells.push((row.key, OVERFLOW_FLAG | marker.len() as u32, marker));
+            } else {
+                cells.push((row.key, bytes.len() as u32, bytes));
+            }
         }
 
-        // 4) Pack each row at offset = HEADER_SIZE
+        // 4) Zero‐out everything AFTER the 12B header (we do NOT touch offsets [0..12))
+        let page = self.pager.get_page_mut(page_num)?;
+        page.data[HEADER_SIZE..].fill(0);
+
+        // 5) Pack each cell at offset = HEADER_SIZE
         let mut offset = HEADER_SIZE;
-        for row in rows {
-            let bytes = row.serialize_mvcc_payload();
-            // 4A: 4 bytes for key
-            let key_bytes = row.key.to_le_bytes();
-            page.data[offset..offset + 4].copy_from_slice(&key_bytes);
+        for (key, len_field, body) in &cells {
+            // 5A: 8 bytes for key
+            write_key(&mut page.data, offset, *key);
 
-            // 4B: 4 bytes for payload length
-            let plen = (bytes.len() as u32).to_le_bytes();
-            page.data[offset + 4..offset + 8].copy_from_slice(&plen);
+            // 5B: 4 bytes for payload length (and overflow flag)
+            let len_at = offset + KEY_SIZE;
+            page.data[len_at..len_at + 4].copy_from_slice(&len_field.to_le_bytes());
 
-            // 4C: payload bytes
-            let start = offset + 8;
-            let end = start + bytes.len();
-            page.data[start..end].copy_from_slice(&bytes);
+            // 5C: payload bytes or overflow marker
+            let start = len_at + 4;
+            let end = start + body.len();
+            page.data[start..end].copy_from_slice(body);
 
             offset = end;
         }
 
-        // 5) Update the cell_count (u16) at offsets 6..8
+        // 6) Update the cell_count (u16) at offsets 6..8
         set_cell_count(&mut page.data, rows.len() as u16);
 
-        // 6) Flush so that the “next_leaf” field (bytes 8..12) is preserved
+        // 7) Flush so that the “next_leaf” field (bytes 8..12) is preserved
+        let parent = get_parent(&page.data);
         self.pager.flush_page(page_num)?;
+
+        // 8) Carry the change in live rows up to every ancestor's count
+        let new_live = rows.iter().filter(|row| row.deleted_tx.is_none()).count() as i64;
+        if page_num != self.root_page {
+            self.add_to_subtree_counts(parent, new_live - old_live as i64)?;
+        }
+        Ok(())
+    }
+
+    /// Number of cells on leaf `page_num` whose version is not deleted. Only
+    /// the payload prefix is inspected, so a spilled payload costs one page.
+    fn live_cells(&mut self, page_num: u32) -> io::Result<u32> {
+        let page = self.pager.get_page(page_num)?;
+        let cell_count = get_cell_count(&page.data) as usize;
+        let mut live = 0;
+        let mut spilled = Vec::new();
+        let mut offset = HEADER_SIZE;
+        for _ in 0..cell_count {
+            let len_at = offset + KEY_SIZE;
+            let len_field = u32::from_le_bytes(page.data[len_at..len_at + 4].try_into().unwrap());
+            let start = len_at + 4;
+            let end = start + (len_field & !OVERFLOW_FLAG) as usize;
+            if end > page.data.len() {
+                return Err(corrupt(page_num, "corrupt payload length".into()));
+            }
+            if len_field & OVERFLOW_FLAG == 0 {
+                if !Row::mvcc_payload_is_deleted(&page.data[start..end]) {
+                    live += 1;
+                }
+            } else if end - start == OVERFLOW_MARKER_SIZE {
+                spilled.push(u32::from_le_bytes(
+                    page.data[start + 4..end].try_into().unwrap(),
+                ));
+            } else {
+                return Err(corrupt(page_num, "corrupt overflow marker".into()));
+            }
+            offset = end;
+        }
+        for first in spilled {
+            let page = self.pager.get_page(first)?;
+            if !Row::mvcc_payload_is_deleted(&page.data[HEADER_SIZE..]) {
+                live += 1;
+            }
+        }
+        Ok(live)
+    }
+
+    /// Live rows below `page_num`: counted on a leaf, stored on an internal node.
+    fn subtree_count(&mut self, page_num: u32) -> io::Result<u32> {
+        let page = self.pager.get_page(page_num)?;
+        if get_node_type(&page.data) == NODE_INTERNAL {
+            Ok(get_subtree_count(&page.data))
+        } else {
+            self.live_cells(page_num)
+        }
+    }
+
+    /// Add `delta` to the subtree count of internal node `page_num` and of
+    /// each of its ancestors up to the root.
+    fn add_to_subtree_counts(&mut self, mut page_num: u32, delta: i64) -> io::Result<()> {
+        while delta != 0 && page_num != 0 {
+            let page = self.pager.get_page_mut(page_num)?;
+            if get_node_type(&page.data) != NODE_INTERNAL {
+                break;
+            }
+            let count = (get_subtree_count(&page.data) as i64 + delta).max(0) as u32;
+            set_subtree_count(&mut page.data, count);
+            let parent = if page_num == self.root_page {
+                0
+            } else {
+                get_parent(&page.data)
+            };
+            self.pager.flush_page(page_num)?;
+            page_num = parent;
+        }
         Ok(())
     }
 
     /// Read all keys and children from an internal node into (keys, children).
     ///
     /// Returns:
-    ///   keys: Vec<i32> of length = cell_count
+    ///   keys: Vec<RowKey> of length = cell_count
     ///   children: Vec<u32> of length = cell_count + 1
-    fn read_all_from_internal(&mut self, page_num: u32) -> io::Result<(Vec<i32>, Vec<u32>)> {
+    fn read_all_from_internal(&mut self, page_num: u32) -> io::Result<(Vec<RowKey>, Vec<u32>)> {
         let page = self.pager.get_page(page_num)?;
         let node_type = get_node_type(&page.data);
         if node_type != NODE_INTERNAL {
@@ -974,9 +1766,8 @@ impl<'a> BTree<'a> {
         offset += 4;
 
         for _ in 0..cell_count {
-            let key_bytes = &page.data[offset..offset + 4];
-            let key = i32::from_le_bytes(key_bytes.try_into().unwrap());
-            offset += 4;
+            let key = read_key(&page.data, offset);
+            offset += KEY_SIZE;
             let child_bytes = &page.data[offset..offset + 4];
             let child = u32::from_le_bytes(child_bytes.try_into().unwrap());
             offset += 4;
@@ -994,7 +1785,7 @@ impl<'a> BTree<'a> {
     fn write_all_to_internal(
         &mut self,
         page_num: u32,
-        keys: &[i32],
+        keys: &[RowKey],
         children: &[u32],
     ) -> io::Result<()> {
         if children.len() != keys.len() + 1 {
@@ -1005,20 +1796,18 @@ impl<'a> BTree<'a> {
         }
 
         // Compute required size:
-        // HEADER_SIZE + 4 (leftmost child) + keys.len()*(4+4)
-        let required = HEADER_SIZE + 4 + keys.len() * 8;
-        if required > PAGE_SIZE {
+        // HEADER_SIZE + 4 (leftmost child) + keys.len()*(KEY_SIZE+4)
+        let required = HEADER_SIZE + 4 + keys.len() * (KEY_SIZE + 4);
+        if required > self.layout().cells_end {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "Internal overflow: not enough space",
             ));
         }
 
-        let page = self.pager.get_page(page_num)?;
+        let page = self.pager.get_page_mut(page_num)?;
         // Zero out body:
-        for idx in HEADER_SIZE..PAGE_SIZE {
-            page.data[idx] = 0;
-        }
+        page.data[HEADER_SIZE..].fill(0);
 
         // Write leftmost child
         let mut offset = HEADER_SIZE;
@@ -1028,9 +1817,8 @@ impl<'a> BTree<'a> {
 
         // Write each [key][child] pair
         for i in 0..keys.len() {
-            let key_bytes = keys[i].to_le_bytes();
-            page.data[offset..offset + 4].copy_from_slice(&key_bytes);
-            offset += 4;
+            write_key(&mut page.data, offset, keys[i]);
+            offset += KEY_SIZE;
             let child_bytes = children[i + 1].to_le_bytes();
             page.data[offset..offset + 4].copy_from_slice(&child_bytes);
             offset += 4;
@@ -1042,23 +1830,171 @@ impl<'a> BTree<'a> {
         Ok(())
     }
 
+    /// Allocate an empty leaf to serve as the root of a new tree and return its
+    /// page. Page 0 holds the pager's meta data, so in a fresh file it is
+    /// skipped.
+    pub fn create_empty(pager: &mut Pager) -> io::Result<u32> {
+        if pager.num_pages() == 0 {
+            pager.allocate_page()?;
+        }
+        let root_page = pager.allocate_page()?;
+        let page = pager.get_page_mut(root_page)?;
+        set_node_type(&mut page.data, NODE_LEAF);
+        set_is_root(&mut page.data, true);
+        set_parent(&mut page.data, 0);
+        set_cell_count(&mut page.data, 0);
+        set_next_leaf(&mut page.data, 0);
+        pager.flush_page(root_page)?;
+        Ok(root_page)
+    }
+
     pub fn open_root(pager: &'a mut Pager, root_page: u32) -> io::Result<Self> {
-        Ok(BTree { root_page, pager })
+        Ok(BTree {
+            root_page,
+            pager,
+            spare_overflow: Vec::new(),
+        })
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::new(self.pager.page_size())
     }
 
     /// Return the page number of the current root node. Callers can use this
     /// after insertions to detect if the root has split.
+    /// Number of live (not deleted) row versions in the tree, read from the
+    /// root's subtree count, which is the sum of its children's counts.
+    /// Versions whose creator has not committed are included, so this equals
+    /// a visible scan only when no transaction is active or aborted.
+    pub fn row_count(&mut self) -> io::Result<usize> {
+        Ok(self.subtree_count(self.root_page)? as usize)
+    }
+
     pub fn root_page(&self) -> u32 {
         self.root_page
     }
 
+    /// Every page of the tree: its internal nodes, its leaves, and the
+    /// overflow pages of their rows.
+    pub fn pages(&mut self) -> io::Result<Vec<u32>> {
+        let mut pages = Vec::new();
+        let mut stack = vec![self.root_page];
+        while let Some(page_num) = stack.pop() {
+            pages.push(page_num);
+            if get_node_type(&self.pager.get_page(page_num)?.data) == NODE_INTERNAL {
+                stack.extend(self.read_all_from_internal(page_num)?.1);
+            } else {
+                pages.extend(self.overflow_pages(page_num)?);
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Follow parent pointers up from the page this tree was opened at to the
+    /// root of the tree that now contains it. A root split moves the root to a
+    /// new page; when the catalog row recording that move is rolled back with
+    /// an aborted transaction, the split itself stays (data pages are not
+    /// reverted), leaving the recorded root a child of the live one. Stops at
+    /// a page without a parent or whose parent does not list it as a child.
+    pub fn live_root(&mut self) -> io::Result<u32> {
+        let mut page_num = self.root_page;
+        let mut visited = std::collections::HashSet::new();
+        while visited.insert(page_num) {
+            let parent = get_parent(&self.pager.get_page(page_num)?.data);
+            if parent == 0 || parent >= self.pager.num_pages() {
+                break;
+            }
+            if get_node_type(&self.pager.get_page(parent)?.data) != NODE_INTERNAL {
+                break;
+            }
+            let (_, children) = self.read_all_from_internal(parent)?;
+            if !children.contains(&page_num) {
+                break;
+            }
+            page_num = parent;
+        }
+        self.root_page = page_num;
+        Ok(page_num)
+    }
+
     pub fn scan_all_rows(&'a mut self) -> RowCursor<'a> {
         self.scan_rows_with_bounds(0, None)
     }
 
     /// Return one visible version per logical key for `snapshot`.
     pub fn scan_visible(&mut self, snapshot: &Snapshot) -> io::Result<Vec<Row>> {
-        let mut rows = self.collect_all_rows()?;
+        let rows = self.collect_all_rows()?;
+        Ok(self.newest_visible(rows, snapshot))
+    }
+
+    /// Like `scan_visible`, decoding only the columns whose index is marked
+    /// in `projection`; the other columns of each row are left as
+    /// `ColumnValue::Null`.
+    pub fn scan_visible_projected(
+        &mut self,
+        snapshot: &Snapshot,
+        projection: &[bool],
+    ) -> io::Result<Vec<Row>> {
+        let rows = self.collect_rows(Some(projection))?;
+        Ok(self.newest_visible(rows, snapshot))
+    }
+
+    /// Like `scan_visible`, restricted to keys within `lower` and `upper`.
+    /// Each bound is `(key, inclusive)`. The walk starts at the leaf holding
+    /// the lower bound and stops at the first key past the upper one.
+    pub fn scan_visible_range(
+        &mut self,
+        lower: Option<(RowKey, bool)>,
+        upper: Option<(RowKey, bool)>,
+        snapshot: &Snapshot,
+    ) -> io::Result<Vec<Row>> {
+        self.scan_range(lower, upper, snapshot, None)
+    }
+
+    /// `scan_visible_range` with the column projection of
+    /// `scan_visible_projected`.
+    pub fn scan_visible_range_projected(
+        &mut self,
+        lower: Option<(RowKey, bool)>,
+        upper: Option<(RowKey, bool)>,
+        snapshot: &Snapshot,
+        projection: &[bool],
+    ) -> io::Result<Vec<Row>> {
+        self.scan_range(lower, upper, snapshot, Some(projection))
+    }
+
+    fn scan_range(
+        &mut self,
+        lower: Option<(RowKey, bool)>,
+        upper: Option<(RowKey, bool)>,
+        snapshot: &Snapshot,
+        projection: Option<&[bool]>,
+    ) -> io::Result<Vec<Row>> {
+        let mut page_num = match lower {
+            Some((key, inclusive)) => self.leaf_for_bound(key, inclusive)?,
+            None => self.leftmost_leaf_page()?,
+        };
+        let mut rows = Vec::new();
+        'leaves: loop {
+            for row in self.read_leaf_rows(page_num, projection)? {
+                if below_lower(row.key, lower) {
+                    continue;
+                }
+                if above_upper(row.key, upper) {
+                    break 'leaves;
+                }
+                rows.push(row);
+            }
+            page_num = get_next_leaf(&self.pager.get_page(page_num)?.data);
+            if page_num == 0 {
+                break;
+            }
+        }
+        Ok(self.newest_visible(rows, snapshot))
+    }
+
+    /// Keep the newest version of each key that `snapshot` can see.
+    fn newest_visible(&mut self, mut rows: Vec<Row>, snapshot: &Snapshot) -> Vec<Row> {
         let tx_table = self.pager.transaction_table().clone();
         rows.retain(|row| Self::row_visible(row, snapshot, &tx_table));
         rows.sort_by_key(|row| (row.key, std::cmp::Reverse(row.created_tx)));
@@ -1072,7 +2008,64 @@ impl<'a> BTree<'a> {
             last_key = Some(row.key);
             visible.push(row);
         }
-        Ok(visible)
+        visible
+    }
+
+    /// Descend to the leaf where keys `>= key` start (`> key` when not
+    /// `inclusive`). Versions of one key can straddle a split, so an
+    /// inclusive bound equal to a separator takes the left child.
+    fn leaf_for_bound(&mut self, key: RowKey, inclusive: bool) -> io::Result<u32> {
+        let mut page_num = self.root_page;
+        while get_node_type(&self.pager.get_page(page_num)?.data) != NODE_LEAF {
+            let (keys, children) = self.read_all_from_internal(page_num)?;
+            let idx = keys
+                .iter()
+                .take_while(|&&sep| if inclusive { sep < key } else { sep <= key })
+                .count();
+            page_num = children[idx];
+        }
+        Ok(page_num)
+    }
+
+    /// Cursor over live rows with keys `>= key` (`> key` when not
+    /// `inclusive`), in ascending order. The cursor is positioned by
+    /// descending from the root rather than walking from the leftmost leaf.
+    pub fn scan_from(&'a mut self, key: RowKey, inclusive: bool) -> RowCursor<'a> {
+        let page_num = self.leaf_for_bound(key, inclusive).unwrap();
+        RowCursor {
+            btree: self,
+            current_page: page_num,
+            offset: HEADER_SIZE,
+            rows_in_page: 0,
+            skip: 0,
+            limit: None,
+            returned: 0,
+            lower: Some((key, inclusive)),
+        }
+    }
+
+    /// Cursor over live rows with keys `<= key` (`< key` when not
+    /// `inclusive`), in descending order. Leaves only link forward, so the
+    /// cursor keeps the path from the root and steps back through it.
+    pub fn scan_desc_from(&'a mut self, key: RowKey, inclusive: bool) -> ReverseRowCursor<'a> {
+        let mut path = Vec::new();
+        let mut page_num = self.root_page;
+        while get_node_type(&self.pager.get_page(page_num).unwrap().data) != NODE_LEAF {
+            let (keys, children) = self.read_all_from_internal(page_num).unwrap();
+            let idx = keys
+                .iter()
+                .take_while(|&&sep| if inclusive { sep <= key } else { sep < key })
+                .count();
+            path.push((page_num, idx));
+            page_num = children[idx];
+        }
+        let pending = self.read_all_rows_from_leaf(page_num).unwrap_or_default();
+        ReverseRowCursor {
+            btree: self,
+            path,
+            pending,
+            upper: (key, inclusive),
+        }
     }
 
     pub fn scan_rows_with_bounds(&'a mut self, skip: usize, limit: Option<usize>) -> RowCursor<'a> {
@@ -1095,6 +2088,7 @@ impl<'a> BTree<'a> {
             skip,
             limit,
             returned: 0,
+            lower: None,
         }
     }
 
@@ -1109,6 +2103,246 @@ impl<'a> BTree<'a> {
         rows[start..end].to_vec()
     }
 
+    /// Walk the tree from the root and check its structural invariants:
+    /// every page is reachable once, parent pointers match, `cell_count`
+    /// agrees with the cells on the page, keys are sorted and stay within
+    /// the separator range of their ancestors, and the `next_leaf` chain
+    /// visits every leaf exactly once in key order.
+    ///
+    /// Returns the first violation found as an `InvalidData` error.
+    pub fn verify(&mut self) -> io::Result<()> {
+        let mut leaves = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        self.verify_page(self.root_page, 0, None, None, &mut visited, &mut leaves)?;
+
+        // Following the chain from the leftmost leaf must reproduce the
+        // in-order leaf sequence from the walk above.
+        let mut page_num = leaves[0];
+        for (i, &expected) in leaves.iter().enumerate() {
+            if page_num != expected {
+                return Err(corrupt(
+                    page_num,
+                    format!(
+                        "leaf chain reaches page {} where page {} was expected",
+                        page_num, expected
+                    ),
+                ));
+            }
+            let next = get_next_leaf(&self.pager.get_page(page_num)?.data);
+            if i + 1 == leaves.len() {
+                if next != 0 {
+                    return Err(corrupt(
+                        page_num,
+                        format!("last leaf links to page {}", next),
+                    ));
+                }
+            } else {
+                page_num = next;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one page and its subtree. Keys must lie in `[lower, upper]`; the
+    /// upper bound is inclusive because versions of one logical key may
+    /// straddle a leaf split.
+    fn verify_page(
+        &mut self,
+        page_num: u32,
+        expected_parent: u32,
+        lower: Option<RowKey>,
+        upper: Option<RowKey>,
+        visited: &mut std::collections::HashSet<u32>,
+        leaves: &mut Vec<u32>,
+    ) -> io::Result<()> {
+        if page_num == 0 || page_num >= self.pager.num_pages() {
+            return Err(corrupt(page_num, "page number out of range".to_string()));
+        }
+        if !visited.insert(page_num) {
+            return Err(corrupt(
+                page_num,
+                "page is referenced more than once".to_string(),
+            ));
+        }
+        let data = self.pager.get_page(page_num)?.data.clone();
+        let parent = get_parent(&data);
+        if parent != expected_parent {
+            return Err(corrupt(
+                page_num,
+                format!(
+                    "parent pointer is {} but page is a child of {}",
+                    parent, expected_parent
+                ),
+            ));
+        }
+        let cell_count = get_cell_count(&data) as usize;
+        let check_key = |key: RowKey, prev: Option<RowKey>| -> io::Result<()> {
+            if prev.is_some_and(|p| key < p) {
+                return Err(corrupt(page_num, format!("key {} is out of order", key)));
+            }
+            if lower.is_some_and(|l| key < l) || upper.is_some_and(|u| key > u) {
+                return Err(corrupt(
+                    page_num,
+                    format!("key {} is outside the range of its parent separators", key),
+                ));
+            }
+            Ok(())
+        };
+
+        match get_node_type(&data) {
+            NODE_LEAF => {
+                let mut offset = HEADER_SIZE;
+                let mut prev = None;
+                for cell in 0..cell_count {
+                    let len_at = offset + KEY_SIZE;
+                    if len_at + 4 > data.len() {
+                        return Err(corrupt(
+                            page_num,
+                            format!(
+                                "cell {} of {} starts past the end of the page",
+                                cell, cell_count
+                            ),
+                        ));
+                    }
+                    let key = read_key(&data, offset);
+                    let len_field =
+                        u32::from_le_bytes(data[len_at..len_at + 4].try_into().unwrap());
+                    let start = len_at + 4;
+                    let end = start.saturating_add((len_field & !OVERFLOW_FLAG) as usize);
+                    if end > data.len() {
+                        return Err(corrupt(
+                            page_num,
+                            format!(
+                                "cell {} has a payload running past the end of the page",
+                                cell
+                            ),
+                        ));
+                    }
+                    let payload = if len_field & OVERFLOW_FLAG == 0 {
+                        data[start..end].to_vec()
+                    } else {
+                        if end - start != OVERFLOW_MARKER_SIZE {
+                            return Err(corrupt(
+                                page_num,
+                                format!("cell {} has a malformed overflow marker", cell),
+                            ));
+                        }
+                        let total =
+                            u32::from_le_bytes(data[start..start + 4].try_into().unwrap()) as usize;
+                        let first = u32::from_le_bytes(data[start + 4..end].try_into().unwrap());
+                        self.verify_overflow(page_num, cell, first, total, visited)?
+                    };
+                    if Row::deserialize_mvcc_payload(key, &payload).is_err() {
+                        return Err(corrupt(
+                            page_num,
+                            format!("cell {} has an unreadable payload", cell),
+                        ));
+                    }
+                    check_key(key, prev)?;
+                    prev = Some(key);
+                    offset = end;
+                }
+                // Leaf bodies are zeroed before they are written, so anything
+                // between the last counted cell and the checksum trailer means
+                // the count is wrong.
+                let cells_end = checksum_offset(data.len());
+                if data[offset.min(cells_end)..cells_end]
+                    .iter()
+                    .any(|&b| b != 0)
+                {
+                    return Err(corrupt(
+                        page_num,
+                        format!(
+                            "cell_count {} does not match the cells on the page",
+                            cell_count
+                        ),
+                    ));
+                }
+                leaves.push(page_num);
+                Ok(())
+            }
+            NODE_INTERNAL => {
+                if HEADER_SIZE + 4 + cell_count * (KEY_SIZE + 4) > checksum_offset(data.len()) {
+                    return Err(corrupt(
+                        page_num,
+                        format!("cell_count {} does not fit in an internal page", cell_count),
+                    ));
+                }
+                let (keys, children) = self.read_all_from_internal(page_num)?;
+                let mut prev = None;
+                for &key in &keys {
+                    check_key(key, prev)?;
+                    prev = Some(key);
+                }
+                for (i, &child) in children.iter().enumerate() {
+                    let child_lower = if i == 0 { lower } else { Some(keys[i - 1]) };
+                    let child_upper = keys.get(i).copied().or(upper);
+                    self.verify_page(child, page_num, child_lower, child_upper, visited, leaves)?;
+                }
+                let mut expected = 0;
+                for &child in &children {
+                    expected += self.subtree_count(child)?;
+                }
+                let stored = get_subtree_count(&self.pager.get_page(page_num)?.data);
+                if stored != expected {
+                    return Err(corrupt(
+                        page_num,
+                        format!(
+                            "subtree count {} but children hold {} live rows",
+                            stored, expected
+                        ),
+                    ));
+                }
+                Ok(())
+            }
+            other => Err(corrupt(page_num, format!("unknown node type {}", other))),
+        }
+    }
+
+    /// Check the overflow chain of `cell` on leaf `page_num` and return the
+    /// reassembled payload. Chain pages count as visited like tree pages.
+    fn verify_overflow(
+        &mut self,
+        page_num: u32,
+        cell: usize,
+        first: u32,
+        total: usize,
+        visited: &mut std::collections::HashSet<u32>,
+    ) -> io::Result<Vec<u8>> {
+        let mut next = first;
+        while next != 0 {
+            if next >= self.pager.num_pages() {
+                return Err(corrupt(
+                    page_num,
+                    format!("cell {} links to overflow page {} out of range", cell, next),
+                ));
+            }
+            if !visited.insert(next) {
+                return Err(corrupt(
+                    next,
+                    "page is referenced more than once".to_string(),
+                ));
+            }
+            let page = self.pager.get_page(next)?;
+            if get_node_type(&page.data) != NODE_OVERFLOW {
+                return Err(corrupt(
+                    next,
+                    format!(
+                        "cell {} of page {} links to a non-overflow page",
+                        cell, page_num
+                    ),
+                ));
+            }
+            next = get_next_leaf(&page.data);
+        }
+        self.read_overflow(first, total).map_err(|e| {
+            corrupt(
+                page_num,
+                format!("cell {} has a broken overflow chain: {}", cell, e),
+            )
+        })
+    }
+
     /// Flush all cached pages to disk (for final cleanup).
     pub fn flush_all(&mut self) -> io::Result<()> {
         for i in 0..self.pager.num_pages() {
@@ -1126,6 +2360,8 @@ pub struct RowCursor<'b> {
     skip: usize,
     limit: Option<usize>,
     returned: usize,
+    /// Rows below this `(key, inclusive)` bound are skipped.
+    lower: Option<(RowKey, bool)>,
 }
 
 impl<'b> Iterator for RowCursor<'b> {
@@ -1138,27 +2374,14 @@ impl<'b> Iterator for RowCursor<'b> {
 
             if self.rows_in_page < cell_count {
                 // Deserialize one row from this page
-                let key =
-                    i32::from_le_bytes(page.data[self.offset..self.offset + 4].try_into().unwrap());
-                let payload_len = u32::from_le_bytes(
-                    page.data[self.offset + 4..self.offset + 8]
-                        .try_into()
-                        .unwrap(),
-                ) as usize;
-                let start = self.offset + 8;
-                let end = start + payload_len;
-                if end > PAGE_SIZE {
-                    return None;
-                }
-                let bytes = &page.data[start..end];
-                let row = Row::deserialize_mvcc_payload(key, bytes).ok()?;
+                let (row, end) = self.btree.read_cell(self.current_page, self.offset, None).ok()?;
 
                 // Advance offsets
                 self.offset = end;
                 self.rows_in_page += 1;
 
                 // Legacy scan wrapper hides versions that have been logically deleted.
-                if row.deleted_tx.is_some() {
+                if row.deleted_tx.is_some() || below_lower(row.key, self.lower) {
                     continue;
                 }
 
@@ -1189,12 +2412,68 @@ impl<'b> Iterator for RowCursor<'b> {
     }
 }
 
+/// Descending counterpart of `RowCursor` returned by `BTree::scan_desc_from`.
+pub struct ReverseRowCursor<'b> {
+    btree: &'b mut BTree<'b>,
+    /// Internal pages above the current leaf with the child index taken.
+    path: Vec<(u32, usize)>,
+    /// Rows of the current leaf not yet returned, in ascending order.
+    pending: Vec<Row>,
+    upper: (RowKey, bool),
+}
+
+impl<'b> Iterator for ReverseRowCursor<'b> {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        loop {
+            while let Some(row) = self.pending.pop() {
+                if row.deleted_tx.is_some() || above_upper(row.key, Some(self.upper)) {
+                    continue;
+                }
+                return Some(row);
+            }
+
+            // Climb to the nearest ancestor with a child left of the path,
+            // then take the rightmost leaf below that child.
+            let mut page_num = loop {
+                let (parent, idx) = self.path.pop()?;
+                if idx > 0 {
+                    self.path.push((parent, idx - 1));
+                    let (_, children) = self.btree.read_all_from_internal(parent).ok()?;
+                    break children[idx - 1];
+                }
+            };
+            while get_node_type(&self.btree.pager.get_page(page_num).ok()?.data) != NODE_LEAF {
+                let (_, children) = self.btree.read_all_from_internal(page_num).ok()?;
+                self.path.push((page_num, children.len() - 1));
+                page_num = children[children.len() - 1];
+            }
+            self.pending = self.btree.read_all_rows_from_leaf(page_num).ok()?;
+        }
+    }
+}
+
+fn below_lower(key: RowKey, lower: Option<(RowKey, bool)>) -> bool {
+    match lower {
+        Some((bound, inclusive)) => key < bound || (!inclusive && key == bound),
+        None => false,
+    }
+}
+
+fn above_upper(key: RowKey, upper: Option<(RowKey, bool)>) -> bool {
+    match upper {
+        Some((bound, inclusive)) => key > bound || (!inclusive && key == bound),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::NamedTempFile;
 
-    fn row_with_tx(key: i32, value: &str, created_tx: TransactionId) -> Row {
+    fn row_with_tx(key: RowKey, value: &str, created_tx: TransactionId) -> Row {
         let mut row = Row::new(
             key,
             RowData(vec![crate::storage::row::ColumnValue::Text(value.into())]),
@@ -1264,7 +2543,9 @@ mod tests {
         btree.insert_version(row_with_tx(1, "before", 1)).unwrap();
         // Transaction 4 was still live when the snapshot was captured.
         let snapshot = Snapshot::new_for_transaction(2, 10, vec![4]);
-        btree.insert_version(row_with_tx(1, "concurrent", 4)).unwrap();
+        btree
+            .insert_version(row_with_tx(1, "concurrent", 4))
+            .unwrap();
 
         assert!(
             btree.has_write_conflict(1, 1, &snapshot).unwrap(),
@@ -0,0 +1,97 @@
+//! Byte storage behind the pager, the WAL, and the clog: a file on disk, or a
+//! growable buffer for a database opened with [`Pager::new_in_memory`].
+//!
+//! [`Pager::new_in_memory`]: crate::storage::pager::Pager::new_in_memory
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+
+pub enum StorageFile {
+    Disk(File),
+    /// Contents live only as long as the value; syncing is a no-op.
+    Memory(Cursor<Vec<u8>>),
+}
+
+impl StorageFile {
+    /// Open `path` for reading and writing, creating it if missing.
+    pub fn open(path: &str) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        Ok(StorageFile::Disk(file))
+    }
+
+    /// An empty buffer that is never written to disk.
+    pub fn in_memory() -> Self {
+        StorageFile::Memory(Cursor::new(Vec::new()))
+    }
+
+    /// Current length in bytes.
+    pub fn len(&self) -> io::Result<u64> {
+        match self {
+            StorageFile::Disk(file) => Ok(file.metadata()?.len()),
+            StorageFile::Memory(buf) => Ok(buf.get_ref().len() as u64),
+        }
+    }
+
+    pub fn is_empty(&self) -> io::Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Truncate or zero-extend to `len` bytes, like [`File::set_len`]. The
+    /// position is left unchanged.
+    pub fn set_len(&mut self, len: u64) -> io::Result<()> {
+        match self {
+            StorageFile::Disk(file) => file.set_len(len),
+            StorageFile::Memory(buf) => {
+                buf.get_mut().resize(len as usize, 0);
+                Ok(())
+            }
+        }
+    }
+
+    /// Force written data to stable storage. Does nothing in memory.
+    pub fn sync_all(&mut self) -> io::Result<()> {
+        match self {
+            StorageFile::Disk(file) => file.sync_all(),
+            StorageFile::Memory(_) => Ok(()),
+        }
+    }
+}
+
+impl Read for StorageFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            StorageFile::Disk(file) => file.read(buf),
+            StorageFile::Memory(cursor) => cursor.read(buf),
+        }
+    }
+}
+
+impl Write for StorageFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            StorageFile::Disk(file) => file.write(buf),
+            StorageFile::Memory(cursor) => cursor.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            StorageFile::Disk(file) => file.flush(),
+            StorageFile::Memory(cursor) => cursor.flush(),
+        }
+    }
+}
+
+impl Seek for StorageFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            StorageFile::Disk(file) => file.seek(pos),
+            StorageFile::Memory(cursor) => cursor.seek(pos),
+        }
+    }
+}
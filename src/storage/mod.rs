@@ -1,5 +1,7 @@
 pub mod btree;
+pub mod file;
 pub mod page;
 pub mod pager;
 pub mod row;
+pub mod text_key;
 pub mod vacuum;
@@ -17,8 +17,17 @@
 // │        16                    │   N    │ PAYLOAD_BYTES (UTF-8 string)  │
 // └───────────────────────────────────────────────────────────────────────┘
 
+/// Page size of a database created without choosing one. The size a file
+/// was created with is recorded in its meta page; see [`Pager::page_size`].
+///
+/// [`Pager::page_size`]: crate::storage::pager::Pager::page_size
 pub const PAGE_SIZE: usize = 4096;
 
+/// Bounds on the page size a database may be created with. Overflow pages
+/// record their chunk length in the u16 cell count, which caps the maximum.
+pub const MIN_PAGE_SIZE: usize = 512;
+pub const MAX_PAGE_SIZE: usize = 65536;
+
 pub const NODE_TYPE_OFFSET: usize   = 0;          // 1 byte
 pub const IS_ROOT_OFFSET: usize     = 1;          // 1 byte
 pub const PARENT_PAGE_OFFSET: usize = 2;          // 4 bytes (u32)
@@ -27,47 +36,57 @@ pub const CELL_COUNT_OFFSET: usize  = 6;          // 2 bytes (u16)
 pub const HEADER_SIZE: usize = 1 + 1 + 4 + 2 + 4; // =12 bytes now
 pub const NODE_INTERNAL: u8 = 0;
 pub const NODE_LEAF: u8     = 1;
+/// Continuation page holding spilled leaf payload bytes. The `next_leaf`
+/// field links to the next page of the chain and `cell_count` holds the
+/// number of payload bytes stored after the header.
+pub const NODE_OVERFLOW: u8 = 2;
+
+/// The last 4 bytes of every page hold a CRC32 of the bytes before them when
+/// the pager runs with checksums enabled. B-Tree cells never extend past it.
+pub fn checksum_offset(page_size: usize) -> usize {
+    page_size - 4
+}
 
 /// Given a raw page buffer, read its node type (internal vs. leaf).
-pub fn get_node_type(page: &[u8; PAGE_SIZE]) -> u8 {
+pub fn get_node_type(page: &[u8]) -> u8 {
     page[NODE_TYPE_OFFSET]
 }
 
 /// Set the node type (internal=0, leaf=1).
-pub fn set_node_type(page: &mut [u8; PAGE_SIZE], node_type: u8) {
+pub fn set_node_type(page: &mut [u8], node_type: u8) {
     page[NODE_TYPE_OFFSET] = node_type;
 }
 
 /// Read the “is_root” flag (0 = false, 1 = true).
-pub fn get_is_root(page: &[u8; PAGE_SIZE]) -> u8 {
+pub fn get_is_root(page: &[u8]) -> u8 {
     page[IS_ROOT_OFFSET]
 }
 
 /// Set or clear the “is_root” flag.
-pub fn set_is_root(page: &mut [u8; PAGE_SIZE], is_root: bool) {
+pub fn set_is_root(page: &mut [u8], is_root: bool) {
     page[IS_ROOT_OFFSET] = if is_root { 1 } else { 0 };
 }
 
 /// Read the parent page number (u32).
-pub fn get_parent(page: &[u8; PAGE_SIZE]) -> u32 {
+pub fn get_parent(page: &[u8]) -> u32 {
     let bytes = &page[PARENT_PAGE_OFFSET..PARENT_PAGE_OFFSET + 4];
     u32::from_le_bytes(bytes.try_into().unwrap())
 }
 
 /// Set the parent page number (u32).
-pub fn set_parent(page: &mut [u8; PAGE_SIZE], parent: u32) {
+pub fn set_parent(page: &mut [u8], parent: u32) {
     page[PARENT_PAGE_OFFSET..PARENT_PAGE_OFFSET + 4]
         .copy_from_slice(&parent.to_le_bytes());
 }
 
 /// Read the number of cells in this node (u16).
-pub fn get_cell_count(page: &[u8; PAGE_SIZE]) -> u16 {
+pub fn get_cell_count(page: &[u8]) -> u16 {
     let bytes = &page[CELL_COUNT_OFFSET..CELL_COUNT_OFFSET + 2];
     u16::from_le_bytes(bytes.try_into().unwrap())
 }
 
 /// Set the number of cells (u16).
-pub fn set_cell_count(page: &mut [u8; PAGE_SIZE], count: u16) {
+pub fn set_cell_count(page: &mut [u8], count: u16) {
     page[CELL_COUNT_OFFSET..CELL_COUNT_OFFSET + 2]
         .copy_from_slice(&count.to_le_bytes());
 }
@@ -80,4 +99,40 @@ pub fn get_next_leaf(page_data: &[u8]) -> u32 {
 /// Leaf‐only: write the 4‐byte next‐leaf pointer
 pub fn set_next_leaf(page_data: &mut [u8], next: u32) {
     page_data[8..12].copy_from_slice(&next.to_le_bytes());
-}
\ No newline at end of file
+}
+
+/// Internal‐only: read the number of live rows in this node's subtree, kept
+/// in the bytes a leaf uses for its next‐leaf pointer.
+pub fn get_subtree_count(page: &[u8]) -> u32 {
+    u32::from_le_bytes(page[8..12].try_into().unwrap())
+}
+
+/// Internal‐only: write the subtree row count
+pub fn set_subtree_count(page: &mut [u8], count: u32) {
+    page[8..12].copy_from_slice(&count.to_le_bytes());
+}
+
+/// CRC-32 (IEEE) of `bytes`.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Read the checksum stored in the page trailer.
+pub fn get_checksum(page: &[u8]) -> u32 {
+    u32::from_le_bytes(page[checksum_offset(page.len())..].try_into().unwrap())
+}
+
+/// Compute the checksum of the page body and store it in the trailer.
+pub fn set_checksum(page: &mut [u8]) {
+    let offset = checksum_offset(page.len());
+    let crc = crc32(&page[..offset]);
+    page[offset..].copy_from_slice(&crc.to_le_bytes());
+}
@@ -1,15 +1,32 @@
-use crate::storage::page::PAGE_SIZE;
+use crate::storage::file::StorageFile;
+use crate::storage::page::{
+    MAX_PAGE_SIZE, MIN_PAGE_SIZE, PAGE_SIZE, checksum_offset, crc32, get_checksum, set_checksum,
+};
 use crate::transaction::{
     IsolationLevel, Snapshot, Transaction, TransactionId, TransactionStatus, TransactionTable,
     WriteIntent, clog::Clog, wal::Wal,
 };
-use std::fs::{File, OpenOptions};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 
 /// Magic marking page 0 as the durable engine meta page. Older databases leave
 /// page 0 zeroed (allocated but unused), so a mismatch means "no meta yet".
 const META_MAGIC: &[u8; 8] = b"AERODBM1";
 
+/// Offset of the page size in the meta page. It lies inside the smallest page,
+/// so it can be read before the size of the file's pages is known. Databases
+/// written before it was recorded left it zeroed and use [`PAGE_SIZE`].
+const META_PAGE_SIZE_OFFSET: usize = 32;
+
+/// Offset of the on-disk format version in the meta page, right after the
+/// page size.
+const META_FORMAT_VERSION_OFFSET: usize = META_PAGE_SIZE_OFFSET + 4;
+
+/// On-disk format written by this build. Version 2 widened B-tree keys from
+/// 32 to 64 bits; files from before it leave the version zeroed and store
+/// 4-byte keys, which this build cannot read.
+const FORMAT_VERSION: u32 = 2;
+
 /// Fixed pages holding the non-versioned schema catalog: page 1 is the table
 /// catalog, page 2 the sequence catalog. Their scans are not MVCC-filtered, so
 /// [`Pager::rollback_transaction`] physically reverts just these pages on abort;
@@ -17,24 +34,71 @@ const META_MAGIC: &[u8; 8] = b"AERODBM1";
 const CATALOG_TABLE_PAGE: u32 = 1;
 const CATALOG_SEQUENCE_PAGE: u32 = 2;
 
-/// A single 4 KiB page of data.
+/// WAL size that triggers a checkpoint by default; see
+/// [`Pager::set_wal_size_limit`].
+pub const DEFAULT_WAL_SIZE_LIMIT: u64 = 4 * 1024 * 1024;
+
+/// How aggressively commits are forced to stable storage; see
+/// [`Pager::set_sync_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncMode {
+    /// fsync every WAL record and clog update as it is written, and the data
+    /// file at each checkpoint. A commit that returned survives power loss.
+    #[default]
+    Full,
+    /// fsync the WAL once per commit, after its commit record, and the data
+    /// file and clog only at checkpoints. A process crash loses nothing, but
+    /// power loss can lose or tear the most recent commits.
+    Normal,
+    /// Never fsync; the operating system writes data back when it chooses.
+    /// Survives a process crash, but power loss can lose or corrupt any
+    /// commit since the OS last flushed.
+    Off,
+}
+
+impl SyncMode {
+    /// Whether every individual WAL record and clog update is fsynced.
+    pub(crate) fn syncs_each_write(self) -> bool {
+        self == SyncMode::Full
+    }
+
+    /// Whether commit and checkpoint barriers are fsynced.
+    pub(crate) fn syncs_barriers(self) -> bool {
+        self != SyncMode::Off
+    }
+}
+
+/// Where a full B-tree node is divided; see [`Pager::set_split_strategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitStrategy {
+    /// Split every full node in half. Suits keys inserted in random order.
+    #[default]
+    Midpoint,
+    /// When the rightmost node of a level overflows, keep it as full as
+    /// possible and move only the last entries to the new right sibling, so
+    /// monotonically increasing keys leave packed pages behind them. Splits
+    /// elsewhere in the tree still divide at the midpoint.
+    AppendOptimized,
+}
+
+/// A single page of data, as long as the pager's page size.
 pub struct Page {
-    pub data: [u8; PAGE_SIZE],
+    pub data: Vec<u8>,
 }
 
 impl Page {
-    pub fn new() -> Self {
+    pub fn new(page_size: usize) -> Self {
         Page {
-            data: [0; PAGE_SIZE],
+            data: vec![0; page_size],
         }
     }
 }
 
-/// Pager: manages reading/writing 4 KiB pages from/into the database file,
+/// Pager: manages reading/writing fixed-size pages from/into the database file,
 /// and keeps a simple in-memory cache. Distinguishes pages already on disk
 /// from pages newly allocated in memory.
 pub struct Pager {
-    file: File,
+    file: StorageFile,
     wal: Wal,
     /// Durable transaction-status store. Survives commit (unlike the WAL) so
     /// aborted/committed statuses remain available to MVCC visibility and vacuum
@@ -63,20 +127,125 @@ pub struct Pager {
     /// (its clog entry may be dropped). Persisted in page-0 meta; advanced by
     /// vacuum once the corresponding versions are reclaimed.
     frozen_xid: TransactionId,
+    /// Stamp a CRC32 into every page written and verify it on every page read.
+    checksums: bool,
+    /// Size of every page in the file, fixed when the file is created.
+    page_size: usize,
+    sync_mode: SyncMode,
+    split_strategy: SplitStrategy,
+    /// Commits to accumulate in the WAL before their pages are written to the
+    /// database file together; see [`Pager::set_group_commit`].
+    group_commit: u32,
+    /// Committed page images logged to the WAL but not yet written to the
+    /// database file, and the number of commits they cover.
+    pending_pages: BTreeMap<u32, Vec<u8>>,
+    pending_commits: u32,
+    /// WAL size in bytes at which a commit or direct page write checkpoints;
+    /// see [`Pager::set_wal_size_limit`].
+    wal_size_limit: u64,
+    /// fsyncs of the database file since open.
+    syncs: u64,
+    /// Row payload bytes decoded into column values since open.
+    bytes_decoded: u64,
+    /// Cached pages marked modified with [`Pager::mark_dirty`] and not yet
+    /// written or handed to a transaction's commit.
+    dirty: BTreeSet<u32>,
+    /// Page images written to the database file since open.
+    page_writes: u64,
+    /// Pages released with [`Pager::free_page`], reused by
+    /// [`Pager::allocate_page`] before the file grows. Kept in memory only.
+    free_pages: BTreeSet<u32>,
 }
 
 impl Pager {
     /// Open (or create) the database file at `filename`.
-    /// - `file_length_pages` is set to floor(file_size / PAGE_SIZE).
+    /// - The page size is the one recorded when the file was created; a new
+    ///   file gets [`PAGE_SIZE`] pages.
+    /// - `file_length_pages` is set to ceil(file_size / page size); a trailing
+    ///   partial page left by a truncated write is zero-extended first.
     /// - `num_pages` is initially the same as `file_length_pages`.
+    ///
+    /// Page checksums are off; see [`Pager::new_with_checksums`].
     pub fn new(filename: &str) -> io::Result<Self> {
-        let mut file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(filename)?;
-        let wal_path = format!("{}.wal", filename);
-        let (wal, wal_tx_table) = Wal::open(&wal_path, &mut file)?;
+        Pager::new_with_checksums(filename, false)
+    }
+
+    /// Open the database file like [`Pager::new`], creating it with
+    /// `page_size` pages if it does not exist yet. Larger pages give internal
+    /// nodes more fan-out and keep longer rows inline at the cost of more
+    /// bytes rewritten per change. The size must be a power of two between
+    /// [`MIN_PAGE_SIZE`] and [`MAX_PAGE_SIZE`]; opening an existing file
+    /// created with a different size fails with `InvalidInput`.
+    pub fn new_with_page_size(filename: &str, page_size: usize) -> io::Result<Self> {
+        if !page_size.is_power_of_two() || !(MIN_PAGE_SIZE..=MAX_PAGE_SIZE).contains(&page_size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "page size {} must be a power of two between {} and {}",
+                    page_size, MIN_PAGE_SIZE, MAX_PAGE_SIZE
+                ),
+            ));
+        }
+        Pager::open(filename, false, Some(page_size))
+    }
+
+    /// Open the database file like [`Pager::new`], optionally with page
+    /// checksums. With `checksums` on, every page written carries a CRC32 in
+    /// its trailer and every page read from disk is checked against it, so a
+    /// corrupted page fails with `InvalidData` instead of being decoded.
+    /// Files written without checksums must be opened with `checksums` off.
+    pub fn new_with_checksums(filename: &str, checksums: bool) -> io::Result<Self> {
+        Pager::open(filename, checksums, None)
+    }
+
+    /// Create an empty database that lives only in memory, WAL and clog
+    /// included, and is discarded when the pager is dropped. Nothing is
+    /// written to disk and syncs are no-ops, so commits are not durable.
+    pub fn new_in_memory() -> io::Result<Self> {
+        Pager::open_files(
+            StorageFile::in_memory(),
+            StorageFile::in_memory(),
+            StorageFile::in_memory(),
+            false,
+            None,
+        )
+    }
+
+    /// Open or create `filename`. A new file is created with `page_size`
+    /// (default [`PAGE_SIZE`]) pages; an existing one must match it if given.
+    fn open(filename: &str, checksums: bool, page_size: Option<usize>) -> io::Result<Self> {
+        Pager::open_files(
+            StorageFile::open(filename)?,
+            StorageFile::open(&format!("{}.wal", filename))?,
+            StorageFile::open(&format!("{}.clog", filename))?,
+            checksums,
+            page_size,
+        )
+    }
+
+    /// [`Pager::open`] over an opened database file and its WAL and clog.
+    fn open_files(
+        mut file: StorageFile,
+        wal_file: StorageFile,
+        clog_file: StorageFile,
+        checksums: bool,
+        page_size: Option<usize>,
+    ) -> io::Result<Self> {
+        let fresh = file.len()? == 0;
+        let page_size = match (Pager::read_page_size(&mut file)?, page_size) {
+            (Some(stored), Some(requested)) if stored != requested => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "database was created with page size {}, not {}",
+                        stored, requested
+                    ),
+                ));
+            }
+            (Some(stored), _) => stored,
+            (None, requested) => requested.unwrap_or(PAGE_SIZE),
+        };
+        let (wal, wal_tx_table) = Wal::open_file(wal_file, &mut file, page_size)?;
         let mut next_commit_ts = wal_tx_table
             .values()
             .filter_map(|status| match status {
@@ -88,19 +257,25 @@ impl Pager {
             .saturating_add(1);
 
         // Determine file length after WAL recovery in case pages were replayed
-        let file_len_after = file.metadata()?.len();
-        let file_length_pages = (file_len_after as usize / PAGE_SIZE) as u32;
+        let file_len_after = file.len()?;
+        let file_length_pages = Pager::align_file_length(&mut file, file_len_after, page_size)?;
 
         // Restore the durable transaction-id counter, commit timestamp, and
         // frozen watermark from page 0. The WAL is truncated on commit, so these
         // counters cannot be derived from transaction-status records alone;
         // page 0 is their home.
-        let meta = Pager::read_meta_page(&mut file, file_length_pages)?;
+        let meta = Pager::read_meta_page(&mut file, file_length_pages, checksums, page_size)?;
         let mut next_transaction_id: TransactionId = 1;
         let mut frozen_xid: TransactionId = 0;
 
-        let clog_path = format!("{}.clog", filename);
-        let mut clog = Clog::open(&clog_path)?;
+        // Commits accumulated by group commit reach page 0 only at the next
+        // checkpoint, so ids still in the WAL may be newer than the meta counter.
+        let wal_next_tx_id = wal_tx_table
+            .keys()
+            .max()
+            .map_or(1, |max| max.saturating_add(1));
+
+        let mut clog = Clog::open_file(clog_file)?;
 
         let tx_table = if let Some((persisted_tx_id, persisted_commit_ts, persisted_frozen)) = meta {
             next_transaction_id = persisted_tx_id.max(1);
@@ -124,8 +299,9 @@ impl Pager {
             clog.reset()?;
             wal_tx_table
         };
+        next_transaction_id = next_transaction_id.max(wal_next_tx_id);
 
-        Ok(Pager {
+        let mut pager = Pager {
             file,
             wal,
             clog,
@@ -137,7 +313,33 @@ impl Pager {
             next_transaction_id,
             next_commit_ts,
             frozen_xid,
-        })
+            checksums,
+            page_size,
+            sync_mode: SyncMode::default(),
+            split_strategy: SplitStrategy::default(),
+            group_commit: 1,
+            pending_pages: BTreeMap::new(),
+            pending_commits: 0,
+            wal_size_limit: DEFAULT_WAL_SIZE_LIMIT,
+            syncs: 0,
+            bytes_decoded: 0,
+            dirty: BTreeSet::new(),
+            page_writes: 0,
+            free_pages: BTreeSet::new(),
+        };
+        // Record a non-default page size before anything else is written, so
+        // the file is reopened (and its WAL replayed) with the right size. A
+        // file without meta is read with the default size.
+        if fresh && page_size != PAGE_SIZE {
+            pager.persist_meta()?;
+            pager.num_pages = pager.file_length_pages;
+        }
+        Ok(pager)
+    }
+
+    /// Size in bytes of every page in the database file.
+    pub fn page_size(&self) -> usize {
+        self.page_size
     }
 
     /// Return a mutable reference to the requested page, loading from disk if it already existed.
@@ -148,7 +350,7 @@ impl Pager {
         // If caller asks beyond the current total, allocate blank pages up to there.
         if page_num >= self.num_pages {
             while page_num >= self.num_pages {
-                self.allocate_page()?;
+                self.append_page();
             }
         }
 
@@ -160,13 +362,16 @@ impl Pager {
         // If not already in cache, create a new Page and load from disk if needed.
         if self.cache[page_num as usize].is_none() {
             // Always start with a zeroed page.
-            let mut page = Box::new(Page::new());
+            let mut page = Box::new(Page::new(self.page_size));
 
             // Only attempt to read from disk if this page existed when we opened file.
             if page_num < self.file_length_pages {
-                let offset = (page_num as u64) * (PAGE_SIZE as u64);
+                let offset = (page_num as u64) * (self.page_size as u64);
                 self.file.seek(SeekFrom::Start(offset))?;
                 self.file.read_exact(&mut page.data)?;
+                if self.checksums {
+                    verify_checksum(page_num, &page.data)?;
+                }
             }
             self.cache[page_num as usize] = Some(page);
         }
@@ -175,15 +380,54 @@ impl Pager {
         Ok(self.cache[page_num as usize].as_mut().unwrap())
     }
 
-    /// Allocate a new page at the end (in memory). Increments `num_pages`.
-    /// Does NOT change `file_length_pages` until we actually flush it.
+    /// [`Pager::get_page`] for a caller about to modify the page: it is marked
+    /// with [`Pager::mark_dirty`] first.
+    pub fn get_page_mut(&mut self, page_num: u32) -> io::Result<&mut Page> {
+        self.mark_dirty(page_num);
+        self.get_page(page_num)
+    }
+
+    /// Allocate a page: the lowest free page, zeroed, if there is one (see
+    /// [`Pager::free_page`]), otherwise a new page at the end (in memory).
     pub fn allocate_page(&mut self) -> io::Result<u32> {
+        if let Some(page_num) = self.free_pages.pop_first() {
+            if self.cache.len() <= page_num as usize {
+                self.cache.resize_with(page_num as usize + 1, || None);
+            }
+            self.cache[page_num as usize] = Some(Box::new(Page::new(self.page_size)));
+            return Ok(page_num);
+        }
+        Ok(self.append_page())
+    }
+
+    /// Add a new page at the end (in memory). Increments `num_pages`.
+    /// Does NOT change `file_length_pages` until we actually flush it.
+    fn append_page(&mut self) -> u32 {
         let new_page_num = self.num_pages;
         self.num_pages += 1;
         if self.cache.len() <= new_page_num as usize {
             self.cache.resize_with(new_page_num as usize + 1, || None);
         }
-        Ok(new_page_num)
+        new_page_num
+    }
+
+    /// Release `page_num`, which nothing may reference any more, for
+    /// [`Pager::allocate_page`] to hand out again. Inside a transaction the
+    /// page is only released when it commits, so a rollback can still find
+    /// it. The free list is not persisted: pages freed before the database is
+    /// closed stay unused after it is reopened.
+    pub fn free_page(&mut self, page_num: u32) {
+        if let Some(tx) = self.transaction.as_mut() {
+            tx.mark_freed(page_num);
+            return;
+        }
+        self.dirty.remove(&page_num);
+        self.free_pages.insert(page_num);
+    }
+
+    /// Number of freed pages waiting to be reused.
+    pub fn free_page_count(&self) -> usize {
+        self.free_pages.len()
     }
 
     /// Write the cached page `page_num` back to disk. If this is a brand-new page (i.e. ≥ `file_length_pages`),
@@ -194,27 +438,66 @@ impl Pager {
             // Just remember the page so its current image is flushed at commit;
             // do not write to disk mid-transaction.
             tx.mark_touched(page_num);
-        } else if let Some(page_box) = &self.cache[page_num as usize] {
-            let data = page_box.data;
+        } else if let Some(data) = self.cached_page_image(page_num) {
             self.wal.append_page(page_num, &data)?;
             self.write_page_raw(page_num, &data)?;
+            // A stale pending image must not overwrite this one at checkpoint.
+            self.pending_pages.remove(&page_num);
+            self.dirty.remove(&page_num);
+            if self.wal.size()? >= self.wal_size_limit {
+                self.checkpoint()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Note that the cached page `page_num` was modified, so the next
+    /// [`Pager::flush_dirty`], or the commit of the live transaction, writes it.
+    pub fn mark_dirty(&mut self, page_num: u32) {
+        self.dirty.insert(page_num);
+    }
+
+    /// Pass every page marked with [`Pager::mark_dirty`], and no other, to
+    /// [`Pager::flush_page`]. Inside a transaction they are written when it
+    /// commits.
+    pub fn flush_dirty(&mut self) -> io::Result<()> {
+        for page_num in std::mem::take(&mut self.dirty) {
+            self.flush_page(page_num)?;
         }
         Ok(())
     }
 
-    /// Copy the current cached image of `page_num`, if the page is resident.
-    fn cached_page_image(&self, page_num: u32) -> Option<[u8; PAGE_SIZE]> {
+    /// Number of page images written to the database file since the pager
+    /// was opened, the meta page included.
+    pub fn page_write_count(&self) -> u64 {
+        self.page_writes
+    }
+
+    /// Copy the current cached image of `page_num`, if the page is resident,
+    /// ready to be written (checksum stamped when enabled).
+    fn cached_page_image(&self, page_num: u32) -> Option<Vec<u8>> {
         self.cache
             .get(page_num as usize)
             .and_then(|slot| slot.as_ref())
-            .map(|page_box| page_box.data)
+            .map(|page_box| self.disk_image(&page_box.data))
     }
 
-    fn write_page_raw(&mut self, page_num: u32, data: &[u8; PAGE_SIZE]) -> io::Result<()> {
-        let offset = (page_num as u64) * (PAGE_SIZE as u64);
+    /// The bytes to write for a page: `data` with its checksum stamped when
+    /// checksums are enabled. WAL images use it too, so replayed pages verify.
+    fn disk_image(&self, data: &[u8]) -> Vec<u8> {
+        let mut image = data.to_vec();
+        if self.checksums {
+            set_checksum(&mut image);
+        }
+        image
+    }
+
+    fn write_page_raw(&mut self, page_num: u32, data: &[u8]) -> io::Result<()> {
+        let offset = (page_num as u64) * (self.page_size as u64);
         self.file.seek(SeekFrom::Start(offset))?;
         self.file.write_all(data)?;
         self.file.flush()?;
+        self.page_writes += 1;
         if page_num >= self.file_length_pages {
             self.file_length_pages = page_num + 1;
         }
@@ -272,7 +555,7 @@ impl Pager {
     pub fn record_write_intent(
         &mut self,
         table_root: u32,
-        key: i32,
+        key: crate::storage::row::RowKey,
         visible_created_tx: TransactionId,
     ) {
         if let Some(tx) = self.transaction.as_mut() {
@@ -319,20 +602,80 @@ impl Pager {
         self.next_transaction_id
     }
 
+    /// Zero-extend a file whose length is not a multiple of `page_size` (a torn
+    /// write of the last page) so every page can be read in full, and return
+    /// the resulting page count. The surviving prefix of the partial page is
+    /// kept; anything it fails to describe reads back as zeroes.
+    fn align_file_length(file: &mut StorageFile, file_len: u64, page_size: usize) -> io::Result<u32> {
+        let page_count = file_len.div_ceil(page_size as u64);
+        if !file_len.is_multiple_of(page_size as u64) {
+            file.set_len(page_count * page_size as u64)?;
+            file.sync_all()?;
+        }
+        u32::try_from(page_count)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "database file too large"))
+    }
+
+    /// Read the page size recorded in the meta page. Returns `None` for an
+    /// empty file; a file without meta, or with meta written before the size
+    /// was recorded, uses [`PAGE_SIZE`]. A meta page recording a format other
+    /// than [`FORMAT_VERSION`] fails with `InvalidData`.
+    fn read_page_size(file: &mut StorageFile) -> io::Result<Option<usize>> {
+        if file.len()? == 0 {
+            return Ok(None);
+        }
+        let mut prefix = [0u8; META_FORMAT_VERSION_OFFSET + 4];
+        file.seek(SeekFrom::Start(0))?;
+        if file.read_exact(&mut prefix).is_err() || &prefix[0..8] != META_MAGIC {
+            return Ok(Some(PAGE_SIZE));
+        }
+        let version =
+            u32::from_le_bytes(prefix[META_FORMAT_VERSION_OFFSET..].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "database file format {} is not supported; this build reads format {}",
+                    version, FORMAT_VERSION
+                ),
+            ));
+        }
+        let stored = u32::from_le_bytes(
+            prefix[META_PAGE_SIZE_OFFSET..META_FORMAT_VERSION_OFFSET]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        if stored == 0 {
+            return Ok(Some(PAGE_SIZE));
+        }
+        if !stored.is_power_of_two() || !(MIN_PAGE_SIZE..=MAX_PAGE_SIZE).contains(&stored) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("meta page records an invalid page size {}", stored),
+            ));
+        }
+        Ok(Some(stored))
+    }
+
     /// Read the durable counters from page 0. Returns `None` when page 0 is
     /// missing or does not carry the meta magic (legacy databases).
     fn read_meta_page(
-        file: &mut File,
+        file: &mut StorageFile,
         file_length_pages: u32,
+        checksums: bool,
+        page_size: usize,
     ) -> io::Result<Option<(TransactionId, u64, TransactionId)>> {
         if file_length_pages < 1 {
             return Ok(None);
         }
-        let mut buf = [0u8; PAGE_SIZE];
+        let mut buf = vec![0u8; page_size];
         file.seek(SeekFrom::Start(0))?;
         if file.read_exact(&mut buf).is_err() || &buf[0..8] != META_MAGIC {
             return Ok(None);
         }
+        if checksums {
+            verify_checksum(0, &buf)?;
+        }
         let next_tx_id = u64::from_le_bytes(buf[8..16].try_into().unwrap());
         let next_commit_ts = u64::from_le_bytes(buf[16..24].try_into().unwrap());
         // frozen_xid was added later; a database written before it left these
@@ -342,15 +685,25 @@ impl Pager {
     }
 
     /// Durably write the transaction-id, commit-timestamp, and frozen-watermark
-    /// counters to page 0.
+    /// counters, the page size, and the format version to page 0.
     fn persist_meta(&mut self) -> io::Result<()> {
-        let mut buf = [0u8; PAGE_SIZE];
+        self.write_meta()?;
+        self.sync_file()
+    }
+
+    /// Write the meta page like [`Pager::persist_meta`] without syncing it.
+    fn write_meta(&mut self) -> io::Result<()> {
+        let mut buf = vec![0u8; self.page_size];
         buf[0..8].copy_from_slice(META_MAGIC);
         buf[8..16].copy_from_slice(&self.next_transaction_id.to_le_bytes());
         buf[16..24].copy_from_slice(&self.next_commit_ts.to_le_bytes());
         buf[24..32].copy_from_slice(&self.frozen_xid.to_le_bytes());
+        buf[META_PAGE_SIZE_OFFSET..META_PAGE_SIZE_OFFSET + 4]
+            .copy_from_slice(&(self.page_size as u32).to_le_bytes());
+        buf[META_FORMAT_VERSION_OFFSET..META_FORMAT_VERSION_OFFSET + 4]
+            .copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        let buf = self.disk_image(&buf);
         self.write_page_raw(0, &buf)?;
-        self.file.sync_all()?;
         if let Some(page_box) = self.cache.get_mut(0).and_then(|slot| slot.as_mut()) {
             page_box.data = buf;
         }
@@ -367,35 +720,146 @@ impl Pager {
             // commit record. Recovery only replays page records that appear
             // before the commit/checkpoint marker and restores the transaction
             // table from transaction-status records. Database pages are written
-            // only after the commit record is durable. Under version-in-place the
-            // image to persist is the page's *current* shared-cache content (it
-            // may also carry other live transactions' uncommitted versions, which
-            // is safe: visibility hides them via the clog).
-            let touched: Vec<u32> = transaction.touched_pages().collect();
+            // only after the commit record is durable, at the next checkpoint.
+            // Under version-in-place the image to persist is the page's
+            // *current* shared-cache content (it may also carry other live
+            // transactions' uncommitted versions, which is safe: visibility
+            // hides them via the clog).
+            // Pages marked dirty but never flushed are logged with the rest.
+            let mut touched: BTreeSet<u32> = transaction.touched_pages().collect();
+            touched.append(&mut self.dirty);
             for &page_num in &touched {
                 if let Some(data) = self.cached_page_image(page_num) {
                     self.wal.append_page(page_num, &data)?;
+                    self.pending_pages.insert(page_num, data);
                 }
             }
             let committed = TransactionStatus::Committed(commit_ts);
             self.wal.append_tx_status(transaction_id, committed)?;
+            self.wal.sync_commit()?;
             self.tx_table.insert(transaction_id, committed);
-            for &page_num in &touched {
-                if let Some(data) = self.cached_page_image(page_num) {
-                    self.write_page_raw(page_num, &data)?;
-                }
-            }
-            // Record the commit durably in the clog before truncating the WAL:
-            // once the WAL is gone the clog is the only cross-reopen witness that
-            // this transaction committed. A crash before this point leaves the
-            // WAL commit record, which recovery replays into the clog instead.
             self.clog.record(transaction_id, committed)?;
-            self.wal.append_checkpoint()?;
-            self.wal.truncate()?;
+            self.free_pages.extend(transaction.freed_pages());
+            self.pending_commits += 1;
+            if self.pending_commits >= self.group_commit
+                || self.wal.size()? >= self.wal_size_limit
+            {
+                self.checkpoint()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the pages of every commit accumulated since the last checkpoint
+    /// to the database file, then truncate the WAL. Runs automatically every
+    /// [`Pager::set_group_commit`] commits, once the WAL reaches
+    /// [`Pager::set_wal_size_limit`], and when the pager is dropped.
+    pub fn checkpoint(&mut self) -> io::Result<()> {
+        if self.pending_commits == 0 && self.pending_pages.is_empty() {
+            // Pages written outside a transaction are already in the file; the
+            // WAL only needs truncating once they, and the clog, are durable.
+            if !self.wal.is_empty()? {
+                self.sync_file()?;
+                self.clog.sync()?;
+                self.wal.truncate()?;
+            }
+            return Ok(());
+        }
+        for (page_num, data) in std::mem::take(&mut self.pending_pages) {
+            self.write_page_raw(page_num, &data)?;
+        }
+        // Persist the advanced transaction-id counter with the pages so a later
+        // session does not restart ids and collide with versions committed here.
+        self.write_meta()?;
+        self.sync_file()?;
+        // The clog must hold the commits durably before the WAL is truncated:
+        // once the WAL is gone the clog is the only cross-reopen witness that
+        // they committed. A crash before this point leaves the WAL commit
+        // records, which recovery replays into the clog instead.
+        self.clog.sync()?;
+        self.wal.append_checkpoint()?;
+        self.wal.truncate()?;
+        self.pending_commits = 0;
+        Ok(())
+    }
+
+    /// Choose how aggressively the WAL, clog, and database file are fsynced.
+    /// The default, [`SyncMode::Full`], makes every returned commit survive
+    /// power loss; [`SyncMode::Normal`] and [`SyncMode::Off`] trade that for
+    /// fewer syncs. None of the modes risk data on a plain process crash.
+    pub fn set_sync_mode(&mut self, mode: SyncMode) {
+        self.sync_mode = mode;
+        self.wal.set_sync_mode(mode);
+        self.clog.set_sync_mode(mode);
+    }
+
+    pub fn sync_mode(&self) -> SyncMode {
+        self.sync_mode
+    }
+
+    /// Choose how B-trees opened on this pager divide full pages. The
+    /// default, [`SplitStrategy::Midpoint`], halves them;
+    /// [`SplitStrategy::AppendOptimized`] packs pages filled by ascending
+    /// keys. The setting is not persisted and does not affect existing pages.
+    pub fn set_split_strategy(&mut self, strategy: SplitStrategy) {
+        self.split_strategy = strategy;
+    }
+
+    pub fn split_strategy(&self) -> SplitStrategy {
+        self.split_strategy
+    }
+
+    /// Accumulate up to `commits` committed transactions in the WAL before
+    /// writing their pages to the database file in one checkpoint. A page
+    /// changed by several of them is written once. The default of 1
+    /// checkpoints after every commit; 0 is treated as 1. Commits are durable
+    /// in the WAL either way, and a reopen replays any that were pending.
+    pub fn set_group_commit(&mut self, commits: u32) -> io::Result<()> {
+        self.group_commit = commits.max(1);
+        if self.pending_commits >= self.group_commit {
+            self.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    /// Checkpoint whenever the WAL grows to `bytes`, even before
+    /// [`Pager::set_group_commit`] commits have accumulated, so the log of a
+    /// long-running process stays bounded. Defaults to
+    /// [`DEFAULT_WAL_SIZE_LIMIT`].
+    pub fn set_wal_size_limit(&mut self, bytes: u64) -> io::Result<()> {
+        self.wal_size_limit = bytes;
+        if self.wal.size()? >= self.wal_size_limit {
+            self.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    /// Current size of the WAL in bytes.
+    pub fn wal_size(&self) -> io::Result<u64> {
+        self.wal.size()
+    }
+
+    /// Number of fsyncs issued on the database file, WAL, and clog since the
+    /// pager was opened.
+    pub fn sync_count(&self) -> u64 {
+        self.syncs + self.wal.sync_count() + self.clog.sync_count()
+    }
+
+    /// Number of row payload bytes decoded into column values since the pager
+    /// was opened. Columns a projected scan skips are not counted.
+    pub fn bytes_decoded(&self) -> u64 {
+        self.bytes_decoded
+    }
+
+    pub(crate) fn note_bytes_decoded(&mut self, bytes: usize) {
+        self.bytes_decoded += bytes as u64;
+    }
+
+    /// fsync the database file unless syncing is off.
+    fn sync_file(&mut self) -> io::Result<()> {
+        if self.sync_mode.syncs_barriers() {
             self.file.sync_all()?;
-            // Persist the advanced transaction-id counter so a later session does
-            // not restart ids and collide with versions committed by this one.
-            self.persist_meta()?;
+            self.syncs += 1;
         }
         Ok(())
     }
@@ -429,11 +893,17 @@ impl Pager {
                 if page_num != CATALOG_TABLE_PAGE && page_num != CATALOG_SEQUENCE_PAGE {
                     continue;
                 }
-                let mut buf = [0u8; PAGE_SIZE];
-                if page_num < self.file_length_pages {
-                    let offset = (page_num as u64) * (PAGE_SIZE as u64);
+                let mut buf = vec![0u8; self.page_size];
+                if let Some(pending) = self.pending_pages.get(&page_num) {
+                    // Committed but not yet checkpointed: the file is stale.
+                    buf.copy_from_slice(pending);
+                } else if page_num < self.file_length_pages {
+                    let offset = (page_num as u64) * (self.page_size as u64);
                     self.file.seek(SeekFrom::Start(offset))?;
                     self.file.read_exact(&mut buf)?;
+                    if self.checksums {
+                        verify_checksum(page_num, &buf)?;
+                    }
                 }
                 if let Some(page_box) = &mut self.cache[page_num as usize] {
                     page_box.data = buf;
@@ -444,6 +914,36 @@ impl Pager {
     }
 }
 
+impl Drop for Pager {
+    fn drop(&mut self) {
+        // Commits held back by group commit are already durable in the WAL; a
+        // failure here only leaves them for recovery to replay at reopen.
+        if self.pending_commits > 0 || !self.pending_pages.is_empty() {
+            let _ = self.checkpoint();
+        }
+    }
+}
+
+/// Check the trailer checksum of a page read from disk. A page that is still
+/// all zeroes was allocated but never written, so it carries no checksum.
+fn verify_checksum(page_num: u32, data: &[u8]) -> io::Result<()> {
+    if data.iter().all(|&b| b == 0) {
+        return Ok(());
+    }
+    let stored = get_checksum(data);
+    let computed = crc32(&data[..checksum_offset(data.len())]);
+    if stored != computed {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "page {} checksum mismatch: stored {:08x}, computed {:08x}",
+                page_num, stored, computed
+            ),
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,12 +2,24 @@ use std::io;
 
 use crate::transaction::TransactionId;
 
+/// Logical row key stored in B-Tree cells. Wide enough for a BIGINT key
+/// column; INTEGER keys are widened losslessly.
+pub type RowKey = i64;
+
+/// Stands in for SQL NULL in the string value maps that predicates and
+/// expressions are evaluated against, so a real NULL is distinct from the
+/// text `'NULL'`. See [`ColumnValue::to_predicate_string`].
+pub const NULL_MARKER: &str = "\u{0}NULL";
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ColumnType {
     Integer,
+    BigInt,
     Text,
     Boolean,
     Char(usize),
+    /// Variable-length text limited to the given number of characters.
+    Varchar(usize),
     SmallInt {
         width: usize,
         unsigned: bool,
@@ -21,16 +33,32 @@ pub enum ColumnType {
         scale: usize,
         unsigned: bool,
     },
+    /// Exact fixed-point number with `precision` total and `scale` fractional digits.
+    Decimal {
+        precision: usize,
+        scale: usize,
+    },
     Date,
     DateTime,
     Timestamp,
     Time,
     Year,
+    /// Raw bytes, entered and displayed as hexadecimal.
+    Blob,
 }
 
 impl ColumnType {
     pub fn from_str(s: &str) -> Option<Self> {
         let upper = s.to_uppercase();
+        if upper.starts_with("VARCHAR") {
+            let start = s.find('(')?;
+            let end = s.find(')')?;
+            return s[start + 1..end]
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .map(ColumnType::Varchar);
+        }
         if upper.starts_with("CHAR") {
             if let Some(start) = s.find('(') {
                 if let Some(end) = s.find(')') {
@@ -109,6 +137,29 @@ impl ColumnType {
                 unsigned,
             });
         }
+        if base.starts_with("DECIMAL") {
+            let mut precision = 10usize;
+            let mut scale = 0usize;
+            if let (Some(start), Some(end)) = (base.find('('), base.find(')')) {
+                let mut parts = base[start + 1..end]
+                    .split(',')
+                    .map(|p| p.trim().parse::<usize>());
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(Ok(p)), None, None) => precision = p,
+                    (Some(Ok(p)), Some(Ok(s)), None) => {
+                        precision = p;
+                        scale = s;
+                    }
+                    _ => return None,
+                }
+            } else if base != "DECIMAL" {
+                return None;
+            }
+            if precision == 0 || precision > MAX_DECIMAL_PRECISION || scale > precision {
+                return None;
+            }
+            return Some(ColumnType::Decimal { precision, scale });
+        }
         if base == "DATE" {
             return Some(ColumnType::Date);
         }
@@ -126,18 +177,35 @@ impl ColumnType {
         }
         match upper.as_str() {
             "INTEGER" | "INT" => Some(ColumnType::Integer),
+            "BIGINT" => Some(ColumnType::BigInt),
             "TEXT" => Some(ColumnType::Text),
             "BOOLEAN" | "BOOL" => Some(ColumnType::Boolean),
+            "BLOB" => Some(ColumnType::Blob),
             _ => None,
         }
     }
 
+    /// Whether values of this type are whole numbers.
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            ColumnType::Integer | ColumnType::BigInt | ColumnType::SmallInt { .. } | ColumnType::MediumInt { .. }
+        )
+    }
+
+    /// Whether values of this type are numbers: integers, doubles or decimals.
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || matches!(self, ColumnType::Double { .. } | ColumnType::Decimal { .. })
+    }
+
     pub fn as_str(&self) -> String {
         match self {
             ColumnType::Integer => "INTEGER".into(),
+            ColumnType::BigInt => "BIGINT".into(),
             ColumnType::Text => "TEXT".into(),
             ColumnType::Boolean => "BOOLEAN".into(),
             ColumnType::Char(size) => format!("CHAR({})", size),
+            ColumnType::Varchar(size) => format!("VARCHAR({})", size),
             ColumnType::SmallInt { width, unsigned } => {
                 let mut s = String::from("SMALLINT");
                 if *width > 0 {
@@ -169,11 +237,13 @@ impl ColumnType {
                 }
                 s
             }
+            ColumnType::Decimal { precision, scale } => format!("DECIMAL({},{})", precision, scale),
             ColumnType::Date => "DATE".into(),
             ColumnType::DateTime => "DATETIME".into(),
             ColumnType::Timestamp => "TIMESTAMP".into(),
             ColumnType::Time => "TIME".into(),
             ColumnType::Year => "YEAR".into(),
+            ColumnType::Blob => "BLOB".into(),
         }
     }
 
@@ -201,6 +271,13 @@ impl ColumnType {
             10 => Some(ColumnType::Timestamp),
             11 => Some(ColumnType::Time),
             12 => Some(ColumnType::Year),
+            13 => Some(ColumnType::BigInt),
+            14 => Some(ColumnType::Varchar(0)),
+            15 => Some(ColumnType::Decimal {
+                precision: 10,
+                scale: 0,
+            }),
+            16 => Some(ColumnType::Blob),
             _ => None,
         }
     }
@@ -219,6 +296,10 @@ impl ColumnType {
             ColumnType::Timestamp => 10,
             ColumnType::Time => 11,
             ColumnType::Year => 12,
+            ColumnType::BigInt => 13,
+            ColumnType::Varchar(_) => 14,
+            ColumnType::Decimal { .. } => 15,
+            ColumnType::Blob => 16,
         }
     }
 }
@@ -227,15 +308,22 @@ impl ColumnType {
 pub enum ColumnValue {
     Null,
     Integer(i32),
+    BigInt(i64),
     Text(String),
     Boolean(bool),
     Char(String),
     Double(f64),
+    /// Fixed-point number stored as `value / 10^scale`.
+    Decimal {
+        value: i64,
+        scale: u8,
+    },
     Date(i32),
     DateTime(i64),
     Timestamp(i64),
     Time(i32),
     Year(u16),
+    Blob(Vec<u8>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -301,12 +389,37 @@ impl RowData {
                     buf.push(0x0A);
                     buf.extend(&y.to_le_bytes());
                 }
+                ColumnValue::BigInt(i) => {
+                    buf.push(0x0B);
+                    buf.extend(&i.to_le_bytes());
+                }
+                ColumnValue::Decimal { value, scale } => {
+                    buf.push(0x0C);
+                    buf.extend(&value.to_le_bytes());
+                    buf.push(*scale);
+                }
+                ColumnValue::Blob(bytes) => {
+                    buf.push(0x0D);
+                    buf.extend(&(bytes.len() as u32).to_le_bytes());
+                    buf.extend(bytes);
+                }
             }
         }
         buf
     }
 
     pub fn deserialize(bytes: &[u8]) -> io::Result<RowData> {
+        Self::deserialize_projected(bytes, None).map(|(row, _)| row)
+    }
+
+    /// Decode only the columns marked in `wanted` (every column when `None`).
+    /// The others are skipped by their encoded length without being decoded
+    /// and come back as `ColumnValue::Null`. Also returns the number of
+    /// payload bytes that were decoded into values.
+    pub fn deserialize_projected(
+        bytes: &[u8],
+        wanted: Option<&[bool]>,
+    ) -> io::Result<(RowData, usize)> {
         if bytes.len() < 2 {
             return Err(io::Error::new(io::ErrorKind::Other, "Row too short"));
         }
@@ -314,12 +427,22 @@ impl RowData {
         let num_cols = u16::from_le_bytes(bytes[offset..offset + 2].try_into().unwrap()) as usize;
         offset += 2;
         let mut cols = Vec::with_capacity(num_cols);
-        for _ in 0..num_cols {
+        let mut decoded = 2;
+        for i in 0..num_cols {
             if offset >= bytes.len() {
                 return Err(io::Error::new(io::ErrorKind::Other, "Unexpected EOF"));
             }
             let tag = bytes[offset];
             offset += 1;
+            if wanted.is_some_and(|w| !w.get(i).copied().unwrap_or(false)) {
+                offset += encoded_value_len(tag, &bytes[offset..])?;
+                if offset > bytes.len() {
+                    return Err(io::Error::other("EOF"));
+                }
+                cols.push(ColumnValue::Null);
+                continue;
+            }
+            let start = offset - 1;
             match tag {
                 0x00 => {
                     cols.push(ColumnValue::Null);
@@ -416,15 +539,64 @@ impl RowData {
                     offset += 2;
                     cols.push(ColumnValue::Year(val));
                 }
+                0x0B => {
+                    if offset + 8 > bytes.len() {
+                        return Err(io::Error::new(io::ErrorKind::Other, "EOF"));
+                    }
+                    let val = i64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+                    offset += 8;
+                    cols.push(ColumnValue::BigInt(val));
+                }
+                0x0C => {
+                    if offset + 9 > bytes.len() {
+                        return Err(io::Error::other("EOF"));
+                    }
+                    let value = i64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+                    let scale = bytes[offset + 8];
+                    offset += 9;
+                    cols.push(ColumnValue::Decimal { value, scale });
+                }
+                0x0D => {
+                    if offset + 4 > bytes.len() {
+                        return Err(io::Error::other("EOF"));
+                    }
+                    let len =
+                        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+                    offset += 4;
+                    if offset + len > bytes.len() {
+                        return Err(io::Error::other("EOF"));
+                    }
+                    cols.push(ColumnValue::Blob(bytes[offset..offset + len].to_vec()));
+                    offset += len;
+                }
                 _ => {
                     return Err(io::Error::new(io::ErrorKind::Other, "Unknown type tag"));
                 }
             }
+            decoded += offset - start;
         }
-        Ok(RowData(cols))
+        Ok((RowData(cols), decoded))
     }
 }
 
+/// Length of the value encoded after type tag `tag`, read from the start of
+/// `rest` for the length-prefixed types.
+fn encoded_value_len(tag: u8, rest: &[u8]) -> io::Result<usize> {
+    Ok(match tag {
+        0x00 => 0,
+        0x03 => 1,
+        0x0A => 2,
+        0x01 | 0x06 | 0x09 => 4,
+        0x05 | 0x07 | 0x08 | 0x0B => 8,
+        0x0C => 9,
+        0x02 | 0x04 | 0x0D => {
+            let len = rest.get(..4).ok_or_else(|| io::Error::other("EOF"))?;
+            4 + u32::from_le_bytes(len.try_into().unwrap()) as usize
+        }
+        _ => return Err(io::Error::other("Unknown type tag")),
+    })
+}
+
 /// Build a `RowData` from raw string values according to the declared column
 /// types. Returns an error if any value cannot be converted or the counts do
 /// not match.
@@ -441,7 +613,7 @@ pub fn build_row_data(
     }
     let mut cols = Vec::with_capacity(columns.len());
     for (v, (name, ty)) in values.iter().zip(columns.iter()) {
-        if v.to_ascii_uppercase() == "NULL" {
+        if v == NULL_MARKER || v.eq_ignore_ascii_case("NULL") {
             cols.push(ColumnValue::Null);
             continue;
         }
@@ -455,6 +627,15 @@ pub fn build_row_data(
                     ));
                 }
             },
+            ColumnType::BigInt => match v.parse::<i64>() {
+                Ok(i) => cols.push(ColumnValue::BigInt(i)),
+                Err(_) => {
+                    return Err(format!(
+                        "Value '{}' for column '{}' is not a valid BIGINT",
+                        v, name
+                    ));
+                }
+            },
             ColumnType::Text => cols.push(ColumnValue::Text(v.clone())),
             ColumnType::Boolean => match v.to_ascii_lowercase().as_str() {
                 "true" => cols.push(ColumnValue::Boolean(true)),
@@ -479,6 +660,15 @@ pub fn build_row_data(
                 }
                 cols.push(ColumnValue::Char(s));
             }
+            ColumnType::Varchar(len) => {
+                if v.chars().count() > *len {
+                    return Err(format!(
+                        "Value '{}' for column '{}' exceeds length {}",
+                        v, name, len
+                    ));
+                }
+                cols.push(ColumnValue::Text(v.clone()));
+            }
             ColumnType::SmallInt { unsigned, .. } => {
                 let val = v.parse::<i32>().map_err(|_| {
                     format!(
@@ -520,6 +710,27 @@ pub fn build_row_data(
                 }
                 cols.push(ColumnValue::Double(val));
             }
+            ColumnType::Decimal { precision, scale } => {
+                let value = parse_decimal(v, *scale).ok_or_else(|| {
+                    format!("Value '{}' for column '{}' is not a valid DECIMAL", v, name)
+                })?;
+                if value.unsigned_abs() >= 10u64.pow(*precision as u32) {
+                    return Err(format!("Value '{}' for column '{}' out of range", v, name));
+                }
+                cols.push(ColumnValue::Decimal {
+                    value,
+                    scale: *scale as u8,
+                });
+            }
+            ColumnType::Blob => match parse_blob_hex(v) {
+                Some(bytes) => cols.push(ColumnValue::Blob(bytes)),
+                None => {
+                    return Err(format!(
+                        "Value '{}' for column '{}' is not a valid hex BLOB",
+                        v, name
+                    ));
+                }
+            },
             ColumnType::Date => match parse_date(v) {
                 Some(d) => cols.push(ColumnValue::Date(d)),
                 None => {
@@ -570,6 +781,151 @@ pub fn build_row_data(
     Ok(RowData(cols))
 }
 
+/// Build a `RowData` from already-typed values, checking each against its
+/// declared column type. Text is stored as given, never re-parsed; numbers are
+/// widened or range-checked the same way [`build_row_data`] checks literals.
+pub fn build_typed_row_data(
+    values: Vec<ColumnValue>,
+    columns: &[(String, ColumnType)],
+) -> Result<RowData, String> {
+    if values.len() != columns.len() {
+        return Err(format!(
+            "Expected {} values, got {}",
+            columns.len(),
+            values.len()
+        ));
+    }
+    let mut cols = Vec::with_capacity(columns.len());
+    for (v, (name, ty)) in values.into_iter().zip(columns.iter()) {
+        cols.push(typed_column_value(v, name, ty)?);
+    }
+    Ok(RowData(cols))
+}
+
+/// Check one typed value against column `name` of type `ty`, converting it to
+/// the column's storage representation. See [`build_typed_row_data`].
+pub fn typed_column_value(
+    v: ColumnValue,
+    name: &str,
+    ty: &ColumnType,
+) -> Result<ColumnValue, String> {
+    let out_of_range = |v: &ColumnValue| {
+        format!(
+            "Value '{}' for column '{}' out of range",
+            v.to_string_value(),
+            name
+        )
+    };
+    let int = match &v {
+        ColumnValue::Integer(i) => Some(*i as i64),
+        ColumnValue::BigInt(i) => Some(*i),
+        _ => None,
+    };
+    let value = match (ty, &v) {
+        (_, ColumnValue::Null) => ColumnValue::Null,
+        (ColumnType::Integer, _) if int.is_some() => {
+            let i = i32::try_from(int.unwrap_or_default()).map_err(|_| out_of_range(&v))?;
+            ColumnValue::Integer(i)
+        }
+        (ColumnType::BigInt, _) if int.is_some() => ColumnValue::BigInt(int.unwrap_or_default()),
+        (ColumnType::SmallInt { unsigned, .. }, _) if int.is_some() => {
+            let range = if *unsigned {
+                0..=65_535
+            } else {
+                -32_768..=32_767
+            };
+            match int.filter(|i| range.contains(i)) {
+                Some(i) => ColumnValue::Integer(i as i32),
+                None => return Err(out_of_range(&v)),
+            }
+        }
+        (ColumnType::MediumInt { unsigned, .. }, _) if int.is_some() => {
+            let range = if *unsigned {
+                0..=16_777_215
+            } else {
+                -8_388_608..=8_388_607
+            };
+            match int.filter(|i| range.contains(i)) {
+                Some(i) => ColumnValue::Integer(i as i32),
+                None => return Err(out_of_range(&v)),
+            }
+        }
+        (ColumnType::Double { unsigned, .. }, _) => {
+            let val = match &v {
+                ColumnValue::Double(d) => *d,
+                _ if int.is_some() => int.unwrap_or_default() as f64,
+                _ => return Err(type_mismatch(&v, name, ty)),
+            };
+            if *unsigned && val < 0.0 {
+                return Err(out_of_range(&v));
+            }
+            ColumnValue::Double(val)
+        }
+        (ColumnType::Decimal { precision, scale }, _) => {
+            let value = match &v {
+                ColumnValue::Decimal { value, scale: from } => {
+                    rescale_decimal(*value as i128, *from, *scale as u8)
+                }
+                _ if int.is_some() => {
+                    rescale_decimal(int.unwrap_or_default() as i128, 0, *scale as u8)
+                }
+                _ => return Err(type_mismatch(&v, name, ty)),
+            };
+            if value.unsigned_abs() >= 10u128.pow(*precision as u32) {
+                return Err(out_of_range(&v));
+            }
+            ColumnValue::Decimal {
+                value: value as i64,
+                scale: *scale as u8,
+            }
+        }
+        (ColumnType::Text, ColumnValue::Text(s) | ColumnValue::Char(s)) => {
+            ColumnValue::Text(s.clone())
+        }
+        (ColumnType::Varchar(len), ColumnValue::Text(s) | ColumnValue::Char(s)) => {
+            if s.chars().count() > *len {
+                return Err(format!(
+                    "Value '{}' for column '{}' exceeds length {}",
+                    s, name, len
+                ));
+            }
+            ColumnValue::Text(s.clone())
+        }
+        (ColumnType::Char(len), ColumnValue::Text(s) | ColumnValue::Char(s)) => {
+            let s = s.trim_end_matches(' ');
+            if s.len() > *len {
+                return Err(format!(
+                    "Value '{}' for column '{}' exceeds length {}",
+                    s, name, len
+                ));
+            }
+            ColumnValue::Char(format!("{:<width$}", s, width = *len))
+        }
+        (ColumnType::Boolean, ColumnValue::Boolean(_))
+        | (ColumnType::Date, ColumnValue::Date(_))
+        | (ColumnType::Time, ColumnValue::Time(_))
+        | (ColumnType::Year, ColumnValue::Year(_))
+        | (ColumnType::Blob, ColumnValue::Blob(_)) => v,
+        (ColumnType::DateTime, ColumnValue::DateTime(ts) | ColumnValue::Timestamp(ts)) => {
+            ColumnValue::DateTime(*ts)
+        }
+        (ColumnType::Timestamp, ColumnValue::DateTime(ts) | ColumnValue::Timestamp(ts)) => {
+            ColumnValue::Timestamp(*ts)
+        }
+        _ => return Err(type_mismatch(&v, name, ty)),
+    };
+    Ok(value)
+}
+
+fn type_mismatch(v: &ColumnValue, name: &str, ty: &ColumnType) -> String {
+    format!(
+        "Value '{}' for column '{}' is not a valid {}",
+        v.to_string_value(),
+        name,
+        ty.as_str()
+    )
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RowVersionRef {
     pub page_num: u32,
@@ -578,7 +934,7 @@ pub struct RowVersionRef {
 
 #[derive(Debug, Clone)]
 pub struct Row {
-    pub key: i32,
+    pub key: RowKey,
     pub data: RowData,
     pub created_tx: TransactionId,
     pub deleted_tx: Option<TransactionId>,
@@ -586,7 +942,7 @@ pub struct Row {
 }
 
 impl Row {
-    pub fn new(key: i32, data: RowData) -> Self {
+    pub fn new(key: RowKey, data: RowData) -> Self {
         Self {
             key,
             data,
@@ -596,6 +952,13 @@ impl Row {
         }
     }
 
+    /// Look up a column value by name. `columns` is the table schema the row
+    /// was built against (e.g. `TableInfo::columns`), in storage order.
+    pub fn get(&self, columns: &[(String, ColumnType)], name: &str) -> Option<&ColumnValue> {
+        let idx = columns.iter().position(|(c, _)| c == name)?;
+        self.data.0.get(idx)
+    }
+
     pub fn serialize_mvcc_payload(&self) -> Vec<u8> {
         let mut buf = Vec::new();
         buf.push(MVCC_ROW_PAYLOAD_FORMAT_VERSION);
@@ -619,15 +982,27 @@ impl Row {
         buf
     }
 
-    pub fn deserialize_mvcc_payload(key: i32, bytes: &[u8]) -> io::Result<Self> {
+    pub fn deserialize_mvcc_payload(key: RowKey, bytes: &[u8]) -> io::Result<Self> {
+        Self::deserialize_mvcc_payload_projected(key, bytes, None).map(|(row, _)| row)
+    }
+
+    /// Like [`Row::deserialize_mvcc_payload`], decoding only the columns
+    /// marked in `wanted`; see [`RowData::deserialize_projected`].
+    pub fn deserialize_mvcc_payload_projected(
+        key: RowKey,
+        bytes: &[u8],
+        wanted: Option<&[bool]>,
+    ) -> io::Result<(Self, usize)> {
         if bytes.first().copied() != Some(MVCC_ROW_PAYLOAD_FORMAT_VERSION) {
-            return Ok(Self {
+            let (data, decoded) = RowData::deserialize_projected(bytes, wanted)?;
+            let row = Self {
                 key,
-                data: RowData::deserialize(bytes)?,
+                data,
                 created_tx: COMMITTED_BOOTSTRAP_TX,
                 deleted_tx: None,
                 version_ptr: None,
-            });
+            };
+            return Ok((row, decoded));
         }
 
         let mut offset = 1;
@@ -690,13 +1065,22 @@ impl Row {
             })
         };
 
-        Ok(Self {
+        let (data, decoded) = RowData::deserialize_projected(&bytes[offset..], wanted)?;
+        let row = Self {
             key,
-            data: RowData::deserialize(&bytes[offset..])?,
+            data,
             created_tx,
             deleted_tx,
             version_ptr,
-        })
+        };
+        Ok((row, decoded))
+    }
+
+    /// Whether an MVCC payload carries a `deleted_tx`, read from its prefix
+    /// without decoding the row data. Legacy payloads are never deleted.
+    pub fn mvcc_payload_is_deleted(prefix: &[u8]) -> bool {
+        prefix.first().copied() == Some(MVCC_ROW_PAYLOAD_FORMAT_VERSION)
+            && prefix.get(9).is_some_and(|&flag| flag != 0)
     }
 }
 
@@ -705,10 +1089,12 @@ impl ColumnValue {
         match self {
             ColumnValue::Null => "NULL".into(),
             ColumnValue::Integer(i) => i.to_string(),
+            ColumnValue::BigInt(i) => i.to_string(),
             ColumnValue::Text(s) => s.clone(),
             ColumnValue::Boolean(b) => b.to_string(),
             ColumnValue::Char(s) => s.clone(),
             ColumnValue::Double(f) => f.to_string(),
+            ColumnValue::Decimal { value, scale } => format_decimal(*value as i128, *scale),
             ColumnValue::Date(d) => {
                 use chrono::{Duration, NaiveDate};
                 let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
@@ -731,6 +1117,70 @@ impl ColumnValue {
                 format!("{}{:02}:{:02}:{:02}", if neg { "-" } else { "" }, h, m, sec)
             }
             ColumnValue::Year(y) => format!("{:04}", y),
+            ColumnValue::Blob(bytes) => format_blob_hex(bytes),
+        }
+    }
+    /// Like [`ColumnValue::to_string_value`], but NULL becomes [`NULL_MARKER`].
+    /// Use this when building value maps for expression evaluation.
+    pub fn to_predicate_string(&self) -> String {
+        match self {
+            ColumnValue::Null => NULL_MARKER.into(),
+            other => other.to_string_value(),
+        }
+    }
+
+    /// Interpret an INTEGER or BIGINT value as a B-tree row key.
+    pub fn as_row_key(&self) -> Option<RowKey> {
+        match self {
+            ColumnValue::Integer(i) => Some(*i as RowKey),
+            ColumnValue::BigInt(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Numeric value of an integer, double or decimal; `None` otherwise.
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            ColumnValue::Integer(i) => Some(*i as f64),
+            ColumnValue::BigInt(i) => Some(*i as f64),
+            ColumnValue::Double(f) => Some(*f),
+            ColumnValue::Decimal { value, scale } => {
+                Some(*value as f64 / 10f64.powi(*scale as i32))
+            }
+            _ => None,
+        }
+    }
+
+    /// Ordering used to sort result rows. NULL sorts first, numbers compare
+    /// by value across integer, double and decimal columns, and other values
+    /// compare within their own type. Unrelated types fall back to comparing
+    /// their display strings.
+    pub fn compare(&self, other: &ColumnValue) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        match (self, other) {
+            (ColumnValue::Null, ColumnValue::Null) => Ordering::Equal,
+            (ColumnValue::Null, _) => Ordering::Less,
+            (_, ColumnValue::Null) => Ordering::Greater,
+            (
+                ColumnValue::Text(a) | ColumnValue::Char(a),
+                ColumnValue::Text(b) | ColumnValue::Char(b),
+            ) => a.cmp(b),
+            (ColumnValue::Boolean(a), ColumnValue::Boolean(b)) => a.cmp(b),
+            (ColumnValue::Date(a), ColumnValue::Date(b))
+            | (ColumnValue::Time(a), ColumnValue::Time(b)) => a.cmp(b),
+            (
+                ColumnValue::DateTime(a) | ColumnValue::Timestamp(a),
+                ColumnValue::DateTime(b) | ColumnValue::Timestamp(b),
+            ) => a.cmp(b),
+            (ColumnValue::Year(a), ColumnValue::Year(b)) => a.cmp(b),
+            (ColumnValue::Blob(a), ColumnValue::Blob(b)) => a.cmp(b),
+            _ => match (self.as_row_key(), other.as_row_key()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => match (self.as_f64(), other.as_f64()) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    _ => self.to_string_value().cmp(&other.to_string_value()),
+                },
+            },
         }
     }
 }
@@ -781,6 +1231,102 @@ pub(crate) fn parse_year(s: &str) -> Option<u16> {
     }
 }
 
+/// Largest DECIMAL precision whose values always fit the backing `i64`.
+pub const MAX_DECIMAL_PRECISION: usize = 18;
+
+/// Parse a decimal literal into an unscaled value with `scale` fractional
+/// digits, rounding half away from zero when the literal has more.
+pub fn parse_decimal(s: &str, scale: usize) -> Option<i64> {
+    let (value, digits) = parse_decimal_literal(s)?;
+    let rescaled = rescale_decimal(value as i128, digits, u8::try_from(scale).ok()?);
+    i64::try_from(rescaled).ok()
+}
+
+/// Parse a decimal literal, keeping as many fractional digits as it has.
+pub fn parse_decimal_literal(s: &str) -> Option<(i64, u8)> {
+    let s = s.trim();
+    let (neg, body) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (int_part, frac_part) = body.split_once('.').unwrap_or((body, ""));
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let scale = u8::try_from(frac_part.len()).ok()?;
+    let digits = format!("{}{}", int_part, frac_part);
+    let mut value: i64 = if digits.is_empty() {
+        0
+    } else {
+        digits.parse().ok()?
+    };
+    if neg {
+        value = -value;
+    }
+    Some((value, scale))
+}
+
+/// Convert an unscaled decimal between scales, rounding half away from zero.
+pub fn rescale_decimal(value: i128, from: u8, to: u8) -> i128 {
+    if to >= from {
+        let factor = 10i128.checked_pow((to - from) as u32).unwrap_or(i128::MAX);
+        value.saturating_mul(factor)
+    } else {
+        let factor = 10i128.pow((from - to).min(38) as u32);
+        let half = factor / 2;
+        if value >= 0 {
+            (value + half) / factor
+        } else {
+            (value - half) / factor
+        }
+    }
+}
+
+/// Render an unscaled decimal with exactly `scale` fractional digits.
+pub fn format_decimal(value: i128, scale: u8) -> String {
+    if scale == 0 {
+        return value.to_string();
+    }
+    let digits = value.unsigned_abs().to_string();
+    let scale = scale as usize;
+    let padded = if digits.len() <= scale {
+        format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+    let (int_part, frac_part) = padded.split_at(padded.len() - scale);
+    format!(
+        "{}{}.{}",
+        if value < 0 { "-" } else { "" },
+        int_part,
+        frac_part
+    )
+}
+
+/// Parse BLOB input: hex digits, optionally wrapped as an `X'...'` literal.
+pub fn parse_blob_hex(s: &str) -> Option<Vec<u8>> {
+    let hex = s
+        .strip_prefix(['X', 'x'])
+        .and_then(|rest| rest.strip_prefix('\''))
+        .and_then(|rest| rest.strip_suffix('\''))
+        .unwrap_or(s);
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Render BLOB bytes as uppercase hex digits.
+pub fn format_blob_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
 #[cfg(test)]
 mod mvcc_tests {
     use super::*;
@@ -825,3 +1371,57 @@ mod mvcc_tests {
         assert_eq!(decoded.version_ptr, None);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_fetches_column_by_name() {
+        let columns = vec![
+            ("id".to_string(), ColumnType::Integer),
+            ("name".to_string(), ColumnType::Text),
+        ];
+        let data = build_row_data(&["1".to_string(), "alice".to_string()], &columns).unwrap();
+        let row = Row::new(1, data);
+
+        assert_eq!(row.get(&columns, "name"), Some(&ColumnValue::Text("alice".into())));
+        assert_eq!(row.get(&columns, "id"), Some(&ColumnValue::Integer(1)));
+        assert_eq!(row.get(&columns, "missing"), None);
+    }
+
+    #[test]
+    fn projected_deserialize_skips_every_value_type() {
+        let data = RowData(vec![
+            ColumnValue::Null,
+            ColumnValue::Integer(1),
+            ColumnValue::Text("text".into()),
+            ColumnValue::Boolean(true),
+            ColumnValue::Char("ch".into()),
+            ColumnValue::Double(1.5),
+            ColumnValue::Date(2),
+            ColumnValue::DateTime(3),
+            ColumnValue::Timestamp(4),
+            ColumnValue::Time(5),
+            ColumnValue::Year(2024),
+            ColumnValue::BigInt(6),
+            ColumnValue::Decimal { value: 7, scale: 2 },
+            ColumnValue::Blob(vec![8, 9]),
+            ColumnValue::Text("last".into()),
+        ]);
+        let bytes = data.serialize();
+        let mut wanted = vec![false; data.0.len()];
+        wanted[14] = true;
+
+        let (projected, decoded) = RowData::deserialize_projected(&bytes, Some(&wanted)).unwrap();
+        let mut expected = vec![ColumnValue::Null; data.0.len()];
+        expected[14] = ColumnValue::Text("last".into());
+        assert_eq!(projected.0, expected);
+        // The column count, plus the tag, length, and bytes of "last".
+        assert_eq!(decoded, 2 + 1 + 4 + 4);
+
+        let (full, decoded) = RowData::deserialize_projected(&bytes, None).unwrap();
+        assert_eq!(full, data);
+        assert_eq!(decoded, bytes.len());
+    }
+}
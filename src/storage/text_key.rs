@@ -0,0 +1,386 @@
+//! Text-keyed access to a [`BTree`].
+//!
+//! A text tree keeps its keys as length-prefixed bytes and compares them
+//! lexicographically wherever an integer tree compares [`RowKey`]s: in the
+//! descent of a find, when placing an insert, and when choosing a split
+//! separator. Leaf cells are `[2B key length][key][4B length][row]`, where the
+//! length field carries [`OVERFLOW_FLAG`] when the row lives in an overflow
+//! chain. Internal pages hold the leftmost child followed by
+//! `[2B key length][key][4B child]` for each separator, the separator being the
+//! first key of its right child. Descents remember the pages they pass through
+//! instead of following parent pointers, which text pages leave at zero.
+//!
+//! Cells are rewritten in place rather than versioned, so text-key changes are
+//! not undone by a rollback, and deletes leave leaves underfull instead of
+//! merging them.
+//!
+//! [`RowKey`]: super::row::RowKey
+
+use super::btree::{BTree, OVERFLOW_FLAG, OVERFLOW_MARKER_SIZE};
+use super::page::{
+    HEADER_SIZE, NODE_INTERNAL, NODE_LEAF, checksum_offset, get_cell_count, get_next_leaf,
+    get_node_type, set_cell_count, set_is_root, set_next_leaf, set_node_type,
+};
+use super::row::RowData;
+use std::io;
+
+/// A leaf cell: the key bytes and everything stored after them, i.e. the
+/// length field followed by the inline row or its overflow marker.
+type LeafCell = (Vec<u8>, Vec<u8>);
+
+/// An internal separator and the child holding keys from it upwards.
+type Separator = (Vec<u8>, u32);
+
+fn malformed(page_num: u32) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("page {} is not a valid text-key page", page_num),
+    )
+}
+
+fn read_u16(data: &[u8], offset: usize) -> usize {
+    u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap()) as usize
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Read a `[2B length][bytes]` key at `offset`, returning it and the offset
+/// just past it, or `None` if it runs past `end`.
+fn read_text_key(data: &[u8], offset: usize, end: usize) -> Option<(Vec<u8>, usize)> {
+    if offset + 2 > end {
+        return None;
+    }
+    let start = offset + 2;
+    let stop = start + read_u16(data, offset);
+    (stop <= end).then(|| (data[start..stop].to_vec(), stop))
+}
+
+fn read_leaf(page_num: u32, data: &[u8]) -> io::Result<Vec<LeafCell>> {
+    let end = checksum_offset(data.len());
+    let mut cells = Vec::new();
+    let mut offset = HEADER_SIZE;
+    for _ in 0..get_cell_count(data) {
+        let (key, len_at) = read_text_key(data, offset, end).ok_or_else(|| malformed(page_num))?;
+        if len_at + 4 > end {
+            return Err(malformed(page_num));
+        }
+        offset = len_at + 4 + (read_u32(data, len_at) & !OVERFLOW_FLAG) as usize;
+        if offset > end {
+            return Err(malformed(page_num));
+        }
+        cells.push((key, data[len_at..offset].to_vec()));
+    }
+    Ok(cells)
+}
+
+fn read_internal(page_num: u32, data: &[u8]) -> io::Result<(u32, Vec<Separator>)> {
+    let end = checksum_offset(data.len());
+    if HEADER_SIZE + 4 > end {
+        return Err(malformed(page_num));
+    }
+    let leftmost = read_u32(data, HEADER_SIZE);
+    let mut separators = Vec::new();
+    let mut offset = HEADER_SIZE + 4;
+    for _ in 0..get_cell_count(data) {
+        let (key, child_at) = read_text_key(data, offset, end).ok_or_else(|| malformed(page_num))?;
+        if child_at + 4 > end {
+            return Err(malformed(page_num));
+        }
+        separators.push((key, read_u32(data, child_at)));
+        offset = child_at + 4;
+    }
+    Ok((leftmost, separators))
+}
+
+fn leaf_size(cells: &[LeafCell]) -> usize {
+    cells.iter().map(|(key, rest)| 2 + key.len() + rest.len()).sum()
+}
+
+fn internal_size(separators: &[Separator]) -> usize {
+    4 + separators.iter().map(|(key, _)| 2 + key.len() + 4).sum::<usize>()
+}
+
+/// Append a `[2B length][bytes]` key to `data` at `offset`.
+fn write_text_key(data: &mut [u8], offset: usize, key: &[u8]) -> usize {
+    data[offset..offset + 2].copy_from_slice(&(key.len() as u16).to_le_bytes());
+    data[offset + 2..offset + 2 + key.len()].copy_from_slice(key);
+    offset + 2 + key.len()
+}
+
+/// Clear `data` and write a header for a text page with `count` entries.
+fn init_page(data: &mut [u8], node_type: u8, is_root: bool, count: usize) {
+    data.fill(0);
+    set_node_type(data, node_type);
+    set_is_root(data, is_root);
+    set_cell_count(data, count as u16);
+}
+
+fn write_leaf(data: &mut [u8], is_root: bool, next_leaf: u32, cells: &[LeafCell]) {
+    init_page(data, NODE_LEAF, is_root, cells.len());
+    set_next_leaf(data, next_leaf);
+    let mut offset = HEADER_SIZE;
+    for (key, rest) in cells {
+        offset = write_text_key(data, offset, key);
+        data[offset..offset + rest.len()].copy_from_slice(rest);
+        offset += rest.len();
+    }
+}
+
+fn write_internal(data: &mut [u8], is_root: bool, leftmost: u32, separators: &[Separator]) {
+    init_page(data, NODE_INTERNAL, is_root, separators.len());
+    data[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&leftmost.to_le_bytes());
+    let mut offset = HEADER_SIZE + 4;
+    for (key, child) in separators {
+        offset = write_text_key(data, offset, key);
+        data[offset..offset + 4].copy_from_slice(&child.to_le_bytes());
+        offset += 4;
+    }
+}
+
+/// The longest prefix of `sizes` whose total stays within half of all of
+/// them, keeping at least one entry on each side.
+fn split_point(sizes: &[usize]) -> usize {
+    let half = sizes.iter().sum::<usize>() / 2;
+    let mut used = 0;
+    let mut at = 0;
+    while at < sizes.len() && used + sizes[at] <= half {
+        used += sizes[at];
+        at += 1;
+    }
+    at.clamp(1, sizes.len() - 1)
+}
+
+impl<'a> BTree<'a> {
+    /// Bytes between a page's header and its checksum trailer.
+    fn text_area(&self) -> usize {
+        checksum_offset(self.pager.page_size()) - HEADER_SIZE
+    }
+
+    /// `key` as bytes, refused when longer than an eighth of a page so that
+    /// every internal page holds several separators.
+    fn text_key_bytes<'k>(&self, key: &'k str) -> io::Result<&'k [u8]> {
+        let max = self.text_area() / 8;
+        if key.len() > max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("text key of {} bytes exceeds the {}-byte limit", key.len(), max),
+            ));
+        }
+        Ok(key.as_bytes())
+    }
+
+    /// The leaf that holds or would hold `key`, and the internal pages passed
+    /// on the way down from the root.
+    fn text_leaf_path(&mut self, key: &[u8]) -> io::Result<(Vec<u32>, u32)> {
+        let mut path = Vec::new();
+        let mut page_num = self.root_page;
+        loop {
+            let page = self.pager.get_page(page_num)?;
+            match get_node_type(&page.data) {
+                NODE_LEAF => return Ok((path, page_num)),
+                NODE_INTERNAL => {
+                    let (leftmost, separators) = read_internal(page_num, &page.data)?;
+                    let i = separators.partition_point(|(sep, _)| sep.as_slice() <= key);
+                    path.push(page_num);
+                    page_num = if i == 0 { leftmost } else { separators[i - 1].1 };
+                }
+                _ => return Err(malformed(page_num)),
+            }
+        }
+    }
+
+    fn read_text_leaf(&mut self, page_num: u32) -> io::Result<Vec<LeafCell>> {
+        let page = self.pager.get_page(page_num)?;
+        read_leaf(page_num, &page.data)
+    }
+
+    /// The length field and row for a new leaf cell, spilling the row to an
+    /// overflow chain when the cell would take more than a third of a page.
+    fn text_cell_rest(&mut self, key_len: usize, data: &RowData) -> io::Result<Vec<u8>> {
+        let bytes = data.serialize();
+        let mut rest = Vec::new();
+        if 2 + key_len + 4 + bytes.len() > self.text_area() / 3 {
+            let first = self.write_overflow(&bytes)?;
+            rest.extend(&(OVERFLOW_FLAG | OVERFLOW_MARKER_SIZE as u32).to_le_bytes());
+            rest.extend(&(bytes.len() as u32).to_le_bytes());
+            rest.extend(&first.to_le_bytes());
+        } else {
+            rest.extend(&(bytes.len() as u32).to_le_bytes());
+            rest.extend(bytes);
+        }
+        Ok(rest)
+    }
+
+    fn decode_text_row(&mut self, rest: &[u8]) -> io::Result<RowData> {
+        let len_field = read_u32(rest, 0);
+        if len_field & OVERFLOW_FLAG == 0 {
+            return RowData::deserialize(&rest[4..]);
+        }
+        let total = read_u32(rest, 4) as usize;
+        let bytes = self.read_overflow(read_u32(rest, 8), total)?;
+        RowData::deserialize(&bytes)
+    }
+
+    /// Hand the overflow chain behind a removed cell back to the pager.
+    fn free_text_overflow(&mut self, rest: &[u8]) -> io::Result<()> {
+        if read_u32(rest, 0) & OVERFLOW_FLAG == 0 {
+            return Ok(());
+        }
+        let mut page_num = read_u32(rest, 8);
+        while page_num != 0 && page_num < self.pager.num_pages() {
+            let next = get_next_leaf(&self.pager.get_page(page_num)?.data);
+            self.pager.free_page(page_num);
+            page_num = next;
+        }
+        Ok(())
+    }
+
+    /// Write `cells` to leaf `page_num`, splitting it by bytes when they no
+    /// longer fit. The root page never moves: when it splits, both halves go
+    /// to new pages and the root becomes their parent.
+    fn store_text_leaf(&mut self, path: Vec<u32>, page_num: u32, mut cells: Vec<LeafCell>) -> io::Result<()> {
+        if leaf_size(&cells) <= self.text_area() {
+            let next = get_next_leaf(&self.pager.get_page(page_num)?.data);
+            let is_root = page_num == self.root_page;
+            write_leaf(&mut self.pager.get_page_mut(page_num)?.data, is_root, next, &cells);
+            return self.pager.flush_page(page_num);
+        }
+        let sizes: Vec<usize> = cells.iter().map(|(key, rest)| 2 + key.len() + rest.len()).collect();
+        let right_cells = cells.split_off(split_point(&sizes));
+        let separator = right_cells[0].0.clone();
+
+        if page_num == self.root_page {
+            let left = self.pager.allocate_page()?;
+            let right = self.pager.allocate_page()?;
+            write_leaf(&mut self.pager.get_page_mut(left)?.data, false, right, &cells);
+            write_leaf(&mut self.pager.get_page_mut(right)?.data, false, 0, &right_cells);
+            let root = &mut self.pager.get_page_mut(page_num)?.data;
+            write_internal(root, true, left, &[(separator, right)]);
+            for page in [left, right, page_num] {
+                self.pager.flush_page(page)?;
+            }
+            return Ok(());
+        }
+
+        let right = self.pager.allocate_page()?;
+        let next = get_next_leaf(&self.pager.get_page(page_num)?.data);
+        write_leaf(&mut self.pager.get_page_mut(right)?.data, false, next, &right_cells);
+        write_leaf(&mut self.pager.get_page_mut(page_num)?.data, false, right, &cells);
+        self.pager.flush_page(right)?;
+        self.pager.flush_page(page_num)?;
+        self.insert_text_separator(path, separator, right)
+    }
+
+    /// Add `separator` pointing at `child` to the last page of `path`,
+    /// splitting internal pages upwards as needed.
+    fn insert_text_separator(&mut self, mut path: Vec<u32>, separator: Vec<u8>, child: u32) -> io::Result<()> {
+        let page_num = path.pop().ok_or_else(|| malformed(self.root_page))?;
+        let (leftmost, mut separators) = {
+            let page = self.pager.get_page(page_num)?;
+            read_internal(page_num, &page.data)?
+        };
+        let at = separators.partition_point(|(sep, _)| *sep <= separator);
+        separators.insert(at, (separator, child));
+        let is_root = page_num == self.root_page;
+        if internal_size(&separators) <= self.text_area() {
+            write_internal(&mut self.pager.get_page_mut(page_num)?.data, is_root, leftmost, &separators);
+            return self.pager.flush_page(page_num);
+        }
+
+        // The middle separator moves up; its child becomes the right half's
+        // leftmost child, so the right half keeps at least one separator.
+        let sizes: Vec<usize> = separators.iter().map(|(key, _)| 2 + key.len() + 4).collect();
+        let at = split_point(&sizes).min(separators.len() - 2);
+        let mut right_separators = separators.split_off(at);
+        let (middle, right_leftmost) = right_separators.remove(0);
+
+        if is_root {
+            let left = self.pager.allocate_page()?;
+            let right = self.pager.allocate_page()?;
+            write_internal(&mut self.pager.get_page_mut(left)?.data, false, leftmost, &separators);
+            write_internal(&mut self.pager.get_page_mut(right)?.data, false, right_leftmost, &right_separators);
+            let root = &mut self.pager.get_page_mut(page_num)?.data;
+            write_internal(root, true, left, &[(middle, right)]);
+            for page in [left, right, page_num] {
+                self.pager.flush_page(page)?;
+            }
+            return Ok(());
+        }
+
+        let right = self.pager.allocate_page()?;
+        write_internal(&mut self.pager.get_page_mut(right)?.data, false, right_leftmost, &right_separators);
+        write_internal(&mut self.pager.get_page_mut(page_num)?.data, false, leftmost, &separators);
+        self.pager.flush_page(right)?;
+        self.pager.flush_page(page_num)?;
+        self.insert_text_separator(path, middle, right)
+    }
+
+    /// Insert `data` under the text `key`. Fails if the key is already present.
+    pub fn insert_text(&mut self, key: &str, data: RowData) -> io::Result<()> {
+        let key = self.text_key_bytes(key)?;
+        let (path, leaf) = self.text_leaf_path(key)?;
+        let mut cells = self.read_text_leaf(leaf)?;
+        let Err(at) = cells.binary_search_by(|(k, _)| k.as_slice().cmp(key)) else {
+            return Err(io::Error::other(format!(
+                "Duplicate key '{}' not allowed",
+                String::from_utf8_lossy(key)
+            )));
+        };
+        let rest = self.text_cell_rest(key.len(), &data)?;
+        cells.insert(at, (key.to_vec(), rest));
+        self.store_text_leaf(path, leaf, cells)
+    }
+
+    /// The row stored under the text `key`, if any.
+    pub fn find_text(&mut self, key: &str) -> io::Result<Option<RowData>> {
+        let key = key.as_bytes();
+        let (_, leaf) = self.text_leaf_path(key)?;
+        let cells = self.read_text_leaf(leaf)?;
+        match cells.binary_search_by(|(k, _)| k.as_slice().cmp(key)) {
+            Ok(at) => self.decode_text_row(&cells[at].1).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Remove the text `key`. Returns whether it was present.
+    pub fn delete_text(&mut self, key: &str) -> io::Result<bool> {
+        let key = key.as_bytes();
+        let (path, leaf) = self.text_leaf_path(key)?;
+        let mut cells = self.read_text_leaf(leaf)?;
+        let Ok(at) = cells.binary_search_by(|(k, _)| k.as_slice().cmp(key)) else {
+            return Ok(false);
+        };
+        let (_, rest) = cells.remove(at);
+        self.free_text_overflow(&rest)?;
+        self.store_text_leaf(path, leaf, cells)?;
+        Ok(true)
+    }
+
+    /// Every text key and its row, in lexicographic byte order of the keys.
+    pub fn scan_text(&mut self) -> io::Result<Vec<(String, RowData)>> {
+        let mut page_num = self.root_page;
+        loop {
+            let page = self.pager.get_page(page_num)?;
+            match get_node_type(&page.data) {
+                NODE_LEAF => break,
+                NODE_INTERNAL => page_num = read_internal(page_num, &page.data)?.0,
+                _ => return Err(malformed(page_num)),
+            }
+        }
+
+        let mut out = Vec::new();
+        loop {
+            let cells = self.read_text_leaf(page_num)?;
+            for (key, rest) in cells {
+                let key = String::from_utf8(key).map_err(|_| malformed(page_num))?;
+                out.push((key, self.decode_text_row(&rest)?));
+            }
+            page_num = get_next_leaf(&self.pager.get_page(page_num)?.data);
+            if page_num == 0 {
+                return Ok(out);
+            }
+        }
+    }
+}
@@ -7,6 +7,8 @@ pub struct VacuumReport {
     pub versions_removed: usize,
     /// Index B-Trees rebuilt to drop stale logical row-key candidates.
     pub indexes_cleaned: usize,
+    /// Pages of rewritten trees returned to the pager's free list.
+    pub pages_freed: usize,
 }
 
 /// Returns true when an MVCC row version whose `deleted_tx` is present can be
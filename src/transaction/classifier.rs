@@ -8,10 +8,17 @@ pub fn statement_requires_transaction(stmt: &Statement) -> bool {
         Statement::Insert { .. }
             | Statement::Update { .. }
             | Statement::Delete { .. }
+            | Statement::Truncate { .. }
             | Statement::CreateTable { .. }
             | Statement::DropTable { .. }
+            | Statement::CreateView { .. }
+            | Statement::DropView { .. }
+            | Statement::RenameTable { .. }
+            | Statement::RenameColumn { .. }
+            | Statement::RenameIndex { .. }
             | Statement::CreateIndex { .. }
             | Statement::DropIndex { .. }
+            | Statement::Reindex { .. }
             | Statement::CreateSequence(_)
     )
 }
@@ -1,6 +1,8 @@
-use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 
+use crate::storage::file::StorageFile;
+use crate::storage::pager::SyncMode;
+
 use super::{TransactionId, TransactionStatus, TransactionTable};
 
 /// Durable commit log ("clog"): the authoritative record of each transaction's
@@ -18,7 +20,10 @@ use super::{TransactionId, TransactionStatus, TransactionTable};
 /// indexed by transaction id (4 states per byte). tx id 0 is the committed
 /// bootstrap sentinel and is never stored.
 pub struct Clog {
-    file: File,
+    file: StorageFile,
+    sync_mode: SyncMode,
+    /// fsyncs issued by [`Clog::record`] and [`Clog::sync`] since open.
+    syncs: u64,
 }
 
 const CLOG_MAGIC: &[u8; 8] = b"AEROCLG1";
@@ -32,13 +37,12 @@ const ST_ABORTED: u8 = 3;
 impl Clog {
     /// Open (or create) the clog sidecar, validating/initializing its header.
     pub fn open(path: &str) -> io::Result<Self> {
-        let mut file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(path)?;
+        Clog::open_file(StorageFile::open(path)?)
+    }
 
-        let len = file.metadata()?.len();
+    /// [`Clog::open`] over an already opened sidecar, e.g. an in-memory one.
+    pub fn open_file(mut file: StorageFile) -> io::Result<Self> {
+        let len = file.len()?;
         if len < HEADER_LEN {
             Clog::write_header(&mut file)?;
         } else {
@@ -51,10 +55,14 @@ impl Clog {
             }
         }
 
-        Ok(Clog { file })
+        Ok(Clog {
+            file,
+            sync_mode: SyncMode::default(),
+            syncs: 0,
+        })
     }
 
-    fn write_header(file: &mut File) -> io::Result<()> {
+    fn write_header(file: &mut StorageFile) -> io::Result<()> {
         file.set_len(0)?;
         file.seek(SeekFrom::Start(0))?;
         file.write_all(CLOG_MAGIC)?;
@@ -86,7 +94,8 @@ impl Clog {
     }
 
     /// Durably record `status` for `tx_id` (read-modify-write of one packed byte,
-    /// fsync'd). The commit timestamp is not stored: MVCC visibility and vacuum
+    /// fsync'd under [`SyncMode::Full`]; otherwise made durable by
+    /// [`Clog::sync`] at the next checkpoint). The commit timestamp is not stored: MVCC visibility and vacuum
     /// only need the discrete state, and the `next_commit_ts` counter is kept in
     /// page-0 meta.
     pub fn record(&mut self, tx_id: TransactionId, status: TransactionStatus) -> io::Result<()> {
@@ -95,7 +104,7 @@ impl Clog {
         let shift = Clog::shift(tx_id);
 
         let mut byte = [0u8; 1];
-        let len = self.file.metadata()?.len();
+        let len = self.file.len()?;
         if offset < len {
             self.file.seek(SeekFrom::Start(offset))?;
             self.file.read_exact(&mut byte)?;
@@ -107,10 +116,31 @@ impl Clog {
         byte[0] = (byte[0] & !(0b11 << shift)) | (code << shift);
         self.file.seek(SeekFrom::Start(offset))?;
         self.file.write_all(&byte)?;
-        self.file.sync_all()?;
+        if self.sync_mode.syncs_each_write() {
+            self.file.sync_all()?;
+            self.syncs += 1;
+        }
         Ok(())
     }
 
+    /// fsync statuses recorded without a sync of their own. Called before the
+    /// WAL that also witnesses them is truncated.
+    pub fn sync(&mut self) -> io::Result<()> {
+        if self.sync_mode.syncs_barriers() && !self.sync_mode.syncs_each_write() {
+            self.file.sync_all()?;
+            self.syncs += 1;
+        }
+        Ok(())
+    }
+
+    pub fn set_sync_mode(&mut self, mode: SyncMode) {
+        self.sync_mode = mode;
+    }
+
+    pub fn sync_count(&self) -> u64 {
+        self.syncs
+    }
+
     /// Rebuild the in-memory transaction table from the durable statuses.
     ///
     /// Any transaction still recorded as `Active` was live when the process last
@@ -118,7 +148,7 @@ impl Clog {
     /// persisted back so a later frozen-rule read never resurrects it. The stored
     /// commit timestamp is a placeholder (`0`); it is unused by visibility.
     pub fn load(&mut self) -> io::Result<TransactionTable> {
-        let len = self.file.metadata()?.len();
+        let len = self.file.len()?;
         let mut table = TransactionTable::new();
         if len <= HEADER_LEN {
             return Ok(table);
@@ -1,3 +1,4 @@
+use crate::execution::runtime::QueryResult;
 use crate::storage::btree::BTree;
 use crate::{catalog::Catalog, error::DbError, error::DbResult, sql::ast::Statement};
 use log::debug;
@@ -30,6 +31,9 @@ pub struct TransactionManager {
     /// operation (operations are serialized by the shared-storage lock). The
     /// single-session `Engine` path leaves this empty.
     detached: HashMap<TransactionId, Transaction>,
+    /// Set by `SET autocommit = off`: the transaction a mutating statement
+    /// begins is left open for an explicit COMMIT or ROLLBACK.
+    manual_commit: bool,
 }
 
 impl TransactionManager {
@@ -67,8 +71,6 @@ impl TransactionManager {
             .pager
             .begin_transaction(transaction_id, snapshot, name, isolation_level)?;
         self.active.insert(transaction_id);
-        // Capture the index map so ROLLBACK can undo DDL that mutated it.
-        catalog.capture_pre_tx_indexes();
         Ok(())
     }
 
@@ -86,24 +88,38 @@ impl TransactionManager {
         if let Some(transaction_id) = transaction_id {
             self.active.remove(&transaction_id);
         }
-        // Committed DDL keeps its index changes; drop the rollback snapshot.
-        catalog.clear_pre_tx_indexes();
         Ok(())
     }
 
     /// Roll back the live transaction: revert its pages, drop it from the active
-    /// set, then rebuild the in-memory catalog/index maps captured at BEGIN.
+    /// set, then rebuild the in-memory catalog and index maps from the reverted
+    /// catalog pages.
     pub fn rollback(&mut self, catalog: &mut Catalog) -> io::Result<()> {
         let transaction_id = catalog.transaction_id();
         catalog.pager.rollback_transaction()?;
         if let Some(transaction_id) = transaction_id {
             self.active.remove(&transaction_id);
         }
-        // Page-level rollback restores table/sequence catalog pages; rebuild the
-        // in-memory maps from them, then restore the (non-persisted) index map
-        // captured at BEGIN so aborted DDL leaves no trace.
+        // Page-level rollback restores the table/sequence catalog pages, which
+        // also record each table's indexes; rebuild the in-memory maps from them
+        // so aborted DDL and index root changes leave no trace.
         catalog.reload_tables()?;
-        catalog.restore_pre_tx_indexes();
+        Ok(())
+    }
+
+    /// Whether mutating statements commit on their own (the default).
+    pub fn autocommit(&self) -> bool {
+        !self.manual_commit
+    }
+
+    /// Turn auto-commit on or off. Turning it back on commits a transaction
+    /// left open while it was off; one opened with BEGIN stays open.
+    pub fn set_autocommit(&mut self, catalog: &mut Catalog, on: bool) -> io::Result<()> {
+        if on && self.mode.is_implicit() && catalog.transaction_active() {
+            self.commit(catalog)?;
+            self.mode = TransactionMode::None;
+        }
+        self.manual_commit = !on;
         Ok(())
     }
 
@@ -168,11 +184,7 @@ impl TransactionManager {
     /// Commit detached transaction `tx_id`, first re-validating write/write
     /// conflicts (first-committer-wins). On conflict the transaction is aborted
     /// and `WriteConflict` returned.
-    pub fn commit_detached(
-        &mut self,
-        catalog: &mut Catalog,
-        tx_id: TransactionId,
-    ) -> DbResult<()> {
+    pub fn commit_detached(&mut self, catalog: &mut Catalog, tx_id: TransactionId) -> DbResult<()> {
         let transaction = self
             .detached
             .remove(&tx_id)
@@ -190,7 +202,11 @@ impl TransactionManager {
     }
 
     /// Abort detached transaction `tx_id`.
-    pub fn abort_detached(&mut self, catalog: &mut Catalog, tx_id: TransactionId) -> io::Result<()> {
+    pub fn abort_detached(
+        &mut self,
+        catalog: &mut Catalog,
+        tx_id: TransactionId,
+    ) -> io::Result<()> {
         let transaction = self
             .detached
             .remove(&tx_id)
@@ -202,7 +218,7 @@ impl TransactionManager {
     /// Re-check every recorded write intent of the installed transaction against
     /// the current version chains. Returns the first logical key that another,
     /// non-visible transaction has changed since this transaction's snapshot.
-    fn first_conflicting_key(&self, catalog: &mut Catalog) -> Option<i32> {
+    fn first_conflicting_key(&self, catalog: &mut Catalog) -> Option<i64> {
         let snapshot = catalog.transaction_snapshot().cloned()?;
         let intents = catalog.pager.transaction_write_set();
         for intent in intents {
@@ -228,12 +244,15 @@ impl TransactionManager {
         catalog: &mut Catalog,
         stmt: Statement,
         execute_stmt: F,
-    ) -> DbResult<()>
+    ) -> DbResult<QueryResult>
     where
-        F: FnOnce(&mut Catalog, Statement) -> DbResult<()>,
+        F: FnOnce(&mut Catalog, Statement) -> DbResult<QueryResult>,
     {
+        if let Statement::Vacuum { table } = &stmt {
+            return self.vacuum(catalog, table.as_deref());
+        }
         if self.handle_transaction_control(catalog, &stmt)? {
-            return Ok(());
+            return Ok(QueryResult::Ack);
         }
 
         let implicit = self.begin_implicit_if_needed(catalog, &stmt)?;
@@ -241,20 +260,62 @@ impl TransactionManager {
         self.finish_implicit_if_needed(catalog, implicit, result)
     }
 
+    /// Run a write that does not come from a parsed statement, such as the
+    /// typed insert API, with the same auto-commit handling as
+    /// [`TransactionManager::execute`].
+    pub fn execute_write<T, F>(&mut self, catalog: &mut Catalog, write: F) -> DbResult<T>
+    where
+        F: FnOnce(&mut Catalog) -> DbResult<T>,
+    {
+        let implicit = self.begin_implicit(catalog)?;
+        let result = write(catalog);
+        self.finish_implicit_if_needed(catalog, implicit, result)
+    }
+
+    /// Compact `table`, or every table, with [`Catalog::compact_table`]. Only
+    /// allowed while no transaction is live, since a live one may still read
+    /// or write the pages of the trees being replaced.
+    fn vacuum(&mut self, catalog: &mut Catalog, table: Option<&str>) -> DbResult<QueryResult> {
+        if catalog.transaction_active() || !self.active.is_empty() {
+            return Err(DbError::InvalidValue(
+                "VACUUM cannot run inside a transaction".into(),
+            ));
+        }
+        let global_xmin = self.global_xmin(catalog);
+        let mut tables: Vec<String> = match table {
+            Some(name) => vec![catalog.get_table(name)?.name.clone()],
+            None => catalog.all_tables().into_iter().map(|t| t.name).collect(),
+        };
+        tables.sort();
+        let mut freed = 0;
+        for name in &tables {
+            freed += catalog.compact_table(name, global_xmin)?.pages_freed;
+        }
+        Ok(QueryResult::Message(format!(
+            "{} table(s) vacuumed, {} page(s) freed",
+            tables.len(),
+            freed
+        )))
+    }
+
     fn handle_transaction_control(
         &mut self,
         catalog: &mut Catalog,
         stmt: &Statement,
     ) -> io::Result<bool> {
         match stmt {
-            Statement::BeginTransaction { name } => {
+            Statement::BeginTransaction { name, isolation } => {
                 if self.mode.is_implicit() && catalog.transaction_active() {
                     self.commit(catalog)?;
                 }
-                self.begin(catalog, name.clone(), IsolationLevel::default())?;
+                self.begin(catalog, name.clone(), isolation.unwrap_or_default())?;
                 self.mode = TransactionMode::Explicit;
                 Ok(true)
             }
+            Statement::SetAutocommit(on) => {
+                self.set_autocommit(catalog, *on)?;
+                Ok(true)
+            }
             Statement::Commit => {
                 self.commit(catalog)?;
                 self.mode = TransactionMode::None;
@@ -274,21 +335,31 @@ impl TransactionManager {
         catalog: &mut Catalog,
         stmt: &Statement,
     ) -> io::Result<bool> {
-        if statement_requires_transaction(stmt) && !catalog.transaction_active() {
-            self.begin(catalog, None, IsolationLevel::default())?;
-            self.mode = TransactionMode::Implicit;
-            return Ok(true);
+        if statement_requires_transaction(stmt) {
+            return self.begin_implicit(catalog);
         }
 
         Ok(false)
     }
 
-    fn finish_implicit_if_needed(
+    /// Begin an auto-commit transaction unless one is already live. Returns
+    /// whether the caller should finish it; with auto-commit off it is left
+    /// open instead.
+    fn begin_implicit(&mut self, catalog: &mut Catalog) -> io::Result<bool> {
+        if catalog.transaction_active() {
+            return Ok(false);
+        }
+        self.begin(catalog, None, IsolationLevel::default())?;
+        self.mode = TransactionMode::Implicit;
+        Ok(!self.manual_commit)
+    }
+
+    fn finish_implicit_if_needed<T>(
         &mut self,
         catalog: &mut Catalog,
         implicit: bool,
-        result: DbResult<()>,
-    ) -> DbResult<()> {
+        result: DbResult<T>,
+    ) -> DbResult<T> {
         if !implicit {
             return result;
         }
@@ -9,7 +9,7 @@ use super::{IsolationLevel, Snapshot, TransactionId};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WriteIntent {
     pub table_root: u32,
-    pub key: i32,
+    pub key: crate::storage::row::RowKey,
     /// The `created_tx` of the version this write was based on (the one visible
     /// to this transaction's snapshot when it wrote).
     pub visible_created_tx: TransactionId,
@@ -32,6 +32,9 @@ pub struct Transaction {
     /// Pages this transaction has written; their current cache image is flushed
     /// (durably) at commit.
     touched_pages: BTreeSet<u32>,
+    /// Pages this transaction released; they become reusable only once it
+    /// commits.
+    freed_pages: BTreeSet<u32>,
     /// Logical keys this transaction updated/deleted, for commit-time conflict
     /// re-validation.
     write_set: Vec<WriteIntent>,
@@ -50,6 +53,7 @@ impl Transaction {
             name,
             isolation_level,
             touched_pages: BTreeSet::new(),
+            freed_pages: BTreeSet::new(),
             write_set: Vec::new(),
         }
     }
@@ -82,6 +86,17 @@ impl Transaction {
         self.touched_pages.iter().copied()
     }
 
+    /// Note that this transaction released `page_num`; it is handed to the
+    /// pager's free list at commit and forgotten on rollback.
+    pub fn mark_freed(&mut self, page_num: u32) {
+        self.freed_pages.insert(page_num);
+    }
+
+    /// The pages this transaction released, ascending.
+    pub fn freed_pages(&self) -> impl Iterator<Item = u32> + '_ {
+        self.freed_pages.iter().copied()
+    }
+
     /// Record a write intent for commit-time conflict re-validation.
     pub fn record_write(&mut self, intent: WriteIntent) {
         self.write_set.push(intent);
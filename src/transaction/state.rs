@@ -23,9 +23,9 @@ impl Default for TransactionMode {
 /// added later through WAL/metastore integration.
 pub type TransactionId = u64;
 
-/// SQL transaction isolation levels. The parser does not expose `SET TRANSACTION
-/// ISOLATION LEVEL` yet, but keeping the isolation decision explicit here lets
-/// that syntax select a policy without reshaping transaction state later.
+/// SQL transaction isolation levels, selected by `BEGIN TRANSACTION SNAPSHOT`
+/// or `BEGIN TRANSACTION ISOLATION LEVEL SNAPSHOT`. Snapshot is the only level
+/// and the default, so a plain `BEGIN` behaves the same way.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IsolationLevel {
     /// One snapshot is captured at BEGIN and reused for the whole transaction.
@@ -1,7 +1,7 @@
-use std::fs::{File, OpenOptions};
 use std::io::{self, ErrorKind, Read, Seek, SeekFrom, Write};
 
-use crate::storage::page::PAGE_SIZE;
+use crate::storage::file::StorageFile;
+use crate::storage::pager::SyncMode;
 
 use super::{CommitTimestamp, TransactionId, TransactionStatus, TransactionTable};
 
@@ -17,7 +17,7 @@ const CHECKPOINT_TAG: u8 = 5;
 pub enum WalRecord {
     PageImage {
         page_num: u32,
-        data: Box<[u8; PAGE_SIZE]>,
+        data: Vec<u8>,
     },
     TxBegin {
         tx_id: TransactionId,
@@ -33,33 +33,58 @@ pub enum WalRecord {
 }
 
 pub struct Wal {
-    file: File,
+    file: StorageFile,
+    sync_mode: SyncMode,
+    /// fsyncs issued since open, not counting recovery.
+    syncs: u64,
 }
 
 impl Wal {
-    pub fn open(path: &str, db_file: &mut File) -> io::Result<(Self, TransactionTable)> {
-        let mut file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(path)?;
-        let tx_table = Wal::recover_internal(&mut file, db_file)?;
+    /// Open the WAL at `path`, replaying its committed page images into
+    /// `db_file`. Page images are `page_size` bytes, the size the database
+    /// file was created with.
+    pub fn open(
+        path: &str,
+        db_file: &mut StorageFile,
+        page_size: usize,
+    ) -> io::Result<(Self, TransactionTable)> {
+        Wal::open_file(StorageFile::open(path)?, db_file, page_size)
+    }
+
+    /// [`Wal::open`] over an already opened log, e.g. an in-memory one.
+    pub fn open_file(
+        mut file: StorageFile,
+        db_file: &mut StorageFile,
+        page_size: usize,
+    ) -> io::Result<(Self, TransactionTable)> {
+        let tx_table = Wal::recover_internal(&mut file, db_file, page_size)?;
         Wal::initialize_empty_log(&mut file)?;
-        Ok((Wal { file }, tx_table))
+        Ok((
+            Wal {
+                file,
+                sync_mode: SyncMode::default(),
+                syncs: 0,
+            },
+            tx_table,
+        ))
     }
 
-    fn recover_internal(wal: &mut File, db: &mut File) -> io::Result<TransactionTable> {
+    fn recover_internal(
+        wal: &mut StorageFile,
+        db: &mut StorageFile,
+        page_size: usize,
+    ) -> io::Result<TransactionTable> {
         wal.seek(SeekFrom::Start(0))?;
         if !Wal::read_or_initialize_header(wal)? {
             return Ok(TransactionTable::new());
         }
 
         let mut tx_table = TransactionTable::new();
-        while let Some(record) = Wal::read_record(wal)? {
+        while let Some(record) = Wal::read_record(wal, page_size)? {
             match record {
                 WalRecord::PageImage { page_num, data } => {
-                    db.seek(SeekFrom::Start(page_num as u64 * PAGE_SIZE as u64))?;
-                    db.write_all(&*data)?;
+                    db.seek(SeekFrom::Start(page_num as u64 * page_size as u64))?;
+                    db.write_all(&data)?;
                 }
                 WalRecord::TxBegin { tx_id } => {
                     tx_table.insert(tx_id, TransactionStatus::Active);
@@ -89,8 +114,8 @@ impl Wal {
     /// WALs did not contain a magic header; they are treated as an incompatible
     /// legacy format and truncated for a clean start instead of being replayed as
     /// ambiguous records.
-    fn read_or_initialize_header(wal: &mut File) -> io::Result<bool> {
-        let len = wal.metadata()?.len();
+    fn read_or_initialize_header(wal: &mut StorageFile) -> io::Result<bool> {
+        let len = wal.len()?;
         if len == 0 {
             wal.write_all(WAL_MAGIC)?;
             wal.sync_all()?;
@@ -110,8 +135,8 @@ impl Wal {
         }
     }
 
-    fn initialize_empty_log(wal: &mut File) -> io::Result<()> {
-        if wal.metadata()?.len() == 0 {
+    fn initialize_empty_log(wal: &mut StorageFile) -> io::Result<()> {
+        if wal.len()? == 0 {
             wal.seek(SeekFrom::Start(0))?;
             wal.write_all(WAL_MAGIC)?;
             wal.sync_all()?;
@@ -119,7 +144,7 @@ impl Wal {
         Ok(())
     }
 
-    fn read_record(wal: &mut File) -> io::Result<Option<WalRecord>> {
+    fn read_record(wal: &mut StorageFile, page_size: usize) -> io::Result<Option<WalRecord>> {
         let mut tag = [0u8; 1];
         match wal.read_exact(&mut tag) {
             Ok(()) => {}
@@ -130,8 +155,8 @@ impl Wal {
         let record = match tag[0] {
             PAGE_IMAGE_TAG => {
                 let page_num = Wal::read_u32(wal)?;
-                let mut data = Box::new([0u8; PAGE_SIZE]);
-                wal.read_exact(data.as_mut())?;
+                let mut data = vec![0u8; page_size];
+                wal.read_exact(&mut data)?;
                 WalRecord::PageImage { page_num, data }
             }
             TX_BEGIN_TAG => WalRecord::TxBegin {
@@ -155,13 +180,13 @@ impl Wal {
         Ok(Some(record))
     }
 
-    fn read_u32(wal: &mut File) -> io::Result<u32> {
+    fn read_u32(wal: &mut StorageFile) -> io::Result<u32> {
         let mut buf = [0u8; 4];
         wal.read_exact(&mut buf)?;
         Ok(u32::from_le_bytes(buf))
     }
 
-    fn read_u64(wal: &mut File) -> io::Result<u64> {
+    fn read_u64(wal: &mut StorageFile) -> io::Result<u64> {
         let mut buf = [0u8; 8];
         wal.read_exact(&mut buf)?;
         Ok(u64::from_le_bytes(buf))
@@ -173,7 +198,7 @@ impl Wal {
             WalRecord::PageImage { page_num, data } => {
                 self.file.write_all(&[PAGE_IMAGE_TAG])?;
                 self.file.write_all(&page_num.to_le_bytes())?;
-                self.file.write_all(&*data)?;
+                self.file.write_all(&data)?;
             }
             WalRecord::TxBegin { tx_id } => {
                 self.file.write_all(&[TX_BEGIN_TAG])?;
@@ -192,14 +217,39 @@ impl Wal {
                 self.file.write_all(&[CHECKPOINT_TAG])?;
             }
         }
+        if self.sync_mode.syncs_each_write() {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
         self.file.sync_all()?;
+        self.syncs += 1;
         Ok(())
     }
 
-    pub fn append_page(&mut self, page_num: u32, data: &[u8; PAGE_SIZE]) -> io::Result<()> {
+    pub fn set_sync_mode(&mut self, mode: SyncMode) {
+        self.sync_mode = mode;
+    }
+
+    pub fn sync_count(&self) -> u64 {
+        self.syncs
+    }
+
+    /// Make the records of a commit durable once its commit record is
+    /// appended. Under [`SyncMode::Full`] each record was already synced.
+    pub fn sync_commit(&mut self) -> io::Result<()> {
+        if self.sync_mode.syncs_barriers() && !self.sync_mode.syncs_each_write() {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    pub fn append_page(&mut self, page_num: u32, data: &[u8]) -> io::Result<()> {
         self.append_record(WalRecord::PageImage {
             page_num,
-            data: Box::new(*data),
+            data: data.to_vec(),
         })
     }
 
@@ -217,6 +267,16 @@ impl Wal {
         }
     }
 
+    /// Current size of the log in bytes, header included.
+    pub fn size(&self) -> io::Result<u64> {
+        self.file.len()
+    }
+
+    /// Whether the log holds no records past its header.
+    pub fn is_empty(&self) -> io::Result<bool> {
+        Ok(self.size()? <= WAL_MAGIC.len() as u64)
+    }
+
     pub fn append_checkpoint(&mut self) -> io::Result<()> {
         self.append_record(WalRecord::Checkpoint)
     }
@@ -225,7 +285,11 @@ impl Wal {
         self.file.set_len(0)?;
         self.file.seek(SeekFrom::Start(0))?;
         self.file.write_all(WAL_MAGIC)?;
-        self.file.sync_all()?;
+        // Replaying an already checkpointed WAL is harmless, so only Full
+        // waits for the truncation to reach disk.
+        if self.sync_mode.syncs_each_write() {
+            self.sync()?;
+        }
         Ok(())
     }
 }
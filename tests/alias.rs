@@ -1,4 +1,4 @@
-use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::{Statement, ColumnDef}}, execution::runtime::{execute_select_statement, format_header}, storage::row::ColumnType};
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::{Statement, ColumnDef}}, execution::runtime::{QueryResult, execute_select_statement, format_header}, storage::row::ColumnType};
 use std::fs;
 
 fn setup_catalog(filename: &str) -> Catalog {
@@ -14,9 +14,9 @@ fn column_aliases() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "employees".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "last_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "last_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -37,17 +37,17 @@ fn table_aliases() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "employees".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "departments".into(),
         columns: vec![
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -56,7 +56,7 @@ fn table_aliases() {
     let stmt = parse_statement("SELECT e.first_name, d.department_name FROM employees AS e JOIN departments d ON e.department_id = d.department_id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let (base_table, base_alias) = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, alias } => (name.clone(), alias.clone()), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias, base_subquery: None, joins, projections: columns, where_predicate };
         let mut out = Vec::new();
         aerodb::execution::runtime::execute_multi_join(&plan, &mut catalog, &mut out).unwrap();
         assert_eq!(out, vec![vec!["John".to_string(), "Sales".to_string()]]);
@@ -70,18 +70,18 @@ fn mixed_aliases() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "employees".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "last_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "last_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "departments".into(),
         columns: vec![
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -90,7 +90,7 @@ fn mixed_aliases() {
     let stmt = parse_statement("SELECT e.first_name fname, e.last_name lname, d.department_name dept FROM employees e JOIN departments d ON e.department_id = d.department_id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let (base_table, base_alias) = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, alias } => (name.clone(), alias.clone()), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias, base_subquery: None, joins, projections: columns, where_predicate };
         let mut out = Vec::new();
         aerodb::execution::runtime::execute_multi_join(&plan, &mut catalog, &mut out).unwrap();
         assert_eq!(out, vec![vec!["John".to_string(), "Doe".to_string(), "Sales".to_string()]]);
@@ -104,9 +104,9 @@ fn subquery_alias() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "employees".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "salary".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "salary".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -136,17 +136,17 @@ fn handle_statement_join_alias() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "employees".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "first_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "departments".into(),
         columns: vec![
-            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "department_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "department_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "department_name".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -156,3 +156,71 @@ fn handle_statement_join_alias() {
     assert!(aerodb::execution::handle_statement(&mut catalog, stmt).is_ok());
 }
 
+
+#[test]
+fn group_and_order_by_select_alias() {
+    let filename = "test_group_order_alias.db";
+    let mut catalog = setup_catalog(filename);
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE e (id INTEGER, dept TEXT, salary INTEGER)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO e VALUES (1, 'ops', 30), (2, 'dev', 50), (3, 'ops', 10), (4, 'art', 20)").unwrap()).unwrap();
+    let run = |catalog: &mut Catalog, sql: &str| {
+        let mut out = Vec::new();
+        let header = execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut out, None).unwrap();
+        (format_header(&header), out)
+    };
+
+    let (header, mut rows) = run(&mut catalog, "SELECT dept AS d, COUNT(*) FROM e GROUP BY d");
+    rows.sort();
+    assert_eq!(header, "d TEXT | COUNT(*) INTEGER");
+    assert_eq!(rows, [["art", "1"], ["dev", "1"], ["ops", "2"]]);
+
+    let (_, rows) = run(&mut catalog, "SELECT dept AS d, SUM(salary) AS total FROM e GROUP BY d ORDER BY d DESC");
+    assert_eq!(rows, [["ops", "40"], ["dev", "50"], ["art", "20"]]);
+
+    let (_, rows) = run(&mut catalog, "SELECT id, salary AS pay FROM e ORDER BY pay");
+    assert_eq!(rows, [["3", "10"], ["4", "20"], ["1", "30"], ["2", "50"]]);
+
+    // A real column wins over an alias of the same name.
+    let (_, rows) = run(&mut catalog, "SELECT salary AS id FROM e ORDER BY id DESC");
+    assert_eq!(rows, [["20"], ["10"], ["50"], ["30"]]);
+}
+
+#[test]
+fn order_by_alias_of_an_expression() {
+    let mut catalog = setup_catalog("test_order_expr_alias.db");
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INTEGER, score INTEGER)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, 4), (2, 9), (3, 1), (4, 6)").unwrap()).unwrap();
+    let run = |catalog: &mut Catalog, sql: &str| {
+        let mut out = Vec::new();
+        execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut out, None).unwrap();
+        out
+    };
+
+    assert_eq!(run(&mut catalog, "SELECT id, score * 2 AS dbl FROM t ORDER BY dbl DESC"), [["2", "18"], ["4", "12"], ["1", "8"], ["3", "2"]]);
+    assert_eq!(run(&mut catalog, "SELECT id, score * 2 AS dbl FROM t ORDER BY dbl LIMIT 2"), [["3", "2"], ["1", "8"]]);
+    // Numeric, not textual: 10 - 9 sorts before 10 - 1.
+    assert_eq!(run(&mut catalog, "SELECT id, 10 - score AS rest FROM t ORDER BY rest"), [["2", "1"], ["4", "4"], ["1", "6"], ["3", "9"]]);
+    // A real column still wins over an alias of the same name.
+    assert_eq!(run(&mut catalog, "SELECT score * 2 AS id FROM t ORDER BY id DESC"), [["12"], ["2"], ["18"], ["8"]]);
+}
+
+#[test]
+fn join_header_uses_select_aliases() {
+    let mut catalog = setup_catalog("test_join_header_alias.db");
+    for sql in [
+        "CREATE TABLE e (id INTEGER, dept TEXT)",
+        "CREATE TABLE o (id INTEGER, eid INTEGER, amt INTEGER)",
+        "INSERT INTO e VALUES (1, 'a')",
+        "INSERT INTO o VALUES (10, 1, 5)",
+    ] {
+        aerodb::execution::handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    }
+    let sql = "SELECT e.id, o.amt AS a, o.amt AS b FROM e JOIN o ON e.id = o.eid";
+    match aerodb::execution::handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { header, rows } => {
+            assert_eq!(format_header(&header), "e.id INTEGER | a INTEGER | b INTEGER");
+            assert_eq!(rows, [["1", "5", "5"]]);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
@@ -62,3 +62,56 @@ fn begin_explicit_preserves_manual_flow() {
     execute_select_with_indexes(&mut check.catalog, "t", None, &mut rows2).unwrap();
     assert_eq!(rows2.len(), 1);
 }
+
+fn row_count(filename: &str) -> usize {
+    let mut engine = open_engine(filename);
+    let mut rows = Vec::new();
+    execute_select_with_indexes(&mut engine.catalog, "t", None, &mut rows).unwrap();
+    rows.len()
+}
+
+#[test]
+fn autocommit_off_holds_writes_until_commit() {
+    let filename = "autocommit_off_commit.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INTEGER PRIMARY KEY)").unwrap()).unwrap();
+    engine.execute(parse_statement("SET autocommit = off").unwrap()).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (1)").unwrap()).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (2)").unwrap()).unwrap();
+    assert_eq!(row_count(filename), 0);
+
+    engine.execute(parse_statement("COMMIT").unwrap()).unwrap();
+    assert_eq!(row_count(filename), 2);
+
+    // Still off: the next write opens a new transaction.
+    engine.execute(parse_statement("INSERT INTO t VALUES (3)").unwrap()).unwrap();
+    engine.execute(parse_statement("ROLLBACK").unwrap()).unwrap();
+    drop(engine);
+    assert_eq!(row_count(filename), 2);
+}
+
+#[test]
+fn autocommit_off_writes_are_lost_without_commit() {
+    let filename = "autocommit_off_reopen.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INTEGER PRIMARY KEY)").unwrap()).unwrap();
+    engine.set_autocommit(false).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (1)").unwrap()).unwrap();
+    drop(engine);
+    assert_eq!(row_count(filename), 0);
+}
+
+#[test]
+fn turning_autocommit_back_on_commits_and_restores_implicit_commits() {
+    let filename = "autocommit_back_on.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INTEGER PRIMARY KEY)").unwrap()).unwrap();
+    engine.set_autocommit(false).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (1)").unwrap()).unwrap();
+    engine.execute(parse_statement("SET autocommit = on").unwrap()).unwrap();
+    assert_eq!(row_count(filename), 1);
+
+    engine.execute(parse_statement("INSERT INTO t VALUES (2)").unwrap()).unwrap();
+    assert_eq!(row_count(filename), 2);
+    assert!(parse_statement("SET autocommit = maybe").is_err());
+}
@@ -88,3 +88,82 @@ fn auto_increment_persists_after_restart() {
         ]);
     }
 }
+
+fn auto_inc_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE items (id INT NOT NULL AUTO_INCREMENT, name TEXT)").unwrap()).unwrap();
+    catalog
+}
+
+fn item_ids(catalog: &mut Catalog) -> Vec<String> {
+    let mut rows = Vec::new();
+    execute_select_with_indexes(catalog, "items", None, &mut rows).unwrap();
+    rows.iter().map(|r| row_to_strings(r)[0].clone()).collect()
+}
+
+#[test]
+fn default_values_stay_above_explicit_and_updated_ids() {
+    let filename = "test_auto_inc_interleaved.db";
+    let mut catalog = auto_inc_catalog(filename);
+    for sql in [
+        "INSERT INTO items (name) VALUES ('a')",
+        "INSERT INTO items VALUES (100, 'b')",
+        "INSERT INTO items VALUES (DEFAULT, 'c')",
+        "INSERT INTO items VALUES (50, 'd'), (DEFAULT, 'e')",
+        "UPDATE items SET id = 500 WHERE name = 'a'",
+        "INSERT INTO items (name) VALUES ('f')",
+    ] {
+        handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    }
+    assert_eq!(item_ids(&mut catalog), ["50", "100", "101", "102", "500", "501"]);
+
+    // The high-water mark survives a reopen.
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO items (name) VALUES ('g')").unwrap()).unwrap();
+    assert_eq!(item_ids(&mut catalog).last().map(String::as_str), Some("502"));
+}
+
+#[test]
+fn large_sequence_values_persist() {
+    let filename = "test_auto_inc_large_sequence.db";
+    let mut catalog = auto_inc_catalog(filename);
+    catalog.create_sequence("big", 5_000_000_000, 1).unwrap();
+    assert_eq!(catalog.next_sequence_value("big").unwrap(), 5_000_000_000);
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.next_sequence_value("big").unwrap(), 5_000_000_001);
+}
+
+#[test]
+fn truncate_restarts_identity_unless_continued() {
+    let filename = "test_auto_inc_truncate.db";
+    let mut catalog = auto_inc_catalog(filename);
+    for sql in [
+        "INSERT INTO items (name) VALUES ('a'), ('b'), ('c')",
+        "DELETE FROM items WHERE id = 3",
+        "INSERT INTO items VALUES (DEFAULT, 'd')",
+    ] {
+        handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    }
+    // DELETE keeps the high-water mark.
+    assert_eq!(item_ids(&mut catalog), ["1", "2", "4"]);
+
+    handle_statement(&mut catalog, parse_statement("TRUNCATE TABLE items CONTINUE IDENTITY").unwrap()).unwrap();
+    assert!(item_ids(&mut catalog).is_empty());
+    handle_statement(&mut catalog, parse_statement("INSERT INTO items VALUES (DEFAULT, 'e')").unwrap()).unwrap();
+    assert_eq!(item_ids(&mut catalog), ["5"]);
+
+    handle_statement(&mut catalog, parse_statement("TRUNCATE items").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO items VALUES (DEFAULT, 'f'), (DEFAULT, 'g')").unwrap()).unwrap();
+    assert_eq!(item_ids(&mut catalog), ["1", "2"]);
+
+    handle_statement(&mut catalog, parse_statement("TRUNCATE TABLE items RESTART IDENTITY").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO items (name) VALUES ('h')").unwrap()).unwrap();
+    assert_eq!(item_ids(&mut catalog), ["1"]);
+
+    assert!(parse_statement("TRUNCATE TABLE items RESET IDENTITY").is_err());
+    assert!(parse_statement("TRUNCATE").is_err());
+}
@@ -0,0 +1,108 @@
+use aerodb::{catalog::Catalog, engine::Engine, storage::{pager::Pager, btree::BTree, row::{ColumnType, ColumnValue, RowData}}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn btree_find_keys_beyond_i32() {
+    let filename = "test_bigint_btree.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut pager = Pager::new(filename).unwrap();
+    let mut btree = BTree::new(&mut pager).unwrap();
+    let base = i32::MAX as i64 + 1;
+    // Enough keys to force leaf and internal splits with 8-byte separators.
+    for i in 0..600i64 {
+        let key = base + i * 1_000_003;
+        btree.insert(key, RowData(vec![ColumnValue::BigInt(key), ColumnValue::Text(format!("row{}", i))])).unwrap();
+    }
+    for i in 0..600i64 {
+        let key = base + i * 1_000_003;
+        let row = btree.find(key).unwrap().expect("key should be found");
+        assert_eq!(row.key, key);
+        assert_eq!(row.data.0[0], ColumnValue::BigInt(key));
+    }
+    assert!(btree.find(base - 1).unwrap().is_none());
+    assert!(btree.find(-base).unwrap().is_none());
+}
+
+#[test]
+fn bigint_column_through_sql() {
+    let filename = "test_bigint_sql.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id BIGINT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (5000000000, 'big'), (-9000000000, 'neg'), (7, 'small')").unwrap()).unwrap();
+
+    assert_eq!(select(&mut catalog, "SELECT name FROM t WHERE id = 5000000000"), vec![vec!["big".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM t WHERE name = 'neg'"), vec![vec!["-9000000000".to_string()]]);
+
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_t_id ON t (id)").unwrap()).unwrap();
+    assert_eq!(select(&mut catalog, "SELECT name FROM t WHERE id = -9000000000"), vec![vec!["neg".to_string()]]);
+
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (99999999999999999999, 'x')").unwrap()).is_err());
+
+    drop(catalog);
+    let catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    let table = catalog.get_table("t").unwrap();
+    assert_eq!(table.columns[0].1, ColumnType::BigInt);
+}
+
+#[test]
+fn bigint_arithmetic_reports_overflow() {
+    let filename = "test_bigint_overflow.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE big (id INTEGER, a BIGINT, b BIGINT)").unwrap()).unwrap();
+    handle_statement(
+        &mut catalog,
+        parse_statement("INSERT INTO big VALUES (1, 9223372036854775000, 9223372036854775000), (2, 9223372036854775000, -5)").unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(select(&mut catalog, "SELECT a + b FROM big WHERE id = 2"), [["9223372036854774995"]]);
+    assert_eq!(select(&mut catalog, "SELECT a - 1 FROM big WHERE id = 2"), [["9223372036854774999"]]);
+
+    let overflows = |catalog: &mut Catalog, sql: &str| {
+        let stmt = parse_statement(sql).unwrap();
+        let mut rows = Vec::new();
+        matches!(
+            execute_select_statement(catalog, &stmt, &mut rows, None),
+            Err(aerodb::error::DbError::Overflow)
+        )
+    };
+    assert!(overflows(&mut catalog, "SELECT a + b FROM big WHERE id = 1"));
+    assert!(overflows(&mut catalog, "SELECT a * 2 FROM big"));
+    assert!(overflows(&mut catalog, "SELECT id FROM big WHERE a + b"));
+}
+
+#[test]
+fn files_from_before_64_bit_keys_are_rejected() {
+    let filename = "test_bigint_format_version.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id BIGINT, v TEXT)").unwrap()).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (5000000000, 'x')").unwrap()).unwrap();
+    drop(engine);
+    assert!(Pager::new(filename).is_ok());
+
+    // Such files recorded no format version after the page size.
+    let mut bytes = fs::read(filename).unwrap();
+    assert_eq!(&bytes[0..8], b"AERODBM1");
+    bytes[36..40].fill(0);
+    fs::write(filename, &bytes).unwrap();
+    let err = Pager::new(filename).err().expect("old format must not open");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("format 0"), "{}", err);
+}
@@ -0,0 +1,51 @@
+use aerodb::{catalog::Catalog, storage::{pager::Pager, row::{ColumnType, ColumnValue, RowData, build_row_data}}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn blob_bytes_round_trip_through_row_data() {
+    let bytes: Vec<u8> = (0..=255u8).chain([0, 0, 0xFF, 0xFE]).collect();
+    let row = RowData(vec![ColumnValue::Integer(1), ColumnValue::Blob(bytes.clone()), ColumnValue::Blob(Vec::new())]);
+    let decoded = RowData::deserialize(&row.serialize()).unwrap();
+    assert_eq!(decoded, row);
+
+    let columns = vec![("data".to_string(), ColumnType::Blob)];
+    let built = build_row_data(&["00FF00".to_string()], &columns).unwrap();
+    assert_eq!(built.0[0], ColumnValue::Blob(vec![0x00, 0xFF, 0x00]));
+    assert_eq!(built.0[0].to_string_value(), "00FF00");
+    assert!(build_row_data(&["ABC".to_string()], &columns).is_err());
+    assert!(build_row_data(&["ZZ".to_string()], &columns).is_err());
+}
+
+#[test]
+fn blob_column_through_sql() {
+    let filename = "test_blob_sql.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE files (id INTEGER, data BLOB)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO files VALUES (1, X'48656C6C6F'), (2, x'00ff0041'), (3, X'')").unwrap()).unwrap();
+    assert_eq!(
+        select(&mut catalog, "SELECT data FROM files"),
+        vec![vec!["48656C6C6F".to_string()], vec!["00FF0041".to_string()], vec!["".to_string()]]
+    );
+    assert_eq!(select(&mut catalog, "SELECT id FROM files WHERE data = x'00ff0041'"), vec![vec!["2".to_string()]]);
+
+    handle_statement(&mut catalog, parse_statement("UPDATE files SET data = X'FFFE00' WHERE id = 1").unwrap()).unwrap();
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO files VALUES (4, X'123')").unwrap()).is_err());
+
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.get_table("files").unwrap().columns[1].1, ColumnType::Blob);
+    assert_eq!(select(&mut catalog, "SELECT data FROM files WHERE id = 1"), vec![vec!["FFFE00".to_string()]]);
+}
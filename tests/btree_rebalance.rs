@@ -0,0 +1,114 @@
+use aerodb::{storage::{pager::Pager, btree::BTree, page::{self, HEADER_SIZE, NODE_LEAF}, row::{ColumnValue, RowData, RowKey}}, transaction::{Snapshot, TransactionStatus, TransactionTable}};
+use std::fs;
+
+fn open_pager(filename: &str) -> Pager {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Pager::new(filename).unwrap()
+}
+
+/// Bulk load `n` rows with keys 0..n and `width`-character payloads.
+fn wide_tree(pager: &mut Pager, n: RowKey, width: usize) -> u32 {
+    let root = BTree::create_empty(pager).unwrap();
+    let rows = (0..n)
+        .map(|key| (key, RowData(vec![ColumnValue::Text(format!("{:0>width$}", key))])))
+        .collect();
+    BTree::open_root(pager, root).unwrap().bulk_load(rows).unwrap()
+}
+
+/// Cell counts of the leaves, left to right along the leaf chain.
+fn leaf_sizes(pager: &mut Pager, root: u32) -> Vec<u16> {
+    let mut page_num = root;
+    while page::get_node_type(&pager.get_page(page_num).unwrap().data) != NODE_LEAF {
+        let data = &pager.get_page(page_num).unwrap().data;
+        page_num = u32::from_le_bytes(data[HEADER_SIZE..HEADER_SIZE + 4].try_into().unwrap());
+    }
+    let mut sizes = Vec::new();
+    loop {
+        let data = &pager.get_page(page_num).unwrap().data;
+        sizes.push(page::get_cell_count(data));
+        page_num = page::get_next_leaf(data);
+        if page_num == 0 {
+            return sizes;
+        }
+    }
+}
+
+fn delete_and_vacuum(tree: &mut BTree, keys: impl IntoIterator<Item = RowKey>) {
+    for key in keys {
+        assert!(tree.delete(key).unwrap());
+    }
+    // Deletes outside a transaction are stamped with the bootstrap tx id 0.
+    let committed = TransactionTable::from([(0, TransactionStatus::Committed(0))]);
+    tree.vacuum_deleted_versions(u64::MAX, &committed).unwrap();
+    tree.verify().unwrap();
+}
+
+fn keys(tree: &mut BTree) -> Vec<RowKey> {
+    let snapshot = Snapshot::new(u64::MAX, Vec::new());
+    tree.scan_visible(&snapshot).unwrap().into_iter().map(|row| row.key).collect()
+}
+
+#[test]
+fn underfull_leaf_borrows_from_sibling_with_surplus() {
+    let mut pager = open_pager("test_rebalance_borrow.db");
+    let root = wide_tree(&mut pager, 48, 300);
+    assert_eq!(leaf_sizes(&mut pager, root), vec![12, 12, 12, 12]);
+
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    delete_and_vacuum(&mut tree, 12..22);
+    let expected: Vec<RowKey> = (0..12).chain(22..48).collect();
+    assert_eq!(keys(&mut tree), expected);
+    assert_eq!(tree.row_count().unwrap(), expected.len());
+
+    // The second leaf took keys 24 and 25 from its right sibling instead of
+    // merging with it.
+    assert_eq!(leaf_sizes(&mut pager, root), vec![12, 4, 10, 12]);
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    assert_eq!(tree.find(24).unwrap().unwrap().key, 24);
+    assert_eq!(tree.find(26).unwrap().unwrap().key, 26);
+}
+
+#[test]
+fn underfull_leaf_from_the_right_borrows_from_its_left_sibling() {
+    let mut pager = open_pager("test_rebalance_borrow_left.db");
+    let root = wide_tree(&mut pager, 48, 300);
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    delete_and_vacuum(&mut tree, 36..46);
+    let expected: Vec<RowKey> = (0..36).chain(46..48).collect();
+    assert_eq!(keys(&mut tree), expected);
+    assert_eq!(leaf_sizes(&mut pager, root), vec![12, 12, 10, 4]);
+}
+
+#[test]
+fn leaves_merge_only_when_the_sibling_is_at_minimum() {
+    let mut pager = open_pager("test_rebalance_merge.db");
+    let root = wide_tree(&mut pager, 48, 300);
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    delete_and_vacuum(&mut tree, (12..22).chain(24..33));
+    let expected: Vec<RowKey> = (0..12).chain(22..24).chain(33..48).collect();
+    assert_eq!(keys(&mut tree), expected);
+    assert_eq!(leaf_sizes(&mut pager, root), vec![12, 5, 12]);
+}
+
+#[test]
+fn heavy_deletes_rebalance_internal_nodes_and_collapse_the_root() {
+    let mut pager = open_pager("test_rebalance_deep.db");
+    // Four 900-byte rows per leaf: 400 leaves under two internal nodes.
+    let root = wide_tree(&mut pager, 1600, 900);
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+
+    // Thin out the first half to one row per leaf, so neighbouring leaves
+    // merge and the left internal node drops below its minimum.
+    delete_and_vacuum(&mut tree, (0..800).filter(|key| key % 4 != 0));
+    let expected: Vec<RowKey> = (0..800).step_by(4).chain(800..1600).collect();
+    assert_eq!(keys(&mut tree), expected);
+    assert_eq!(tree.row_count().unwrap(), expected.len());
+
+    // Down to two rows the tree shrinks back to a single leaf root.
+    delete_and_vacuum(&mut tree, expected.iter().copied().filter(|&key| key != 4 && key != 1500));
+    assert_eq!(keys(&mut tree), vec![4, 1500]);
+    assert_eq!(tree.row_count().unwrap(), 2);
+    assert_eq!(leaf_sizes(&mut pager, root), vec![2]);
+    assert_eq!(page::get_node_type(&pager.get_page(root).unwrap().data), NODE_LEAF);
+}
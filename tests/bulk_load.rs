@@ -0,0 +1,116 @@
+use aerodb::{catalog::Catalog, storage::{pager::Pager, btree::BTree, row::{ColumnValue, Row, RowData, RowKey}}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn open_pager(filename: &str) -> Pager {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Pager::new(filename).unwrap()
+}
+
+fn sample_rows(n: i64) -> Vec<(RowKey, RowData)> {
+    (0..n)
+        .map(|i| (i * 3, RowData(vec![ColumnValue::Integer(i as i32), ColumnValue::Text(format!("value-{}", i))])))
+        .collect()
+}
+
+fn scan(pager: &mut Pager, root: u32) -> Vec<Row> {
+    let mut tree = BTree::open_root(pager, root).unwrap();
+    tree.scan_all_rows().collect()
+}
+
+#[test]
+fn bulk_load_matches_individual_inserts() {
+    let mut bulk_pager = open_pager("test_bulk_load_bulk.db");
+    let mut insert_pager = open_pager("test_bulk_load_insert.db");
+    let rows = sample_rows(1200);
+
+    let bulk_root = BTree::create_empty(&mut bulk_pager).unwrap();
+    let bulk_root = {
+        let mut tree = BTree::open_root(&mut bulk_pager, bulk_root).unwrap();
+        let root = tree.bulk_load(rows.clone()).unwrap();
+        tree.verify().unwrap();
+        root
+    };
+    let insert_root = BTree::create_empty(&mut insert_pager).unwrap();
+    let insert_root = {
+        let mut tree = BTree::open_root(&mut insert_pager, insert_root).unwrap();
+        for (key, data) in rows.clone() {
+            tree.insert(key, data).unwrap();
+        }
+        tree.root_page()
+    };
+
+    let bulk_rows = scan(&mut bulk_pager, bulk_root);
+    let insert_rows = scan(&mut insert_pager, insert_root);
+    assert_eq!(bulk_rows.len(), rows.len());
+    assert_eq!(
+        bulk_rows.iter().map(|r| (r.key, r.data.clone())).collect::<Vec<_>>(),
+        insert_rows.iter().map(|r| (r.key, r.data.clone())).collect::<Vec<_>>()
+    );
+
+    let mut bulk = BTree::open_root(&mut bulk_pager, bulk_root).unwrap();
+    let mut inserted = BTree::open_root(&mut insert_pager, insert_root).unwrap();
+    for key in -1..(1200 * 3 + 1) {
+        let a = bulk.find(key).unwrap().map(|r| r.data);
+        let b = inserted.find(key).unwrap().map(|r| r.data);
+        assert_eq!(a, b, "find({}) differs", key);
+    }
+}
+
+#[test]
+fn bulk_load_builds_multiple_internal_levels() {
+    let mut pager = open_pager("test_bulk_load_deep.db");
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    let root = tree.bulk_load(sample_rows(40_000)).unwrap();
+    tree.verify().unwrap();
+    for key in [0, 3, 59_997, 119_997] {
+        assert_eq!(tree.find(key).unwrap().unwrap().key, key);
+    }
+    assert!(tree.find(1).unwrap().is_none());
+    drop(tree);
+
+    let rows = scan(&mut pager, root);
+    assert_eq!(rows.len(), 40_000);
+    assert!(rows.windows(2).all(|w| w[0].key < w[1].key));
+}
+
+#[test]
+fn bulk_load_rejects_unsorted_input_and_non_empty_tree() {
+    let mut pager = open_pager("test_bulk_load_reject.db");
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    let mut rows = sample_rows(3);
+    rows.swap(0, 1);
+    assert!(tree.bulk_load(rows).is_err());
+
+    assert_eq!(tree.bulk_load(sample_rows(3)).unwrap(), root);
+    assert!(tree.bulk_load(sample_rows(3)).is_err());
+}
+
+#[test]
+fn create_index_over_existing_rows_uses_bulk_load() {
+    let filename = "test_bulk_load_index.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE items (id INTEGER, grp INTEGER)").unwrap()).unwrap();
+    for chunk in (0..600).collect::<Vec<i32>>().chunks(100) {
+        let values: Vec<String> = chunk.iter().map(|i| format!("({}, {})", i, i % 7)).collect();
+        let sql = format!("INSERT INTO items VALUES {}", values.join(", "));
+        handle_statement(&mut catalog, parse_statement(&sql).unwrap()).unwrap();
+    }
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_id ON items (id)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_grp ON items (grp)").unwrap()).unwrap();
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+
+    let stmt = parse_statement("SELECT id FROM items WHERE id = 437").unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap();
+    assert_eq!(rows, vec![vec!["437".to_string()]]);
+
+    let stmt = parse_statement("SELECT id FROM items WHERE grp = 3").unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap();
+    assert_eq!(rows.len(), (0..600).filter(|i| i % 7 == 3).count());
+}
@@ -14,8 +14,8 @@ fn char_column_basic() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "items".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "code".into(), col_type: ColumnType::Char(3), not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "code".into(), col_type: ColumnType::Char(3), not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -41,8 +41,8 @@ fn char_column_validate_length() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "items".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "code".into(), col_type: ColumnType::Char(3), not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "code".into(), col_type: ColumnType::Char(3), not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -0,0 +1,74 @@
+use aerodb::{engine::Engine, execution::runtime::{QueryResult, execute_select_statement}, sql::parser::parse_statement, storage::pager::Pager};
+use std::fs;
+
+fn cleanup(filename: &str) {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+}
+
+fn run(engine: &mut Engine, sql: &str) -> QueryResult {
+    engine.execute(parse_statement(sql).unwrap()).unwrap()
+}
+
+fn count(engine: &mut Engine) -> String {
+    let stmt = parse_statement("SELECT COUNT(*) FROM t").unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(&mut engine.catalog, &stmt, &mut rows, None).unwrap();
+    rows[0][0].clone()
+}
+
+fn wal_len(filename: &str) -> u64 {
+    fs::metadata(format!("{}.wal", filename)).unwrap().len()
+}
+
+#[test]
+fn checkpoint_statement_empties_the_wal() {
+    let filename = "test_checkpoint_statement.db";
+    cleanup(filename);
+    let mut pager = Pager::new(filename).unwrap();
+    pager.set_group_commit(1000).unwrap();
+    let mut engine = Engine::with_pager(pager).unwrap();
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT)");
+    for i in 1..=100 {
+        run(&mut engine, &format!("INSERT INTO t VALUES ({}, 'v{}')", i, i));
+    }
+    let before = wal_len(filename);
+    assert!(before > 100 * 4096, "WAL holds every pending commit: {before}");
+
+    assert!(matches!(run(&mut engine, "CHECKPOINT"), QueryResult::Message(_)));
+    assert!(wal_len(filename) <= 8, "WAL left at {} bytes", wal_len(filename));
+    assert_eq!(count(&mut engine), "100");
+
+    // Nothing is left to replay: the data file alone holds every row.
+    std::mem::forget(engine);
+    let mut engine = Engine::new(filename);
+    assert_eq!(count(&mut engine), "100");
+    assert_eq!(engine.catalog.integrity_check().unwrap(), None);
+    assert!(parse_statement("CHECKPOINT now").is_err());
+    cleanup(filename);
+}
+
+#[test]
+fn wal_size_limit_checkpoints_automatically() {
+    let filename = "test_checkpoint_size_limit.db";
+    cleanup(filename);
+    let limit = 64 * 1024;
+    let mut pager = Pager::new(filename).unwrap();
+    pager.set_group_commit(1000).unwrap();
+    pager.set_wal_size_limit(limit).unwrap();
+    let mut engine = Engine::with_pager(pager).unwrap();
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT)");
+    let mut largest = 0;
+    for i in 1..=200 {
+        run(&mut engine, &format!("INSERT INTO t VALUES ({}, 'v{}')", i, i));
+        largest = largest.max(wal_len(filename));
+    }
+    assert!(largest < limit, "WAL grew to {largest} bytes");
+    assert_eq!(count(&mut engine), "200");
+
+    std::mem::forget(engine);
+    let mut engine = Engine::new(filename);
+    assert_eq!(count(&mut engine), "200");
+    cleanup(filename);
+}
@@ -0,0 +1,79 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::{execute_select_statement, execute_select_with_indexes},
+    sql::{ast::Expr, parser::parse_statement},
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|mut r| r.remove(0)).collect()
+}
+
+#[test]
+fn nocase_column_filters_ignore_case() {
+    let filename = "test_collate_nocase_filter.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT COLLATE NOCASE, tag TEXT)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'Bob', 'x'), (2, 'bob', 'X'), (3, 'ALICE', 'x')");
+
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name = 'BOB'"), ["1", "2"]);
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name != 'bob'"), ["3"]);
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name < 'b'"), ["3"]);
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users u WHERE u.name = 'alice'"), ["3"]);
+    // Other columns keep comparing case-sensitively.
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE tag = 'X'"), ["2"]);
+    // Stored values keep their case.
+    assert_eq!(
+        ids(&mut engine.catalog, "SELECT name FROM users WHERE name = 'bOB'"),
+        ["Bob", "bob"]
+    );
+
+    drop(engine);
+    let mut engine = Engine::new(filename);
+    assert!(engine.catalog.get_table("users").unwrap().is_nocase("name"));
+    assert!(!engine.catalog.get_table("users").unwrap().is_nocase("tag"));
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name = 'Alice'"), ["3"]);
+}
+
+#[test]
+fn nocase_index_lookup_matches_any_case() {
+    let filename = "test_collate_nocase_index.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT COLLATE NOCASE)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'Bob'), (2, 'carol')");
+    run(&mut engine, "CREATE INDEX idx_name ON users (name)");
+    run(&mut engine, "INSERT INTO users VALUES (3, 'BOB')");
+
+    let mut rows = Vec::new();
+    let selection = Expr::Equals { left: "name".into(), right: "bob".into() };
+    assert!(execute_select_with_indexes(&mut engine.catalog, "users", Some(selection), &mut rows).unwrap());
+    let keys: Vec<i64> = rows.iter().map(|r| r.key).collect();
+    assert_eq!(keys, [1, 3]);
+
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name = 'bOb'"), ["1", "3"]);
+    assert_eq!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name = 'CAROL'"), ["2"]);
+    assert!(ids(&mut engine.catalog, "SELECT id FROM users WHERE name = 'dave'").is_empty());
+}
+
+#[test]
+fn collate_requires_a_known_collation_on_text() {
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n TEXT COLLATE BINARY)").is_ok());
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n TEXT COLLATE RTRIM)").is_err());
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n INTEGER COLLATE NOCASE)").is_err());
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n TEXT COLLATE)").is_err());
+}
@@ -0,0 +1,99 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    error::DbError,
+    execution::runtime::execute_select_statement,
+    sql::parser::parse_statement,
+    storage::row::ColumnValue,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+fn csv_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}-{}.csv", name, std::process::id()));
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn export_quotes_fields_and_reads_back() {
+    let mut engine = setup_engine("test_csv_export_roundtrip.db");
+    run(&mut engine, "CREATE TABLE places (id INTEGER, name TEXT, note TEXT, open BOOLEAN)");
+    let text = |s: &str| ColumnValue::Text(s.to_string());
+    engine
+        .insert_typed(
+            "places",
+            vec![
+                vec![ColumnValue::Integer(1), text("Paris, France"), text("say \"bonjour\""), ColumnValue::Boolean(true)],
+                vec![ColumnValue::Integer(2), text("Oslo"), text(""), ColumnValue::Boolean(false)],
+                vec![ColumnValue::Integer(3), text("two\nlines"), ColumnValue::Null, ColumnValue::Boolean(true)],
+            ],
+        )
+        .unwrap();
+
+    let path = csv_path("export-roundtrip");
+    assert_eq!(engine.export_csv("SELECT * FROM places", &path).unwrap(), 3);
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        concat!(
+            "id,name,note,open\r\n",
+            "1,\"Paris, France\",\"say \"\"bonjour\"\"\",true\r\n",
+            "2,Oslo,\"\",false\r\n",
+            "3,\"two\nlines\",,true\r\n",
+        )
+    );
+
+    let original = select(&mut engine.catalog, "SELECT * FROM places");
+    run(&mut engine, "CREATE TABLE copy (id INTEGER, name TEXT, note TEXT, open BOOLEAN)");
+    assert_eq!(engine.import_csv("copy", &path, true).unwrap(), 3);
+    assert_eq!(select(&mut engine.catalog, "SELECT * FROM copy"), original);
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM copy WHERE note IS NULL"), vec![vec!["3".to_string()]]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn export_writes_the_query_header_and_rejects_non_queries() {
+    let mut engine = setup_engine("test_csv_export_header.db");
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT)");
+    let path = csv_path("export-header");
+    assert_eq!(engine.export_csv("SELECT v AS \"value, text\", id FROM t", &path).unwrap(), 0);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "\"value, text\",id\r\n");
+    assert!(matches!(
+        engine.export_csv("DELETE FROM t WHERE id = 1", &path),
+        Err(DbError::InvalidValue(_))
+    ));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn text_spelled_null_survives_a_round_trip() {
+    let mut engine = setup_engine("test_csv_export_null_text.db");
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT)");
+    run(&mut engine, "INSERT INTO t VALUES (1, 'NULL'), (2, NULL)");
+
+    let path = csv_path("export-null-text");
+    assert_eq!(engine.export_csv("SELECT * FROM t", &path).unwrap(), 2);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "id,v\r\n1,NULL\r\n2,\r\n");
+
+    run(&mut engine, "CREATE TABLE copy (id INTEGER, v TEXT)");
+    assert_eq!(engine.import_csv("copy", &path, true).unwrap(), 2);
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM copy WHERE v = 'NULL'"), [["1"]]);
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM copy WHERE v IS NULL"), [["2"]]);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,107 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::{CsvImportError, Engine},
+    error::DbError,
+    execution::runtime::execute_select_statement,
+    sql::parser::parse_statement,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut engine = Engine::new(filename);
+    engine
+        .execute(parse_statement("CREATE TABLE people (id INTEGER, name TEXT, age INTEGER, active BOOLEAN)").unwrap())
+        .unwrap();
+    engine
+}
+
+fn write_csv(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}-{}.csv", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+fn row(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn positional_import_handles_quotes_and_embedded_commas() {
+    let mut engine = setup_engine("test_csv_import_positional.db");
+    let path = write_csv(
+        "positional",
+        "1,\"Smith, Jane\",34,true\r\n2,\"Say \"\"hi\"\"\",,false\n\n3,\"two\nlines\",51,true\n",
+    );
+    assert_eq!(engine.import_csv("people", &path, false).unwrap(), 3);
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT id, name, age, active FROM people"),
+        vec![
+            row(&["1", "Smith, Jane", "34", "true"]),
+            row(&["2", "Say \"hi\"", "NULL", "false"]),
+            row(&["3", "two\nlines", "51", "true"]),
+        ]
+    );
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM people WHERE age IS NULL"), vec![row(&["2"])]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn header_maps_fields_by_column_name() {
+    let mut engine = setup_engine("test_csv_import_header.db");
+    let path = write_csv("header", "name, active ,id\nAda,true,10\nGrace,false,11\n");
+    assert_eq!(engine.import_csv("people", &path, true).unwrap(), 2);
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT id, name, age, active FROM people"),
+        vec![row(&["10", "Ada", "NULL", "true"]), row(&["11", "Grace", "NULL", "false"])]
+    );
+    fs::remove_file(&path).unwrap();
+
+    let path = write_csv("header-unknown", "id,nickname\n12,Bob\n");
+    match engine.import_csv("people", &path, true).unwrap_err() {
+        CsvImportError::Record { line, error: DbError::ColumnNotFound(col) } => {
+            assert_eq!((line, col.as_str()), (2, "nickname"));
+        }
+        other => panic!("Expected unknown column, got {:?}", other),
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn bad_record_reports_its_line_and_rolls_back_the_import() {
+    let mut engine = setup_engine("test_csv_import_rollback.db");
+    let path = write_csv("rollback", "id,name,age,active\n1,a,20,true\n2,b,twenty,false\n3,c,30,true\n");
+    let err = engine.import_csv("people", &path, true).unwrap_err();
+    match &err {
+        CsvImportError::Record { line, error: DbError::InvalidValue(msg) } => {
+            assert_eq!(*line, 3);
+            assert!(msg.contains("twenty"), "{}", msg);
+        }
+        other => panic!("Expected invalid value, got {:?}", other),
+    }
+    assert!(err.to_string().starts_with("line 3: "), "{}", err);
+    assert!(select(&mut engine.catalog, "SELECT id FROM people").is_empty());
+    fs::remove_file(&path).unwrap();
+
+    let path = write_csv("unterminated", "1,\"open,20,true\n");
+    assert!(matches!(
+        engine.import_csv("people", &path, false),
+        Err(CsvImportError::Record { line: 1, error: DbError::ParseError(_) })
+    ));
+    fs::remove_file(&path).unwrap();
+    let path = write_csv("missing", "1,a,20,true\n");
+    assert!(matches!(
+        engine.import_csv("missing", &path, false),
+        Err(CsvImportError::Db(DbError::TableNotFound(_)))
+    ));
+    fs::remove_file(&path).unwrap();
+}
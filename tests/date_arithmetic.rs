@@ -0,0 +1,87 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r.join(",")).collect()
+}
+
+fn events(filename: &str) -> Catalog {
+    let mut catalog = setup_catalog(filename);
+    run(&mut catalog, "CREATE TABLE events (id INTEGER, day DATE, at DATETIME)");
+    run(&mut catalog, "INSERT INTO events VALUES (1, '2023-12-31', '2024-03-01 12:00:00')");
+    run(&mut catalog, "INSERT INTO events VALUES (2, '2024-01-01', '2024-01-01 00:00:00')");
+    run(&mut catalog, "INSERT INTO events VALUES (3, '2024-02-15', '2024-02-15 08:30:00')");
+    run(&mut catalog, "INSERT INTO events VALUES (4, '2024-10-01', '2023-12-31 23:59:59')");
+    catalog
+}
+
+#[test]
+fn between_on_date_columns_compares_stored_days() {
+    let mut catalog = events("test_date_between.db");
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM events WHERE day BETWEEN '2024-01-01' AND '2024-03-01'"),
+        vec!["2", "3"]
+    );
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM events WHERE at BETWEEN '2024-01-01 00:00:00' AND '2024-02-28 00:00:00'"),
+        vec!["2", "3"]
+    );
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM events WHERE day >= '2024-01-01' AND day < '2024-10-01'"),
+        vec!["2", "3"]
+    );
+}
+
+#[test]
+fn date_only_literals_compare_against_datetime_as_midnight() {
+    let mut catalog = events("test_date_midnight.db");
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE at = '2024-01-01'"), vec!["2"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE at < '2024-01-01'"), vec!["4"]);
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM events WHERE at BETWEEN '2024-01-01' AND '2024-02-16'"),
+        vec!["2", "3"]
+    );
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE at != '2024-01-01'"), vec!["1", "3", "4"]);
+}
+
+#[test]
+fn order_by_datetime_is_chronological() {
+    let mut catalog = events("test_date_order.db");
+    assert_eq!(select(&mut catalog, "SELECT id FROM events ORDER BY at"), vec!["4", "2", "3", "1"]);
+    assert_eq!(
+        select(&mut catalog, "SELECT id, at FROM events WHERE at >= '2024-01-01' ORDER BY at DESC"),
+        vec!["1,2024-03-01 12:00:00", "3,2024-02-15 08:30:00", "2,2024-01-01 00:00:00"]
+    );
+}
+
+#[test]
+fn date_add_and_date_sub_shift_by_days() {
+    let mut catalog = events("test_date_add.db");
+    assert_eq!(
+        select(&mut catalog, "SELECT DATE_ADD(day, 45), DATE_SUB(at, INTERVAL 1 DAY) FROM events WHERE id = 3"),
+        vec!["2024-03-31,2024-02-14 08:30:00"]
+    );
+    // Month and leap-year boundaries come from the day count, not the text.
+    assert_eq!(
+        select(&mut catalog, "SELECT DATE_ADD(day, INTERVAL 59 DAY) AS later FROM events WHERE id = 1"),
+        vec!["2024-02-28"]
+    );
+    assert_eq!(select(&mut catalog, "SELECT DATE_ADD(day, -1) FROM events WHERE id = 2"), vec!["2023-12-31"]);
+    assert_eq!(select(&mut catalog, "SELECT DATE_ADD('2024-02-28', 1)"), vec!["2024-02-29"]);
+
+    assert!(parse_statement("SELECT DATE_ADD(day, 'soon') FROM events").is_err());
+    assert!(parse_statement("SELECT DATE_ADD(day) FROM events").is_err());
+}
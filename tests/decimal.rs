@@ -0,0 +1,84 @@
+use aerodb::{catalog::Catalog, storage::{pager::Pager, row::ColumnType}, sql::parser::parse_statement, execution::runtime::{QueryResult, handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn decimal_type_parsing() {
+    assert_eq!(ColumnType::from_str("DECIMAL(10,2)"), Some(ColumnType::Decimal { precision: 10, scale: 2 }));
+    assert_eq!(ColumnType::from_str("DECIMAL"), Some(ColumnType::Decimal { precision: 10, scale: 0 }));
+    assert_eq!(ColumnType::from_str("DECIMAL(2,3)"), None);
+    assert_eq!(ColumnType::from_str("DECIMAL(19,2)"), None);
+}
+
+#[test]
+fn decimal_sum_has_no_rounding_error() {
+    let filename = "test_decimal_sum.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE payments (id INTEGER, amount DECIMAL(10,2))").unwrap()).unwrap();
+    for i in 1..=10 {
+        handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO payments VALUES ({}, 0.1)", i)).unwrap()).unwrap();
+    }
+    handle_statement(&mut catalog, parse_statement("INSERT INTO payments VALUES (11, 0.2)").unwrap()).unwrap();
+
+    assert_eq!(select(&mut catalog, "SELECT amount FROM payments WHERE id = 1"), vec![vec!["0.10".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT SUM(amount) FROM payments"), vec![vec!["1.20".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT AVG(amount) FROM payments"), vec![vec!["0.11".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT amount + 0.2 FROM payments WHERE id = 1"), vec![vec!["0.30".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT amount * 3 FROM payments WHERE id = 11"), vec![vec!["0.60".to_string()]]);
+
+    // Extra fractional digits round at the declared scale; precision bounds the integer part.
+    handle_statement(&mut catalog, parse_statement("INSERT INTO payments VALUES (12, 2.675)").unwrap()).unwrap();
+    assert_eq!(select(&mut catalog, "SELECT amount FROM payments WHERE id = 12"), vec![vec!["2.68".to_string()]]);
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO payments VALUES (13, 123456789.00)").unwrap()).is_err());
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO payments VALUES (13, 'abc')").unwrap()).is_err());
+
+    handle_statement(&mut catalog, parse_statement("PRAGMA float_precision = 4").unwrap()).unwrap();
+    match handle_statement(&mut catalog, parse_statement("SELECT amount FROM payments WHERE id = 12").unwrap()).unwrap() {
+        QueryResult::Rows { rows, .. } => assert_eq!(rows, vec![vec!["2.6800".to_string()]]),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(select(&mut catalog, "SELECT amount FROM payments WHERE amount = 2.68"), vec![vec!["2.68".to_string()]]);
+
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.get_table("payments").unwrap().columns[1].1, ColumnType::Decimal { precision: 10, scale: 2 });
+    assert_eq!(select(&mut catalog, "SELECT SUM(amount) FROM payments"), vec![vec!["3.88".to_string()]]);
+}
+
+#[test]
+fn decimal_aggregates_report_the_column_type() {
+    let mut catalog = setup_catalog("test_decimal_aggregate_header.db");
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE payments (id INTEGER, payer TEXT, amount DECIMAL(10,2))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO payments VALUES (1, 'a', 1.25), (2, 'a', 2.50), (3, 'b', 0.10)").unwrap()).unwrap();
+
+    let decimal = ColumnType::Decimal { precision: 10, scale: 2 };
+    for sql in [
+        "SELECT payer, SUM(amount), AVG(amount) FROM payments GROUP BY payer",
+        "SELECT SUM(amount), AVG(amount) FROM payments",
+    ] {
+        match handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap() {
+            QueryResult::Rows { header, .. } => {
+                let types: Vec<ColumnType> = header.iter().rev().take(2).map(|(_, ty)| *ty).collect();
+                assert_eq!(types, [decimal, decimal], "{}", sql);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+    handle_statement(&mut catalog, parse_statement("PRAGMA float_precision = 3").unwrap()).unwrap();
+    match handle_statement(&mut catalog, parse_statement("SELECT payer, SUM(amount) FROM payments GROUP BY payer ORDER BY payer").unwrap()).unwrap() {
+        QueryResult::Rows { rows, .. } => assert_eq!(rows, [["a", "3.750"], ["b", "0.100"]]),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
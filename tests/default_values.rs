@@ -39,3 +39,55 @@ fn unqualified_insert_wrong_count() {
     let res = handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1)").unwrap());
     assert!(res.is_err());
 }
+
+#[test]
+fn expression_defaults_are_computed_at_insert_and_persist() {
+    let filename = "test_defaults_expressions.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement(
+        "CREATE TABLE t (id INTEGER, ttl INTEGER DEFAULT (60 * 60), ratio DOUBLE DEFAULT (1 / 4), created TIMESTAMP DEFAULT CURRENT_TIMESTAMP, note TEXT DEFAULT '(1 + 2)')",
+    ).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t (id) VALUES (1)").unwrap()).unwrap();
+
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t (id) VALUES (2)").unwrap()).unwrap();
+    for id in ["1", "2"] {
+        let mut out = Vec::new();
+        execute_select_with_indexes(&mut catalog, "t", Some(Expr::Equals { left: "id".into(), right: id.into() }), &mut out).unwrap();
+        assert_eq!(out[0].data.0[1], ColumnValue::Integer(3600));
+        assert_eq!(out[0].data.0[2], ColumnValue::Double(0.25));
+        assert!(matches!(out[0].data.0[3], ColumnValue::Timestamp(ts) if ts > 1_600_000_000));
+        assert_eq!(out[0].data.0[4], ColumnValue::Text("(1 + 2)".into()));
+    }
+
+    assert!(parse_statement("CREATE TABLE u (id INTEGER, n INTEGER DEFAULT (a + 1))").is_err());
+}
+
+#[test]
+fn nested_default_arithmetic_is_computed_and_persists() {
+    let filename = "test_defaults_nested_arithmetic.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement(
+        "CREATE TABLE t (id INTEGER, a INTEGER DEFAULT (2 * 3 + 1), b INTEGER DEFAULT (2 * (3 + 1)), c INTEGER DEFAULT (-3), d INTEGER DEFAULT (10 - -4 % 3))",
+    ).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t (id) VALUES (1)").unwrap()).unwrap();
+
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t (id) VALUES (2)").unwrap()).unwrap();
+    for id in ["1", "2"] {
+        let mut out = Vec::new();
+        execute_select_with_indexes(&mut catalog, "t", Some(Expr::Equals { left: "id".into(), right: id.into() }), &mut out).unwrap();
+        assert_eq!(out[0].data.0[1..], [ColumnValue::Integer(7), ColumnValue::Integer(8), ColumnValue::Integer(-3), ColumnValue::Integer(11)]);
+    }
+}
+
+#[test]
+fn unparenthesized_default_arithmetic_is_rejected() {
+    let err = parse_statement("CREATE TABLE t (id INTEGER, n INTEGER DEFAULT 5 + 1)").unwrap_err();
+    assert!(err.contains("parenthesized"), "{}", err);
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n INTEGER DEFAULT (2 * (3 + a)))").is_err());
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n INTEGER DEFAULT (2 * (3 + 1))").is_err());
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, n INTEGER DEFAULT -5)").is_ok());
+}
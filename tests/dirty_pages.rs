@@ -0,0 +1,94 @@
+use aerodb::storage::btree::BTree;
+use aerodb::storage::pager::Pager;
+use aerodb::storage::row::{ColumnValue, RowData};
+use aerodb::transaction::{IsolationLevel, Snapshot};
+
+#[test]
+fn flush_dirty_writes_only_marked_pages() {
+    let mut pager = Pager::new_in_memory().unwrap();
+    for _ in 0..6 {
+        pager.allocate_page().unwrap();
+    }
+    let start = pager.num_pages() - 6;
+    pager.get_page(start + 1).unwrap().data[0] = 1;
+    pager.mark_dirty(start + 1);
+    pager.get_page(start + 4).unwrap().data[0] = 4;
+    pager.mark_dirty(start + 4);
+    pager.mark_dirty(start + 4);
+    // Read but not modified.
+    let _ = pager.get_page(start + 2).unwrap();
+
+    let before = pager.page_write_count();
+    pager.flush_dirty().unwrap();
+    assert_eq!(pager.page_write_count() - before, 2);
+
+    let before = pager.page_write_count();
+    pager.flush_dirty().unwrap();
+    assert_eq!(pager.page_write_count(), before);
+}
+
+#[test]
+fn commit_logs_pages_marked_dirty_in_the_transaction() {
+    let path = std::env::temp_dir().join(format!("dirty-pages-{}.db", std::process::id()));
+    let path = path.to_str().unwrap().to_string();
+    for suffix in ["", ".wal", ".clog"] {
+        let _ = std::fs::remove_file(format!("{path}{suffix}"));
+    }
+    let page = {
+        let mut pager = Pager::new(&path).unwrap();
+        // Page 0 holds the pager's meta data.
+        pager.allocate_page().unwrap();
+        let page = pager.allocate_page().unwrap();
+        let id = pager.allocate_transaction_id();
+        let snapshot = Snapshot::new_for_transaction(id, pager.peek_next_transaction_id(), Vec::new());
+        pager.begin_transaction(id, snapshot, None, IsolationLevel::default()).unwrap();
+        pager.get_page(page).unwrap().data[10] = 42;
+        pager.mark_dirty(page);
+        pager.commit_transaction().unwrap();
+        page
+    };
+    let mut pager = Pager::new(&path).unwrap();
+    assert_eq!(pager.get_page(page).unwrap().data[10], 42);
+    drop(pager);
+    for suffix in ["", ".wal", ".clog"] {
+        let _ = std::fs::remove_file(format!("{path}{suffix}"));
+    }
+}
+
+#[test]
+fn btree_inserts_mark_the_pages_they_change() {
+    let mut pager = Pager::new_in_memory().unwrap();
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let id = pager.allocate_transaction_id();
+    let snapshot = Snapshot::new_for_transaction(id, pager.peek_next_transaction_id(), Vec::new());
+    pager.begin_transaction(id, snapshot, None, IsolationLevel::default()).unwrap();
+    {
+        let mut tree = BTree::open_root(&mut pager, root).unwrap();
+        for key in 0..200 {
+            tree.insert(key, RowData(vec![ColumnValue::Integer(key as i32)])).unwrap();
+        }
+    }
+    // Nothing reaches the file inside the transaction; once it is gone the
+    // changed pages are still owed a write.
+    assert!(pager.uninstall_transaction().is_some());
+    let before = pager.page_write_count();
+    pager.flush_dirty().unwrap();
+    assert!(pager.page_write_count() - before > 1);
+}
+
+#[test]
+fn flush_all_writes_every_page() {
+    let mut pager = Pager::new_in_memory().unwrap();
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    for key in 0..200 {
+        tree.insert(key, RowData(vec![ColumnValue::Integer(key as i32)])).unwrap();
+    }
+    let pages = tree.pages().unwrap().len() as u64;
+    tree.flush_all().unwrap();
+    drop(tree);
+    // Every page of the tree is written again, changed since or not.
+    let before = pager.page_write_count();
+    BTree::open_root(&mut pager, root).unwrap().flush_all().unwrap();
+    assert!(pager.page_write_count() - before >= pages);
+}
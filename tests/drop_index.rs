@@ -1,4 +1,4 @@
-use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_select_with_indexes}};
+use aerodb::{catalog::Catalog, engine::Engine, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{QueryResult, handle_statement, execute_select_with_indexes}};
 use std::fs;
 
 fn setup_catalog(filename: &str) -> Catalog {
@@ -72,3 +72,145 @@ fn selects_use_full_scan_after_drop() {
         panic!("expected select");
     }
 }
+
+#[test]
+fn indexes_survive_reopen() {
+    let filename = "test_indexes_survive_reopen.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, 'a'), (2, 'b')").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON t (name)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_id ON t (id)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("DROP INDEX idx_id").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (3, 'c')").unwrap()).unwrap();
+    drop(catalog);
+
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    let names: Vec<String> = catalog.all_indexes().into_iter().map(|i| i.name).collect();
+    assert_eq!(names, ["idx_name"]);
+    let mut rows = Vec::new();
+    let selection = aerodb::sql::ast::Expr::Equals { left: "name".into(), right: "c".into() };
+    assert!(execute_select_with_indexes(&mut catalog, "t", Some(selection), &mut rows).unwrap());
+    assert_eq!(rows.iter().map(|r| r.key).collect::<Vec<_>>(), [3]);
+}
+
+#[test]
+fn create_index_if_not_exists_is_idempotent() {
+    let filename = "test_create_index_if_not_exists.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+    let sql = "CREATE INDEX IF NOT EXISTS idx_name ON t (name)";
+    match parse_statement(sql).unwrap() {
+        Statement::CreateIndex { index_name, if_not_exists, .. } => {
+            assert_eq!(index_name, "idx_name");
+            assert!(if_not_exists);
+        }
+        other => panic!("expected create index, got {:?}", other),
+    }
+    handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    assert!(catalog.index_exists("idx_name"));
+    match handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Message(msg) => assert_eq!(msg, "Index idx_name already exists"),
+        other => panic!("expected message, got {:?}", other),
+    }
+    assert_eq!(catalog.all_indexes().len(), 1);
+    assert!(handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON t (name)").unwrap()).is_err());
+    // A missing table is still an error.
+    assert!(handle_statement(&mut catalog, parse_statement("CREATE INDEX IF NOT EXISTS idx_x ON missing (id)").unwrap()).is_err());
+    assert!(catalog.table_exists("t") && !catalog.table_exists("missing"));
+}
+
+#[test]
+fn drop_index_if_exists_tolerates_missing_index() {
+    let filename = "test_drop_index_if_exists.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx1 ON t (name)").unwrap()).unwrap();
+
+    handle_statement(&mut catalog, parse_statement("DROP INDEX IF EXISTS idx1").unwrap()).unwrap();
+    assert!(!catalog.index_exists("idx1"));
+    match handle_statement(&mut catalog, parse_statement("DROP INDEX IF EXISTS idx1").unwrap()).unwrap() {
+        QueryResult::Message(msg) => assert_eq!(msg, "Index idx1 does not exist"),
+        other => panic!("expected message, got {:?}", other),
+    }
+    assert!(handle_statement(&mut catalog, parse_statement("DROP INDEX idx1").unwrap()).is_err());
+}
+
+#[test]
+fn drop_table_indexes_stay_dropped_after_reopen() {
+    let filename = "test_drop_table_indexes_reopen.db";
+    let mut catalog = setup_catalog(filename);
+
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, 'a')").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx1 ON t (name)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx2 ON t (id)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("DROP TABLE t").unwrap()).unwrap();
+    assert!(catalog.find_index("t", "name").is_none());
+    assert!(catalog.find_index("t", "id").is_none());
+    drop(catalog);
+
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert!(catalog.find_index("t", "name").is_none());
+    assert!(catalog.find_index("t", "id").is_none());
+    assert!(!catalog.index_exists("idx1") && !catalog.index_exists("idx2"));
+
+    // A table recreated under the same name starts without indexes.
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (2, 'b')").unwrap()).unwrap();
+    assert!(catalog.all_indexes().is_empty());
+    let mut rows = Vec::new();
+    let selection = aerodb::sql::ast::Expr::Equals { left: "name".into(), right: "b".into() };
+    assert!(!execute_select_with_indexes(&mut catalog, "t", Some(selection), &mut rows).unwrap());
+}
+
+/// Size of `filename` after creating and filling a table with an index
+/// `rounds` times, dropping it between rounds.
+fn size_after_recreating(filename: &str, rounds: usize) -> u64 {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut engine = Engine::new(filename);
+    for round in 0..rounds {
+        if round > 0 {
+            engine.execute(parse_statement("DROP TABLE t").unwrap()).unwrap();
+        }
+        engine.execute(parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+        engine.execute(parse_statement("CREATE INDEX idx_name ON t (name)").unwrap()).unwrap();
+        for i in 1..=200 {
+            let sql = format!("INSERT INTO t VALUES ({}, '{}')", i, "x".repeat(100 + i));
+            engine.execute(parse_statement(&sql).unwrap()).unwrap();
+        }
+    }
+    drop(engine);
+    fs::metadata(filename).unwrap().len()
+}
+
+#[test]
+fn recreating_a_dropped_table_reuses_its_pages() {
+    let once = size_after_recreating("test_drop_table_pages_once.db", 1);
+    let thrice = size_after_recreating("test_drop_table_pages_thrice.db", 3);
+    assert!(thrice <= once, "file grew from {} to {} bytes", once, thrice);
+}
+
+#[test]
+fn rolled_back_drop_keeps_the_tree() {
+    let filename = "test_drop_table_rollback_pages.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INT, name TEXT)").unwrap()).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (1, 'a'), (2, 'b')").unwrap()).unwrap();
+    engine.execute(parse_statement("BEGIN").unwrap()).unwrap();
+    engine.execute(parse_statement("DROP TABLE t").unwrap()).unwrap();
+    engine.execute(parse_statement("ROLLBACK").unwrap()).unwrap();
+    // Had the dropped pages been released, this table would take them over.
+    engine.execute(parse_statement("CREATE TABLE u (id INT, name TEXT)").unwrap()).unwrap();
+    engine.execute(parse_statement("INSERT INTO u VALUES (9, 'z')").unwrap()).unwrap();
+
+    match engine.execute(parse_statement("SELECT name FROM t").unwrap()).unwrap() {
+        QueryResult::Rows { rows, .. } => assert_eq!(rows, [["a"], ["b"]]),
+        other => panic!("expected rows, got {:?}", other),
+    }
+}
@@ -0,0 +1,47 @@
+use aerodb::{catalog::Catalog, execution::runtime::handle_statement, sql::parser::parse_statement, storage::{pager::Pager, row::ColumnValue}};
+use std::{fs, io};
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+#[test]
+fn dump_index_lists_every_value_and_its_rows() {
+    let mut catalog = setup_catalog("test_dump_index.db");
+    run(&mut catalog, "CREATE TABLE pets (id INTEGER, kind TEXT)");
+    run(&mut catalog, "INSERT INTO pets VALUES (1, 'cat'), (2, 'dog'), (3, 'cat')");
+    run(&mut catalog, "CREATE INDEX idx_kind ON pets (kind)");
+    // Rows added after the build are posted by the insert path.
+    run(&mut catalog, "INSERT INTO pets VALUES (4, 'fish'), (5, 'cat')");
+
+    let entries = catalog.dump_index("idx_kind").unwrap();
+    let mut sorted = entries.clone();
+    sorted.sort();
+    assert_eq!(
+        sorted,
+        vec![
+            ("cat".to_string(), vec![1, 3, 5]),
+            ("dog".to_string(), vec![2]),
+            ("fish".to_string(), vec![4]),
+        ]
+    );
+    let total: usize = entries.iter().map(|(_, keys)| keys.len()).sum();
+    assert_eq!(total, 5);
+
+    // Entries come back in hash order, the order the tree stores them in.
+    let hashes: Vec<i64> = entries
+        .iter()
+        .map(|(value, _)| Catalog::hash_value(&ColumnValue::Text(value.clone())))
+        .collect();
+    assert!(hashes.windows(2).all(|w| w[0] < w[1]));
+
+    let err = catalog.dump_index("missing").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
@@ -0,0 +1,35 @@
+use aerodb::{engine::Engine, error::DbError, execution::runtime::QueryResult};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+#[test]
+fn create_insert_and_select_from_strings() {
+    let mut engine = setup_engine("test_execute_str.db");
+    engine.execute_str("CREATE TABLE notes (id INTEGER, body TEXT)").unwrap();
+    assert!(matches!(
+        engine.execute_str("INSERT INTO notes VALUES (1, 'first'), (2, 'second')").unwrap(),
+        QueryResult::Affected(2)
+    ));
+    match engine.execute_str("SELECT body FROM notes WHERE id = 2").unwrap() {
+        QueryResult::Rows { header, rows } => {
+            assert_eq!(header.len(), 1);
+            assert_eq!(header[0].0, "body");
+            assert_eq!(rows, vec![vec!["second".to_string()]]);
+        }
+        _ => panic!("expected rows"),
+    }
+}
+
+#[test]
+fn parse_and_execution_errors_are_db_errors() {
+    let mut engine = setup_engine("test_execute_str_errors.db");
+    assert!(matches!(engine.execute_str("SELEKT 1"), Err(DbError::ParseError(_))));
+    let err = engine.execute_str("SELECT * FROM missing").unwrap_err();
+    assert!(!matches!(err, DbError::ParseError(_)), "{:?}", err);
+}
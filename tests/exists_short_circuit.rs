@@ -0,0 +1,61 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::execute_select_statement,
+    sql::parser::parse_statement,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut engine = Engine::new(filename);
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'ann'), (2, 'bob'), (3, 'cat')");
+    run(&mut engine, "CREATE TABLE orders (id INTEGER, user_id INTEGER)");
+    // User 1 owns the first order and user 2 the last; user 3 has none.
+    let values: Vec<String> = (1..=500)
+        .map(|i| format!("({}, {})", i, if i == 500 { 2 } else if i == 1 { 1 } else { 4 }))
+        .collect();
+    run(&mut engine, &format!("INSERT INTO orders VALUES {}", values.join(", ")));
+    engine
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn names(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|mut r| r.remove(0)).collect()
+}
+
+#[test]
+fn exists_stops_the_inner_scan_at_the_first_match() {
+    let mut engine = setup_engine("test_exists_short_circuit.db");
+    let before = engine.catalog.rows_visited();
+    assert_eq!(
+        names(
+            &mut engine.catalog,
+            "SELECT name FROM users WHERE EXISTS (SELECT id FROM orders WHERE orders.user_id = users.id)"
+        ),
+        ["ann", "bob"]
+    );
+    // 3 outer rows, then 1 inner row for ann and the full table for bob and cat.
+    assert_eq!(engine.catalog.rows_visited() - before, 3 + 1 + 500 + 500);
+}
+
+#[test]
+fn limit_stops_a_table_scan_early() {
+    let mut engine = setup_engine("test_limit_short_circuit.db");
+    let before = engine.catalog.rows_visited();
+    assert_eq!(names(&mut engine.catalog, "SELECT id FROM orders LIMIT 2 OFFSET 3"), ["4", "5"]);
+    assert_eq!(engine.catalog.rows_visited() - before, 5);
+
+    let before = engine.catalog.rows_visited();
+    assert_eq!(names(&mut engine.catalog, "SELECT id FROM orders WHERE user_id = 2 LIMIT 1"), ["500"]);
+    assert_eq!(engine.catalog.rows_visited() - before, 500);
+}
@@ -0,0 +1,97 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{QueryResult, handle_statement},
+    sql::{ast::Statement, parser::parse_statement},
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT, team TEXT)");
+    run(&mut catalog, "CREATE TABLE orders (id INTEGER, user_id INTEGER, total INTEGER)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'ann', 'red'), (2, 'bo', 'blue')");
+    catalog
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn explain(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    match run(catalog, &format!("EXPLAIN {}", sql)) {
+        QueryResult::Rows { header, rows } => {
+            assert_eq!(header[0].0, "QUERY PLAN");
+            rows.into_iter().map(|mut r| r.remove(0)).collect()
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn equality_on_indexed_column_uses_index_scan() {
+    let mut catalog = setup_catalog("test_explain_index.db");
+    run(&mut catalog, "CREATE INDEX idx_users_id ON users(id)");
+    let plan = explain(&mut catalog, "SELECT name FROM users WHERE id = 1");
+    assert_eq!(plan, vec!["Index Scan using idx_users_id on users (id = 1)"]);
+
+    let plan = explain(&mut catalog, "SELECT name FROM users u WHERE u.id = 2 AND name = 'bo'");
+    assert!(plan[0].starts_with("Index Scan using idx_users_id"), "{:?}", plan);
+    assert!(plan[0].contains("with filter"), "{:?}", plan);
+}
+
+#[test]
+fn unindexed_predicates_use_seq_scan() {
+    let mut catalog = setup_catalog("test_explain_seq.db");
+    assert_eq!(explain(&mut catalog, "SELECT * FROM users"), vec!["Seq Scan on users"]);
+    assert_eq!(explain(&mut catalog, "SELECT * FROM users WHERE id = 1"), vec!["Seq Scan on users with filter"]);
+
+    run(&mut catalog, "CREATE INDEX idx_users_id ON users(id)");
+    let plan = explain(&mut catalog, "SELECT * FROM users WHERE id > 1");
+    assert_eq!(plan, vec!["Seq Scan on users with filter"]);
+}
+
+#[test]
+fn explain_reports_sort_limit_and_aggregate_steps() {
+    let mut catalog = setup_catalog("test_explain_steps.db");
+    let plan = explain(&mut catalog, "SELECT team, COUNT(*) FROM users GROUP BY team ORDER BY team DESC LIMIT 5");
+    assert_eq!(
+        plan,
+        vec![
+            "Limit (5 rows)",
+            "  -> Sort (team DESC)",
+            "    -> Group Aggregate (group by team)",
+            "      -> Seq Scan on users",
+        ]
+    );
+    assert_eq!(explain(&mut catalog, "SELECT COUNT(*) FROM users"), vec!["Aggregate", "  -> Seq Scan on users"]);
+}
+
+#[test]
+fn explain_lists_join_order() {
+    let mut catalog = setup_catalog("test_explain_join.db");
+    let plan = explain(
+        &mut catalog,
+        "SELECT u.name, o.total FROM users u LEFT JOIN orders o ON u.id = o.user_id",
+    );
+    assert_eq!(
+        plan,
+        vec![
+            "Nested Loop Join (order: users u, orders o)",
+            "  -> Seq Scan on users u",
+            "  -> Left Join: Seq Scan on orders o",
+        ]
+    );
+}
+
+#[test]
+fn explain_accepts_queries_and_unions_only() {
+    let mut catalog = setup_catalog("test_explain_parse.db");
+    assert!(matches!(parse_statement("explain SELECT id FROM users").unwrap(), Statement::Explain(_)));
+    assert!(parse_statement("EXPLAIN DELETE FROM users").is_err());
+    let plan = explain(&mut catalog, "SELECT id FROM users UNION SELECT id FROM orders");
+    assert_eq!(plan, vec!["Union", "  -> Seq Scan on users", "  -> Seq Scan on orders"]);
+    assert!(handle_statement(&mut catalog, parse_statement("EXPLAIN SELECT * FROM missing").unwrap()).is_err());
+}
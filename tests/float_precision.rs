@@ -0,0 +1,77 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{QueryResult, handle_statement}};
+use aerodb::storage::row::ColumnValue;
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn rows(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    match run(catalog, sql) {
+        QueryResult::Rows { rows, .. } => rows,
+        other => panic!("expected rows, got {:?}", other),
+    }
+}
+
+#[test]
+fn pragma_float_precision_controls_double_output() {
+    let mut catalog = setup_catalog("test_float_precision.db");
+    run(&mut catalog, "CREATE TABLE t (id INT, price DOUBLE)");
+    run(&mut catalog, "INSERT INTO t VALUES (1, 2.5)");
+    assert_eq!(rows(&mut catalog, "SELECT price FROM t"), [["2.5"]]);
+
+    assert_eq!(run(&mut catalog, "PRAGMA float_precision = 2"), QueryResult::Message("float_precision = 2".into()));
+    assert_eq!(catalog.float_precision(), Some(2));
+    assert_eq!(rows(&mut catalog, "SELECT price FROM t"), [["2.50"]]);
+    assert_eq!(rows(&mut catalog, "SELECT id, price * 3 FROM t"), [["1", "7.50"]]);
+    // Values themselves stay exact.
+    assert_eq!(ColumnValue::Double(0.1 + 0.2).to_string_value(), "0.30000000000000004");
+
+    run(&mut catalog, "PRAGMA float_precision = DEFAULT");
+    assert_eq!(catalog.float_precision(), None);
+    assert_eq!(rows(&mut catalog, "SELECT price FROM t"), [["2.5"]]);
+}
+
+#[test]
+fn float_precision_leaves_filters_exact() {
+    let mut catalog = setup_catalog("test_float_precision_filters.db");
+    run(&mut catalog, "CREATE TABLE t (id INT, price DOUBLE)");
+    run(&mut catalog, "INSERT INTO t VALUES (1, 2.6), (2, 2.4)");
+    run(&mut catalog, "PRAGMA float_precision = 0");
+
+    assert_eq!(rows(&mut catalog, "SELECT id FROM t WHERE price = 2.6"), [["1"]]);
+    assert_eq!(rows(&mut catalog, "SELECT id FROM t WHERE price > 2.45"), [["1"]]);
+    assert_eq!(rows(&mut catalog, "SELECT id, price FROM t ORDER BY price"), [["2", "2"], ["1", "3"]]);
+}
+
+#[test]
+fn float_precision_is_per_catalog() {
+    let mut first = setup_catalog("test_float_precision_first.db");
+    let mut second = setup_catalog("test_float_precision_second.db");
+    run(&mut first, "PRAGMA float_precision = 3");
+    assert_eq!(first.float_precision(), Some(3));
+    assert_eq!(second.float_precision(), None);
+    assert_eq!(run(&mut second, "PRAGMA float_precision"), QueryResult::Message("float_precision = DEFAULT".into()));
+}
+
+#[test]
+fn invalid_float_precision_is_rejected() {
+    let mut catalog = setup_catalog("test_float_precision_invalid.db");
+    run(&mut catalog, "PRAGMA float_precision = 2");
+    assert!(handle_statement(&mut catalog, parse_statement("PRAGMA float_precision = abc").unwrap()).is_err());
+    assert!(handle_statement(&mut catalog, parse_statement("PRAGMA float_precision = 18").unwrap()).is_err());
+    assert_eq!(catalog.float_precision(), Some(2));
+}
+
+#[test]
+fn unknown_pragma_is_rejected() {
+    let filename = "test_unknown_pragma.db";
+    let mut catalog = setup_catalog(filename);
+    assert!(handle_statement(&mut catalog, parse_statement("PRAGMA no_such_setting = 1").unwrap()).is_err());
+}
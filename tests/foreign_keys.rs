@@ -13,7 +13,7 @@ fn foreign_key_basic() {
     let create_users = Statement::CreateTable {
         table_name: "users".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false}
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false}
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     };
@@ -59,7 +59,7 @@ fn foreign_key_on_delete_cascade() {
     let create_users = Statement::CreateTable {
         table_name: "users".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false}
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false}
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     };
@@ -88,3 +88,65 @@ fn foreign_key_on_delete_cascade() {
     assert!(rows.is_empty());
 }
 
+
+#[test]
+fn update_validates_changed_foreign_keys() {
+    let filename = "test_fk_update.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    let run = |catalog: &mut Catalog, sql: &str| aerodb::execution::handle_statement(catalog, parse_statement(sql).unwrap());
+
+    run(&mut catalog, "CREATE TABLE users (id INTEGER)").unwrap();
+    run(&mut catalog, "CREATE TABLE orders (id INTEGER, user_id INTEGER, note TEXT, FOREIGN KEY (user_id) REFERENCES users (id))").unwrap();
+    run(&mut catalog, "INSERT INTO users VALUES (1), (2)").unwrap();
+    run(&mut catalog, "INSERT INTO orders VALUES (1, 1, 'a')").unwrap();
+
+    let res = run(&mut catalog, "UPDATE orders SET user_id = 9 WHERE id = 1");
+    assert!(matches!(res, Err(aerodb::error::DbError::ForeignKeyViolation(_))));
+    run(&mut catalog, "UPDATE orders SET user_id = 2 WHERE id = 1").unwrap();
+    // A NULL reference has no parent to check.
+    run(&mut catalog, "UPDATE orders SET user_id = NULL WHERE id = 1").unwrap();
+    run(&mut catalog, "UPDATE orders SET note = 'b' WHERE id = 1").unwrap();
+
+    let mut rows = Vec::new();
+    aerodb::execution::execute_select_with_indexes(&mut catalog, "orders", None, &mut rows).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert!(matches!(rows[0].data.0[1], aerodb::storage::row::ColumnValue::Null));
+}
+
+#[test]
+fn deferred_foreign_keys_accept_a_child_before_its_parent() {
+    let filename = "test_fk_deferred.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut engine = aerodb::engine::Engine::new(filename);
+    {
+        let mut run = |sql: &str| engine.execute(parse_statement(sql).unwrap()).map(|_| ());
+
+        run("CREATE TABLE nodes (id INTEGER, parent_id INTEGER, FOREIGN KEY (parent_id) REFERENCES nodes (id))").unwrap();
+        let hierarchy = "INSERT INTO nodes VALUES (3, 2), (2, 1), (1, NULL)";
+        // Checked row by row, node 3 names a parent not inserted yet.
+        assert!(matches!(run(hierarchy), Err(aerodb::error::DbError::ForeignKeyViolation(_))));
+
+        run("SET CONSTRAINTS ALL DEFERRED").unwrap();
+        run(hierarchy).unwrap();
+        // A batch that is not internally consistent still fails at the end.
+        assert!(matches!(
+            run("INSERT INTO nodes VALUES (5, 4), (4, 9)"),
+            Err(aerodb::error::DbError::ForeignKeyViolation(_))
+        ));
+        run("SET CONSTRAINTS IMMEDIATE").unwrap();
+        assert!(run("INSERT INTO nodes VALUES (7, 6), (6, 1)").is_err());
+    }
+
+    engine.catalog.set_defer_foreign_keys(true);
+    assert_eq!(engine.validate_insert(parse_statement("INSERT INTO nodes VALUES (11, 10), (10, 3)").unwrap()).unwrap(), 2);
+
+    let mut rows = Vec::new();
+    aerodb::execution::execute_select_with_indexes(&mut engine.catalog, "nodes", None, &mut rows).unwrap();
+    let keys: Vec<i64> = rows.iter().map(|r| r.key).collect();
+    assert_eq!(keys, [1, 2, 3]);
+    assert!(parse_statement("SET CONSTRAINTS LATER").is_err());
+}
@@ -0,0 +1,64 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn totals(catalog: &mut Catalog) -> Vec<Vec<String>> {
+    let stmt = parse_statement("SELECT id, total FROM orders").unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+fn row(id: &str, total: &str) -> Vec<String> {
+    vec![id.to_string(), total.to_string()]
+}
+
+#[test]
+fn parse_generated_column() {
+    let stmt = parse_statement("CREATE TABLE orders (id INTEGER, qty INTEGER, price INTEGER, total INTEGER GENERATED ALWAYS AS (qty * price) STORED)").unwrap();
+    if let Statement::CreateTable { columns, .. } = stmt {
+        assert!(columns[2].generated.is_none());
+        assert!(columns[3].generated.is_some());
+    } else {
+        panic!("expected CREATE TABLE");
+    }
+    assert!(parse_statement("CREATE TABLE t (id INTEGER, total INTEGER GENERATED ALWAYS (id))").is_err());
+}
+
+#[test]
+fn generated_column_computed_on_insert_and_update() {
+    let filename = "test_generated_columns.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE orders (id INTEGER, qty INTEGER, price INTEGER, total INTEGER GENERATED ALWAYS AS (qty * price) STORED)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO orders (id, qty, price) VALUES (1, 2, 3), (2, 4, 5)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO orders VALUES (3, 7, 6, DEFAULT)").unwrap()).unwrap();
+    assert_eq!(totals(&mut catalog), vec![row("1", "6"), row("2", "20"), row("3", "42")]);
+
+    handle_statement(&mut catalog, parse_statement("UPDATE orders SET qty = 10 WHERE id = 1").unwrap()).unwrap();
+    assert_eq!(totals(&mut catalog), vec![row("1", "30"), row("2", "20"), row("3", "42")]);
+
+    // The expression is persisted with the table definition.
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("UPDATE orders SET price = 1 WHERE id = 2").unwrap()).unwrap();
+    assert_eq!(totals(&mut catalog), vec![row("1", "30"), row("2", "4"), row("3", "42")]);
+}
+
+#[test]
+fn generated_column_rejects_direct_assignment() {
+    let filename = "test_generated_reject.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE orders (id INTEGER, qty INTEGER, price INTEGER, total INTEGER GENERATED ALWAYS AS (qty * price) STORED)").unwrap()).unwrap();
+    let err = handle_statement(&mut catalog, parse_statement("INSERT INTO orders (id, qty, price, total) VALUES (1, 2, 3, 99)").unwrap()).unwrap_err();
+    assert!(err.to_string().contains("generated column 'total'"), "{}", err);
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO orders VALUES (1, 2, 3, 99)").unwrap()).is_err());
+
+    handle_statement(&mut catalog, parse_statement("INSERT INTO orders (id, qty, price) VALUES (1, 2, 3)").unwrap()).unwrap();
+    assert!(handle_statement(&mut catalog, parse_statement("UPDATE orders SET total = 5 WHERE id = 1").unwrap()).is_err());
+    assert_eq!(totals(&mut catalog), vec![row("1", "6")]);
+}
@@ -0,0 +1,61 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{execute_select_statement, handle_statement},
+    sql::parser::parse_statement,
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "CREATE TABLE scores (id INTEGER, score1 INTEGER, score2 INTEGER, name TEXT)");
+    run(&mut catalog, "INSERT INTO scores VALUES (1, 90, 75, 'ada'), (2, 60, 85, 'Bob'), (3, 70, 70, 'cy'), (4, 100, NULL, 'dee')");
+    catalog
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r.join(",")).collect()
+}
+
+#[test]
+fn greatest_and_least_pick_per_row() {
+    let mut catalog = setup_catalog("test_greatest_least_rows.db");
+    assert_eq!(
+        select(&mut catalog, "SELECT id, GREATEST(score1, score2) AS best, LEAST(score1, score2) AS worst FROM scores"),
+        vec!["1,90,75", "2,85,60", "3,70,70", "4,NULL,NULL"]
+    );
+    // Clamping into 65..=95.
+    assert_eq!(
+        select(&mut catalog, "SELECT LEAST(GREATEST(score1, 65), 95) FROM scores"),
+        vec!["90", "65", "70", "95"]
+    );
+}
+
+#[test]
+fn text_arguments_compare_lexicographically() {
+    let mut catalog = setup_catalog("test_greatest_least_text.db");
+    assert_eq!(
+        select(&mut catalog, "SELECT GREATEST(name, 'b'), LEAST(name, 'b') FROM scores WHERE id < 3"),
+        vec!["b,ada", "b,Bob"]
+    );
+    // A number mixed with text compares as text: '9' sorts after '10'.
+    assert_eq!(select(&mut catalog, "SELECT GREATEST(10, '9', -3)"), vec!["9"]);
+    assert_eq!(select(&mut catalog, "SELECT GREATEST(10, 9, -3), LEAST(10, 9, -3)"), vec!["10,-3"]);
+}
+
+#[test]
+fn greatest_in_where_clause() {
+    let mut catalog = setup_catalog("test_greatest_least_where.db");
+    assert_eq!(select(&mut catalog, "SELECT id FROM scores WHERE GREATEST(score1, score2) >= 85"), vec!["1", "2"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM scores WHERE LEAST(score1, score2) = 70"), vec!["3"]);
+    assert!(parse_statement("SELECT GREATEST() FROM scores").is_err());
+}
@@ -11,10 +11,10 @@ fn create_matches_table(catalog: &mut Catalog) {
     aerodb::execution::handle_statement(catalog, Statement::CreateTable {
         table_name: "matches".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            aerodb::sql::ast::ColumnDef { name: "team".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            aerodb::sql::ast::ColumnDef { name: "league".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            aerodb::sql::ast::ColumnDef { name: "score".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            aerodb::sql::ast::ColumnDef { name: "team".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            aerodb::sql::ast::ColumnDef { name: "league".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            aerodb::sql::ast::ColumnDef { name: "score".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(),
         primary_key: None,
@@ -101,3 +101,38 @@ fn reject_extra_group_column() {
     assert!(matches!(res, Err(aerodb::error::DbError::GroupByMismatch(_))));
 }
 
+
+fn grouped_rows(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let mut out = Vec::new();
+    aerodb::execution::runtime::execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut out, None).unwrap();
+    out
+}
+
+#[test]
+fn order_categories_by_descending_count() {
+    let filename = "test_group_order_count.db";
+    let mut catalog = setup_catalog(filename);
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE products (id INTEGER, category TEXT, price INTEGER)").unwrap()).unwrap();
+    let rows = [(1, "toys", 5), (2, "books", 12), (3, "books", 8), (4, "garden", 30), (5, "books", 4), (6, "toys", 100)];
+    for (id, category, price) in rows {
+        aerodb::execution::handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO products VALUES ({}, '{}', {})", id, category, price)).unwrap()).unwrap();
+    }
+    // Ten or more items would sort before "2" if compared as text.
+    for id in 7..17 {
+        aerodb::execution::handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO products VALUES ({}, 'tools', 1)", id)).unwrap()).unwrap();
+    }
+
+    let expected = vec![
+        vec!["tools".to_string(), "10".to_string()],
+        vec!["books".to_string(), "3".to_string()],
+        vec!["toys".to_string(), "2".to_string()],
+        vec!["garden".to_string(), "1".to_string()],
+    ];
+    assert_eq!(grouped_rows(&mut catalog, "SELECT category, COUNT(*) FROM products GROUP BY category ORDER BY COUNT(*) DESC"), expected);
+    assert_eq!(grouped_rows(&mut catalog, "SELECT category, COUNT(*) AS n FROM products GROUP BY category ORDER BY n DESC"), expected);
+    assert_eq!(grouped_rows(&mut catalog, "SELECT category, COUNT(*) AS n FROM products GROUP BY category ORDER BY count(*) DESC LIMIT 2"), expected[..2].to_vec());
+
+    let by_sum = grouped_rows(&mut catalog, "SELECT category, SUM(p.price) FROM products p GROUP BY category ORDER BY SUM(p.price)");
+    let names: Vec<&str> = by_sum.iter().map(|r| r[0].as_str()).collect();
+    assert_eq!(names, vec!["tools", "books", "garden", "toys"]);
+}
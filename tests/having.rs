@@ -1,4 +1,4 @@
-use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{execute_group_query, format_header}, storage::row::ColumnType};
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{execute_group_query, execute_select_statement, format_header}, storage::row::ColumnType};
 use std::fs;
 
 fn setup_catalog(filename: &str) -> Catalog {
@@ -14,9 +14,9 @@ fn having_basic_sum() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "sales".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "region".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "amount".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "region".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "amount".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -52,9 +52,9 @@ fn having_with_where() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "employees".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "dept".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "active".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "dept".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "active".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -96,7 +96,7 @@ fn having_filters_all() {
     let mut catalog = setup_catalog(filename);
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "t".into(),
-        columns: vec![aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false}],
+        columns: vec![aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false}],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     for i in 1..=3 {
@@ -118,9 +118,9 @@ fn having_sum_double() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "orders".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "user_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "total".into(), col_type: ColumnType::Double { precision: 10, scale: 2, unsigned: true }, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "user_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "total".into(), col_type: ColumnType::Double { precision: 10, scale: 2, unsigned: true }, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -145,9 +145,9 @@ fn having_ignores_alias() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "orders".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "user_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "total".into(), col_type: ColumnType::Double { precision: 10, scale: 2, unsigned: true }, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "user_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "total".into(), col_type: ColumnType::Double { precision: 10, scale: 2, unsigned: true }, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -164,3 +164,100 @@ fn having_ignores_alias() {
         assert_eq!(out, vec![vec!["2".to_string(), "20".to_string()]]);
     } else { panic!("expected select") }
 }
+
+#[test]
+fn having_correlated_exists() {
+    let filename = "test_having_exists.db";
+    let mut catalog = setup_catalog(filename);
+    for sql in [
+        "CREATE TABLE sales (id INTEGER, region TEXT, amount INTEGER)",
+        "CREATE TABLE targets (id INTEGER, name TEXT)",
+        "INSERT INTO sales VALUES (1, 'north', 50), (2, 'north', 60), (3, 'south', 40), (4, 'east', 90)",
+        "INSERT INTO targets VALUES (1, 'north'), (2, 'south')",
+    ] {
+        aerodb::execution::handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    }
+    let select = |catalog: &mut Catalog, sql: &str| {
+        let mut out = Vec::new();
+        execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut out, None).unwrap();
+        out.sort();
+        out
+    };
+    assert_eq!(
+        select(&mut catalog, "SELECT region, SUM(amount) FROM sales GROUP BY region HAVING EXISTS (SELECT id FROM targets WHERE targets.name = sales.region)"),
+        vec![vec!["north".to_string(), "110".to_string()], vec!["south".to_string(), "40".to_string()]]
+    );
+    assert_eq!(
+        select(&mut catalog, "SELECT region, SUM(amount) FROM sales GROUP BY region HAVING SUM(amount) > 50 AND EXISTS (SELECT id FROM targets WHERE targets.name = sales.region)"),
+        vec![vec!["north".to_string(), "110".to_string()]]
+    );
+}
+
+fn sales_catalog(filename: &str) -> Catalog {
+    let mut catalog = setup_catalog(filename);
+    for sql in [
+        "CREATE TABLE sales (id INTEGER, region TEXT, amount INTEGER)",
+        "INSERT INTO sales VALUES (1, 'north', 50), (2, 'north', 60), (3, 'south', 40), (4, 'south', 20), (5, 'east', 9)",
+    ] {
+        aerodb::execution::handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    }
+    catalog
+}
+
+fn select_sorted(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let mut out = Vec::new();
+    execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut out, None).unwrap();
+    out.sort();
+    out
+}
+
+#[test]
+fn having_matches_aggregates_however_written() {
+    let mut catalog = sales_catalog("test_having_spelling.db");
+    let rows = |r: &[&[&str]]| r.iter().map(|r| r.iter().map(|v| v.to_string()).collect()).collect::<Vec<Vec<String>>>();
+
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT region, COUNT(*) FROM sales GROUP BY region HAVING count( * ) >= 2"),
+        rows(&[&["north", "2"], &["south", "2"]])
+    );
+    // Compared as numbers: 60 is not above 100 even though "60" > "100".
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT region, SUM(amount) FROM sales GROUP BY region HAVING sum(amount) > 100"),
+        rows(&[&["north", "110"]])
+    );
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT region, SUM(amount) AS total FROM sales GROUP BY region HAVING total > 50"),
+        rows(&[&["north", "110"], &["south", "60"]])
+    );
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT region, SUM(amount) AS total FROM sales GROUP BY region HAVING SUM(amount) < 60"),
+        rows(&[&["east", "9"]])
+    );
+    // Aggregates missing from the select list are computed for HAVING.
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT region FROM sales GROUP BY region HAVING SUM(amount) > 100"),
+        rows(&[&["north"]])
+    );
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT region, COUNT(*) FROM sales GROUP BY region HAVING MAX(amount) < 50 AND MIN(sales.amount) > 5"),
+        rows(&[&["east", "1"], &["south", "2"]])
+    );
+}
+
+#[test]
+fn aggregates_without_group_by_form_one_group() {
+    let mut catalog = sales_catalog("test_having_implicit_group.db");
+    let rows = |r: &[&[&str]]| r.iter().map(|r| r.iter().map(|v| v.to_string()).collect()).collect::<Vec<Vec<String>>>();
+
+    assert_eq!(select_sorted(&mut catalog, "SELECT SUM(amount) FROM sales HAVING SUM(amount) > 100"), rows(&[&["179"]]));
+    assert_eq!(select_sorted(&mut catalog, "SELECT COUNT(*) FROM sales HAVING COUNT(*) >= 2"), rows(&[&["5"]]));
+    assert!(select_sorted(&mut catalog, "SELECT COUNT(*) FROM sales HAVING AVG(amount) > 100").is_empty());
+
+    // With every row filtered out there is still one group.
+    assert_eq!(select_sorted(&mut catalog, "SELECT COUNT(*) FROM sales WHERE amount > 1000"), rows(&[&["0"]]));
+    assert_eq!(
+        select_sorted(&mut catalog, "SELECT COUNT(*), SUM(amount), MAX(amount) FROM sales WHERE amount > 1000"),
+        rows(&[&["0", "NULL", "NULL"]])
+    );
+    assert!(select_sorted(&mut catalog, "SELECT COUNT(*) FROM sales WHERE amount > 1000 HAVING COUNT(*) >= 2").is_empty());
+}
@@ -0,0 +1,42 @@
+use aerodb::{engine::Engine, execution::runtime::QueryResult, storage::pager::Pager};
+use std::{collections::BTreeSet, fs};
+
+fn dir_entries() -> BTreeSet<String> {
+    fs::read_dir(".")
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+fn rows(engine: &mut Engine, sql: &str) -> Vec<Vec<String>> {
+    match engine.execute_str(sql).unwrap() {
+        QueryResult::Rows { rows, .. } => rows,
+        _ => panic!("expected rows from {}", sql),
+    }
+}
+
+#[test]
+fn in_memory_database_runs_without_touching_disk() {
+    let before = dir_entries();
+    let mut engine = Engine::with_pager(Pager::new_in_memory().unwrap()).unwrap();
+    engine.execute_str("CREATE TABLE kv (id INTEGER PRIMARY KEY, v TEXT)").unwrap();
+    engine.execute_str("CREATE INDEX idx_v ON kv (v)").unwrap();
+    for i in 1..=300 {
+        engine.execute_str(&format!("INSERT INTO kv VALUES ({}, 'value {}')", i, i)).unwrap();
+    }
+    assert_eq!(rows(&mut engine, "SELECT COUNT(*) FROM kv"), vec![vec!["300".to_string()]]);
+    assert_eq!(rows(&mut engine, "SELECT id FROM kv WHERE v = 'value 42'"), vec![vec!["42".to_string()]]);
+
+    engine.execute_str("BEGIN").unwrap();
+    engine.execute_str("DELETE FROM kv WHERE id > 100").unwrap();
+    engine.execute_str("ROLLBACK").unwrap();
+    assert_eq!(rows(&mut engine, "SELECT COUNT(*) FROM kv"), vec![vec!["300".to_string()]]);
+    assert_eq!(engine.catalog.integrity_check().unwrap(), None);
+
+    drop(engine);
+    assert_eq!(dir_entries(), before);
+
+    // Every in-memory pager starts empty.
+    let mut engine = Engine::with_pager(Pager::new_in_memory().unwrap()).unwrap();
+    assert!(engine.execute_str("SELECT * FROM kv").is_err());
+}
@@ -0,0 +1,69 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{execute_select_statement, execute_select_with_indexes, handle_statement},
+    sql::{ast::Expr, parser::parse_statement},
+    storage::{pager::Pager, row::ColumnValue},
+};
+use std::{collections::HashMap, fs};
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+/// Two distinct strings with the same index hash, found by a birthday search.
+fn colliding_strings() -> (String, String) {
+    let mut seen = HashMap::new();
+    for i in 0.. {
+        let s = format!("v{}", i);
+        let hash = Catalog::hash_value(&ColumnValue::Text(s.clone()));
+        if let Some(other) = seen.insert(hash, s.clone()) {
+            return (other, s);
+        }
+    }
+    unreachable!()
+}
+
+fn indexed_keys(catalog: &mut Catalog, value: &str) -> Vec<i64> {
+    let mut rows = Vec::new();
+    let selection = Expr::Equals { left: "tag".into(), right: value.into() };
+    assert!(execute_select_with_indexes(catalog, "items", Some(selection), &mut rows).unwrap());
+    rows.iter().map(|r| r.key).collect()
+}
+
+#[test]
+fn colliding_values_are_independently_queryable() {
+    let (a, b) = colliding_strings();
+    assert_ne!(a, b);
+    let mut catalog = setup_catalog("test_index_collisions.db");
+    run(&mut catalog, "CREATE TABLE items (id INTEGER, tag TEXT)");
+    run(&mut catalog, &format!("INSERT INTO items VALUES (1, '{}'), (2, '{}')", a, b));
+    // Built from existing rows, then extended through the insert path.
+    run(&mut catalog, "CREATE INDEX idx_tag ON items (tag)");
+    run(&mut catalog, &format!("INSERT INTO items VALUES (3, '{}'), (4, '{}')", b, a));
+
+    assert_eq!(indexed_keys(&mut catalog, &a), [1, 4]);
+    assert_eq!(indexed_keys(&mut catalog, &b), [2, 3]);
+    assert!(indexed_keys(&mut catalog, "absent").is_empty());
+
+    let mut dump = catalog.dump_index("idx_tag").unwrap();
+    dump.sort();
+    let mut expected = vec![(a.clone(), vec![1, 4]), (b.clone(), vec![2, 3])];
+    expected.sort();
+    assert_eq!(dump, expected);
+
+    let mut rows = Vec::new();
+    let stmt = parse_statement(&format!("SELECT id FROM items WHERE tag = '{}'", b)).unwrap();
+    execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap();
+    assert_eq!(rows, vec![vec!["2".to_string()], vec!["3".to_string()]]);
+
+    run(&mut catalog, "REINDEX INDEX idx_tag");
+    assert_eq!(indexed_keys(&mut catalog, &a), [1, 4]);
+    assert_eq!(indexed_keys(&mut catalog, &b), [2, 3]);
+}
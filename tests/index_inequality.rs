@@ -0,0 +1,46 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_select_with_indexes}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE tasks (id INTEGER, status TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_status ON tasks(status)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO tasks VALUES (1, 'done'), (2, 'open'), (3, 'done'), (4, 'done'), (5, NULL), (6, 'open')").unwrap()).unwrap();
+    // Leaves a stale `done` index entry for row 4.
+    handle_statement(&mut catalog, parse_statement("UPDATE tasks SET status = 'blocked' WHERE id = 4").unwrap()).unwrap();
+    catalog
+}
+
+/// Run `sql` through the index-aware scan, returning whether an index was
+/// consulted and the keys of the matching rows.
+fn scan(catalog: &mut Catalog, sql: &str) -> (bool, Vec<i64>) {
+    let Statement::Select { where_predicate, .. } = parse_statement(sql).unwrap() else {
+        panic!("expected select");
+    };
+    let mut rows = Vec::new();
+    let used = execute_select_with_indexes(catalog, "tasks", where_predicate, &mut rows).unwrap();
+    (used, rows.iter().map(|r| r.key).collect())
+}
+
+#[test]
+fn inequality_scans_without_the_index_by_default() {
+    let mut catalog = setup_catalog("test_index_ne_scan.db");
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status != 'done'"), (false, vec![2, 4, 6]));
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status <> 'open' AND id > 1"), (false, vec![3, 4]));
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status != 'missing'"), (false, vec![1, 2, 3, 4, 6]));
+}
+
+#[test]
+fn inequality_uses_index_exclusion_when_enabled() {
+    let mut catalog = setup_catalog("test_index_ne_exclusion.db");
+    catalog.set_index_exclusion(true);
+    // The stale entry for row 4 must not exclude it, and NULL stays unknown.
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status != 'done'"), (true, vec![2, 4, 6]));
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status <> 'open' AND id > 1"), (true, vec![3, 4]));
+    // No index entry for the value: nothing to exclude, plain scan.
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status != 'missing'"), (false, vec![1, 2, 3, 4, 6]));
+    // Equality still takes the index lookup path.
+    assert_eq!(scan(&mut catalog, "SELECT * FROM tasks WHERE status = 'done'"), (true, vec![1, 3]));
+}
@@ -0,0 +1,61 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::{Statement, TableRef}}, execution::runtime::{handle_statement, execute_select_with_indexes}};
+use aerodb::storage::row::Row;
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn select_rows(catalog: &mut Catalog, sql: &str) -> (bool, Vec<Row>) {
+    if let Statement::Select { from, where_predicate, .. } = parse_statement(sql).unwrap() {
+        let table_name = match from.first().unwrap() {
+            TableRef::Named { name, .. } => name.clone(),
+            _ => panic!("expected table"),
+        };
+        let mut rows = Vec::new();
+        let used = execute_select_with_indexes(catalog, &table_name, where_predicate, &mut rows).unwrap();
+        (used, rows)
+    } else {
+        panic!("expected select");
+    }
+}
+
+#[test]
+fn indexed_equality_with_residual_condition() {
+    let filename = "test_index_residual.db";
+    let mut catalog = setup_catalog(filename);
+
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT, score INT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON t (name)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, 'x', 3)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (2, 'x', 7)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (3, 'y', 9)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (4, 'x', 10)").unwrap()).unwrap();
+
+    let (used, rows) = select_rows(&mut catalog, "SELECT * FROM t WHERE name = 'x' AND score > 5");
+    assert!(used);
+    let mut keys: Vec<i64> = rows.iter().map(|r| r.key).collect();
+    keys.sort();
+    assert_eq!(keys, vec![2, 4]);
+
+    let (used, rows) = select_rows(&mut catalog, "SELECT * FROM t WHERE score > 5 AND name = 'x'");
+    assert!(used);
+    assert_eq!(rows.len(), 2);
+}
+
+#[test]
+fn disjunction_does_not_use_index() {
+    let filename = "test_index_residual_or.db";
+    let mut catalog = setup_catalog(filename);
+
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, name TEXT, score INT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON t (name)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, 'x', 3)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (2, 'y', 9)").unwrap()).unwrap();
+
+    let (used, rows) = select_rows(&mut catalog, "SELECT * FROM t WHERE name = 'x' OR score > 5");
+    assert!(!used);
+    assert_eq!(rows.len(), 2);
+}
@@ -0,0 +1,49 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn query(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn select_all_table_names() {
+    let filename = "test_information_schema_tables.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE orders (id INT, user_id INT, total INT)").unwrap()).unwrap();
+
+    let rows = query(&mut catalog, "SELECT table_name FROM information_schema.tables");
+    assert_eq!(rows, vec![vec!["orders".to_string()], vec!["users".to_string()]]);
+
+    let rows = query(&mut catalog, "SELECT table_name, column_count FROM information_schema.tables WHERE table_name = 'orders'");
+    assert_eq!(rows, vec![vec!["orders".to_string(), "3".to_string()]]);
+}
+
+#[test]
+fn select_columns_of_table() {
+    let filename = "test_information_schema_columns.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INT PRIMARY KEY, name TEXT NOT NULL, age INT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE other (id INT)").unwrap()).unwrap();
+
+    let rows = query(
+        &mut catalog,
+        "SELECT column_name, data_type, is_nullable, is_primary_key FROM information_schema.columns WHERE table_name = 'users'",
+    );
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0], vec!["id", "INTEGER", "YES", "true"]);
+    assert_eq!(rows[1], vec!["name", "TEXT", "NO", "false"]);
+    assert_eq!(rows[2], vec!["age", "INTEGER", "YES", "false"]);
+
+    // The statement path used by the REPL accepts the virtual table too.
+    handle_statement(&mut catalog, parse_statement("SELECT * FROM information_schema.columns").unwrap()).unwrap();
+}
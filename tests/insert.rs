@@ -4,6 +4,7 @@ use aerodb::{
     sql::{parser::parse_statement, ast::Statement},
     execution::runtime::{handle_statement, execute_select_with_indexes},
     engine::Engine,
+    error::DbError,
 };
 use std::fs;
 
@@ -57,3 +58,39 @@ fn multi_value_insert_column_list() {
     execute_select_with_indexes(&mut catalog, "nums", None, &mut rows).unwrap();
     assert_eq!(rows.len(), 2);
 }
+
+#[test]
+fn multi_value_insert_error_names_the_row() {
+    let filename = "multi_insert_row_error.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE people (id INTEGER, name TEXT NOT NULL)").unwrap()).unwrap();
+    let res = engine.execute(parse_statement(
+        "INSERT INTO people VALUES (1, 'a'), (2, 'b'), (3, DEFAULT), (4, 'd'), (5, 'e')",
+    ).unwrap());
+    match res {
+        Err(DbError::InvalidValue(msg)) => assert!(msg.starts_with("row 3: ") && msg.contains("'name'"), "{}", msg),
+        other => panic!("expected InvalidValue, got {:?}", other),
+    }
+    let mut rows = Vec::new();
+    execute_select_with_indexes(&mut engine.catalog, "people", None, &mut rows).unwrap();
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn multi_value_insert_omitted_not_null_column() {
+    let filename = "multi_insert_omitted.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE people (id INTEGER, name TEXT NOT NULL)").unwrap()).unwrap();
+    let res = engine.execute(parse_statement("INSERT INTO people (id) VALUES (1), (2)").unwrap());
+    match res {
+        Err(DbError::InvalidValue(msg)) => assert_eq!(msg, "row 1: column 'name' requires a value"),
+        other => panic!("expected InvalidValue, got {:?}", other),
+    }
+    let mut rows = Vec::new();
+    execute_select_with_indexes(&mut engine.catalog, "people", None, &mut rows).unwrap();
+    assert!(rows.is_empty());
+}
@@ -0,0 +1,84 @@
+use aerodb::{catalog::Catalog, storage::{btree::BTree, pager::Pager, page::PAGE_SIZE}, sql::parser::parse_statement, execution::runtime::handle_statement};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+fn cleanup(filename: &str) {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+}
+
+/// Create `t` with an index and enough rows to split the table tree.
+/// Returns the table's root page.
+fn build(filename: &str, rows: usize) -> u32 {
+    cleanup(filename);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INTEGER, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON t(name)").unwrap()).unwrap();
+    for i in 1..=rows {
+        let sql = format!("INSERT INTO t VALUES ({}, 'name-{:0>40}')", i, i);
+        handle_statement(&mut catalog, parse_statement(&sql).unwrap()).unwrap();
+    }
+    catalog.get_table("t").unwrap().root_page
+}
+
+/// Overwrite bytes in the database file. The WAL is removed first so that
+/// recovery does not replay the original page image over the patch.
+fn patch(filename: &str, offset: u64, bytes: &[u8]) {
+    fs::remove_file(format!("{}.wal", filename)).unwrap();
+    let mut file = OpenOptions::new().read(true).write(true).open(filename).unwrap();
+    file.seek(SeekFrom::Start(offset)).unwrap();
+    file.write_all(bytes).unwrap();
+}
+
+fn read_u32(filename: &str, offset: u64) -> u32 {
+    let mut file = fs::File::open(filename).unwrap();
+    file.seek(SeekFrom::Start(offset)).unwrap();
+    let mut buf = [0u8; 4];
+    file.read_exact(&mut buf).unwrap();
+    u32::from_le_bytes(buf)
+}
+
+#[test]
+fn healthy_tree_passes() {
+    let filename = "test_integrity_ok.db";
+    let root = build(filename, 300);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+    handle_statement(&mut catalog, parse_statement("DELETE FROM t WHERE id > 100").unwrap()).unwrap();
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+    handle_statement(&mut catalog, parse_statement("PRAGMA integrity_check").unwrap()).unwrap();
+    drop(catalog);
+
+    let mut pager = Pager::new(filename).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    tree.verify().unwrap();
+}
+
+#[test]
+fn wrong_cell_count_is_reported() {
+    let filename = "test_integrity_cells.db";
+    let root = build(filename, 3);
+    // A single-leaf tree: claim one cell fewer than the page holds.
+    patch(filename, root as u64 * PAGE_SIZE as u64 + 6, &2u16.to_le_bytes());
+
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    let violation = catalog.integrity_check().unwrap().expect("corruption not detected");
+    assert!(violation.starts_with("table 't'"), "{}", violation);
+    assert!(violation.contains("cell_count 2"), "{}", violation);
+}
+
+#[test]
+fn wrong_parent_pointer_is_reported() {
+    let filename = "test_integrity_parent.db";
+    let root = build(filename, 300);
+    let root_offset = root as u64 * PAGE_SIZE as u64;
+    // The leftmost child pointer follows the 12-byte header of an internal node.
+    assert_eq!(read_u32(filename, root_offset) & 0xFF, 0, "root should be internal");
+    let child = read_u32(filename, root_offset + 12);
+    patch(filename, child as u64 * PAGE_SIZE as u64 + 2, &9999u32.to_le_bytes());
+
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    let violation = catalog.integrity_check().unwrap().expect("corruption not detected");
+    assert!(violation.contains(&format!("page {}: parent pointer", child)), "{}", violation);
+}
@@ -0,0 +1,44 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::{Expr, Statement}}, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r[0].clone()).collect()
+}
+
+#[test]
+fn parse_is_not_true() {
+    let stmt = parse_statement("SELECT id FROM t WHERE active IS NOT TRUE").unwrap();
+    if let Statement::Select { where_predicate: Some(Expr::IsTruth { expr, truth, negated }), .. } = stmt {
+        assert_eq!(expr, "active");
+        assert_eq!(truth, Some(true));
+        assert!(negated);
+    } else {
+        panic!("expected IS NOT TRUE predicate");
+    }
+    assert!(parse_statement("SELECT id FROM t WHERE active IS MAYBE").is_err());
+}
+
+#[test]
+fn is_truth_forms_over_boolean_column() {
+    let filename = "test_is_truth.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INT, active BOOLEAN)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, true), (2, false), (3, NULL)").unwrap()).unwrap();
+
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS TRUE"), vec!["1"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS FALSE"), vec!["2"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS UNKNOWN"), vec!["3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS NOT TRUE"), vec!["2", "3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS NOT FALSE"), vec!["1", "3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS NOT UNKNOWN"), vec!["1", "2"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE active IS NOT TRUE AND id > 2"), vec!["3"]);
+}
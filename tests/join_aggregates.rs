@@ -0,0 +1,101 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{QueryResult, handle_statement},
+    sql::parser::parse_statement,
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut catalog, "CREATE TABLE orders (id INTEGER, user_id INTEGER, total INTEGER)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'ann'), (2, 'bo'), (3, 'cy')");
+    run(&mut catalog, "INSERT INTO orders VALUES (10, 1, 5), (11, 1, 7), (12, 2, 3), (13, 9, 100)");
+    catalog
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn rows(catalog: &mut Catalog, sql: &str) -> (Vec<String>, Vec<Vec<String>>) {
+    match run(catalog, sql) {
+        QueryResult::Rows { header, mut rows } => {
+            rows.sort();
+            (header.into_iter().map(|(n, _)| n).collect(), rows)
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn count_over_a_join_counts_joined_rows() {
+    let mut catalog = setup_catalog("test_join_agg_count.db");
+    let (header, got) = rows(&mut catalog, "SELECT COUNT(*) FROM users u JOIN orders o ON u.id = o.user_id");
+    assert_eq!(header, vec!["COUNT(*)"]);
+    assert_eq!(got, vec![vec!["3".to_string()]]);
+
+    let (_, got) = rows(&mut catalog, "SELECT COUNT(*) FROM users u LEFT JOIN orders o ON u.id = o.user_id");
+    assert_eq!(got, vec![vec!["4".to_string()]]);
+
+    let (_, got) = rows(
+        &mut catalog,
+        "SELECT MIN(o.total), MAX(o.total), SUM(o.total) FROM users u JOIN orders o ON u.id = o.user_id WHERE o.total > 4",
+    );
+    assert_eq!(got, vec![vec!["5".to_string(), "7".to_string(), "12".to_string()]]);
+}
+
+#[test]
+fn sum_of_right_table_grouped_by_left_column() {
+    let mut catalog = setup_catalog("test_join_agg_group.db");
+    let (header, got) = rows(
+        &mut catalog,
+        "SELECT u.name, SUM(o.total) AS spent, COUNT(*) FROM users u JOIN orders o ON u.id = o.user_id GROUP BY u.name",
+    );
+    assert_eq!(header, vec!["u.name", "spent", "COUNT(*)"]);
+    assert_eq!(
+        got,
+        vec![
+            vec!["ann".to_string(), "12".to_string(), "2".to_string()],
+            vec!["bo".to_string(), "3".to_string(), "1".to_string()],
+        ]
+    );
+
+    // Unambiguous bare names resolve against the joined columns.
+    let (_, got) = rows(
+        &mut catalog,
+        "SELECT name, AVG(total), COUNT(*) FROM users u JOIN orders o ON u.id = o.user_id GROUP BY name HAVING COUNT(*) > 1",
+    );
+    assert_eq!(got, vec![vec!["ann".to_string(), "6".to_string(), "2".to_string()]]);
+}
+
+fn ordered_rows(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    match run(catalog, sql) {
+        QueryResult::Rows { rows, .. } => rows,
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn order_by_sorts_joined_rows() {
+    let mut catalog = setup_catalog("test_join_order_by.db");
+    let join = "SELECT u.name, o.total FROM users u JOIN orders o ON u.id = o.user_id";
+    assert_eq!(ordered_rows(&mut catalog, &format!("{join} ORDER BY o.total DESC")), [["ann", "7"], ["ann", "5"], ["bo", "3"]]);
+    assert_eq!(ordered_rows(&mut catalog, &format!("{join} ORDER BY total")), [["bo", "3"], ["ann", "5"], ["ann", "7"]]);
+    // The key need not be selected, and LIMIT applies after sorting.
+    let sql = "SELECT u.name FROM users u JOIN orders o ON u.id = o.user_id ORDER BY o.id DESC LIMIT 1";
+    assert_eq!(ordered_rows(&mut catalog, sql), [["bo"]]);
+    assert!(handle_statement(&mut catalog, parse_statement(&format!("{join} ORDER BY id")).unwrap()).is_err());
+}
+
+#[test]
+fn order_by_sorts_grouped_joins() {
+    let mut catalog = setup_catalog("test_join_group_order_by.db");
+    let grouped = "SELECT u.name, COUNT(*) AS n FROM users u JOIN orders o ON u.id = o.user_id GROUP BY u.name";
+    assert_eq!(ordered_rows(&mut catalog, &format!("{grouped} ORDER BY n DESC")), [["ann", "2"], ["bo", "1"]]);
+    assert_eq!(ordered_rows(&mut catalog, &format!("{grouped} ORDER BY n")), [["bo", "1"], ["ann", "2"]]);
+    assert_eq!(ordered_rows(&mut catalog, &format!("{grouped} ORDER BY u.name DESC LIMIT 1")), [["bo", "1"]]);
+}
@@ -0,0 +1,76 @@
+use aerodb::{engine::Engine, error::DbError, sql::parser::parse_statement};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+#[test]
+fn rows_become_objects_with_typed_values() {
+    let filename = "test_json_export_typed.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE flags (id INTEGER, enabled BOOLEAN, label TEXT, weight DOUBLE)");
+    run(&mut engine, "INSERT INTO flags VALUES (1, true, 'dark \"mode\"', 1.5), (2, false, NULL, NULL)");
+
+    assert_eq!(
+        engine.query_to_json("SELECT * FROM flags").unwrap(),
+        concat!(
+            r#"[{"id":1,"enabled":true,"label":"dark \"mode\"","weight":1.5},"#,
+            r#"{"id":2,"enabled":false,"label":null,"weight":null}]"#
+        )
+    );
+    assert_eq!(
+        engine.query_to_json("SELECT enabled AS on_flag, id FROM flags WHERE id = 2").unwrap(),
+        r#"[{"on_flag":false,"id":2}]"#
+    );
+    assert_eq!(engine.query_to_json("SELECT COUNT(*) FROM flags").unwrap(), r#"[{"COUNT(*)":2}]"#);
+}
+
+#[test]
+fn empty_results_and_non_queries() {
+    let filename = "test_json_export_empty.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT)");
+    run(&mut engine, "INSERT INTO t VALUES (1, 'line\nbreak')");
+
+    assert_eq!(engine.query_to_json("SELECT id FROM t WHERE id = 99").unwrap(), "[]");
+    assert_eq!(engine.query_to_json("SELECT v FROM t").unwrap(), r#"[{"v":"line\nbreak"}]"#);
+    assert!(matches!(
+        engine.query_to_json("DELETE FROM t WHERE id = 1"),
+        Err(DbError::InvalidValue(_))
+    ));
+    assert_eq!(engine.query_to_json("SELECT id FROM t").unwrap(), r#"[{"id":1}]"#);
+}
+
+#[test]
+fn text_spelled_null_stays_a_string() {
+    let filename = "test_json_export_null_text.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT, n INTEGER)");
+    run(&mut engine, "INSERT INTO t VALUES (1, 'NULL', NULL), (2, NULL, 5)");
+
+    assert_eq!(
+        engine.query_to_json("SELECT * FROM t").unwrap(),
+        r#"[{"id":1,"v":"NULL","n":null},{"id":2,"v":null,"n":5}]"#
+    );
+    assert_eq!(
+        engine.query_to_json("SELECT id, MAX(v) FROM t GROUP BY id ORDER BY id").unwrap(),
+        r#"[{"id":1,"MAX(v)":"NULL"},{"id":2,"MAX(v)":null}]"#
+    );
+    assert_eq!(
+        engine.query_to_json("SELECT n + 1 AS m FROM t WHERE id = 1").unwrap(),
+        r#"[{"m":null}]"#
+    );
+    // Results outside an export still show NULL as before.
+    match engine.execute(parse_statement("SELECT v FROM t WHERE id = 2").unwrap()).unwrap() {
+        aerodb::execution::QueryResult::Rows { rows, .. } => assert_eq!(rows, [["NULL"]]),
+        other => panic!("expected rows, got {:?}", other),
+    }
+}
@@ -0,0 +1,43 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE nums (id INTEGER)").unwrap()).unwrap();
+    for i in 1..=8 {
+        handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO nums VALUES ({})", i)).unwrap()).unwrap();
+    }
+    catalog
+}
+
+fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r[0].clone()).collect()
+}
+
+#[test]
+fn parse_limit_all() {
+    match parse_statement("SELECT * FROM nums LIMIT ALL OFFSET 5").unwrap() {
+        Statement::Select { limit, offset, .. } => {
+            assert_eq!(limit, None);
+            assert_eq!(offset, Some(5));
+        }
+        _ => panic!("Expected select"),
+    }
+    let err = parse_statement("SELECT * FROM nums LIMIT -1").unwrap_err();
+    assert!(err.contains("negative"), "{}", err);
+    assert!(parse_statement("SELECT * FROM nums LIMIT 2 OFFSET -3").is_err());
+}
+
+#[test]
+fn limit_all_returns_every_row() {
+    let mut catalog = setup_catalog("test_limit_all.db");
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums LIMIT ALL").len(), 8);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums LIMIT ALL OFFSET 5"), vec!["6", "7", "8"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums LIMIT 2 OFFSET 5"), vec!["6", "7"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums WHERE id > 2 LIMIT 3"), vec!["3", "4", "5"]);
+}
@@ -0,0 +1,98 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{QueryResult, handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE nums (id INTEGER, parity TEXT)").unwrap()).unwrap();
+    for i in 1..=6 {
+        let parity = if i % 2 == 0 { "even" } else { "odd" };
+        handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO nums VALUES ({}, '{}')", i, parity)).unwrap()).unwrap();
+    }
+    catalog
+}
+
+fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r[0].clone()).collect()
+}
+
+fn limit_offset(sql: &str) -> (Option<usize>, Option<usize>) {
+    match parse_statement(sql).unwrap() {
+        Statement::Select { limit, offset, .. } => (limit, offset),
+        _ => panic!("Expected select"),
+    }
+}
+
+#[test]
+fn parse_fetch_first_as_limit() {
+    assert_eq!(limit_offset("SELECT id FROM nums FETCH FIRST 3 ROWS ONLY"), (Some(3), None));
+    assert_eq!(limit_offset("SELECT id FROM nums FETCH NEXT 1 ROW ONLY;"), (Some(1), None));
+    assert_eq!(limit_offset("SELECT id FROM nums fetch first row only"), (Some(1), None));
+    assert_eq!(limit_offset("SELECT id FROM nums OFFSET 2 ROWS FETCH NEXT 2 ROWS ONLY"), (Some(2), Some(2)));
+    assert_eq!(limit_offset("SELECT id FROM nums WHERE id > 1 ORDER BY id FETCH FIRST 0 ROWS ONLY"), (Some(0), None));
+    assert_eq!(limit_offset("SELECT parity FROM nums GROUP BY parity LIMIT 1"), (Some(1), None));
+}
+
+#[test]
+fn parse_rejects_bad_counts() {
+    for sql in [
+        "SELECT id FROM nums LIMIT -1",
+        "SELECT id FROM nums OFFSET -2",
+        "SELECT id FROM nums FETCH FIRST -3 ROWS ONLY",
+    ] {
+        let err = parse_statement(sql).unwrap_err();
+        assert!(err.contains("negative"), "{}: {}", sql, err);
+    }
+    assert!(parse_statement("SELECT id FROM nums LIMIT two").is_err());
+    assert!(parse_statement("SELECT id FROM nums LIMIT 2 FETCH FIRST 3 ROWS ONLY").is_err());
+    assert!(parse_statement("SELECT id FROM nums FETCH FIRST 3 ROWS").is_err());
+    assert!(parse_statement("SELECT id FROM nums FETCH 3 ROWS ONLY").is_err());
+    assert!(parse_statement("SELECT id FROM nums LIMIT 2 3").is_err());
+}
+
+#[test]
+fn limit_and_offset_boundaries() {
+    let mut catalog = setup_catalog("test_limit_offset_bounds.db");
+    assert!(ids(&mut catalog, "SELECT id FROM nums LIMIT 0").is_empty());
+    assert!(ids(&mut catalog, "SELECT id FROM nums ORDER BY id DESC LIMIT 0").is_empty());
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums LIMIT 100").len(), 6);
+    assert!(ids(&mut catalog, "SELECT id FROM nums OFFSET 6").is_empty());
+    assert!(ids(&mut catalog, "SELECT id FROM nums LIMIT 3 OFFSET 50").is_empty());
+    assert!(ids(&mut catalog, "SELECT id FROM nums ORDER BY id OFFSET 50").is_empty());
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums OFFSET 5"), vec!["6"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums WHERE id > 2 OFFSET 1 LIMIT 100"), vec!["4", "5", "6"]);
+}
+
+#[test]
+fn fetch_first_limits_like_limit() {
+    let mut catalog = setup_catalog("test_limit_offset_fetch.db");
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums FETCH FIRST 2 ROWS ONLY"), vec!["1", "2"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums ORDER BY id DESC OFFSET 1 ROW FETCH NEXT 2 ROWS ONLY"), vec!["5", "4"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM nums WHERE parity = 'odd' FETCH FIRST ROW ONLY"), vec!["1"]);
+    assert!(ids(&mut catalog, "SELECT id FROM nums FETCH FIRST 0 ROWS ONLY").is_empty());
+    assert_eq!(ids(&mut catalog, "SELECT parity FROM nums GROUP BY parity ORDER BY parity FETCH FIRST 1 ROW ONLY"), vec!["even"]);
+}
+
+#[test]
+fn limit_and_offset_apply_to_joins() {
+    let mut catalog = setup_catalog("test_limit_offset_join.db");
+    let mut run = |sql: &str| handle_statement(&mut catalog, parse_statement(sql).unwrap()).unwrap();
+    run("CREATE TABLE e (id INTEGER, dept TEXT)");
+    run("CREATE TABLE o (id INTEGER, eid INTEGER, amt INTEGER)");
+    run("INSERT INTO e VALUES (1, 'a'), (2, 'b')");
+    run("INSERT INTO o VALUES (10, 1, 5), (11, 1, 7), (12, 2, 3), (13, 2, 9)");
+    let mut count = |sql: &str| match run(sql) {
+        QueryResult::Rows { rows, .. } => rows.len(),
+        other => panic!("unexpected result {:?}", other),
+    };
+    assert_eq!(count("SELECT e.id, o.amt FROM e JOIN o ON e.id = o.eid"), 4);
+    assert_eq!(count("SELECT e.id, o.amt FROM e JOIN o ON e.id = o.eid LIMIT 1"), 1);
+    assert_eq!(count("SELECT e.id, o.amt FROM e JOIN o ON e.id = o.eid LIMIT 2 OFFSET 3"), 1);
+    assert_eq!(count("SELECT e.id, o.amt FROM e JOIN o ON e.id = o.eid OFFSET 1 ROW FETCH FIRST 2 ROWS ONLY"), 2);
+    assert_eq!(count("SELECT e.dept, COUNT(*) FROM e JOIN o ON e.id = o.eid GROUP BY e.dept LIMIT 1"), 1);
+    assert_eq!(count("SELECT COUNT(*) FROM e JOIN o ON e.id = o.eid OFFSET 1"), 0);
+}
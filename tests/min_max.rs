@@ -0,0 +1,83 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{QueryResult, format_header, handle_statement},
+    sql::parser::parse_statement,
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "CREATE TABLE hires (id INTEGER, dept TEXT, name TEXT, hired DATE, rating DOUBLE)");
+    run(
+        &mut catalog,
+        "INSERT INTO hires VALUES \
+         (1, 'eng', 'carol', '2021-03-09', 4.5), \
+         (2, 'eng', 'alice', '2019-11-30', 3.25), \
+         (3, 'eng', 'bob', '2020-01-15', NULL), \
+         (4, 'ops', 'dave', '2018-07-01', 10.0), \
+         (5, 'ops', 'Zed', '2022-02-02', 9.75)",
+    );
+    catalog
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn rows(catalog: &mut Catalog, sql: &str) -> (String, Vec<Vec<String>>) {
+    match run(catalog, sql) {
+        QueryResult::Rows { header, mut rows } => {
+            rows.sort();
+            (format_header(&header), rows)
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+fn row(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn max_name_and_min_date_within_groups() {
+    let mut catalog = setup_catalog("test_min_max_groups.db");
+    let (header, got) = rows(
+        &mut catalog,
+        "SELECT dept, MAX(name), MIN(hired) FROM hires GROUP BY dept",
+    );
+    assert_eq!(header, "dept TEXT | MAX(name) TEXT | MIN(hired) DATE");
+    // Text compares byte-wise, so 'Zed' sorts before 'dave'.
+    assert_eq!(
+        got,
+        vec![row(&["eng", "carol", "2019-11-30"]), row(&["ops", "dave", "2018-07-01"])]
+    );
+}
+
+#[test]
+fn doubles_compare_numerically_and_nulls_are_skipped() {
+    let mut catalog = setup_catalog("test_min_max_doubles.db");
+    let (_, got) = rows(&mut catalog, "SELECT MIN(rating), MAX(rating), MAX(hired) FROM hires");
+    assert_eq!(got, vec![row(&["3.25", "10", "2022-02-02"])]);
+
+    let (_, got) = rows(&mut catalog, "SELECT MIN(rating) FROM hires WHERE id = 3");
+    assert_eq!(got, vec![row(&["NULL"])]);
+}
+
+#[test]
+fn join_aggregates_keep_the_column_type() {
+    let mut catalog = setup_catalog("test_min_max_join.db");
+    run(&mut catalog, "CREATE TABLE depts (floor INTEGER, code TEXT)");
+    run(&mut catalog, "INSERT INTO depts VALUES (3, 'eng'), (1, 'ops')");
+    let (header, got) = rows(
+        &mut catalog,
+        "SELECT d.floor, MIN(h.name), MAX(h.hired) FROM depts d JOIN hires h ON d.code = h.dept GROUP BY d.floor",
+    );
+    assert_eq!(header, "d.floor INTEGER | MIN(h.name) TEXT | MAX(h.hired) DATE");
+    assert_eq!(
+        got,
+        vec![row(&["1", "Zed", "2022-02-02"]), row(&["3", "alice", "2021-03-09"])]
+    );
+}
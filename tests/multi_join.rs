@@ -16,17 +16,17 @@ fn join_two_tables() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "a".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "v".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "v".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "b".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "w".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "w".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -46,7 +46,7 @@ fn join_two_tables() {
     let stmt = parse_statement("SELECT a.v, b.w FROM a JOIN b ON a.id = b.a_id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 3);
@@ -61,26 +61,26 @@ fn join_three_tables() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "a".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "v".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "v".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "b".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "w".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "w".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "c".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "b_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "x".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "b_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "x".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -93,7 +93,7 @@ fn join_three_tables() {
     let stmt = parse_statement("SELECT a.v, b.w, c.x FROM a JOIN b ON a.id = b.a_id JOIN c ON b.id = c.b_id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 2);
@@ -108,17 +108,17 @@ fn join_with_where() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "a".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "v".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "v".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "b".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "w".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "w".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -129,7 +129,7 @@ fn join_with_where() {
     let stmt = parse_statement("SELECT a.v, b.w FROM a JOIN b ON a.id = b.a_id WHERE a.v = av1").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 1);
@@ -144,15 +144,15 @@ fn left_join_preserves_left_rows() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "a".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "b".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -163,7 +163,7 @@ fn left_join_preserves_left_rows() {
     let stmt = parse_statement("SELECT a.id, b.id FROM a LEFT JOIN b ON a.id = b.a_id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 2);
@@ -179,15 +179,15 @@ fn right_join_preserves_right_rows() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "a".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "b".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "a_id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -198,7 +198,7 @@ fn right_join_preserves_right_rows() {
     let stmt = parse_statement("SELECT a.id, b.id FROM a RIGHT JOIN b ON a.id = b.a_id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 2);
@@ -214,14 +214,14 @@ fn cross_join_and_non_equality_predicate() {
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "a".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
     aerodb::execution::handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "b".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -233,7 +233,7 @@ fn cross_join_and_non_equality_predicate() {
     let stmt = parse_statement("SELECT a.id, b.id FROM a CROSS JOIN b").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 4);
@@ -242,9 +242,83 @@ fn cross_join_and_non_equality_predicate() {
     let stmt = parse_statement("SELECT a.id, b.id FROM a JOIN b ON a.id >= b.id").unwrap();
     if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
         let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
-        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, joins, projections: columns, where_predicate };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
         let mut results = Vec::new();
         execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
         assert_eq!(results.len(), 0);
     } else { panic!("expected select") }
 }
+
+#[test]
+fn join_on_two_columns() {
+    let filename = "test_join_composite.db";
+    let mut catalog = setup_catalog(filename);
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE a (id INTEGER, x INTEGER, y INTEGER)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE b (id INTEGER, x INTEGER, y INTEGER, w TEXT)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO a VALUES (1, 1, 1), (2, 1, 2), (3, 2, 1)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO b VALUES (1, 1, 1, 'both'), (2, 1, 3, 'x only'), (3, 3, 1, 'y only'), (4, 2, 1, 'both')").unwrap()).unwrap();
+
+    let stmt = parse_statement("SELECT a.id, b.w FROM a JOIN b ON a.x = b.x AND a.y = b.y").unwrap();
+    if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
+        let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
+        let mut results = Vec::new();
+        execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
+        assert_eq!(results, vec![
+            vec![String::from("1"), String::from("both")],
+            vec![String::from("3"), String::from("both")],
+        ]);
+    } else { panic!("expected select") }
+}
+
+#[test]
+fn left_join_on_parenthesized_composite_condition() {
+    let filename = "test_join_composite_paren.db";
+    let mut catalog = setup_catalog(filename);
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE a (id INTEGER, x INTEGER, y INTEGER)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE b (id INTEGER, x INTEGER, y INTEGER, w TEXT)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO a VALUES (1, 1, 1), (2, 1, 2)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO b VALUES (1, 1, 1, 'both'), (2, 1, 3, 'x only')").unwrap()).unwrap();
+
+    let stmt = parse_statement("SELECT a.id, b.w FROM a LEFT JOIN b ON (a.x = b.x AND a.y = b.y)").unwrap();
+    if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
+        let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
+        let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
+        let mut results = Vec::new();
+        execute_multi_join(&plan, &mut catalog, &mut results).unwrap();
+        assert_eq!(results, vec![
+            vec![String::from("1"), String::from("both")],
+            vec![String::from("2"), String::from("NULL")],
+        ]);
+    } else { panic!("expected select") }
+
+    assert!(parse_statement("SELECT a.id FROM a JOIN b ON (a.x = b.x AND a.y = b.y").is_err());
+}
+
+#[test]
+fn join_where_compares_by_column_type() {
+    let filename = "test_join_where_typed.db";
+    let mut catalog = setup_catalog(filename);
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE a (id INTEGER, name TEXT, code TEXT)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("CREATE TABLE b (id INTEGER, a_id INTEGER, price INTEGER)").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO a VALUES (1, 'x', '9'), (2, 'y', '10')").unwrap()).unwrap();
+    aerodb::execution::handle_statement(&mut catalog, parse_statement("INSERT INTO b VALUES (1, 1, 20), (2, 1, 100), (3, 1, 150), (4, 2, 1000)").unwrap()).unwrap();
+
+    let run = |catalog: &mut Catalog, sql: &str| {
+        let stmt = parse_statement(sql).unwrap();
+        if let Statement::Select { columns, from, joins, where_predicate, .. } = stmt {
+            let base_table = match from.first().unwrap() { aerodb::sql::ast::TableRef::Named { name, .. } => name.clone(), _ => panic!("expected table") };
+            let plan = aerodb::execution::plan::MultiJoinPlan { base_table, base_alias: None, base_subquery: None, joins, projections: columns, where_predicate };
+            let mut results = Vec::new();
+            execute_multi_join(&plan, catalog, &mut results).unwrap();
+            results.into_iter().map(|r| r.join(",")).collect::<Vec<_>>()
+        } else { panic!("expected select") }
+    };
+
+    // Compared as text, '20' would sort after '100'.
+    assert_eq!(run(&mut catalog, "SELECT b.id, b.price FROM a JOIN b ON a.id = b.a_id WHERE b.price > 100"), ["3,150", "4,1000"]);
+    assert_eq!(run(&mut catalog, "SELECT b.id FROM a JOIN b ON a.id = b.a_id WHERE a.name = 'x'"), ["1", "2", "3"]);
+    assert_eq!(run(&mut catalog, "SELECT b.id FROM a JOIN b ON a.id = b.a_id WHERE a.name = 'x' AND b.price <= 100"), ["1", "2"]);
+    // A TEXT column compares as text even when its values look numeric.
+    assert_eq!(run(&mut catalog, "SELECT b.id FROM a JOIN b ON a.id = b.a_id WHERE a.code > '5'"), ["1", "2", "3"]);
+}
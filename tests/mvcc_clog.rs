@@ -56,7 +56,7 @@ fn committed_delete_is_vacuumable_after_reopen() {
         "committed tombstone must remain reclaimable across reopen (clog persists Committed)"
     );
 
-    let keys: Vec<i32> = select_all(&mut engine, "t").iter().map(|r| r.key).collect();
+    let keys: Vec<i64> = select_all(&mut engine, "t").iter().map(|r| r.key).collect();
     assert_eq!(keys, vec![1, 3], "only live rows survive");
 }
 
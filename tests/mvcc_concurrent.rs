@@ -172,7 +172,7 @@ fn concurrent_threads_never_lose_or_corrupt_committed_state() {
                 let sql = format!("UPDATE t SET v = 't{thread_idx}i{iter}' WHERE id = {key}");
                 let tx = db.begin().unwrap();
                 match db.execute(&tx, stmt(&sql)) {
-                    Ok(()) => match db.commit(tx) {
+                    Ok(_) => match db.commit(tx) {
                         Ok(()) => {}
                         Err(_) => {} // lost a first-committer-wins race; fine
                     },
@@ -192,11 +192,11 @@ fn concurrent_threads_never_lose_or_corrupt_committed_state() {
     let rows = db.query_all(&reader, "t").unwrap();
     db.abort(reader).unwrap();
 
-    let mut keys: Vec<i32> = rows.iter().map(|r| r.key).collect();
+    let mut keys: Vec<i64> = rows.iter().map(|r| r.key).collect();
     keys.sort_unstable();
     assert_eq!(
         keys,
-        (0..KEYS).collect::<Vec<_>>(),
+        (0..KEYS as i64).collect::<Vec<_>>(),
         "every key must remain visible exactly once (no lost or torn writes)"
     );
 
@@ -81,7 +81,7 @@ fn aborted_work_stays_invisible_across_reopen() {
     }
 
     let mut engine = reopen(filename);
-    let keys: Vec<i32> = select_all(&mut engine, "t").iter().map(|r| r.key).collect();
+    let keys: Vec<i64> = select_all(&mut engine, "t").iter().map(|r| r.key).collect();
     assert_eq!(
         keys,
         vec![1, 3],
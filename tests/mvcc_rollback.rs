@@ -8,12 +8,12 @@
 //! DML rollback is now *logical*: aborted row versions are left in place and
 //! hidden by MVCC visibility (see `mvcc_logical_abort.rs`), not physically
 //! reverted. The observable outcome asserted here is unchanged. DDL rollback
-//! still reverts the non-versioned schema catalog pages plus the in-memory
-//! index snapshot.
+//! still reverts the non-versioned schema catalog pages, which also record
+//! each table's indexes.
 
 use aerodb::{
-    engine::Engine, execution::runtime::execute_select_with_indexes, sql::parser::parse_statement,
-    storage::row::ColumnValue, storage::row::Row,
+    engine::Engine, execution::runtime::execute_select_with_indexes, sql::ast::Expr,
+    sql::parser::parse_statement, storage::row::ColumnValue, storage::row::Row,
 };
 use std::fs;
 
@@ -136,3 +136,36 @@ fn drop_table_rollback_restores_table() {
         "rolled-back DROP TABLE must restore the table"
     );
 }
+
+#[test]
+fn insert_rollback_keeps_index_lookups_consistent() {
+    let mut engine = setup_engine("mvcc_rollback_index_insert.db");
+    exec(&mut engine, "CREATE TABLE t (id INTEGER PRIMARY KEY, v TEXT)");
+    exec(&mut engine, "CREATE INDEX idx_t_v ON t (v)");
+    for i in 0..50 {
+        exec(&mut engine, &format!("INSERT INTO t VALUES ({}, 'v{}')", i, i));
+    }
+
+    // Enough rows to split both the table and index roots before aborting.
+    exec(&mut engine, "BEGIN");
+    for i in 50..1500 {
+        exec(&mut engine, &format!("INSERT INTO t VALUES ({}, 'v{}')", i, i));
+    }
+    exec(&mut engine, "ROLLBACK");
+
+    let lookup = |engine: &mut Engine, value: &str| {
+        let mut rows = Vec::new();
+        let selection = Expr::Equals { left: "v".into(), right: value.into() };
+        assert!(execute_select_with_indexes(&mut engine.catalog, "t", Some(selection), &mut rows).unwrap());
+        rows.iter().map(|r| r.key).collect::<Vec<_>>()
+    };
+    assert_eq!(engine.catalog.integrity_check().unwrap(), None);
+    for i in 0..50 {
+        assert_eq!(lookup(&mut engine, &format!("v{}", i)), [i]);
+    }
+    assert!(lookup(&mut engine, "v700").is_empty());
+
+    exec(&mut engine, "INSERT INTO t VALUES (1500, 'v1500')");
+    assert_eq!(lookup(&mut engine, "v1500"), [1500]);
+    assert_eq!(select_all(&mut engine, "t").len(), 51);
+}
@@ -46,7 +46,7 @@ fn vacuum_prunes_committed_deleted_version() {
     );
 
     let rows = select_all(&mut engine, "t");
-    let keys: Vec<i32> = rows.iter().map(|r| r.key).collect();
+    let keys: Vec<i64> = rows.iter().map(|r| r.key).collect();
     assert_eq!(keys, vec![1, 3], "only live rows survive the vacuum");
 }
 
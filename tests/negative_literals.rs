@@ -0,0 +1,57 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{QueryResult, execute_select_statement, format_header, handle_statement},
+    sql::parser::parse_statement,
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "CREATE TABLE ledger (id INTEGER, balance INTEGER, rate DOUBLE)");
+    run(&mut catalog, "INSERT INTO ledger VALUES (1, -5, -2.5), (2, 10, 0.75), (-3,-7,-0.5)");
+    catalog
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r.join(",")).collect()
+}
+
+#[test]
+fn negative_values_insert_and_filter() {
+    let mut catalog = setup_catalog("test_negative_literals_filter.db");
+    assert_eq!(select(&mut catalog, "SELECT * FROM ledger"), vec!["-3,-7,-0.5", "1,-5,-2.5", "2,10,0.75"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE balance = -5"), vec!["1"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE balance=-7"), vec!["-3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE balance < -6"), vec!["-3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE rate >= -1"), vec!["-3", "2"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE rate = -2.5"), vec!["1"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE balance BETWEEN -8 AND -1"), vec!["-3", "1"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ledger WHERE id = -3"), vec!["-3"]);
+    assert_eq!(select(&mut catalog, "SELECT balance - -1 FROM ledger WHERE id = 2"), vec!["11"]);
+
+    run(&mut catalog, "UPDATE ledger SET balance = -9, rate = -0.25 WHERE id = 2");
+    assert_eq!(select(&mut catalog, "SELECT balance, rate FROM ledger WHERE id = 2"), vec!["-9,-0.25"]);
+}
+
+#[test]
+fn negative_literals_in_the_select_list() {
+    let mut catalog = setup_catalog("test_negative_literals_select.db");
+    match run(&mut catalog, "SELECT -5, -2.5 AS r") {
+        QueryResult::Rows { header, rows } => {
+            assert_eq!(format_header(&header), "-5 INTEGER | r DOUBLE(8,2)");
+            assert_eq!(rows, vec![vec!["-5".to_string(), "-2.5".to_string()]]);
+        }
+        other => panic!("expected rows, got {:?}", other),
+    }
+    assert_eq!(select(&mut catalog, "SELECT id, -1 FROM ledger WHERE id = 1"), vec!["1,-1"]);
+}
@@ -47,3 +47,25 @@ fn enforce_not_null() {
     ).unwrap();
     assert!(matches!(out[0].data.0[3], ColumnValue::Null));
 }
+
+#[test]
+fn update_to_null_respects_not_null() {
+    let filename = "test_not_null_update.db";
+    let mut catalog = setup_catalog(filename);
+    let run = |catalog: &mut Catalog, sql: &str| handle_statement(catalog, parse_statement(sql).unwrap());
+    run(&mut catalog, "CREATE TABLE persons (id INTEGER NOT NULL, last_name TEXT NOT NULL, age INTEGER, PRIMARY KEY (id))").unwrap();
+    run(&mut catalog, "INSERT INTO persons VALUES (1, 'siyo', 30)").unwrap();
+
+    let res = run(&mut catalog, "UPDATE persons SET last_name = NULL WHERE id = 1");
+    assert!(matches!(res, Err(aerodb::error::DbError::NullViolation(col)) if col == "last_name"));
+    let res = run(&mut catalog, "UPDATE persons SET id = NULL WHERE id = 1");
+    assert!(matches!(res, Err(aerodb::error::DbError::NullViolation(col)) if col == "id"));
+    // Nullable columns accept NULL, and the quoted text 'NULL' stays text.
+    run(&mut catalog, "UPDATE persons SET age = NULL, last_name = 'NULL' WHERE id = 1").unwrap();
+
+    let mut out = Vec::new();
+    aerodb::execution::runtime::execute_select_with_indexes(&mut catalog, "persons", None, &mut out).unwrap();
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].data.0[1], ColumnValue::Text("NULL".into()));
+    assert!(matches!(out[0].data.0[2], ColumnValue::Null));
+}
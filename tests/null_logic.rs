@@ -0,0 +1,81 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.sort();
+    rows
+}
+
+fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    select(catalog, sql).into_iter().map(|r| r[0].clone()).collect()
+}
+
+/// Row 2 holds a real NULL note; row 3 holds the text 'NULL'.
+fn setup(filename: &str) -> Catalog {
+    let mut catalog = setup_catalog(filename);
+    run(&mut catalog, "CREATE TABLE t (id INTEGER, note TEXT, score INTEGER)");
+    run(&mut catalog, "INSERT INTO t VALUES (1, 'a', 10), (2, NULL, NULL), (3, 'b', 1)");
+    run(&mut catalog, "UPDATE t SET note = 'NULL' WHERE id = 3");
+    catalog
+}
+
+#[test]
+fn comparisons_with_null_are_unknown() {
+    let mut catalog = setup("test_null_compare.db");
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE note = 'NULL'"), vec!["3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE note = 'a'"), vec!["1"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE note != 'a'"), vec!["3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE score > 0"), vec!["1", "3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE score <= 10"), vec!["1", "3"]);
+    // UNKNOWN OR TRUE is TRUE; UNKNOWN AND TRUE is filtered out.
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE note = 'a' OR id = 2"), vec!["1", "2"]);
+    assert!(ids(&mut catalog, "SELECT id FROM t WHERE note != 'a' AND id = 2").is_empty());
+}
+
+#[test]
+fn is_null_matches_only_real_nulls() {
+    let mut catalog = setup("test_null_is_null.db");
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE note IS NULL"), vec!["2"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE note IS NOT NULL"), vec!["1", "3"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t WHERE score IS NULL AND note IS NULL"), vec!["2"]);
+    assert_eq!(select(&mut catalog, "SELECT note FROM t WHERE id = 2"), vec![vec!["NULL".to_string()]]);
+
+    // `!=` keeps the NULL row out of the DELETE as well.
+    run(&mut catalog, "DELETE FROM t WHERE score != 1");
+    assert_eq!(ids(&mut catalog, "SELECT id FROM t"), vec!["2", "3"]);
+}
+
+#[test]
+fn group_by_separates_null_from_text() {
+    let mut catalog = setup("test_null_group.db");
+    run(&mut catalog, "INSERT INTO t VALUES (4, NULL, 5)");
+    assert_eq!(
+        select(&mut catalog, "SELECT note, COUNT(*) FROM t GROUP BY note"),
+        vec![
+            vec!["NULL".to_string(), "1".to_string()],
+            vec!["NULL".to_string(), "2".to_string()],
+            vec!["a".to_string(), "1".to_string()],
+        ]
+    );
+    assert_eq!(
+        select(&mut catalog, "SELECT note, COUNT(*) FROM t GROUP BY note HAVING note IS NULL"),
+        vec![vec!["NULL".to_string(), "2".to_string()]]
+    );
+    assert_eq!(
+        select(&mut catalog, "SELECT note, COUNT(*) FROM t GROUP BY note HAVING note = 'NULL'"),
+        vec![vec!["NULL".to_string(), "1".to_string()]]
+    );
+}
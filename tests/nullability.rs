@@ -9,9 +9,10 @@ fn setup_catalog(filename: &str) -> Catalog {
 
 #[test]
 fn parse_insert_null() {
-    let stmt = parse_statement("INSERT INTO users VALUES (1, NULL)").unwrap();
+    let stmt = parse_statement("INSERT INTO users VALUES (1, NULL), (2, 'NULL')").unwrap();
     if let Statement::Insert { rows, .. } = stmt {
-        assert!(matches!(rows[0][1], Expr::Literal(ref v) if v == "NULL"));
+        assert!(matches!(rows[0][1], Expr::Null));
+        assert!(matches!(rows[1][1], Expr::Literal(ref v) if v == "NULL"));
     } else { panic!("expected insert"); }
 }
 
@@ -22,8 +23,8 @@ fn insert_and_retrieve_null() {
     handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "users".into(),
         columns: vec![
-            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false},
-            aerodb::sql::ast::ColumnDef { name: "nickname".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false},
+            aerodb::sql::ast::ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
+            aerodb::sql::ast::ColumnDef { name: "nickname".into(), col_type: ColumnType::Text, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false},
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -37,3 +38,27 @@ fn insert_and_retrieve_null() {
     ).unwrap();
     assert_eq!(out[0].data.0[1], ColumnValue::Null);
 }
+
+#[test]
+fn explicit_null_respects_not_null() {
+    let filename = "test_null_explicit.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, nickname TEXT, email TEXT NOT NULL)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, NULL, 'a@x'), (2, 'NULL', 'b@x')").unwrap()).unwrap();
+    let err = handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (3, 'c', NULL)").unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "null value in column 'email' violates not-null constraint");
+
+    let mut out = Vec::new();
+    execute_select_with_indexes(&mut catalog, "users", None, &mut out).unwrap();
+    assert_eq!(out.len(), 2);
+    assert_eq!(out[0].data.0[1], ColumnValue::Null);
+    // Quoted, NULL is just text.
+    assert_eq!(out[1].data.0[1], ColumnValue::Text("NULL".into()));
+
+    // A comparison with the NULL keyword is never true.
+    for sql in ["SELECT id FROM users WHERE nickname = NULL", "SELECT id FROM users WHERE nickname != NULL"] {
+        let mut out = Vec::new();
+        aerodb::execution::runtime::execute_select_statement(&mut catalog, &parse_statement(sql).unwrap(), &mut out, None).unwrap();
+        assert!(out.is_empty(), "{sql}");
+    }
+}
@@ -21,10 +21,11 @@ fn parse_between_expression() {
     let stmt = parse_statement("SELECT id FROM users WHERE id BETWEEN 1 AND 3").unwrap();
     if let Statement::Select { where_predicate: Some(pred), .. } = stmt {
         match pred {
-            Expr::Between { expr, low, high } => {
+            Expr::Between { expr, low, high, negated } => {
                 assert_eq!(expr, "id");
                 assert_eq!(low, "1");
                 assert_eq!(high, "3");
+                assert!(!negated);
             }
             _ => panic!("expected Between"),
         }
@@ -33,7 +34,7 @@ fn parse_between_expression() {
 
 #[test]
 fn evaluate_between_true() {
-    let expr = Expr::Between { expr: "5".into(), low: "1".into(), high: "10".into() };
+    let expr = Expr::Between { expr: "5".into(), low: "1".into(), high: "10".into(), negated: false };
     assert_eq!(
         aerodb::sql::ast::evaluate_expression(&expr, &HashMap::new()),
         ColumnValue::Boolean(true)
@@ -0,0 +1,69 @@
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{QueryResult, handle_statement},
+    sql::parser::parse_statement,
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut catalog, "CREATE TABLE orders (id INTEGER, user_id INTEGER, total INTEGER)");
+    // bo has no orders; order 12 belongs to no user.
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'ann'), (2, 'bo')");
+    run(&mut catalog, "INSERT INTO orders VALUES (10, 1, 5), (11, 1, 7), (12, 9, 3)");
+    catalog
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn rows(catalog: &mut Catalog, sql: &str) -> (Vec<String>, Vec<Vec<String>>) {
+    match run(catalog, sql) {
+        QueryResult::Rows { header, mut rows } => {
+            rows.sort();
+            (header.into_iter().map(|(n, _)| n).collect(), rows)
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+fn strings(rows: &[&[&str]]) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = rows.iter().map(|r| r.iter().map(|s| s.to_string()).collect()).collect();
+    rows.sort();
+    rows
+}
+
+#[test]
+fn right_join_pads_unmatched_orders_with_nulls() {
+    let mut catalog = setup_catalog("test_outer_right.db");
+    let (_, got) = rows(&mut catalog, "SELECT u.name, o.id FROM users u RIGHT JOIN orders o ON u.id = o.user_id");
+    assert_eq!(got, strings(&[&["ann", "10"], &["ann", "11"], &["NULL", "12"]]));
+
+    let (_, got) = rows(&mut catalog, "SELECT u.name, o.id FROM users u RIGHT OUTER JOIN orders o ON u.id = o.user_id");
+    assert_eq!(got.len(), 3);
+}
+
+#[test]
+fn full_outer_join_keeps_unmatched_rows_from_both_sides() {
+    let mut catalog = setup_catalog("test_outer_full.db");
+    let (_, got) = rows(&mut catalog, "SELECT u.name, o.id FROM users u FULL OUTER JOIN orders o ON u.id = o.user_id");
+    assert_eq!(got, strings(&[&["ann", "10"], &["ann", "11"], &["bo", "NULL"], &["NULL", "12"]]));
+
+    let (_, got) = rows(&mut catalog, "SELECT u.name, o.id FROM users u FULL JOIN orders o ON u.id = o.user_id WHERE o.id IS NULL");
+    assert_eq!(got, strings(&[&["bo", "NULL"]]));
+}
+
+#[test]
+fn star_projection_stays_aligned_with_the_header() {
+    let mut catalog = setup_catalog("test_outer_star.db");
+    let (header, got) = rows(&mut catalog, "SELECT * FROM users u FULL OUTER JOIN orders o ON u.id = o.user_id");
+    assert_eq!(header, vec!["u.id", "u.name", "o.id", "o.user_id", "o.total"]);
+    assert!(got.iter().all(|r| r.len() == header.len()));
+    assert!(got.contains(&vec!["NULL".into(), "NULL".into(), "12".into(), "9".into(), "3".into()]));
+    assert!(got.contains(&vec!["2".into(), "bo".into(), "NULL".into(), "NULL".into(), "NULL".into()]));
+}
@@ -0,0 +1,76 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+fn long_text(seed: usize, len: usize) -> String {
+    (0..len).map(|i| (b'a' + ((i * 7 + seed) % 26) as u8) as char).collect()
+}
+
+#[test]
+fn long_text_round_trips_through_overflow_pages() {
+    let filename = "test_overflow_pages.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE docs (id INTEGER, body TEXT)").unwrap()).unwrap();
+    let big = long_text(0, 10_000);
+    handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO docs VALUES (1, '{}')", big)).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO docs VALUES (2, 'short')").unwrap()).unwrap();
+
+    assert_eq!(select(&mut catalog, "SELECT body FROM docs WHERE id = 1"), vec![vec![big.clone()]]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM docs"), vec![vec!["1".to_string()], vec!["2".to_string()]]);
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+
+    // The spilled bytes are durable and survive a reopen.
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(select(&mut catalog, "SELECT body FROM docs WHERE id = 1"), vec![vec![big]]);
+}
+
+#[test]
+fn many_long_rows_split_leaves_and_update_in_place() {
+    let filename = "test_overflow_pages_many.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE docs (id INTEGER, body TEXT)").unwrap()).unwrap();
+    for id in 0..40 {
+        let sql = format!("INSERT INTO docs VALUES ({}, '{}')", id, long_text(id, 3000 + id * 50));
+        handle_statement(&mut catalog, parse_statement(&sql).unwrap()).unwrap();
+    }
+    let replacement = long_text(99, 6000);
+    handle_statement(&mut catalog, parse_statement(&format!("UPDATE docs SET body = '{}' WHERE id = 7", replacement)).unwrap()).unwrap();
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+
+    let rows = select(&mut catalog, "SELECT id, body FROM docs");
+    assert_eq!(rows.len(), 40);
+    for row in rows {
+        let id: usize = row[0].parse().unwrap();
+        let expected = if id == 7 { replacement.clone() } else { long_text(id, 3000 + id * 50) };
+        assert_eq!(row[1], expected, "row {}", id);
+    }
+}
+
+#[test]
+fn rows_far_larger_than_a_page_insert_without_a_size_limit() {
+    let filename = "test_overflow_pages_huge.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE docs (id INTEGER, body TEXT, tag TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_tag ON docs (tag)").unwrap()).unwrap();
+    // Both the row and its indexed value span dozens of pages.
+    let body = long_text(1, 200_000);
+    let tag = long_text(2, 100_000);
+    handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO docs VALUES (1, '{}', '{}')", body, tag)).unwrap()).unwrap();
+
+    assert_eq!(select(&mut catalog, &format!("SELECT id FROM docs WHERE tag = '{}'", tag)), vec![vec!["1".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT body FROM docs WHERE id = 1"), vec![vec![body]]);
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+}
@@ -0,0 +1,91 @@
+use aerodb::{catalog::Catalog, error::DbError, storage::{pager::Pager, page::PAGE_SIZE}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+fn cleanup(filename: &str) {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+}
+
+fn open(filename: &str, checksums: bool) -> Catalog {
+    Catalog::open(Pager::new_with_checksums(filename, checksums).unwrap()).unwrap()
+}
+
+fn populate(catalog: &mut Catalog) -> u32 {
+    handle_statement(catalog, parse_statement("CREATE TABLE items (id INTEGER, name TEXT)").unwrap()).unwrap();
+    handle_statement(catalog, parse_statement("INSERT INTO items VALUES (1, 'one'), (2, 'two')").unwrap()).unwrap();
+    catalog.get_table("items").unwrap().root_page
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Result<Vec<Vec<String>>, DbError> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None)?;
+    Ok(rows)
+}
+
+/// Flip one byte of `page_num` on disk. The WAL is removed first so recovery
+/// does not replay the original page image over the corruption.
+fn flip_byte(filename: &str, page_num: u32, offset: usize) {
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut file = OpenOptions::new().read(true).write(true).open(filename).unwrap();
+    let pos = page_num as u64 * PAGE_SIZE as u64 + offset as u64;
+    let mut byte = [0u8; 1];
+    file.seek(SeekFrom::Start(pos)).unwrap();
+    file.read_exact(&mut byte).unwrap();
+    file.seek(SeekFrom::Start(pos)).unwrap();
+    file.write_all(&[byte[0] ^ 0x40]).unwrap();
+}
+
+#[test]
+fn checksummed_database_reopens_cleanly() {
+    let filename = "test_checksums_clean.db";
+    cleanup(filename);
+    let mut catalog = open(filename, true);
+    populate(&mut catalog);
+    drop(catalog);
+
+    let mut catalog = open(filename, true);
+    assert_eq!(select(&mut catalog, "SELECT name FROM items").unwrap(), vec![vec!["one".to_string()], vec!["two".to_string()]]);
+}
+
+#[test]
+fn flipped_byte_in_table_page_is_detected() {
+    let filename = "test_checksums_table.db";
+    cleanup(filename);
+    let mut catalog = open(filename, true);
+    let root = populate(&mut catalog);
+    drop(catalog);
+
+    flip_byte(filename, root, 20);
+    let mut catalog = open(filename, true);
+    let err = select(&mut catalog, "SELECT name FROM items").unwrap_err();
+    assert!(matches!(err, DbError::Io(_)), "{:?}", err);
+    assert!(err.to_string().contains(&format!("page {} checksum mismatch", root)), "{}", err);
+}
+
+#[test]
+fn flipped_byte_in_catalog_page_fails_open() {
+    let filename = "test_checksums_catalog.db";
+    cleanup(filename);
+    let mut catalog = open(filename, true);
+    populate(&mut catalog);
+    drop(catalog);
+
+    flip_byte(filename, 1, 40);
+    let err = Catalog::open(Pager::new_with_checksums(filename, true).unwrap()).err().expect("corrupt catalog page should fail to open");
+    assert!(err.to_string().contains("page 1 checksum mismatch"), "{}", err);
+}
+
+#[test]
+fn files_without_checksums_still_open() {
+    let filename = "test_checksums_legacy.db";
+    cleanup(filename);
+    let mut catalog = open(filename, false);
+    populate(&mut catalog);
+    drop(catalog);
+
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(select(&mut catalog, "SELECT id FROM items").unwrap().len(), 2);
+}
@@ -0,0 +1,80 @@
+use aerodb::{catalog::Catalog, engine::Engine, storage::{pager::Pager, page::PAGE_SIZE}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn cleanup(filename: &str) {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn database_reopens_with_the_page_size_it_was_created_with() {
+    let filename = "test_page_size_small.db";
+    cleanup(filename);
+    let mut catalog = Catalog::open(Pager::new_with_page_size(filename, 1024).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE items (id INTEGER, label TEXT)").unwrap()).unwrap();
+    // Enough rows to split 1 KiB leaves several times, plus one spilled payload.
+    for i in 1..=200 {
+        handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO items VALUES ({}, 'item-{}')", i, i)).unwrap()).unwrap();
+    }
+    let long = "x".repeat(3000);
+    handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO items VALUES (500, '{}')", long)).unwrap()).unwrap();
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+    drop(catalog);
+    assert_eq!(fs::metadata(filename).unwrap().len() % 1024, 0);
+
+    let pager = Pager::new(filename).unwrap();
+    assert_eq!(pager.page_size(), 1024);
+    let mut catalog = Catalog::open(pager).unwrap();
+    assert_eq!(select(&mut catalog, "SELECT label FROM items WHERE id = 137"), vec![vec!["item-137".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT label FROM items WHERE id = 500"), vec![vec![long]]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM items").len(), 201);
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+}
+
+#[test]
+fn larger_pages_survive_committed_transactions() {
+    let filename = "test_page_size_large.db";
+    cleanup(filename);
+    drop(Pager::new_with_page_size(filename, 16384).unwrap());
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INTEGER, v TEXT)").unwrap()).unwrap();
+    engine.execute(parse_statement("INSERT INTO t VALUES (1, 'a'), (2, 'b')").unwrap()).unwrap();
+    drop(engine);
+
+    assert_eq!(Pager::new(filename).unwrap().page_size(), 16384);
+    let mut engine = Engine::new(filename);
+    assert_eq!(select(&mut engine.catalog, "SELECT v FROM t"), vec![vec!["a".to_string()], vec!["b".to_string()]]);
+}
+
+#[test]
+fn default_page_size_is_unchanged() {
+    let filename = "test_page_size_default.db";
+    cleanup(filename);
+    assert_eq!(Pager::new(filename).unwrap().page_size(), PAGE_SIZE);
+    cleanup(filename);
+    drop(Catalog::open(Pager::new_with_page_size(filename, PAGE_SIZE).unwrap()).unwrap());
+    assert_eq!(Pager::new(filename).unwrap().page_size(), PAGE_SIZE);
+}
+
+#[test]
+fn invalid_or_mismatched_page_sizes_are_rejected() {
+    let filename = "test_page_size_invalid.db";
+    cleanup(filename);
+    for size in [0, 256, 1000, 3000, 131072] {
+        let err = Pager::new_with_page_size(filename, size).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{}", size);
+    }
+
+    drop(Catalog::open(Pager::new_with_page_size(filename, 2048).unwrap()).unwrap());
+    let err = Pager::new_with_page_size(filename, 8192).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(Pager::new_with_page_size(filename, 2048).unwrap().page_size(), 2048);
+}
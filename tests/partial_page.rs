@@ -0,0 +1,47 @@
+use aerodb::{catalog::Catalog, storage::{pager::Pager, page::PAGE_SIZE}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+fn cleanup(filename: &str) {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+}
+
+#[test]
+fn trailing_partial_page_is_zero_extended_on_open() {
+    let filename = "test_partial_page.db";
+    cleanup(filename);
+    {
+        let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+        handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INTEGER, name TEXT)").unwrap()).unwrap();
+        handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (1, 'a'), (2, 'b')").unwrap()).unwrap();
+    }
+
+    // Simulate a torn write of a new last page.
+    let full_pages = fs::metadata(filename).unwrap().len() / PAGE_SIZE as u64;
+    let mut file = OpenOptions::new().append(true).open(filename).unwrap();
+    file.write_all(&[0xAB; 100]).unwrap();
+    drop(file);
+
+    let pager = Pager::new(filename).unwrap();
+    assert_eq!(pager.file_length_pages() as u64, full_pages + 1);
+    assert_eq!(fs::metadata(filename).unwrap().len(), (full_pages + 1) * PAGE_SIZE as u64);
+
+    let mut catalog = Catalog::open(pager).unwrap();
+    let stmt = parse_statement("SELECT name FROM t").unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap();
+    assert_eq!(rows, vec![vec!["a".to_string()], vec!["b".to_string()]]);
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t VALUES (3, 'c')").unwrap()).unwrap();
+}
+
+#[test]
+fn partial_first_page_opens_cleanly() {
+    let filename = "test_partial_first_page.db";
+    cleanup(filename);
+    fs::write(filename, [0u8; 10]).unwrap();
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INTEGER)").unwrap()).unwrap();
+    assert_eq!(fs::metadata(filename).unwrap().len() % PAGE_SIZE as u64, 0);
+}
@@ -0,0 +1,123 @@
+use aerodb::{
+    engine::Engine,
+    error::DbError,
+    execution::runtime::QueryResult,
+    sql::{ast::{Expr, Statement}, parser::parse_statement},
+    storage::row::ColumnValue,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE users (id INTEGER, name TEXT, age INTEGER)").unwrap()).unwrap();
+    engine
+}
+
+fn rows(result: QueryResult) -> Vec<Vec<String>> {
+    match result {
+        QueryResult::Rows { rows, .. } => rows,
+        other => panic!("expected rows, got {:?}", other),
+    }
+}
+
+#[test]
+fn parser_records_placeholder_positions() {
+    match parse_statement("INSERT INTO users VALUES (?, 'x', ?)").unwrap() {
+        Statement::Insert { rows, .. } => {
+            assert!(matches!(rows[0][0], Expr::Placeholder(1)));
+            assert!(matches!(rows[0][2], Expr::Placeholder(2)));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_statement("SELECT id FROM users WHERE name = ? AND age > ?").unwrap() {
+        Statement::Select { where_predicate: Some(Expr::And(a, b)), .. } => {
+            assert!(matches!(*a, Expr::Equals { ref right, .. } if right == "?1"));
+            assert!(matches!(*b, Expr::GreaterThan { ref right, .. } if right == "?2"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    // A `?` inside a string literal is not a parameter.
+    match parse_statement("INSERT INTO users VALUES (1, 'who?', ?)").unwrap() {
+        Statement::Insert { rows, .. } => assert!(matches!(rows[0][2], Expr::Placeholder(1))),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn prepared_insert_runs_with_several_bindings() {
+    let mut engine = setup_engine("test_prepared_insert.db");
+    let mut insert = engine.prepare("INSERT INTO users (id, name, age) VALUES (?, ?, ?)").unwrap();
+    assert_eq!(insert.param_count(), 3);
+    let names = ["o'brien", "a, b", "NULL", "plain"];
+    for (i, name) in names.iter().enumerate() {
+        let params = [ColumnValue::Integer(i as i32), ColumnValue::Text(name.to_string()), ColumnValue::Integer(20 + i as i32)];
+        assert_eq!(insert.execute(&params).unwrap(), QueryResult::Affected(1));
+    }
+    insert.execute(&[ColumnValue::Integer(9), ColumnValue::Null, ColumnValue::Null]).unwrap();
+
+    let stored = rows(engine.execute(parse_statement("SELECT name FROM users").unwrap()).unwrap());
+    let stored: Vec<&str> = stored.iter().map(|r| r[0].as_str()).collect();
+    assert_eq!(stored, ["o'brien", "a, b", "NULL", "plain", "NULL"]);
+    let nulls = rows(engine.execute(parse_statement("SELECT id FROM users WHERE name IS NULL").unwrap()).unwrap());
+    assert_eq!(nulls, vec![vec!["9".to_string()]]);
+}
+
+#[test]
+fn prepared_select_runs_with_several_bindings() {
+    let mut engine = setup_engine("test_prepared_select.db");
+    engine.execute(parse_statement("INSERT INTO users VALUES (1, 'ann', 30), (2, 'bob', 40), (3, 'cy', 50)").unwrap()).unwrap();
+    let mut select = engine.prepare("SELECT name FROM users WHERE age >= ? AND id != ?").unwrap();
+    assert_eq!(rows(select.execute(&[ColumnValue::Integer(40), ColumnValue::Integer(3)]).unwrap()), vec![vec!["bob".to_string()]]);
+    assert_eq!(rows(select.execute(&[ColumnValue::Integer(0), ColumnValue::Integer(2)]).unwrap()).len(), 2);
+
+    let mut by_name = engine.prepare("SELECT id FROM users WHERE name = ?").unwrap();
+    assert_eq!(rows(by_name.execute(&[ColumnValue::Text("cy".into())]).unwrap()), vec![vec!["3".to_string()]]);
+    assert!(rows(by_name.execute(&[ColumnValue::Text("zed".into())]).unwrap()).is_empty());
+}
+
+#[test]
+fn wrong_parameter_count_or_type_is_rejected() {
+    let mut engine = setup_engine("test_prepared_errors.db");
+    let mut insert = engine.prepare("INSERT INTO users VALUES (?, ?, ?)").unwrap();
+    let err = insert.execute(&[ColumnValue::Integer(1)]).unwrap_err();
+    assert!(matches!(err, DbError::InvalidValue(ref m) if m.contains("expects 3")), "{:?}", err);
+    let err = insert
+        .execute(&[ColumnValue::Integer(1), ColumnValue::Text("x".into()), ColumnValue::Text("old".into())])
+        .unwrap_err();
+    assert!(matches!(err, DbError::InvalidValue(ref m) if m.contains("'age'")), "{:?}", err);
+}
+
+#[test]
+fn bound_text_is_a_value_even_when_spelled_like_a_column() {
+    let mut engine = setup_engine("test_prepared_column_names.db");
+    engine.execute(parse_statement("INSERT INTO users VALUES (1, 'ann', 30), (2, 'name', 40), (3, 'it''s', 50)").unwrap()).unwrap();
+    let text = |s: &str| ColumnValue::Text(s.into());
+
+    let mut select = engine.prepare("SELECT id FROM users WHERE name = ?").unwrap();
+    assert_eq!(rows(select.execute(&[text("name")]).unwrap()), vec![vec!["2".to_string()]]);
+    assert!(rows(select.execute(&[text("age")]).unwrap()).is_empty());
+    assert_eq!(rows(select.execute(&[text("it's")]).unwrap()), vec![vec!["3".to_string()]]);
+    let mut select = engine.prepare("SELECT id FROM users WHERE ? = name").unwrap();
+    assert_eq!(rows(select.execute(&[text("name")]).unwrap()), vec![vec!["2".to_string()]]);
+
+    // Key-range and index lookups read the bound value too.
+    let mut by_id = engine.prepare("SELECT name FROM users WHERE id = ?").unwrap();
+    assert_eq!(rows(by_id.execute(&[ColumnValue::Integer(1)]).unwrap()), vec![vec!["ann".to_string()]]);
+    engine.execute(parse_statement("CREATE INDEX idx_name ON users(name)").unwrap()).unwrap();
+    let mut select = engine.prepare("SELECT id FROM users WHERE name = ?").unwrap();
+    assert_eq!(rows(select.execute(&[text("name")]).unwrap()), vec![vec!["2".to_string()]]);
+
+    let mut update = engine.prepare("UPDATE users SET age = ? WHERE name = ?").unwrap();
+    assert_eq!(update.execute(&[ColumnValue::Integer(41), text("name")]).unwrap(), QueryResult::Affected(1));
+    assert_eq!(update.execute(&[ColumnValue::Integer(51), text("it's")]).unwrap(), QueryResult::Affected(1));
+    assert_eq!(update.execute(&[ColumnValue::Integer(0), text("id")]).unwrap(), QueryResult::Affected(0));
+
+    let mut delete = engine.prepare("DELETE FROM users WHERE name = ?").unwrap();
+    assert_eq!(delete.execute(&[text("age")]).unwrap(), QueryResult::Affected(0));
+    assert_eq!(delete.execute(&[text("it's")]).unwrap(), QueryResult::Affected(1));
+    assert_eq!(delete.execute(&[text("name")]).unwrap(), QueryResult::Affected(1));
+    let left = rows(engine.execute(parse_statement("SELECT id, age FROM users").unwrap()).unwrap());
+    assert_eq!(left, vec![vec!["1".to_string(), "30".to_string()]]);
+}
@@ -83,3 +83,63 @@ fn primary_key_persistence() {
     }
 }
 
+
+#[test]
+fn composite_primary_key_allows_repeated_columns() {
+    let filename = "pk_comp_repeat.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE enrollments (id INT, student INT, course INT, PRIMARY KEY(student, course))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO enrollments VALUES (1,10,100), (2,10,200), (3,20,100)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO enrollments VALUES (4,20,200)").unwrap()).unwrap();
+    let mut rows = Vec::new();
+    execute_select_with_indexes(&mut catalog, "enrollments", None, &mut rows).unwrap();
+    assert_eq!(rows.len(), 4);
+}
+
+#[test]
+fn composite_primary_key_rejects_duplicate_pair() {
+    let filename = "pk_comp_collide.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE enrollments (id INT, student INT, course INT, PRIMARY KEY(student, course))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO enrollments VALUES (1,10,100), (2,10,200)").unwrap()).unwrap();
+    let dup = handle_statement(&mut catalog, parse_statement("INSERT INTO enrollments VALUES (3,10,200)").unwrap());
+    assert!(matches!(dup, Err(aerodb::error::DbError::DuplicateKey(3))));
+    let in_batch = handle_statement(&mut catalog, parse_statement("INSERT INTO enrollments VALUES (4,30,300), (5,30,300)").unwrap());
+    assert!(matches!(in_batch, Err(aerodb::error::DbError::DuplicateKey(5))));
+    let update = handle_statement(&mut catalog, parse_statement("UPDATE enrollments SET course = 100 WHERE id = 2").unwrap());
+    assert!(matches!(update, Err(aerodb::error::DbError::DuplicateKey(_))));
+}
+
+#[test]
+fn composite_primary_key_still_keys_rows_on_first_column() {
+    let filename = "pk_comp_row_key.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE orders (order_id INT, item_id INT, PRIMARY KEY(order_id, item_id))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO orders VALUES (1,1)").unwrap()).unwrap();
+    // The pair is unique, but the row key (order_id) is already taken.
+    let res = handle_statement(&mut catalog, parse_statement("INSERT INTO orders VALUES (1,2)").unwrap());
+    assert!(matches!(res, Err(aerodb::error::DbError::DuplicateKey(1))));
+}
+
+#[test]
+fn text_primary_key_duplicate_reports_table_and_value() {
+    let filename = "pk_text_dup.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE accounts (id INT, email TEXT, region TEXT, PRIMARY KEY(email))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO accounts VALUES (1,'ada@example.com','eu')").unwrap()).unwrap();
+    let dup = handle_statement(&mut catalog, parse_statement("INSERT INTO accounts VALUES (2,'ada@example.com','us')").unwrap());
+    match dup {
+        Err(aerodb::error::DbError::DuplicateKeyValue { table, key }) => {
+            assert_eq!((table.as_str(), key.as_str()), ("accounts", "'ada@example.com'"));
+        }
+        other => panic!("Expected duplicate key value, got {:?}", other),
+    }
+
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE seats (id INT, row_label TEXT, num INT, PRIMARY KEY(row_label, num))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO seats VALUES (1,'A',1)").unwrap()).unwrap();
+    let dup = handle_statement(&mut catalog, parse_statement("INSERT INTO seats VALUES (2,'A',1)").unwrap()).unwrap_err();
+    assert_eq!(dup.to_string(), "duplicate primary key ('A', 1) in table 'seats'");
+    // An integer key keeps reporting the row key.
+    let dup = handle_statement(&mut catalog, parse_statement("INSERT INTO seats VALUES (1,'B',1)").unwrap());
+    assert!(matches!(dup, Err(aerodb::error::DbError::DuplicateKey(1))));
+}
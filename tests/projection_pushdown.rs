@@ -0,0 +1,104 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::{execute_select_projected, execute_select_statement},
+    sql::{ast::Expr, parser::parse_statement},
+    storage::row::ColumnValue,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+/// A table `wide` with an integer `id`, a short `tag`, and 16 padded text
+/// columns `c0`..`c15`.
+fn wide_table(filename: &str) -> Engine {
+    let mut engine = setup_engine(filename);
+    let columns: Vec<String> = (0..16).map(|i| format!("c{i} TEXT")).collect();
+    run(&mut engine, &format!("CREATE TABLE wide (id INTEGER, tag TEXT, {})", columns.join(", ")));
+    for id in 0..50 {
+        let values: Vec<String> = (0..16).map(|i| format!("'{}'", format!("{id}-{i}").repeat(8))).collect();
+        let tag = if id % 5 == 0 { "'five'" } else { "NULL" };
+        run(&mut engine, &format!("INSERT INTO wide VALUES ({id}, {tag}, {})", values.join(", ")));
+    }
+    engine
+}
+
+#[test]
+fn single_column_projection_decodes_fewer_bytes() {
+    let mut engine = wide_table("test_projection_bytes.db");
+
+    let before = engine.catalog.bytes_decoded();
+    let all = select(&mut engine.catalog, "SELECT * FROM wide");
+    let full_bytes = engine.catalog.bytes_decoded() - before;
+
+    let before = engine.catalog.bytes_decoded();
+    let ids = select(&mut engine.catalog, "SELECT id FROM wide");
+    let projected_bytes = engine.catalog.bytes_decoded() - before;
+
+    assert_eq!(ids.len(), 50);
+    assert_eq!(ids, all.iter().map(|r| vec![r[0].clone()]).collect::<Vec<_>>());
+    assert!(
+        projected_bytes * 20 < full_bytes,
+        "projected scan decoded {projected_bytes} bytes, full scan {full_bytes}"
+    );
+}
+
+#[test]
+fn projected_queries_match_full_rows() {
+    let mut engine = wide_table("test_projection_results.db");
+    let all = select(&mut engine.catalog, "SELECT * FROM wide");
+    let column = |row: &Vec<String>, name: &str| {
+        let idx = match name {
+            "id" => 0,
+            "tag" => 1,
+            c => 2 + c[1..].parse::<usize>().unwrap(),
+        };
+        row[idx].clone()
+    };
+
+    // WHERE and ORDER BY columns outside the select list are still read.
+    let rows = select(&mut engine.catalog, "SELECT c3 FROM wide WHERE tag = 'five' ORDER BY c7 DESC");
+    let mut expected: Vec<_> = all.iter().filter(|r| column(r, "tag") == "five").collect();
+    expected.sort_by_key(|r| std::cmp::Reverse(column(r, "c7")));
+    assert_eq!(rows, expected.iter().map(|r| vec![column(r, "c3")]).collect::<Vec<_>>());
+
+    // NULLs in projected columns stay NULL; skipped columns never show up.
+    let rows = select(&mut engine.catalog, "SELECT id, tag FROM wide WHERE id < 2");
+    assert_eq!(rows, [["0", "five"], ["1", "NULL"]]);
+
+    // Expressions read whatever they need.
+    let rows = select(&mut engine.catalog, "SELECT id + 1 FROM wide WHERE c15 = '3-153-153-153-153-153-153-153-15'");
+    assert_eq!(rows, [["4"]]);
+}
+
+#[test]
+fn execute_select_projected_leaves_skipped_columns_null() {
+    let mut engine = wide_table("test_projection_api.db");
+    let mut projection = vec![false; 18];
+    projection[0] = true;
+    projection[5] = true;
+    let selection = Expr::Equals { left: "id".into(), right: "7".into() };
+    let mut rows = Vec::new();
+    execute_select_projected(&mut engine.catalog, "wide", Some(selection), &projection, &mut rows).unwrap();
+    assert_eq!(rows.len(), 1);
+    let values = &rows[0].data.0;
+    assert_eq!(values[0], ColumnValue::Integer(7));
+    assert_eq!(values[5], ColumnValue::Text("7-37-37-37-37-37-37-37-3".into()));
+    assert!(values.iter().enumerate().all(|(i, v)| i == 0 || i == 5 || *v == ColumnValue::Null));
+}
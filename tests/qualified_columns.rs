@@ -0,0 +1,61 @@
+use aerodb::{catalog::Catalog, error::DbError, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, name TEXT, team TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'carol', 'red'), (2, 'alice', 'blue'), (3, 'bob', 'red')").unwrap()).unwrap();
+    catalog
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Result<Vec<Vec<String>>, DbError> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None)?;
+    Ok(rows)
+}
+
+fn strings(rows: &[&[&str]]) -> Vec<Vec<String>> {
+    rows.iter().map(|r| r.iter().map(|s| s.to_string()).collect()).collect()
+}
+
+#[test]
+fn table_name_qualifies_projection_and_where() {
+    let mut catalog = setup_catalog("test_qualified_table.db");
+    let rows = select(&mut catalog, "SELECT users.name FROM users WHERE users.id = 2").unwrap();
+    assert_eq!(rows, strings(&[&["alice"]]));
+}
+
+#[test]
+fn alias_and_table_name_both_resolve() {
+    let mut catalog = setup_catalog("test_qualified_alias.db");
+    let rows = select(&mut catalog, "SELECT users.id FROM users u WHERE u.id = 1").unwrap();
+    assert_eq!(rows, strings(&[&["1"]]));
+    let rows = select(&mut catalog, "SELECT u.name FROM users AS u WHERE u.team = 'red' ORDER BY u.name").unwrap();
+    assert_eq!(rows, strings(&[&["bob"], &["carol"]]));
+}
+
+#[test]
+fn qualified_group_by_and_order_by() {
+    let mut catalog = setup_catalog("test_qualified_group.db");
+    let rows = select(&mut catalog, "SELECT u.team, COUNT(u.id) FROM users u GROUP BY u.team ORDER BY u.team DESC").unwrap();
+    assert_eq!(rows, strings(&[&["red", "2"], &["blue", "1"]]));
+    let rows = select(&mut catalog, "SELECT id FROM users ORDER BY users.id DESC").unwrap();
+    assert_eq!(rows, strings(&[&["3"], &["2"], &["1"]]));
+}
+
+#[test]
+fn unknown_qualifier_is_rejected() {
+    let mut catalog = setup_catalog("test_qualified_reject.db");
+    for sql in [
+        "SELECT x.id FROM users",
+        "SELECT id FROM users u WHERE x.id = 1",
+        "SELECT id FROM users ORDER BY x.id",
+        "SELECT COUNT(id) FROM users GROUP BY x.team",
+    ] {
+        let err = select(&mut catalog, sql).unwrap_err();
+        assert!(matches!(err, DbError::ColumnNotFound(ref c) if c.starts_with("x.")), "{}: {:?}", sql, err);
+    }
+}
@@ -0,0 +1,44 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::handle_statement};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'alice'), (2, 'bob'), (3, 'alice'), (4, 'carol')").unwrap()).unwrap();
+    catalog
+}
+
+#[test]
+fn indexed_lookup_matches_full_scan() {
+    let mut catalog = setup_catalog("test_query_eq_index.db");
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON users(name)").unwrap()).unwrap();
+    // An equality on an indexed column uses the index; wrapping it in an OR
+    // forces the full-scan path.
+    assert!(catalog.query_eq(
+        "SELECT id FROM users WHERE name = 'alice'",
+        "SELECT id FROM users WHERE name = 'alice' OR name = 'alice'",
+    ).unwrap());
+    assert!(!catalog.query_eq(
+        "SELECT id FROM users WHERE name = 'alice'",
+        "SELECT id FROM users WHERE name = 'bob' OR name = 'bob'",
+    ).unwrap());
+}
+
+#[test]
+fn ordered_comparison_checks_row_order() {
+    let mut catalog = setup_catalog("test_query_eq_order.db");
+    let forward = "SELECT name FROM users WHERE id = 1 UNION ALL SELECT name FROM users WHERE id = 2";
+    let backward = "SELECT name FROM users WHERE id = 2 UNION ALL SELECT name FROM users WHERE id = 1";
+    assert!(catalog.query_eq(forward, backward).unwrap());
+    assert!(!catalog.query_eq_ordered(forward, backward).unwrap());
+    assert!(catalog.query_eq_ordered(forward, forward).unwrap());
+
+    // Duplicates count: a multiset comparison, not a set comparison.
+    assert!(!catalog.query_eq(
+        "SELECT name FROM users WHERE id = 1 UNION ALL SELECT name FROM users WHERE id = 1",
+        "SELECT name FROM users WHERE id = 1",
+    ).unwrap());
+    assert!(catalog.query_eq("SELECT FROM", "SELECT 1").is_err());
+}
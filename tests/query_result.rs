@@ -0,0 +1,59 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::{QueryResult, handle_statement},
+    sql::parser::parse_statement,
+    storage::{pager::Pager, row::ColumnType},
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+#[test]
+fn select_returns_header_and_rows() {
+    let mut catalog = setup_catalog("test_query_result_select.db");
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    assert_eq!(run(&mut catalog, "INSERT INTO users VALUES (1, 'ann')"), QueryResult::Affected(1));
+    assert_eq!(run(&mut catalog, "INSERT INTO users VALUES (2, 'bo'), (3, 'cy')"), QueryResult::Affected(2));
+    assert_eq!(
+        run(&mut catalog, "SELECT name, id FROM users WHERE id < 3"),
+        QueryResult::Rows {
+            header: vec![("name".into(), ColumnType::Text), ("id".into(), ColumnType::Integer)],
+            rows: vec![vec!["ann".into(), "1".into()], vec!["bo".into(), "2".into()]],
+        }
+    );
+}
+
+#[test]
+fn dml_and_ddl_report_their_outcome() {
+    let mut catalog = setup_catalog("test_query_result_dml.db");
+    assert_eq!(run(&mut catalog, "CREATE TABLE t (id INTEGER, v INTEGER)"), QueryResult::Message("Table t created".into()));
+    run(&mut catalog, "INSERT INTO t VALUES (1, 1), (2, 2), (3, 3)");
+    assert_eq!(run(&mut catalog, "UPDATE t SET v = 0 WHERE id > 1"), QueryResult::Affected(2));
+    assert_eq!(run(&mut catalog, "DELETE FROM t WHERE v = 0"), QueryResult::Affected(2));
+    assert_eq!(run(&mut catalog, "PRAGMA integrity_check"), QueryResult::Message("OK".into()));
+}
+
+#[test]
+fn engine_returns_results_and_acks_transaction_control() {
+    let filename = "test_query_result_engine.db";
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut engine = Engine::new(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INTEGER)").unwrap()).unwrap();
+    assert_eq!(engine.execute(parse_statement("BEGIN").unwrap()).unwrap(), QueryResult::Ack);
+    assert_eq!(engine.execute(parse_statement("INSERT INTO t VALUES (7)").unwrap()).unwrap(), QueryResult::Affected(1));
+    assert_eq!(engine.execute(parse_statement("COMMIT").unwrap()).unwrap(), QueryResult::Ack);
+    match engine.execute(parse_statement("SELECT id FROM t").unwrap()).unwrap() {
+        QueryResult::Rows { rows, .. } => assert_eq!(rows, vec![vec!["7".to_string()]]),
+        other => panic!("expected rows, got {:?}", other),
+    }
+}
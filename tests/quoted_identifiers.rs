@@ -0,0 +1,99 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::{QueryResult, execute_select_statement},
+    sql::{ast::{SelectItem, Statement}, parser::parse_statement},
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+/// Rows of a query run through the engine, which also handles joins.
+fn query(engine: &mut Engine, sql: &str) -> Vec<Vec<String>> {
+    match engine.execute(parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { rows, .. } => rows,
+        other => panic!("Expected rows, got {:?}", other),
+    }
+}
+
+#[test]
+fn keyword_table_and_column_names_can_be_quoted() {
+    let filename = "test_quoted_identifiers_keywords.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE \"my table\" (\"order\" INTEGER, \"select\" TEXT, `group by` INTEGER)");
+    run(&mut engine, "INSERT INTO \"my table\" VALUES (1, 'a', 10)");
+    run(&mut engine, "INSERT INTO \"my table\" (\"order\", \"select\", `group by`) VALUES (2, 'b', 20)");
+
+    let columns: Vec<String> = engine.catalog.get_table("my table").unwrap().columns.iter().map(|(name, _)| name.clone()).collect();
+    assert_eq!(columns, ["order", "select", "group by"]);
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT \"select\", `group by` FROM \"my table\" WHERE \"order\" = 2"),
+        vec![vec!["b".to_string(), "20".to_string()]]
+    );
+
+    run(&mut engine, "UPDATE `my table` SET \"select\" = 'z' WHERE \"order\" = 1");
+    run(&mut engine, "DELETE FROM \"my table\" WHERE `order` = 2");
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT \"order\", \"select\" FROM \"my table\" ORDER BY \"order\""),
+        vec![vec!["1".to_string(), "z".to_string()]]
+    );
+}
+
+#[test]
+fn quoted_names_work_in_qualified_columns_keys_and_indexes() {
+    let filename = "test_quoted_identifiers_qualified.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE \"from\" (id INTEGER, \"where\" TEXT, PRIMARY KEY (\"id\"))");
+    run(&mut engine, "CREATE TABLE \"to\" (id INTEGER, \"from id\" INTEGER, FOREIGN KEY (\"from id\") REFERENCES \"from\"(\"id\"))");
+    run(&mut engine, "INSERT INTO \"from\" VALUES (1, 'x')");
+    run(&mut engine, "INSERT INTO \"to\" VALUES (7, 1)");
+    assert!(engine.execute(parse_statement("INSERT INTO \"to\" VALUES (8, 5)").unwrap()).is_err());
+
+    assert_eq!(
+        query(&mut engine, "SELECT \"to\".id, \"from\".\"where\" FROM \"to\" JOIN \"from\" ON \"to\".\"from id\" = \"from\".id"),
+        vec![vec!["7".to_string(), "x".to_string()]]
+    );
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT f.\"where\" FROM \"from\" f WHERE f.\"where\" = 'x'"),
+        vec![vec!["x".to_string()]]
+    );
+
+    run(&mut engine, "CREATE INDEX `idx where` ON `from` (`where`)");
+    let index = engine.catalog.all_indexes().into_iter().find(|idx| idx.name == "idx where").unwrap();
+    assert_eq!((index.table_name.as_str(), index.column_name.as_str()), ("from", "where"));
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM \"from\" WHERE \"where\" = 'x'"), vec![vec!["1".to_string()]]);
+}
+
+#[test]
+fn qualified_quoted_identifier_is_one_column_reference() {
+    let stmt = parse_statement("SELECT t.\"order\", \"my t\".\"group\" FROM t").unwrap();
+    match stmt {
+        Statement::Select { columns, .. } => {
+            let names: Vec<&str> = columns
+                .iter()
+                .map(|col| match &col.expr {
+                    SelectItem::Column(name) => name.as_str(),
+                    other => panic!("Expected column, got {:?}", other),
+                })
+                .collect();
+            assert_eq!(names, ["t.order", "my t.group"]);
+        }
+        other => panic!("Expected select statement, got {:?}", other),
+    }
+}
@@ -1,4 +1,27 @@
-use aerodb::sql::{ast::{Expr, SelectItem, Statement, TableRef}, parser::parse_statement};
+use aerodb::{
+    catalog::Catalog,
+    execution::runtime::{execute_select_statement, handle_statement},
+    sql::{ast::{Expr, SelectItem, Statement, TableRef}, parser::parse_statement},
+    storage::pager::Pager,
+};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
 
 #[test]
 fn insert_quoted_literal_with_spaces() {
@@ -52,3 +75,33 @@ fn where_clause_quoted_literal_with_spaces() {
         other => panic!("Expected select statement, got {:?}", other),
     }
 }
+
+#[test]
+fn spaced_and_punctuated_literals_survive_insert_and_where() {
+    let mut catalog = setup_catalog("test_quoted_tokens_roundtrip.db");
+    run(&mut catalog, "CREATE TABLE notes (id INTEGER, title TEXT, body TEXT DEFAULT 'n/a, none')");
+    run(&mut catalog, "INSERT INTO notes VALUES (1, 'hello world', 'a,b (c)'), (2, '  two  spaces ', 'x <= y; z')");
+    run(&mut catalog, "INSERT INTO notes (id, title) VALUES (3, 'it''s here')");
+
+    assert_eq!(
+        select(&mut catalog, "SELECT title, body FROM notes"),
+        vec![
+            vec!["hello world".to_string(), "a,b (c)".to_string()],
+            vec!["  two  spaces ".to_string(), "x <= y; z".to_string()],
+            vec!["it's here".to_string(), "n/a, none".to_string()],
+        ]
+    );
+    assert_eq!(select(&mut catalog, "SELECT id FROM notes WHERE title = 'hello world'"), vec![vec!["1".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM notes WHERE title='  two  spaces '"), vec![vec!["2".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM notes WHERE body = 'a,b (c)'"), vec![vec!["1".to_string()]]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM notes WHERE title = 'it''s here'"), vec![vec!["3".to_string()]]);
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM notes WHERE id IN (SELECT id FROM notes WHERE title <> 'it''s here')").len(),
+        2
+    );
+
+    run(&mut catalog, "UPDATE notes SET body = 'moved, again' WHERE title = 'hello world'");
+    assert_eq!(select(&mut catalog, "SELECT body FROM notes WHERE id = 1"), vec![vec!["moved, again".to_string()]]);
+    run(&mut catalog, "UPDATE notes SET body = 'that''s all' WHERE id = 2");
+    assert_eq!(select(&mut catalog, "SELECT body FROM notes WHERE id = 2"), vec![vec!["that's all".to_string()]]);
+}
@@ -0,0 +1,139 @@
+use aerodb::{engine::Engine, storage::{pager::Pager, btree::BTree, page::{self, NODE_LEAF}, row::{ColumnValue, RowData, RowKey}}, sql::parser::parse_statement, execution::runtime::execute_select_statement, transaction::Snapshot};
+use std::fs;
+
+fn open_pager(filename: &str) -> Pager {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Pager::new(filename).unwrap()
+}
+
+/// Bulk load the even keys 0..4000 with rows wide enough that the tree
+/// grows two internal levels.
+fn even_keys_tree(pager: &mut Pager) -> u32 {
+    BTree::new(pager).unwrap();
+    let root = pager.allocate_page().unwrap();
+    let page = pager.get_page(root).unwrap();
+    page::set_node_type(&mut page.data, NODE_LEAF);
+    page::set_is_root(&mut page.data, true);
+    page::set_parent(&mut page.data, 0);
+    page::set_cell_count(&mut page.data, 0);
+    page::set_next_leaf(&mut page.data, 0);
+    pager.flush_page(root).unwrap();
+
+    let rows = (0..2000)
+        .map(|i| (i * 2, RowData(vec![ColumnValue::Text("r".repeat(900))])))
+        .collect();
+    let root = BTree::open_root(pager, root).unwrap().bulk_load(rows).unwrap();
+    BTree::open_root(pager, root).unwrap().verify().unwrap();
+    root
+}
+
+fn keys_from(pager: &mut Pager, root: u32, key: RowKey, inclusive: bool) -> Vec<RowKey> {
+    let mut tree = BTree::open_root(pager, root).unwrap();
+    tree.scan_from(key, inclusive).map(|row| row.key).collect()
+}
+
+fn keys_desc_from(pager: &mut Pager, root: u32, key: RowKey, inclusive: bool) -> Vec<RowKey> {
+    let mut tree = BTree::open_root(pager, root).unwrap();
+    tree.scan_desc_from(key, inclusive).map(|row| row.key).collect()
+}
+
+#[test]
+fn scan_from_a_mid_key_yields_the_tail_in_order() {
+    let mut pager = open_pager("test_range_scan_from.db");
+    let root = even_keys_tree(&mut pager);
+    // Four rows per leaf put a second internal level under the root.
+    let first_child = u32::from_le_bytes(pager.get_page(root).unwrap().data[page::HEADER_SIZE..page::HEADER_SIZE + 4].try_into().unwrap());
+    assert_ne!(page::get_node_type(&pager.get_page(first_child).unwrap().data), NODE_LEAF);
+
+    // Present keys, absent keys between them, and the ends of the range.
+    for key in [-5, 0, 1, 2, 777, 1000, 1001, 2998, 3999, 9000] {
+        let inclusive: Vec<RowKey> = (0..4000).step_by(2).filter(|&k| k >= key).collect();
+        let exclusive: Vec<RowKey> = (0..4000).step_by(2).filter(|&k| k > key).collect();
+        assert_eq!(keys_from(&mut pager, root, key, true), inclusive, "from {}", key);
+        assert_eq!(keys_from(&mut pager, root, key, false), exclusive, "after {}", key);
+    }
+}
+
+#[test]
+fn scan_desc_from_a_mid_key_yields_the_head_in_reverse() {
+    let mut pager = open_pager("test_range_scan_desc.db");
+    let root = even_keys_tree(&mut pager);
+
+    for key in [-5, 0, 1, 2, 777, 1000, 1001, 2998, 3999, 9000] {
+        let inclusive: Vec<RowKey> = (0..4000).rev().filter(|&k| k % 2 == 0 && k <= key).collect();
+        let exclusive: Vec<RowKey> = (0..4000).rev().filter(|&k| k % 2 == 0 && k < key).collect();
+        assert_eq!(keys_desc_from(&mut pager, root, key, true), inclusive, "down from {}", key);
+        assert_eq!(keys_desc_from(&mut pager, root, key, false), exclusive, "below {}", key);
+    }
+}
+
+#[test]
+fn range_cursors_skip_deleted_rows() {
+    let mut pager = open_pager("test_range_scan_deleted.db");
+    let root = even_keys_tree(&mut pager);
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    for key in (1000..1100).step_by(2) {
+        assert!(tree.delete(key).unwrap());
+    }
+
+    let tail: Vec<RowKey> = (1100..4000).step_by(2).collect();
+    assert_eq!(keys_from(&mut pager, root, 1000, true), tail);
+    let head: Vec<RowKey> = (0..1000).rev().filter(|k| k % 2 == 0).collect();
+    assert_eq!(keys_desc_from(&mut pager, root, 1098, true), head);
+
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    let snapshot = Snapshot::new(u64::MAX, Vec::new());
+    let window: Vec<RowKey> = tree
+        .scan_visible_range(Some((990, false)), Some((1110, true)), &snapshot)
+        .unwrap()
+        .into_iter()
+        .map(|row| row.key)
+        .collect();
+    assert_eq!(window, vec![992, 994, 996, 998, 1100, 1102, 1104, 1106, 1108, 1110]);
+}
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn exec(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn ids(engine: &mut Engine, sql: &str) -> Vec<i64> {
+    let mut rows = Vec::new();
+    execute_select_statement(&mut engine.catalog, &parse_statement(sql).unwrap(), &mut rows, None).unwrap();
+    rows.into_iter().map(|row| row[0].parse().unwrap()).collect()
+}
+
+#[test]
+fn where_clauses_on_the_key_column_scan_only_the_range() {
+    let mut engine = setup_engine("test_range_scan_sql.db");
+    exec(&mut engine, "CREATE TABLE t (id INTEGER, name TEXT)");
+    for id in 0..400 {
+        exec(&mut engine, &format!("INSERT INTO t VALUES ({}, 'name{}')", id, id % 7));
+    }
+
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE id > 395"), vec![396, 397, 398, 399]);
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE 3 >= id"), vec![0, 1, 2, 3]);
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE id BETWEEN 200 AND 203"), vec![200, 201, 202, 203]);
+    assert_eq!(
+        ids(&mut engine, "SELECT id FROM t WHERE id >= 100 AND id < 130 AND name = 'name2'"),
+        vec![100, 107, 114, 121, 128]
+    );
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE id > 10 AND id < 5"), Vec::<i64>::new());
+    // Only conjuncts bound the scan; an OR still sees every row.
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE id < 2 OR id > 398"), vec![0, 1, 399]);
+
+    // The range scan applies the same visibility rules as a full scan.
+    exec(&mut engine, "BEGIN");
+    exec(&mut engine, "DELETE FROM t WHERE id = 397");
+    exec(&mut engine, "INSERT INTO t VALUES (400, 'late')");
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE id > 395"), vec![396, 398, 399, 400]);
+    exec(&mut engine, "ROLLBACK");
+    assert_eq!(ids(&mut engine, "SELECT id FROM t WHERE id > 395"), vec![396, 397, 398, 399]);
+}
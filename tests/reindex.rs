@@ -0,0 +1,84 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_select_statement, QueryResult}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) -> QueryResult {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap()
+}
+
+fn message(catalog: &mut Catalog, sql: &str) -> String {
+    match run(catalog, sql) {
+        QueryResult::Message(message) => message,
+        _ => panic!("expected a message from {}", sql),
+    }
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &parse_statement(sql).unwrap(), &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r.join(",")).collect()
+}
+
+#[test]
+fn parse_reindex_forms() {
+    assert!(matches!(parse_statement("REINDEX").unwrap(), Statement::Reindex { index: None, table: None }));
+    match parse_statement("REINDEX INDEX idx_name").unwrap() {
+        Statement::Reindex { index, table } => {
+            assert_eq!(index.as_deref(), Some("idx_name"));
+            assert!(table.is_none());
+        }
+        _ => panic!("expected REINDEX"),
+    }
+    match parse_statement("reindex table users").unwrap() {
+        Statement::Reindex { index, table } => {
+            assert!(index.is_none());
+            assert_eq!(table.as_deref(), Some("users"));
+        }
+        _ => panic!("expected REINDEX"),
+    }
+    assert!(parse_statement("REINDEX INDEX").is_err());
+    assert!(parse_statement("REINDEX users").is_err());
+}
+
+#[test]
+fn reindex_rebuilds_the_selected_indexes() {
+    let mut catalog = setup_catalog("test_reindex_scope.db");
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT, age INTEGER)");
+    run(&mut catalog, "CREATE TABLE pets (id INTEGER, owner INTEGER)");
+    run(&mut catalog, "CREATE INDEX idx_name ON users (name)");
+    run(&mut catalog, "CREATE INDEX idx_age ON users (age)");
+    run(&mut catalog, "CREATE INDEX idx_owner ON pets (owner)");
+    // Enough distinct values that each index spans several leaves.
+    for id in 0..600 {
+        run(&mut catalog, &format!("INSERT INTO users VALUES ({}, 'user-{:0>60}', {})", id, id, id % 300));
+        run(&mut catalog, &format!("INSERT INTO pets VALUES ({}, {})", id, id % 200));
+    }
+
+    assert_eq!(message(&mut catalog, "REINDEX INDEX idx_name"), "1 index(es) rebuilt");
+    assert_eq!(message(&mut catalog, "REINDEX TABLE users"), "2 index(es) rebuilt");
+    assert_eq!(message(&mut catalog, "REINDEX"), "3 index(es) rebuilt");
+    assert_eq!(catalog.integrity_check().unwrap(), None);
+
+    let name = format!("user-{:0>60}", 321);
+    assert_eq!(select(&mut catalog, &format!("SELECT id FROM users WHERE name = '{}'", name)), vec!["321"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM users WHERE age = 7"), vec!["7", "307"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM pets WHERE owner = 3"), vec!["3", "203", "403"]);
+
+    // The rebuilt trees keep taking new entries.
+    run(&mut catalog, "INSERT INTO users VALUES (600, 'late', 7)");
+    assert_eq!(select(&mut catalog, "SELECT id FROM users WHERE name = 'late'"), vec!["600"]);
+}
+
+#[test]
+fn reindex_reports_unknown_names() {
+    let mut catalog = setup_catalog("test_reindex_unknown.db");
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    assert!(handle_statement(&mut catalog, parse_statement("REINDEX INDEX missing").unwrap()).is_err());
+    assert!(handle_statement(&mut catalog, parse_statement("REINDEX TABLE missing").unwrap()).is_err());
+    assert_eq!(message(&mut catalog, "REINDEX TABLE users"), "0 index(es) rebuilt");
+}
@@ -0,0 +1,121 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+fn row(id: &str, name: &str) -> Vec<String> {
+    vec![id.to_string(), name.to_string()]
+}
+
+#[test]
+fn parse_rename_statements() {
+    assert!(matches!(
+        parse_statement("ALTER TABLE users RENAME TO members").unwrap(),
+        Statement::RenameTable { ref old_name, ref new_name } if old_name == "users" && new_name == "members"
+    ));
+    assert!(matches!(
+        parse_statement("ALTER TABLE users RENAME COLUMN id TO user_id").unwrap(),
+        Statement::RenameColumn { ref table_name, ref old_name, ref new_name }
+            if table_name == "users" && old_name == "id" && new_name == "user_id"
+    ));
+    assert!(parse_statement("ALTER TABLE users RENAME members").is_err());
+    assert!(matches!(
+        parse_statement("ALTER INDEX idx_a RENAME TO idx_b").unwrap(),
+        Statement::RenameIndex { ref old_name, ref new_name } if old_name == "idx_a" && new_name == "idx_b"
+    ));
+    assert!(parse_statement("ALTER INDEX idx_a TO idx_b").is_err());
+}
+
+#[test]
+fn rename_keeps_auto_increment_sequence() {
+    let filename = "test_rename_sequence.db";
+    let mut catalog = setup_catalog(filename);
+    run(&mut catalog, "CREATE TABLE users (id INT NOT NULL AUTO_INCREMENT, name TEXT)");
+    run(&mut catalog, "INSERT INTO users (name) VALUES ('a'), ('b')");
+
+    run(&mut catalog, "ALTER TABLE users RENAME TO members");
+    assert!(catalog.get_table("users").is_err());
+    run(&mut catalog, "INSERT INTO members VALUES (DEFAULT, 'c')");
+
+    run(&mut catalog, "ALTER TABLE members RENAME COLUMN id TO member_id");
+    run(&mut catalog, "INSERT INTO members (name) VALUES ('d')");
+    assert_eq!(
+        select(&mut catalog, "SELECT member_id, name FROM members"),
+        vec![row("1", "a"), row("2", "b"), row("3", "c"), row("4", "d")]
+    );
+
+    // The renamed sequence is persisted.
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    run(&mut catalog, "INSERT INTO members (name) VALUES ('e')");
+    assert_eq!(select(&mut catalog, "SELECT name FROM members WHERE member_id = 5"), vec![vec!["e".to_string()]]);
+}
+
+#[test]
+fn rename_carries_indexes_and_foreign_keys() {
+    let filename = "test_rename_dependents.db";
+    let mut catalog = setup_catalog(filename);
+    run(&mut catalog, "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)");
+    run(&mut catalog, "CREATE TABLE orders (id INTEGER, user_id INTEGER, FOREIGN KEY (user_id) REFERENCES users(id))");
+    run(&mut catalog, "CREATE INDEX idx_users_name ON users(name)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'a')");
+
+    run(&mut catalog, "ALTER TABLE users RENAME TO people");
+    run(&mut catalog, "ALTER TABLE people RENAME COLUMN id TO person_id");
+    let fk = &catalog.get_table("orders").unwrap().fks[0];
+    assert_eq!(fk.parent_table, "people");
+    assert_eq!(fk.parent_columns, vec!["person_id".to_string()]);
+    assert!(catalog.find_index("people", "name").is_some());
+
+    run(&mut catalog, "INSERT INTO orders VALUES (10, 1)");
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO orders VALUES (11, 2)").unwrap()).is_err());
+    assert!(handle_statement(&mut catalog, parse_statement("ALTER TABLE people RENAME TO orders").unwrap()).is_err());
+    assert!(handle_statement(&mut catalog, parse_statement("ALTER TABLE people RENAME COLUMN missing TO x").unwrap()).is_err());
+
+    drop(catalog);
+    let catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.get_table("people").unwrap().columns[0].0, "person_id");
+    assert_eq!(catalog.get_table("orders").unwrap().fks[0].parent_table, "people");
+}
+
+#[test]
+fn rename_index_keeps_its_tree_and_rejects_taken_names() {
+    let filename = "test_rename_index.db";
+    let mut catalog = setup_catalog(filename);
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut catalog, "CREATE TABLE orders (id INTEGER, user_id INTEGER)");
+    run(&mut catalog, "CREATE INDEX idx_name ON users(name)");
+    run(&mut catalog, "CREATE INDEX idx_user ON orders(user_id)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'a'), (2, 'b')");
+
+    run(&mut catalog, "ALTER INDEX idx_name RENAME TO idx_users_name");
+    assert!(!catalog.index_exists("idx_name"));
+    assert_eq!(catalog.find_index("users", "name").unwrap().name, "idx_users_name");
+    run(&mut catalog, "INSERT INTO users VALUES (3, 'b')");
+    assert_eq!(select(&mut catalog, "SELECT id FROM users WHERE name = 'b'"), vec![vec!["2".to_string()], vec!["3".to_string()]]);
+
+    // Index names are unique across tables.
+    assert!(handle_statement(&mut catalog, parse_statement("ALTER INDEX idx_users_name RENAME TO idx_user").unwrap()).is_err());
+    assert!(handle_statement(&mut catalog, parse_statement("ALTER INDEX missing RENAME TO idx_x").unwrap()).is_err());
+    assert_eq!(catalog.find_index("orders", "user_id").unwrap().name, "idx_user");
+
+    drop(catalog);
+    let catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert!(catalog.index_exists("idx_users_name"));
+    assert!(!catalog.index_exists("idx_name"));
+}
@@ -0,0 +1,137 @@
+use aerodb::{engine::Engine, storage::{pager::Pager, btree::BTree, row::{ColumnValue, RowData, RowKey}}, sql::parser::parse_statement, execution::runtime::execute_select_statement, transaction::{Snapshot, TransactionStatus, TransactionTable}};
+use std::collections::BTreeSet;
+use std::fs;
+
+fn open_pager(filename: &str) -> Pager {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Pager::new(filename).unwrap()
+}
+
+/// Deterministic xorshift sequence so failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Mostly short payloads, with the occasional one long enough to spill.
+fn payload(key: RowKey) -> RowData {
+    let len = if key % 17 == 0 { 1500 } else { 8 + (key % 5) as usize };
+    RowData(vec![ColumnValue::Integer(key as i32), ColumnValue::Text("x".repeat(len))])
+}
+
+fn visible_rows(tree: &mut BTree) -> usize {
+    tree.scan_visible(&Snapshot::new(u64::MAX, Vec::new())).unwrap().len()
+}
+
+/// Insert or delete random keys, checking the maintained count against a full
+/// scan and the structural invariants as the tree grows.
+fn churn(tree: &mut BTree, live: &mut BTreeSet<RowKey>, rng: &mut Rng, ops: usize, key_space: u64) {
+    for op in 0..ops {
+        let key = (rng.next() % key_space) as RowKey;
+        if live.remove(&key) {
+            assert!(tree.delete(key).unwrap());
+        } else {
+            tree.insert(key, payload(key)).unwrap();
+            live.insert(key);
+        }
+        if op % 200 == 0 {
+            assert_eq!(tree.row_count().unwrap(), live.len(), "after {} ops", op);
+        }
+    }
+    assert_eq!(tree.row_count().unwrap(), live.len());
+    assert_eq!(tree.row_count().unwrap(), visible_rows(tree));
+    tree.verify().unwrap();
+}
+
+#[test]
+fn row_count_matches_scan_after_random_inserts_and_deletes() {
+    let mut pager = open_pager("test_row_count_random.db");
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    let mut live = BTreeSet::new();
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    churn(&mut tree, &mut live, &mut rng, 3000, 1500);
+}
+
+#[test]
+fn row_count_survives_splits_in_a_three_level_tree() {
+    let mut pager = open_pager("test_row_count_deep.db");
+    let root = BTree::create_empty(&mut pager).unwrap();
+    // Wide rows keep leaves small, so a few thousand fill two internal levels.
+    let rows: Vec<(RowKey, RowData)> = (0..6000)
+        .map(|i| (i * 2, RowData(vec![ColumnValue::Text("w".repeat(300))])))
+        .collect();
+    let root = BTree::open_root(&mut pager, root).unwrap().bulk_load(rows).unwrap();
+
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    assert_eq!(tree.row_count().unwrap(), 6000);
+    tree.verify().unwrap();
+
+    // Odd keys land between the packed even keys and split full leaves.
+    let mut live: BTreeSet<RowKey> = (0..6000).map(|i| i * 2).collect();
+    let mut rng = Rng(42);
+    churn(&mut tree, &mut live, &mut rng, 800, 12_000);
+}
+
+#[test]
+fn row_count_ignores_vacuumed_versions() {
+    let mut pager = open_pager("test_row_count_vacuum.db");
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    for key in 0..500 {
+        tree.insert(key, payload(key)).unwrap();
+    }
+    for key in (0..500).step_by(3) {
+        tree.delete(key).unwrap();
+    }
+    assert_eq!(tree.row_count().unwrap(), 333);
+    let committed = TransactionTable::from([(0, TransactionStatus::Committed(0))]);
+    assert_eq!(tree.vacuum_deleted_versions(u64::MAX, &committed).unwrap(), 167);
+    assert_eq!(tree.row_count().unwrap(), 333);
+    assert_eq!(visible_rows(&mut tree), 333);
+    tree.verify().unwrap();
+}
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn exec(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn count(engine: &mut Engine, sql: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    execute_select_statement(&mut engine.catalog, &parse_statement(sql).unwrap(), &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn count_star_follows_deletes_and_rollbacks() {
+    let mut engine = setup_engine("test_row_count_sql.db");
+    exec(&mut engine, "CREATE TABLE t (id INTEGER, name TEXT)");
+    for id in 0..300 {
+        exec(&mut engine, &format!("INSERT INTO t VALUES ({}, 'n{}')", id, id));
+    }
+    exec(&mut engine, "DELETE FROM t WHERE id < 100");
+    assert_eq!(count(&mut engine, "SELECT COUNT(*) FROM t"), vec![vec!["200".to_string()]]);
+    assert_eq!(count(&mut engine, "SELECT COUNT(*) FROM t WHERE id < 150"), vec![vec!["50".to_string()]]);
+
+    // Versions of an open or aborted transaction are not in the scan's view.
+    exec(&mut engine, "BEGIN");
+    exec(&mut engine, "INSERT INTO t VALUES (1000, 'pending')");
+    assert_eq!(count(&mut engine, "SELECT COUNT(*) FROM t"), vec![vec!["201".to_string()]]);
+    exec(&mut engine, "ROLLBACK");
+    assert_eq!(count(&mut engine, "SELECT COUNT(*) FROM t"), vec![vec!["200".to_string()]]);
+    assert_eq!(engine.catalog.integrity_check().unwrap(), None);
+}
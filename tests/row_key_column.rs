@@ -0,0 +1,50 @@
+use aerodb::{catalog::Catalog, error::DbError, storage::{pager::Pager, row::ColumnType}, sql::parser::parse_statement, execution::runtime::handle_statement};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+#[test]
+fn create_table_rejects_a_non_integer_first_column() {
+    let mut catalog = setup_catalog("test_row_key_reject.db");
+    for sql in [
+        "CREATE TABLE users (name TEXT, age INTEGER)",
+        "CREATE TABLE flags (on_off BOOLEAN)",
+        "CREATE TABLE prices (amount DECIMAL(8,2), id INTEGER PRIMARY KEY)",
+    ] {
+        match handle_statement(&mut catalog, parse_statement(sql).unwrap()) {
+            Err(DbError::Io(e)) => {
+                assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+                assert!(e.to_string().contains("row key"), "{}", e);
+            }
+            other => panic!("{}: expected rejection, got {:?}", sql, other),
+        }
+    }
+    // Nothing was created, so the names are still free.
+    assert!(catalog.get_table("users").is_err());
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'ann')").unwrap()).unwrap();
+}
+
+#[test]
+fn create_table_accepts_integer_first_columns() {
+    let mut catalog = setup_catalog("test_row_key_accept.db");
+    for (name, ty) in [("a", "INTEGER"), ("b", "BIGINT"), ("c", "SMALLINT"), ("d", "MEDIUMINT(6) UNSIGNED")] {
+        let sql = format!("CREATE TABLE {} (id {}, v TEXT)", name, ty);
+        handle_statement(&mut catalog, parse_statement(&sql).unwrap()).unwrap();
+        handle_statement(&mut catalog, parse_statement(&format!("INSERT INTO {} VALUES (7, 'x')", name)).unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn catalog_api_rejects_a_non_integer_first_column() {
+    let mut catalog = setup_catalog("test_row_key_api.db");
+    let err = catalog
+        .create_table_with_fks("t", vec![("code".into(), ColumnType::Text, false, None, false)], Vec::new(), None)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(catalog.get_table("t").is_err());
+}
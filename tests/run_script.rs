@@ -0,0 +1,88 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    error::DbError,
+    execution::runtime::{QueryResult, execute_select_statement},
+    sql::parser::{parse_statement, split_statements},
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+const SEED: &str = "
+-- Schema
+CREATE TABLE products (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL, /* display name */
+    price INTEGER
+);
+
+-- Seed data; the semicolon inside the string is not a separator.
+INSERT INTO products VALUES (1, 'widget; small', 5), (2, 'gadget', 12);
+INSERT INTO products VALUES (3, 'doohickey', 7);
+UPDATE products SET price = 6 WHERE id = 1;
+DELETE FROM products WHERE id = 2;
+SELECT name FROM products WHERE id = 3 -- no trailing semicolon
+";
+
+#[test]
+fn script_runs_every_statement_in_order() {
+    let filename = "test_run_script_seed.db";
+    let mut engine = setup_engine(filename);
+    let results = engine.run_script(SEED).unwrap();
+    assert_eq!(results.len(), 6);
+    assert!(matches!(results[1], QueryResult::Affected(2)));
+    match &results[5] {
+        QueryResult::Rows { rows, .. } => assert_eq!(rows, &vec![vec!["doohickey".to_string()]]),
+        other => panic!("Expected rows, got {:?}", other),
+    }
+
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT id, name, price FROM products"),
+        vec![
+            vec!["1".to_string(), "widget; small".to_string(), "6".to_string()],
+            vec!["3".to_string(), "doohickey".to_string(), "7".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn script_stops_at_the_first_failing_statement() {
+    let filename = "test_run_script_error.db";
+    let mut engine = setup_engine(filename);
+    let script = "CREATE TABLE t (id INTEGER PRIMARY KEY);
+        INSERT INTO t VALUES (1);
+        INSERT INTO t VALUES (1);
+        INSERT INTO t VALUES (2);";
+    let err = engine.run_script(script).unwrap_err();
+    assert_eq!(err.statement, 3);
+    assert!(matches!(err.error, DbError::DuplicateKey(1)));
+    assert!(err.to_string().starts_with("statement 3: "), "{}", err);
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM t"), vec![vec!["1".to_string()]]);
+
+    let err = engine.run_script("SELECT id FROM t; SELEC id FROM t").unwrap_err();
+    assert_eq!(err.statement, 2);
+    assert!(matches!(err.error, DbError::ParseError(_)));
+}
+
+#[test]
+fn splitting_ignores_semicolons_in_quotes_and_comments() {
+    let script = "SELECT 'a;b'; -- c; d\nSELECT \"x;y\" /* ; */ FROM t;; \n -- only a comment;\n";
+    assert_eq!(
+        split_statements(script),
+        vec!["SELECT 'a;b'", "-- c; d\nSELECT \"x;y\" /* ; */ FROM t"]
+    );
+    assert!(split_statements("  ; -- nothing here\n").is_empty());
+}
@@ -111,6 +111,7 @@ fn joins_and_aggregates_read_visible_rows_inside_transaction() {
         let plan = aerodb::execution::plan::MultiJoinPlan {
             base_table,
             base_alias: None,
+            base_subquery: None,
             joins,
             projections: columns,
             where_predicate,
@@ -0,0 +1,62 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::{Expr, Statement}}, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE emp (id INTEGER, dept TEXT, salary INTEGER)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO emp VALUES (1, 'eng', 100), (2, 'eng', 200), (3, 'ops', 50), (4, 'ops', 70)").unwrap()).unwrap();
+    catalog
+}
+
+fn ids(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r[0].clone()).collect()
+}
+
+#[test]
+fn parse_scalar_subquery() {
+    match parse_statement("SELECT id FROM emp WHERE salary <> (SELECT MAX(salary) FROM emp) AND id > 1").unwrap() {
+        Statement::Select { where_predicate: Some(Expr::And(left, _)), .. } => match *left {
+            Expr::ScalarSubquery { left, op, .. } => {
+                assert_eq!(left, "salary");
+                assert_eq!(op, "!=");
+            }
+            other => panic!("expected scalar subquery, got {:?}", other),
+        },
+        other => panic!("unexpected statement {:?}", other),
+    }
+}
+
+#[test]
+fn uncorrelated_scalar_subquery() {
+    let mut catalog = setup_catalog("test_scalar_uncorrelated.db");
+    assert_eq!(ids(&mut catalog, "SELECT id FROM emp WHERE salary > (SELECT AVG(salary) FROM emp)"), vec!["2"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM emp WHERE salary < (SELECT AVG(salary) FROM emp)"), vec!["1", "3", "4"]);
+    assert_eq!(ids(&mut catalog, "SELECT id FROM emp WHERE salary = (SELECT MIN(salary) FROM emp)"), vec!["3"]);
+    // An empty subquery yields NULL, which matches nothing.
+    assert!(ids(&mut catalog, "SELECT id FROM emp WHERE salary = (SELECT salary FROM emp WHERE id = 99)").is_empty());
+}
+
+#[test]
+fn correlated_scalar_subquery() {
+    let mut catalog = setup_catalog("test_scalar_correlated.db");
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE budgets (id INTEGER, dept TEXT, cap INTEGER)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO budgets VALUES (1, 'eng', 150), (2, 'ops', 60)").unwrap()).unwrap();
+    assert_eq!(
+        ids(&mut catalog, "SELECT id FROM emp WHERE salary > (SELECT cap FROM budgets WHERE budgets.dept = emp.dept)"),
+        vec!["2", "4"]
+    );
+}
+
+#[test]
+fn scalar_subquery_rejects_multiple_rows() {
+    let mut catalog = setup_catalog("test_scalar_multi.db");
+    let stmt = parse_statement("SELECT id FROM emp WHERE salary = (SELECT salary FROM emp)").unwrap();
+    let mut rows = Vec::new();
+    let err = execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap_err();
+    assert!(err.to_string().contains("more than one row"), "{}", err);
+}
@@ -31,8 +31,8 @@ fn execute_select_add_expr() {
     handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "numbers".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "val".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "val".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -51,8 +51,8 @@ fn execute_select_mul_double_expr() {
     handle_statement(&mut catalog, Statement::CreateTable {
         table_name: "orders".into(),
         columns: vec![
-            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false },
-            ColumnDef { name: "total".into(), col_type: ColumnType::Double { precision: 8, scale: 2, unsigned: true }, not_null: false, default_value: None, auto_increment: false, primary_key: false },
+            ColumnDef { name: "id".into(), col_type: ColumnType::Integer, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
+            ColumnDef { name: "total".into(), col_type: ColumnType::Double { precision: 8, scale: 2, unsigned: true }, not_null: false, default_value: None, auto_increment: false, primary_key: false, generated: None, nocase: false },
         ],
         fks: Vec::new(), primary_key: None, if_not_exists: false,
     }).unwrap();
@@ -64,3 +64,30 @@ fn execute_select_mul_double_expr() {
         assert_eq!(out, vec![vec![String::from("1"), String::from("21")]]);
     } else { panic!("expected select") }
 }
+
+#[test]
+fn execute_select_multi_column_exprs() {
+    let filename = "test_select_multi_column_exprs.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE lines (id INTEGER, price DOUBLE, quantity INTEGER, a INTEGER, b BIGINT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO lines VALUES (1, 2.5, 4, 3, 10), (2, 1.25, 3, -1, 7), (3, 9.99, 0, 5, NULL)").unwrap()).unwrap();
+
+    let mut out = Vec::new();
+    let stmt = parse_statement("SELECT id, price * quantity AS total FROM lines").unwrap();
+    let header = execute_select_statement(&mut catalog, &stmt, &mut out, None).unwrap();
+    assert!(matches!(header[1], (ref name, ColumnType::Double { .. }) if name == "total"));
+    assert_eq!(out, vec![vec!["1".to_string(), "10".into()], vec!["2".into(), "3.75".into()], vec!["3".into(), "0".into()]]);
+
+    let mut out = Vec::new();
+    let stmt = parse_statement("SELECT a + b, a * 2, b - a FROM lines").unwrap();
+    let header = execute_select_statement(&mut catalog, &stmt, &mut out, None).unwrap();
+    assert!(header.iter().all(|(_, ty)| *ty == ColumnType::BigInt));
+    assert_eq!(
+        out,
+        vec![
+            vec!["13".to_string(), "6".into(), "7".into()],
+            vec!["6".into(), "-2".into(), "8".into()],
+            vec!["NULL".into(), "10".into(), "NULL".into()],
+        ]
+    );
+}
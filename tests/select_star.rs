@@ -0,0 +1,68 @@
+use aerodb::{
+    engine::Engine,
+    execution::runtime::QueryResult,
+    sql::{ast::{SelectItem, Statement}, parser::parse_statement},
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn query(engine: &mut Engine, sql: &str) -> (Vec<String>, Vec<Vec<String>>) {
+    match engine.execute(parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { header, rows } => (header.into_iter().map(|(n, _)| n).collect(), rows),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn star_combines_with_other_items() {
+    let mut engine = setup_engine("test_select_star_mixed.db");
+    run(&mut engine, "CREATE TABLE items (id INTEGER, price INTEGER)");
+    run(&mut engine, "INSERT INTO items VALUES (1, 10), (2, 25)");
+
+    let (header, rows) = query(&mut engine, "SELECT *, price * 2 AS doubled FROM items");
+    assert_eq!(header, ["id", "price", "doubled"]);
+    assert_eq!(rows, [["1", "10", "20"], ["2", "25", "50"]]);
+
+    let (header, rows) = query(&mut engine, "SELECT 'x' AS tag, i.* FROM items i WHERE id = 2");
+    assert_eq!(header, ["tag", "id", "price"]);
+    assert_eq!(rows, [["x", "2", "25"]]);
+
+    assert!(engine.execute(parse_statement("SELECT other.* FROM items").unwrap()).is_err());
+}
+
+#[test]
+fn qualified_star_in_a_join() {
+    let mut engine = setup_engine("test_select_star_join.db");
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut engine, "CREATE TABLE orders (id INTEGER, user_id INTEGER, total INTEGER)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'ann'), (2, 'bob')");
+    run(&mut engine, "INSERT INTO orders VALUES (10, 1, 5), (11, 2, 7)");
+
+    let (header, mut rows) =
+        query(&mut engine, "SELECT u.*, o.total FROM users u JOIN orders o ON u.id = o.user_id");
+    rows.sort();
+    assert_eq!(header, ["u.id", "u.name", "o.total"]);
+    assert_eq!(rows, [["1", "ann", "5"], ["2", "bob", "7"]]);
+
+    let (header, _) =
+        query(&mut engine, "SELECT o.id, * FROM users u JOIN orders o ON u.id = o.user_id");
+    assert_eq!(header, ["o.id", "u.id", "u.name", "o.id", "o.user_id", "o.total"]);
+}
+
+#[test]
+fn qualified_star_parses_to_its_source() {
+    let Statement::Select { columns, .. } = parse_statement("SELECT t.*, id FROM t").unwrap() else {
+        panic!("expected select");
+    };
+    assert!(matches!(&columns[0].expr, SelectItem::AllFrom(t) if t == "t"));
+}
@@ -0,0 +1,82 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::Statement}, execution::runtime::{handle_statement, execute_show_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn show(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_show_statement(catalog, &stmt, &mut rows).unwrap();
+    handle_statement(catalog, stmt).unwrap();
+    rows
+}
+
+#[test]
+fn parse_show_statements() {
+    assert!(matches!(parse_statement("SHOW TABLES").unwrap(), Statement::ShowTables));
+    assert!(matches!(parse_statement("show indexes").unwrap(), Statement::ShowIndexes { table: None }));
+    match parse_statement("SHOW INDEXES FROM users").unwrap() {
+        Statement::ShowIndexes { table } => assert_eq!(table, Some("users".into())),
+        _ => panic!("expected SHOW INDEXES"),
+    }
+    assert!(parse_statement("SHOW INDEXES users").is_err());
+}
+
+#[test]
+fn show_tables_and_indexes() {
+    let filename = "test_show_statements.db";
+    let mut catalog = setup_catalog(filename);
+
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INT, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE orders (id INT, user_id INT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_name ON users (name)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE INDEX idx_user ON orders (user_id)").unwrap()).unwrap();
+
+    let tables = show(&mut catalog, "SHOW TABLES");
+    assert_eq!(tables, vec![vec!["orders".to_string()], vec!["users".to_string()]]);
+
+    let indexes = show(&mut catalog, "SHOW INDEXES");
+    let names: Vec<&str> = indexes.iter().map(|r| r[0].as_str()).collect();
+    assert_eq!(names, vec!["idx_name", "idx_user"]);
+
+    let indexes = show(&mut catalog, "SHOW INDEXES FROM orders");
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(&indexes[0][..3], &["idx_user", "orders", "user_id"]);
+
+    let stmt = parse_statement("SHOW INDEXES FROM missing").unwrap();
+    assert!(handle_statement(&mut catalog, stmt).is_err());
+}
+
+#[test]
+fn describe_lists_column_attributes() {
+    let filename = "test_describe_statement.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(
+        &mut catalog,
+        parse_statement(
+            "CREATE TABLE items (id INT NOT NULL AUTO_INCREMENT PRIMARY KEY, name VARCHAR(20) NOT NULL DEFAULT 'none', qty INT DEFAULT 1, note TEXT)",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(matches!(parse_statement("DESC items").unwrap(), Statement::Describe { .. }));
+    assert!(parse_statement("DESCRIBE").is_err());
+    let row = |cells: [&str; 6]| cells.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        show(&mut catalog, "DESCRIBE items"),
+        vec![
+            row(["id", "INTEGER", "false", "NULL", "true", "true"]),
+            row(["name", "VARCHAR(20)", "false", "none", "false", "false"]),
+            row(["qty", "INTEGER", "true", "1", "false", "false"]),
+            row(["note", "TEXT", "true", "NULL", "false", "false"]),
+        ]
+    );
+
+    let stmt = parse_statement("DESCRIBE missing").unwrap();
+    assert!(handle_statement(&mut catalog, stmt).is_err());
+}
@@ -0,0 +1,81 @@
+use aerodb::{
+    db::Database,
+    engine::Engine,
+    execution::runtime::QueryResult,
+    sql::{ast::Statement, parser::parse_statement},
+    transaction::IsolationLevel,
+};
+use std::fs;
+
+fn cleanup(filename: &str) {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+}
+
+fn stmt(sql: &str) -> Statement {
+    parse_statement(sql).unwrap()
+}
+
+#[test]
+fn begin_parses_snapshot_isolation() {
+    for (sql, name) in [
+        ("BEGIN TRANSACTION SNAPSHOT", None),
+        ("BEGIN SNAPSHOT", None),
+        ("BEGIN TRANSACTION ISOLATION LEVEL SNAPSHOT report", Some("report")),
+        ("BEGIN TRANSACTION SNAPSHOT tx1", Some("tx1")),
+    ] {
+        match stmt(sql) {
+            Statement::BeginTransaction { name: n, isolation } => {
+                assert_eq!(n.as_deref(), name, "{}", sql);
+                assert_eq!(isolation, Some(IsolationLevel::Snapshot), "{}", sql);
+            }
+            other => panic!("{}: unexpected {:?}", sql, other),
+        }
+    }
+    assert!(matches!(stmt("BEGIN"), Statement::BeginTransaction { isolation: None, .. }));
+    let err = parse_statement("BEGIN TRANSACTION ISOLATION LEVEL READ UNCOMMITTED").unwrap_err();
+    assert!(err.contains("Unsupported isolation level"), "{}", err);
+}
+
+#[test]
+fn snapshot_transaction_sees_its_own_writes() {
+    let filename = "test_snapshot_own_writes.db";
+    cleanup(filename);
+    let mut engine = Engine::new(filename);
+    engine.execute(stmt("CREATE TABLE t (id INTEGER, v TEXT)")).unwrap();
+    engine.execute(stmt("BEGIN TRANSACTION SNAPSHOT")).unwrap();
+    assert!(engine.catalog.transaction_snapshot().is_some());
+    engine.execute(stmt("INSERT INTO t VALUES (1, 'a')")).unwrap();
+    let count = |engine: &mut Engine| match engine.execute(stmt("SELECT id FROM t")).unwrap() {
+        QueryResult::Rows { rows, .. } => rows.len(),
+        other => panic!("expected rows, got {:?}", other),
+    };
+    assert_eq!(count(&mut engine), 1);
+    engine.execute(stmt("COMMIT")).unwrap();
+    assert_eq!(count(&mut engine), 1);
+}
+
+#[test]
+fn snapshot_does_not_see_rows_inserted_after_it_began() {
+    let filename = "test_snapshot_repeatable.db";
+    cleanup(filename);
+    let db = Database::open(filename).unwrap();
+    db.autocommit(stmt("CREATE TABLE t (id INTEGER PRIMARY KEY, v TEXT)")).unwrap();
+    db.autocommit(stmt("INSERT INTO t VALUES (1, 'before')")).unwrap();
+
+    let reader = db.begin().unwrap();
+    assert_eq!(db.query_all(&reader, "t").unwrap().len(), 1);
+    db.autocommit(stmt("INSERT INTO t VALUES (2, 'after')")).unwrap();
+    db.autocommit(stmt("DELETE FROM t WHERE id = 1")).unwrap();
+
+    // Repeated reads inside the snapshot return the rows as of BEGIN.
+    let rows = db.query_all(&reader, "t").unwrap();
+    assert_eq!(rows.iter().map(|r| r.key).collect::<Vec<_>>(), vec![1]);
+    db.commit(reader).unwrap();
+
+    let fresh = db.begin().unwrap();
+    let rows = db.query_all(&fresh, "t").unwrap();
+    assert_eq!(rows.iter().map(|r| r.key).collect::<Vec<_>>(), vec![2]);
+    db.abort(fresh).unwrap();
+}
@@ -0,0 +1,52 @@
+use aerodb::storage::{
+    btree::BTree,
+    pager::{Pager, SplitStrategy},
+    row::{ColumnValue, RowData},
+};
+use std::fs;
+
+fn open_pager(filename: &str, strategy: SplitStrategy) -> Pager {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    let mut pager = Pager::new(filename).unwrap();
+    pager.set_split_strategy(strategy);
+    pager
+}
+
+fn row(i: i64) -> RowData {
+    RowData(vec![ColumnValue::Integer(i as i32), ColumnValue::Text(format!("value-{}", i))])
+}
+
+/// Pages used after inserting `keys` in order, checking the tree afterwards.
+fn pages_after_inserts(filename: &str, strategy: SplitStrategy, keys: impl Iterator<Item = i64>) -> u32 {
+    let mut pager = open_pager(filename, strategy);
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+    let mut inserted = 0;
+    for key in keys {
+        tree.insert(key, row(key)).unwrap();
+        inserted += 1;
+    }
+    tree.verify().unwrap();
+    assert_eq!(tree.row_count().unwrap(), inserted);
+    let scanned: Vec<i64> = tree.scan_all_rows().map(|r| r.key).collect();
+    assert!(scanned.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(scanned.len(), inserted);
+    pager.num_pages()
+}
+
+#[test]
+fn append_optimized_packs_sequential_inserts() {
+    let midpoint = pages_after_inserts("test_split_midpoint.db", SplitStrategy::Midpoint, 1..=1000);
+    let append =
+        pages_after_inserts("test_split_append.db", SplitStrategy::AppendOptimized, 1..=1000);
+    assert!(append < midpoint, "append-optimized used {} pages, midpoint {}", append, midpoint);
+}
+
+#[test]
+fn append_optimized_still_splits_random_inserts_correctly() {
+    // A fixed permutation of 0..1000; non-rightmost splits fall back to the midpoint.
+    let keys = (0..1000).map(|i| (i * 389) % 1000);
+    pages_after_inserts("test_split_append_random.db", SplitStrategy::AppendOptimized, keys);
+}
@@ -0,0 +1,45 @@
+use aerodb::sql::{ast::{Expr, Statement}, parser::parse_statement};
+
+/// `Statement` has no `PartialEq`; its debug form captures the whole AST.
+fn ast(sql: &str) -> String {
+    format!("{:?}", parse_statement(sql).unwrap())
+}
+
+#[test]
+fn trailing_line_comment_is_ignored() {
+    let clean = ast("SELECT id, name FROM users WHERE id = 1");
+    assert_eq!(ast("SELECT id, name FROM users WHERE id = 1 -- look up the admin"), clean);
+    assert_eq!(ast("-- header\nSELECT id, name -- columns\nFROM users\nWHERE id = 1"), clean);
+}
+
+#[test]
+fn inline_block_comment_is_ignored() {
+    let clean = ast("INSERT INTO users (id, name) VALUES (1, 'Alice')");
+    assert_eq!(ast("INSERT INTO users /* target */ (id, name) VALUES (1, 'Alice')"), clean);
+    assert_eq!(ast("INSERT INTO users (id,/* spans\nlines */name) VALUES (1, 'Alice')"), clean);
+    assert_eq!(ast("INSERT/**/INTO users (id, name) VALUES (1, 'Alice')"), clean);
+}
+
+#[test]
+fn comment_markers_inside_string_literals_are_kept() {
+    let stmt = parse_statement("INSERT INTO t VALUES ('a -- b', 'c /* d */ e') -- trailing").unwrap();
+    match stmt {
+        Statement::Insert { rows, .. } => {
+            let values: Vec<&Expr> = rows[0].iter().collect();
+            match (values[0], values[1]) {
+                (Expr::Literal(first), Expr::Literal(second)) => {
+                    assert_eq!(first, "a -- b");
+                    assert_eq!(second, "c /* d */ e");
+                }
+                other => panic!("Expected literals, got {:?}", other),
+            }
+        }
+        other => panic!("Expected insert statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn unterminated_block_comment_is_an_error() {
+    let err = parse_statement("SELECT id FROM users /* never closed").unwrap_err();
+    assert!(err.contains("Unterminated block comment"), "{}", err);
+}
@@ -68,3 +68,53 @@ fn current_timestamp_with_parentheses() {
     }
 }
 
+
+#[test]
+fn now_and_current_date_populate_defaults_per_column_type() {
+    let filename = "test_clock_defaults.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(
+        &mut catalog,
+        parse_statement(
+            "CREATE TABLE visits (id INTEGER, created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP, seen DATETIME DEFAULT NOW(), day DATE DEFAULT CURRENT_DATE, stamp DATE DEFAULT CURRENT_TIMESTAMP)",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO visits (id) VALUES (1)").unwrap()).unwrap();
+    let mut out = Vec::new();
+    execute_select_with_indexes(&mut catalog, "visits", None, &mut out).unwrap();
+    let now = chrono::Local::now().timestamp();
+    let today = now.div_euclid(86_400) as i32;
+    match &out[0].data.0[1..] {
+        [ColumnValue::Timestamp(created), ColumnValue::DateTime(seen), ColumnValue::Date(day), ColumnValue::Date(stamp)] => {
+            assert!((created - now).abs() <= 5);
+            assert!((seen - now).abs() <= 5);
+            assert!((day - today).abs() <= 1);
+            assert_eq!(day, stamp);
+        }
+        other => panic!("unexpected values {:?}", other),
+    }
+
+    // The defaults survive a reopen of the catalog.
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO visits (id) VALUES (2)").unwrap()).unwrap();
+    let mut out = Vec::new();
+    execute_select_with_indexes(&mut catalog, "visits", None, &mut out).unwrap();
+    assert!(matches!(out[1].data.0[3], ColumnValue::Date(d) if (d - today).abs() <= 1));
+}
+
+#[test]
+fn select_clock_functions() {
+    let filename = "test_select_clock.db";
+    let mut catalog = setup_catalog(filename);
+    use aerodb::{execution::runtime::QueryResult, storage::row::ColumnType};
+    let result = handle_statement(&mut catalog, parse_statement("SELECT NOW(), CURRENT_DATE, CURRENT_TIMESTAMP").unwrap()).unwrap();
+    let QueryResult::Rows { header, rows } = result else { panic!("expected rows") };
+    let types: Vec<ColumnType> = header.into_iter().map(|(_, t)| t).collect();
+    assert_eq!(types, vec![ColumnType::DateTime, ColumnType::Date, ColumnType::DateTime]);
+    let today = chrono::DateTime::from_timestamp(chrono::Local::now().timestamp(), 0).unwrap().format("%Y-%m-%d").to_string();
+    assert_eq!(rows[0][1], today);
+    assert!(rows[0][0].starts_with(&today) && rows[0][0].len() == 19, "{:?}", rows);
+}
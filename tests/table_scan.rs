@@ -0,0 +1,60 @@
+use aerodb::{engine::Engine, sql::parser::parse_statement, storage::row::ColumnValue};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+#[test]
+fn scan_yields_rows_keyed_by_column_name_across_leaves() {
+    let filename = "test_table_scan_leaves.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE people (id INTEGER, name TEXT, age INTEGER)");
+    // Enough rows to split the table over several leaf pages.
+    for i in 1..=300 {
+        run(&mut engine, &format!("INSERT INTO people VALUES ({}, 'person-{}', {})", i, i, i % 90));
+    }
+    run(&mut engine, "DELETE FROM people WHERE id = 150");
+    run(&mut engine, "UPDATE people SET age = 99 WHERE id = 7");
+
+    let scan = engine.catalog.scan_table("people").unwrap();
+    assert_eq!(scan.columns(), ["id", "name", "age"]);
+    let rows: Vec<_> = scan.collect();
+    assert_eq!(rows.len(), 299);
+    assert!(rows.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(rows.iter().all(|(key, _)| *key != 150));
+
+    let (key, seventh) = &rows[6];
+    assert_eq!(*key, 7);
+    assert_eq!(seventh["name"], ColumnValue::Text("person-7".to_string()));
+    assert_eq!(seventh["age"], ColumnValue::Integer(99));
+    assert_eq!(rows[298].1["id"], ColumnValue::Integer(300));
+}
+
+#[test]
+fn scan_sees_the_open_transaction_and_rejects_unknown_tables() {
+    let filename = "test_table_scan_tx.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE t (id INTEGER, v TEXT)");
+    run(&mut engine, "INSERT INTO t VALUES (1, 'committed')");
+    run(&mut engine, "BEGIN");
+    run(&mut engine, "INSERT INTO t VALUES (2, 'pending')");
+    assert_eq!(engine.catalog.scan_table("t").unwrap().count(), 2);
+    run(&mut engine, "ROLLBACK");
+
+    let values: Vec<ColumnValue> = engine
+        .catalog
+        .scan_table("t")
+        .unwrap()
+        .map(|(_, mut row)| row.remove("v").unwrap())
+        .collect();
+    assert_eq!(values, vec![ColumnValue::Text("committed".to_string())]);
+    assert!(engine.catalog.scan_table("missing").is_err());
+}
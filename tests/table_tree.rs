@@ -0,0 +1,63 @@
+use aerodb::{catalog::Catalog, storage::{pager::Pager, row::{ColumnValue, RowData}}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::{fs, io};
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t (id INTEGER, name TEXT)").unwrap()).unwrap();
+    catalog
+}
+
+fn row(i: i64) -> RowData {
+    RowData(vec![ColumnValue::Integer(i as i32), ColumnValue::Text(format!("name-{}", i))])
+}
+
+fn count(catalog: &mut Catalog) -> String {
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &parse_statement("SELECT COUNT(*) FROM t").unwrap(), &mut rows, None).unwrap();
+    rows[0][0].clone()
+}
+
+#[test]
+fn with_table_tree_records_a_split_root() {
+    let filename = "test_table_tree_split.db";
+    let mut catalog = setup_catalog(filename);
+    let old_root = catalog.get_table("t").unwrap().root_page;
+
+    let tree_root = catalog
+        .with_table_tree("t", |tree| {
+            for i in 1..=500 {
+                tree.insert(i, row(i))?;
+            }
+            Ok::<_, io::Error>(tree.root_page())
+        })
+        .unwrap();
+    assert_ne!(tree_root, old_root);
+    assert_eq!(catalog.get_table("t").unwrap().root_page, tree_root);
+    assert_eq!(count(&mut catalog), "500");
+
+    drop(catalog);
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.get_table("t").unwrap().root_page, tree_root);
+    assert_eq!(count(&mut catalog), "500");
+}
+
+#[test]
+fn with_table_tree_records_the_root_when_the_closure_fails() {
+    let filename = "test_table_tree_error.db";
+    let mut catalog = setup_catalog(filename);
+    let old_root = catalog.get_table("t").unwrap().root_page;
+
+    let result = catalog.with_table_tree("t", |tree| {
+        for i in 1..=500 {
+            tree.insert(i, row(i))?;
+        }
+        tree.insert(1, row(1))
+    });
+    assert!(result.is_err());
+    assert_ne!(catalog.get_table("t").unwrap().root_page, old_root);
+    assert_eq!(count(&mut catalog), "500");
+
+    assert!(catalog.with_table_tree("missing", |_| Ok::<_, io::Error>(())).is_err());
+}
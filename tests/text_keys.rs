@@ -0,0 +1,84 @@
+use aerodb::storage::{
+    btree::BTree,
+    pager::Pager,
+    row::{ColumnValue, RowData},
+};
+
+fn row(n: i32) -> RowData {
+    RowData(vec![ColumnValue::Integer(n), ColumnValue::Text(format!("payload {}", n))])
+}
+
+#[test]
+fn text_keys_scan_back_in_sorted_order() {
+    let mut pager = Pager::new_in_memory().unwrap();
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let mut tree = BTree::open_root(&mut pager, root).unwrap();
+
+    // Keys that are prefixes of one another, and enough generated keys to split.
+    let mut keys: Vec<String> = ["applesauce", "apple", "apple pie", "applesauce2", "", "zeta", "\u{e9}clair", "Zeta"]
+        .iter()
+        .map(|k| k.to_string())
+        .collect();
+    keys.extend((0..600).map(|i| format!("key-{}", (i * 7919) % 600)));
+    for (n, key) in keys.iter().enumerate() {
+        tree.insert_text(key, row(n as i32)).unwrap();
+    }
+
+    assert!(tree.insert_text("apple", row(0)).is_err());
+    assert_eq!(tree.find_text("apple pie").unwrap(), Some(row(2)));
+    assert_eq!(tree.find_text("").unwrap(), Some(row(4)));
+    assert_eq!(tree.find_text("apples").unwrap(), None);
+
+    assert!(tree.delete_text("applesauce").unwrap());
+    assert!(!tree.delete_text("applesauce").unwrap());
+    assert_eq!(tree.find_text("applesauce2").unwrap(), Some(row(3)));
+
+    let mut expected: Vec<String> = keys.into_iter().filter(|k| k != "applesauce").collect();
+    expected.sort();
+    let scanned = tree.scan_text().unwrap();
+    let scanned_keys: Vec<String> = scanned.iter().map(|(k, _)| k.clone()).collect();
+    assert_eq!(scanned_keys, expected);
+    assert_eq!(scanned[0], (String::new(), row(4)));
+}
+
+#[test]
+fn thousands_of_keys_split_across_pages() {
+    // Small pages make the internal levels split as well as the leaves.
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("text_keys.db");
+    let mut pager = Pager::new_with_page_size(path.to_str().unwrap(), 512).unwrap();
+    let root = BTree::create_empty(&mut pager).unwrap();
+    let pages_before = pager.num_pages();
+    {
+        let mut tree = BTree::open_root(&mut pager, root).unwrap();
+
+        // Keys sharing a long common prefix are told apart by their full bytes.
+        let keys: Vec<String> = (0..5000).map(|i| format!("customer-{:06}", (i * 7919) % 5000)).collect();
+        for (n, key) in keys.iter().enumerate() {
+            tree.insert_text(key, row(n as i32)).unwrap();
+        }
+        assert!(tree.insert_text("customer-000007", row(0)).is_err());
+        for key in keys.iter().filter(|k| k.ends_with('5')) {
+            assert!(tree.delete_text(key).unwrap());
+        }
+        assert!(tree.find_text("customer-004998").unwrap().is_some());
+        assert_eq!(tree.find_text("customer-004995").unwrap(), None);
+        assert_eq!(tree.find_text("customer-0049").unwrap(), None);
+
+        // A row larger than a page goes to overflow pages and comes back whole.
+        let big = RowData(vec![ColumnValue::Text("x".repeat(20_000))]);
+        tree.insert_text("customer-big", big.clone()).unwrap();
+        assert_eq!(tree.find_text("customer-big").unwrap(), Some(big));
+
+        let mut expected: Vec<String> = keys.into_iter().filter(|k| !k.ends_with('5')).collect();
+        expected.push("customer-big".to_string());
+        expected.sort();
+        let scanned: Vec<String> = tree.scan_text().unwrap().into_iter().map(|(k, _)| k).collect();
+        assert_eq!(scanned, expected);
+
+        assert!(tree.delete_text("customer-big").unwrap());
+        assert!(tree.insert_text(&"k".repeat(4096), row(0)).is_err());
+    }
+    assert!(pager.num_pages() > pages_before + 500);
+    assert!(pager.free_page_count() > 0);
+}
@@ -0,0 +1,138 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn run(catalog: &mut Catalog, sql: &str) {
+    handle_statement(catalog, parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r.join(",")).collect()
+}
+
+#[test]
+fn text_range_filters_compare_lexicographically() {
+    let mut catalog = setup_catalog("test_typed_cmp_text.db");
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'alice'), (2, 'mallory'), (3, 'zed'), (4, 'bob')");
+    assert_eq!(select(&mut catalog, "SELECT name FROM users WHERE name > 'm'"), vec!["mallory", "zed"]);
+    assert_eq!(select(&mut catalog, "SELECT name FROM users WHERE name <= 'bob'"), vec!["alice", "bob"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM users WHERE name >= 'b' AND name < 'n'"), vec!["2", "4"]);
+
+    run(&mut catalog, "DELETE FROM users WHERE name < 'c'");
+    assert_eq!(select(&mut catalog, "SELECT name FROM users"), vec!["mallory", "zed"]);
+}
+
+#[test]
+fn date_range_filters_compare_chronologically() {
+    let mut catalog = setup_catalog("test_typed_cmp_date.db");
+    run(&mut catalog, "CREATE TABLE events (id INTEGER, day DATE, at DATETIME, slot TIME)");
+    run(&mut catalog, "INSERT INTO events VALUES (1, '2023-12-31', '2023-12-31 23:59:59', '09:00:00')");
+    run(&mut catalog, "INSERT INTO events VALUES (2, '2024-01-15', '2024-01-15 08:30:00', '13:30:00')");
+    run(&mut catalog, "INSERT INTO events VALUES (3, '2024-02-01', '2024-02-01 00:00:00', '100:00:00')");
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE day >= '2024-01-01'"), vec!["2", "3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE day < '2024-02-01'"), vec!["1", "2"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE at > '2024-01-15 08:00:00'"), vec!["2", "3"]);
+    // 100 hours sorts after 13:30 even though it is shorter as text.
+    assert_eq!(select(&mut catalog, "SELECT id FROM events WHERE slot > '12:00:00'"), vec!["2", "3"]);
+}
+
+#[test]
+fn numeric_comparisons_stay_numeric() {
+    let mut catalog = setup_catalog("test_typed_cmp_numeric.db");
+    run(&mut catalog, "CREATE TABLE items (id INTEGER, price DOUBLE)");
+    run(&mut catalog, "INSERT INTO items VALUES (9, 2.5), (10, 10.0), (100, 99.9)");
+    // As text, '10' and '100' would sort before '9'.
+    assert_eq!(select(&mut catalog, "SELECT id FROM items WHERE id > 9"), vec!["10", "100"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM items WHERE price <= 10"), vec!["9", "10"]);
+}
+
+#[test]
+fn derived_tables_use_the_inner_column_types() {
+    let mut catalog = setup_catalog("test_typed_cmp_derived.db");
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'amy'), (2, 'tom')");
+    assert_eq!(select(&mut catalog, "SELECT id FROM (SELECT id, name FROM users) AS u WHERE name > 'b'"), vec!["2"]);
+}
+
+#[test]
+fn column_to_column_comparisons_use_declared_types() {
+    let mut catalog = setup_catalog("test_typed_cmp_columns.db");
+    run(&mut catalog, "CREATE TABLE ranges (id INTEGER, start_date DATE, end_date DATE, min INTEGER, max INTEGER, lo TEXT, hi TEXT, due DATETIME)");
+    run(&mut catalog, "INSERT INTO ranges VALUES (1, '2024-01-05', '2024-02-01', 9, 10, '10', '9', '2024-01-05 10:00:00')");
+    run(&mut catalog, "INSERT INTO ranges VALUES (2, '2024-03-01', '2024-02-28', 10, 9, 'a', 'b', '2024-03-01 00:00:00')");
+    run(&mut catalog, "INSERT INTO ranges VALUES (3, '2024-02-10', '2024-02-10', 2, 2, 'x', 'x', NULL)");
+
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE start_date < end_date"), vec!["1"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE start_date >= end_date"), vec!["2", "3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE min > max"), vec!["2"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE min <= max"), vec!["1", "3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE min = max"), vec!["3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE min != max"), vec!["1", "2"]);
+    // Text columns compare lexicographically even when they hold digits.
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE lo < hi"), vec!["1", "2"]);
+    // A DATE against a DATETIME reads the date as midnight, on either side.
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE start_date < due"), vec!["1"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM ranges WHERE due = start_date"), vec!["2"]);
+}
+
+#[test]
+fn between_compares_in_the_column_type() {
+    let mut catalog = setup_catalog("test_typed_cmp_between.db");
+    run(&mut catalog, "CREATE TABLE users (id INTEGER, name TEXT, joined DATE, score INTEGER)");
+    run(&mut catalog, "INSERT INTO users VALUES (1, 'alice', '2023-12-31', 9), (2, 'mallory', '2024-01-15', 10)");
+    run(&mut catalog, "INSERT INTO users VALUES (3, 'zed', '2024-02-01', 100), (4, 'm', '2024-01-01', NULL)");
+
+    assert_eq!(select(&mut catalog, "SELECT name FROM users WHERE name BETWEEN 'a' AND 'm'"), vec!["alice", "m"]);
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM users WHERE joined BETWEEN '2024-01-01' AND '2024-01-15'"),
+        vec!["2", "4"]
+    );
+    assert_eq!(select(&mut catalog, "SELECT id FROM users WHERE score BETWEEN 9 AND 10"), vec!["1", "2"]);
+
+    assert_eq!(select(&mut catalog, "SELECT name FROM users WHERE name NOT BETWEEN 'a' AND 'm'"), vec!["mallory", "zed"]);
+    // A NULL operand is UNKNOWN either way.
+    assert_eq!(select(&mut catalog, "SELECT id FROM users WHERE score NOT BETWEEN 9 AND 10"), vec!["3"]);
+    assert_eq!(
+        select(&mut catalog, "SELECT id FROM users WHERE id NOT BETWEEN 2 AND 3 AND joined NOT BETWEEN '2024-01-01' AND '2024-12-31'"),
+        vec!["1"]
+    );
+    assert!(parse_statement("SELECT id FROM users WHERE id NOT 1").is_err());
+}
+
+#[test]
+fn update_and_delete_filters_compare_equality_by_type() {
+    let mut catalog = setup_catalog("test_typed_cmp_equality.db");
+    run(&mut catalog, "CREATE TABLE items (id INTEGER, price DOUBLE, active BOOLEAN)");
+    run(&mut catalog, "INSERT INTO items VALUES (1, 10.0, true), (2, 10.5, false), (3, 7.25, true)");
+
+    run(&mut catalog, "UPDATE items SET active = false WHERE price = 10");
+    assert_eq!(select(&mut catalog, "SELECT id FROM items WHERE active = true"), vec!["3"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM items WHERE active = FALSE"), vec!["1", "2"]);
+    assert_eq!(select(&mut catalog, "SELECT id FROM items WHERE active = 0"), vec!["1", "2"]);
+
+    run(&mut catalog, "DELETE FROM items WHERE active = TRUE");
+    run(&mut catalog, "DELETE FROM items WHERE price = 10.50");
+    assert_eq!(select(&mut catalog, "SELECT id FROM items"), vec!["1"]);
+}
+
+#[test]
+fn untyped_equality_is_numeric_for_numbers() {
+    use aerodb::{sql::ast::{Expr, evaluate_expression}, storage::row::ColumnValue};
+    use std::collections::HashMap;
+    let values = HashMap::from([("total".to_string(), "10".to_string())]);
+    let equals = |right: &str| {
+        evaluate_expression(&Expr::Equals { left: "total".into(), right: right.into() }, &values)
+    };
+    assert_eq!(equals("10.0"), ColumnValue::Boolean(true));
+    assert_eq!(equals("10.5"), ColumnValue::Boolean(false));
+    assert_eq!(equals("ten"), ColumnValue::Boolean(false));
+}
@@ -0,0 +1,102 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    error::DbError,
+    execution::runtime::execute_select_statement,
+    sql::parser::parse_statement,
+    storage::row::ColumnValue,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Engine::new(filename)
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+fn text(s: &str) -> ColumnValue {
+    ColumnValue::Text(s.to_string())
+}
+
+#[test]
+fn text_with_separators_and_quotes_is_stored_verbatim() {
+    let filename = "test_typed_insert_text.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE notes (id INTEGER, body TEXT)").unwrap()).unwrap();
+    let tricky = [" leading, trailing ", "a,b , c", "it's \"quoted\"", "42", "NULL"];
+    let rows = tricky
+        .iter()
+        .enumerate()
+        .map(|(i, s)| vec![ColumnValue::Integer(i as i32), text(s)])
+        .collect();
+    assert_eq!(engine.insert_typed("notes", rows).unwrap(), tricky.len());
+    drop(engine);
+
+    let mut engine = Engine::new(filename);
+    let rows = select(&mut engine.catalog, "SELECT body FROM notes");
+    let bodies: Vec<&str> = rows.iter().map(|r| r[0].as_str()).collect();
+    assert_eq!(bodies, tricky);
+    // The text "NULL" is a value, not SQL NULL.
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM notes WHERE body IS NULL").len(), 0);
+}
+
+#[test]
+fn typed_values_are_checked_against_column_types() {
+    let filename = "test_typed_insert_types.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE t (id INTEGER, big BIGINT, code CHAR(4), price DECIMAL(6,2))").unwrap()).unwrap();
+    engine
+        .insert_typed(
+            "t",
+            vec![vec![
+                ColumnValue::Integer(1),
+                ColumnValue::Integer(7),
+                text("ab"),
+                ColumnValue::Integer(3),
+            ]],
+        )
+        .unwrap();
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT big, code, price FROM t"),
+        vec![vec!["7".to_string(), "ab  ".to_string(), "3.00".to_string()]]
+    );
+
+    let err = engine
+        .insert_typed("t", vec![vec![ColumnValue::Integer(2), text("7"), ColumnValue::Null, ColumnValue::Null]])
+        .unwrap_err();
+    assert!(matches!(err, DbError::InvalidValue(ref m) if m.contains("'big'")), "{:?}", err);
+    let err = engine.insert_typed("t", vec![vec![ColumnValue::Integer(2)]]).unwrap_err();
+    assert!(matches!(err, DbError::InvalidValue(_)), "{:?}", err);
+}
+
+#[test]
+fn failed_typed_insert_rolls_back_the_batch() {
+    let filename = "test_typed_insert_rollback.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE nums (n INTEGER PRIMARY KEY, label TEXT)").unwrap()).unwrap();
+    let row = |n: i32| vec![ColumnValue::Integer(n), text("x")];
+    assert!(engine.insert_typed("nums", vec![row(1), row(1)]).is_err());
+    assert_eq!(engine.insert_typed("nums", vec![row(2), row(3)]).unwrap(), 2);
+    assert_eq!(select(&mut engine.catalog, "SELECT n FROM nums"), vec![vec!["2".to_string()], vec!["3".to_string()]]);
+}
+
+#[test]
+fn null_auto_increment_draws_from_the_sequence() {
+    let filename = "test_typed_insert_auto.db";
+    let mut engine = setup_engine(filename);
+    engine.execute(parse_statement("CREATE TABLE users (id INT NOT NULL AUTO_INCREMENT, name TEXT)").unwrap()).unwrap();
+    engine.insert_typed("users", vec![vec![ColumnValue::Null, text("a")]]).unwrap();
+    engine.insert_typed("users", vec![vec![ColumnValue::Integer(10), text("b")]]).unwrap();
+    engine.execute(parse_statement("INSERT INTO users (name) VALUES ('c')").unwrap()).unwrap();
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT id FROM users"),
+        vec![vec!["1".to_string()], vec!["10".to_string()], vec!["11".to_string()]]
+    );
+}
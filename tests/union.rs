@@ -0,0 +1,74 @@
+use aerodb::{catalog::Catalog, storage::pager::Pager, sql::{parser::parse_statement, ast::{SetOp, Statement}}, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t1 (id INTEGER, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE t2 (id INTEGER, name TEXT)").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t1 VALUES (1, 'a'), (2, 'b')").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO t2 VALUES (2, 'b'), (3, 'c')").unwrap()).unwrap();
+    catalog
+}
+
+fn names(catalog: &mut Catalog, sql: &str) -> Vec<String> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r[0].clone()).collect()
+}
+
+#[test]
+fn parse_union() {
+    let stmt = parse_statement("SELECT name FROM t1 UNION ALL SELECT name FROM t2 union SELECT 'x'").unwrap();
+    match stmt {
+        Statement::SetOp { op: SetOp::Union, left, .. } => {
+            assert!(matches!(*left, Statement::SetOp { op: SetOp::UnionAll, .. }));
+        }
+        other => panic!("expected UNION, got {:?}", other),
+    }
+    // UNION inside parentheses or quotes belongs to the inner query.
+    assert!(matches!(
+        parse_statement("SELECT name FROM t1 WHERE id IN (SELECT id FROM t2 UNION SELECT 1)").unwrap(),
+        Statement::Select { .. }
+    ));
+    assert!(matches!(parse_statement("SELECT 'a UNION b'").unwrap(), Statement::Select { .. }));
+    assert!(parse_statement("SELECT name FROM t1 UNION DELETE FROM t2").is_err());
+}
+
+#[test]
+fn union_removes_duplicates() {
+    let mut catalog = setup_catalog("test_union_dedup.db");
+    assert_eq!(
+        names(&mut catalog, "SELECT name FROM t1 UNION SELECT name FROM t2"),
+        vec!["a", "b", "c"]
+    );
+    let stmt = parse_statement("SELECT id, name FROM t1 UNION SELECT id, name FROM t2").unwrap();
+    let mut rows = Vec::new();
+    let header = execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap();
+    assert_eq!(header.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(), vec!["id", "name"]);
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn union_all_keeps_duplicates() {
+    let mut catalog = setup_catalog("test_union_all.db");
+    assert_eq!(
+        names(&mut catalog, "SELECT name FROM t1 UNION ALL SELECT name FROM t2"),
+        vec!["a", "b", "b", "c"]
+    );
+    assert_eq!(
+        names(&mut catalog, "SELECT name FROM t1 UNION ALL SELECT name FROM t2 UNION SELECT 'a'"),
+        vec!["a", "b", "c"]
+    );
+}
+
+#[test]
+fn union_rejects_mismatched_column_counts() {
+    let mut catalog = setup_catalog("test_union_mismatch.db");
+    let stmt = parse_statement("SELECT id, name FROM t1 UNION SELECT name FROM t2").unwrap();
+    let mut rows = Vec::new();
+    let err = execute_select_statement(&mut catalog, &stmt, &mut rows, None).unwrap_err();
+    assert!(err.to_string().contains("column counts"), "{}", err);
+}
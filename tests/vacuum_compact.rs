@@ -0,0 +1,71 @@
+use aerodb::{
+    engine::Engine,
+    execution::runtime::QueryResult,
+    sql::parser::parse_statement,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) -> QueryResult {
+    engine.execute(parse_statement(sql).unwrap()).unwrap()
+}
+
+fn rows(engine: &mut Engine, sql: &str) -> Vec<Vec<String>> {
+    match run(engine, sql) {
+        QueryResult::Rows { rows, .. } => rows,
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn vacuum_repacks_a_sparse_table() {
+    let mut engine = setup_engine("test_vacuum_compact.db");
+    run(&mut engine, "CREATE TABLE items (id INTEGER, name TEXT)");
+    run(&mut engine, "CREATE INDEX idx_name ON items (name)");
+    for batch in 0..10 {
+        let values: Vec<String> = (0..100)
+            .map(|i| {
+                let id = batch * 100 + i;
+                format!("({}, 'item-{}-padding-padding-padding')", id, id)
+            })
+            .collect();
+        run(&mut engine, &format!("INSERT INTO items VALUES {}", values.join(", ")));
+    }
+    run(&mut engine, "DELETE FROM items WHERE id >= 100");
+    let before = engine.catalog.table_page_count("items").unwrap();
+
+    match run(&mut engine, "VACUUM items") {
+        QueryResult::Message(msg) => assert!(msg.starts_with("1 table(s) vacuumed"), "{}", msg),
+        other => panic!("unexpected result {:?}", other),
+    }
+    let after = engine.catalog.table_page_count("items").unwrap();
+    assert!(after * 4 < before, "{} pages before VACUUM, {} after", before, after);
+
+    assert_eq!(rows(&mut engine, "SELECT COUNT(*) FROM items"), [["100"]]);
+    assert_eq!(
+        rows(&mut engine, "SELECT id FROM items WHERE name = 'item-42-padding-padding-padding'"),
+        [["42"]]
+    );
+    // New rows land in pages the old tree freed.
+    run(&mut engine, "INSERT INTO items VALUES (5000, 'new')");
+    assert_eq!(rows(&mut engine, "SELECT name FROM items WHERE id = 5000"), [["new"]]);
+    run(&mut engine, "VACUUM");
+}
+
+#[test]
+fn vacuum_refuses_to_run_in_a_transaction() {
+    let mut engine = setup_engine("test_vacuum_in_tx.db");
+    run(&mut engine, "CREATE TABLE t (id INTEGER)");
+    run(&mut engine, "BEGIN");
+    assert!(engine.execute(parse_statement("VACUUM t").unwrap()).is_err());
+    run(&mut engine, "ROLLBACK");
+    assert!(engine.execute(parse_statement("VACUUM missing").unwrap()).is_err());
+    assert!(parse_statement("VACUUM TABLE t").is_ok());
+    assert!(parse_statement("VACUUM a b c").is_err());
+}
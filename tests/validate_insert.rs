@@ -0,0 +1,79 @@
+use aerodb::{engine::Engine, error::DbError, sql::parser::parse_statement, execution::runtime::execute_select_statement};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn exec(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn validate(engine: &mut Engine, sql: &str) -> Result<usize, DbError> {
+    engine.validate_insert(parse_statement(sql).unwrap())
+}
+
+fn select(engine: &mut Engine, sql: &str) -> Vec<String> {
+    let mut rows = Vec::new();
+    execute_select_statement(&mut engine.catalog, &parse_statement(sql).unwrap(), &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r.join(",")).collect()
+}
+
+fn shop(filename: &str) -> Engine {
+    let mut engine = setup_engine(filename);
+    exec(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT NOT NULL)");
+    exec(&mut engine, "CREATE TABLE orders (id INTEGER, user_id INTEGER, FOREIGN KEY (user_id) REFERENCES users (id))");
+    exec(&mut engine, "INSERT INTO users VALUES (1, 'ann'), (2, 'bob')");
+    engine
+}
+
+#[test]
+fn a_valid_batch_is_counted_but_not_written() {
+    let mut engine = shop("test_validate_insert_ok.db");
+    assert_eq!(validate(&mut engine, "INSERT INTO orders VALUES (10, 1), (11, 2), (12, 1)").unwrap(), 3);
+    assert_eq!(validate(&mut engine, "INSERT INTO users (id, name) VALUES (3, 'cy')").unwrap(), 1);
+    assert!(select(&mut engine, "SELECT id FROM orders").is_empty());
+    assert_eq!(select(&mut engine, "SELECT id FROM users"), vec!["1", "2"]);
+
+    // The same batch still inserts for real afterwards.
+    exec(&mut engine, "INSERT INTO orders VALUES (10, 1), (11, 2), (12, 1)");
+    assert_eq!(select(&mut engine, "SELECT id FROM orders"), vec!["10", "11", "12"]);
+}
+
+#[test]
+fn a_foreign_key_violation_is_reported_without_writing_any_row() {
+    let mut engine = shop("test_validate_insert_fk.db");
+    match validate(&mut engine, "INSERT INTO orders VALUES (10, 1), (11, 99), (12, 2)") {
+        Err(DbError::ForeignKeyViolation(_)) => {}
+        other => panic!("expected a foreign key violation, got {:?}", other),
+    }
+    assert!(select(&mut engine, "SELECT id FROM orders").is_empty());
+    assert_eq!(engine.catalog.integrity_check().unwrap(), None);
+}
+
+#[test]
+fn other_checks_run_against_the_table_and_the_batch() {
+    let mut engine = shop("test_validate_insert_checks.db");
+    assert!(matches!(validate(&mut engine, "INSERT INTO users VALUES (3, NULL)"), Err(DbError::NullViolation(col)) if col == "name"));
+    assert!(matches!(validate(&mut engine, "INSERT INTO users VALUES (2, 'again')"), Err(DbError::DuplicateKey(2))));
+    assert!(matches!(validate(&mut engine, "INSERT INTO users VALUES (5, 'a'), (5, 'b')"), Err(DbError::DuplicateKey(5))));
+    assert!(matches!(validate(&mut engine, "INSERT INTO users VALUES ('x', 'a')"), Err(DbError::InvalidValue(_))));
+    assert!(validate(&mut engine, "SELECT id FROM users").is_err());
+    assert_eq!(select(&mut engine, "SELECT id FROM users"), vec!["1", "2"]);
+}
+
+#[test]
+fn validation_does_not_advance_sequences() {
+    let mut engine = setup_engine("test_validate_insert_seq.db");
+    exec(&mut engine, "CREATE TABLE items (id INTEGER NOT NULL AUTO_INCREMENT, name TEXT)");
+    exec(&mut engine, "INSERT INTO items (name) VALUES ('a')");
+    assert_eq!(validate(&mut engine, "INSERT INTO items (name) VALUES ('b'), ('c')").unwrap(), 2);
+    // An explicit id within the batch moves the simulated sequence past it.
+    assert_eq!(validate(&mut engine, "INSERT INTO items (id, name) VALUES (2, 'b')").unwrap(), 1);
+    assert_eq!(validate(&mut engine, "INSERT INTO items (id, name) VALUES (5, 'b'), (DEFAULT, 'c')").unwrap(), 2);
+    exec(&mut engine, "INSERT INTO items (name) VALUES ('b')");
+    assert_eq!(select(&mut engine, "SELECT id, name FROM items"), vec!["1,a", "2,b"]);
+}
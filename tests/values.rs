@@ -0,0 +1,67 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::{QueryResult, execute_select_statement},
+    sql::parser::parse_statement,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> (Vec<String>, Vec<Vec<String>>) {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    let header = execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    (header.into_iter().map(|(name, _)| name).collect(), rows)
+}
+
+#[test]
+fn values_runs_as_a_query_and_a_subquery() {
+    let mut engine = setup_engine("test_values_query.db");
+    let (header, rows) = select(&mut engine.catalog, "VALUES (1, 'a'), (2, 'b')");
+    assert_eq!(header, ["column1", "column2"]);
+    assert_eq!(rows, [["1", "a"], ["2", "b"]]);
+
+    let (header, rows) = select(
+        &mut engine.catalog,
+        "SELECT column2 FROM (VALUES (1, 'a'), (2, 'b'), (3, NULL)) AS v WHERE column1 >= 2",
+    );
+    assert_eq!(header, ["column2"]);
+    assert_eq!(rows, [["b"], ["NULL"]]);
+
+    let (_, rows) = select(&mut engine.catalog, "VALUES (1) UNION ALL VALUES (1) UNION VALUES (2)");
+    assert_eq!(rows, [["1"], ["2"]]);
+}
+
+#[test]
+fn tables_join_against_values() {
+    let mut engine = setup_engine("test_values_join.db");
+    run(&mut engine, "CREATE TABLE t (id INTEGER, name TEXT)");
+    run(&mut engine, "INSERT INTO t VALUES (1, 'one'), (2, 'two'), (3, 'three')");
+
+    let sql = "SELECT t.name, v.column2 FROM t JOIN (VALUES (1, 'x'), (3, 'z')) AS v ON t.id = v.column1";
+    match engine.execute(parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { header, mut rows } => {
+            rows.sort();
+            let names: Vec<_> = header.into_iter().map(|(n, _)| n).collect();
+            assert_eq!(names, ["t.name", "v.column2"]);
+            assert_eq!(rows, [["one", "x"], ["three", "z"]]);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn values_rows_must_have_equal_length() {
+    assert!(parse_statement("VALUES (1, 'a'), (2)").is_err());
+    assert!(parse_statement("SELECT * FROM t JOIN (VALUES (1)) ON t.id = column1").is_err());
+}
@@ -0,0 +1,52 @@
+use aerodb::{catalog::Catalog, storage::{pager::Pager, row::ColumnType}, sql::parser::parse_statement, execution::runtime::{handle_statement, execute_select_statement}};
+use std::fs;
+
+fn setup_catalog(filename: &str) -> Catalog {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    Catalog::open(Pager::new(filename).unwrap()).unwrap()
+}
+
+fn names(catalog: &mut Catalog) -> Vec<String> {
+    let stmt = parse_statement("SELECT name FROM users").unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows.into_iter().map(|r| r[0].clone()).collect()
+}
+
+#[test]
+fn varchar_accepts_values_without_padding() {
+    let filename = "test_varchar_accept.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, name VARCHAR(5))").unwrap()).unwrap();
+    handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'ab'), (2, 'abcde')").unwrap()).unwrap();
+    assert_eq!(names(&mut catalog), vec!["ab", "abcde"]);
+}
+
+#[test]
+fn varchar_rejects_over_length_values() {
+    let filename = "test_varchar_reject.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, name VARCHAR(3))").unwrap()).unwrap();
+    let err = handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'abcd')").unwrap()).unwrap_err();
+    assert!(err.to_string().contains("exceeds length 3"), "{}", err);
+
+    handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'abc')").unwrap()).unwrap();
+    let err = handle_statement(&mut catalog, parse_statement("UPDATE users SET name = 'toolong' WHERE id = 1").unwrap()).unwrap_err();
+    assert!(err.to_string().contains("exceeds length 3"), "{}", err);
+    assert_eq!(names(&mut catalog), vec!["abc"]);
+
+    assert!(parse_statement("CREATE TABLE bad (id INTEGER, name VARCHAR)").is_err());
+}
+
+#[test]
+fn varchar_length_survives_reopen() {
+    let filename = "test_varchar_reopen.db";
+    let mut catalog = setup_catalog(filename);
+    handle_statement(&mut catalog, parse_statement("CREATE TABLE users (id INTEGER, name VARCHAR(12))").unwrap()).unwrap();
+    drop(catalog);
+
+    let mut catalog = Catalog::open(Pager::new(filename).unwrap()).unwrap();
+    assert_eq!(catalog.get_table("users").unwrap().columns[1].1, ColumnType::Varchar(12));
+    assert!(handle_statement(&mut catalog, parse_statement("INSERT INTO users VALUES (1, 'thirteen char')").unwrap()).is_err());
+}
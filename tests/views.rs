@@ -0,0 +1,131 @@
+use aerodb::{
+    catalog::Catalog,
+    engine::Engine,
+    execution::runtime::{QueryResult, execute_select_statement},
+    sql::parser::parse_statement,
+};
+use std::fs;
+
+fn setup_engine(filename: &str) -> Engine {
+    let _ = fs::remove_file(filename);
+    let _ = fs::remove_file(format!("{}.wal", filename));
+    let _ = fs::remove_file(format!("{}.clog", filename));
+    Engine::new(filename)
+}
+
+fn run(engine: &mut Engine, sql: &str) {
+    engine.execute(parse_statement(sql).unwrap()).unwrap();
+}
+
+fn select(catalog: &mut Catalog, sql: &str) -> Vec<Vec<String>> {
+    let stmt = parse_statement(sql).unwrap();
+    let mut rows = Vec::new();
+    execute_select_statement(catalog, &stmt, &mut rows, None).unwrap();
+    rows
+}
+
+#[test]
+fn view_over_a_filtered_table_persists_until_dropped() {
+    let filename = "test_views_persist.db";
+    let mut engine = setup_engine(filename);
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT, age INTEGER)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'alice', 30), (2, 'bob', 17), (3, 'carol', 45)");
+    run(&mut engine, "CREATE VIEW adults AS SELECT id, name FROM users WHERE age >= 18");
+
+    assert_eq!(select(&mut engine.catalog, "SELECT * FROM adults"), [["1", "alice"], ["3", "carol"]]);
+    assert_eq!(select(&mut engine.catalog, "SELECT name FROM adults WHERE id > 1"), [["carol"]]);
+    // The view reads the table as it is now.
+    run(&mut engine, "INSERT INTO users VALUES (4, 'dave', 52)");
+    assert_eq!(select(&mut engine.catalog, "SELECT name FROM adults WHERE id = 4"), [["dave"]]);
+
+    drop(engine);
+    let mut engine = Engine::new(filename);
+    assert!(engine.catalog.view_sql("adults").is_some());
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM adults"), [["1"], ["3"], ["4"]]);
+    // Table catalog rows still load and change beside the view's.
+    run(&mut engine, "CREATE TABLE pets (id INTEGER, owner INTEGER)");
+    run(&mut engine, "INSERT INTO pets VALUES (1, 3)");
+
+    run(&mut engine, "DROP VIEW adults");
+    assert!(engine.catalog.view_sql("adults").is_none());
+    assert!(engine.execute(parse_statement("SELECT * FROM adults").unwrap()).is_err());
+    assert!(engine.execute(parse_statement("DROP VIEW adults").unwrap()).is_err());
+    run(&mut engine, "DROP VIEW IF EXISTS adults");
+
+    drop(engine);
+    let mut engine = Engine::new(filename);
+    assert!(engine.catalog.view_sql("adults").is_none());
+    assert_eq!(select(&mut engine.catalog, "SELECT owner FROM pets"), [["3"]]);
+}
+
+#[test]
+fn views_join_with_tables_and_share_their_namespace() {
+    let mut engine = setup_engine("test_views_join.db");
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT, active INTEGER)");
+    run(&mut engine, "CREATE TABLE orders (id INTEGER, user_id INTEGER, total INTEGER)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'alice', 1), (2, 'bob', 0)");
+    run(&mut engine, "INSERT INTO orders VALUES (10, 1, 5), (11, 2, 7)");
+    run(&mut engine, "CREATE VIEW active_users AS SELECT id, name FROM users WHERE active = 1");
+
+    let sql = "SELECT o.id, u.name FROM orders o JOIN active_users u ON o.user_id = u.id";
+    match engine.execute(parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { rows, .. } => assert_eq!(rows, [["10", "alice"]]),
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    assert!(engine.execute(parse_statement("CREATE VIEW users AS SELECT * FROM orders").unwrap()).is_err());
+    assert!(engine.execute(parse_statement("CREATE TABLE active_users (id INTEGER)").unwrap()).is_err());
+    assert!(parse_statement("CREATE VIEW v AS DELETE FROM users WHERE id = 1").is_err());
+}
+
+#[test]
+fn views_apply_order_by_before_limit() {
+    let mut engine = setup_engine("test_views_order.db");
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT, age INTEGER)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'bob', 30), (2, 'alice', 9), (3, 'carol', 45), (4, 'dave', 100)");
+    run(&mut engine, "CREATE VIEW v AS SELECT id, name, age FROM users");
+
+    assert_eq!(select(&mut engine.catalog, "SELECT name FROM v ORDER BY name DESC"), [["dave"], ["carol"], ["bob"], ["alice"]]);
+    assert_eq!(select(&mut engine.catalog, "SELECT name FROM v ORDER BY name DESC LIMIT 1"), [["dave"]]);
+    // Numeric columns order by value, not by their text.
+    assert_eq!(select(&mut engine.catalog, "SELECT id FROM v WHERE age > 20 ORDER BY age"), [["1"], ["3"], ["4"]]);
+    assert_eq!(select(&mut engine.catalog, "SELECT * FROM v ORDER BY v.age DESC LIMIT 1 OFFSET 1"), [["3", "carol", "45"]]);
+}
+
+#[test]
+fn views_aggregate_and_join_as_the_from_source() {
+    let mut engine = setup_engine("test_views_aggregate.db");
+    run(&mut engine, "CREATE TABLE users (id INTEGER, name TEXT, active INTEGER)");
+    run(&mut engine, "CREATE TABLE orders (id INTEGER, user_id INTEGER, total INTEGER)");
+    run(&mut engine, "INSERT INTO users VALUES (1, 'alice', 1), (2, 'bob', 0), (3, 'carol', 1)");
+    run(&mut engine, "INSERT INTO orders VALUES (10, 1, 5), (11, 2, 7), (12, 1, 8), (13, 3, 2)");
+    run(&mut engine, "CREATE VIEW active_users AS SELECT id, name FROM users WHERE active = 1");
+    run(&mut engine, "CREATE VIEW big_orders AS SELECT user_id, total FROM orders WHERE total > 2");
+
+    assert_eq!(select(&mut engine.catalog, "SELECT COUNT(*) FROM active_users"), [["2"]]);
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT user_id, SUM(total) FROM big_orders GROUP BY user_id ORDER BY user_id"),
+        [["1", "13"], ["2", "7"]]
+    );
+    assert_eq!(
+        select(&mut engine.catalog, "SELECT user_id, COUNT(*) FROM big_orders GROUP BY user_id HAVING COUNT(*) > 1"),
+        [["1", "2"]]
+    );
+
+    let sql = "SELECT u.name, o.id FROM active_users u JOIN orders o ON o.user_id = u.id WHERE o.total > 4";
+    match engine.execute(parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { mut rows, .. } => {
+            rows.sort();
+            assert_eq!(rows, [["alice", "10"], ["alice", "12"]]);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    let sql = "SELECT u.name, COUNT(*) FROM active_users u JOIN orders o ON o.user_id = u.id GROUP BY u.name";
+    match engine.execute(parse_statement(sql).unwrap()).unwrap() {
+        QueryResult::Rows { mut rows, .. } => {
+            rows.sort();
+            assert_eq!(rows, [["alice", "2"], ["carol", "1"]]);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}